@@ -17,7 +17,7 @@
 use beefy_primitives::crypto::AuthorityId as BeefyId;
 use bp_millau::derive_account_from_rialto_id;
 use millau_runtime::{
-	AccountId, AuraConfig, BalancesConfig, BeefyConfig, BridgeRialtoMessagesConfig,
+	AccountId, AssetsConfig, AuraConfig, BalancesConfig, BeefyConfig, BridgeRialtoMessagesConfig,
 	BridgeWestendGrandpaConfig, GenesisConfig, GrandpaConfig, SessionConfig, SessionKeys,
 	Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
@@ -156,6 +156,7 @@ fn endowed_accounts() -> Vec<AccountId> {
 			bp_millau::AccountId,
 			bp_millau::AccountIdConverter,
 		>(),
+		token_bridge_pallet_account(),
 		derive_account_from_rialto_id(bp_runtime::SourceAccount::Account(
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
 		)),
@@ -177,6 +178,16 @@ fn endowed_accounts() -> Vec<AccountId> {
 	]
 }
 
+/// Returns account of the token bridge pallet.
+///
+/// The account must exist, because it is used to send messages. It also owns the wrapped asset.
+fn token_bridge_pallet_account() -> AccountId {
+	pallet_bridge_token_bridge::pallet_account_id::<
+		millau_runtime::Runtime,
+		millau_runtime::WithRialtoTokenBridgeInstance,
+	>()
+}
+
 fn session_keys(aura: AuraId, beefy: BeefyId, grandpa: GrandpaId) -> SessionKeys {
 	SessionKeys { aura, beefy, grandpa }
 }
@@ -213,6 +224,16 @@ fn testnet_genesis(
 			owner: Some(get_account_id_from_seed::<sr25519::Public>("George")),
 			..Default::default()
 		},
+		assets: AssetsConfig {
+			assets: vec![(
+				millau_runtime::WRAPPED_RIALTO_TOKENS_ASSET_ID,
+				token_bridge_pallet_account(),
+				true,
+				1,
+			)],
+			metadata: vec![],
+			accounts: vec![],
+		},
 		bridge_rialto_messages: BridgeRialtoMessagesConfig {
 			owner: Some(get_account_id_from_seed::<sr25519::Public>("RialtoMessagesOwner")),
 			..Default::default()
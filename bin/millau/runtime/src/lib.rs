@@ -224,16 +224,39 @@ impl pallet_beefy::Config for Runtime {
 	type BeefyId = BeefyId;
 }
 
+parameter_types! {
+	pub const MaxUsedNoncesPerAccount: u32 = 1024;
+	/// Oldest spec version of incoming messages that may be dispatched by this runtime.
+	///
+	/// The value is stored in the runtime storage, so it may be changed by the governance
+	/// (e.g. using `System::set_storage`) when runtime upgrade is compatible with messages that
+	/// are already in flight. Until it is changed, only messages encoded for the current runtime
+	/// are accepted. The governance must raise it again after any upgrade that changes
+	/// encoding of calls.
+	pub storage MinCompatibleSpecVersion: bp_message_dispatch::SpecVersion = VERSION.spec_version;
+	/// Newest spec version of incoming messages that may be dispatched by this runtime.
+	///
+	/// Messages may never be encoded for a newer runtime, so it is always the current version.
+	pub MaxCompatibleSpecVersion: bp_message_dispatch::SpecVersion = VERSION.spec_version;
+}
+
 impl pallet_bridge_dispatch::Config for Runtime {
 	type Event = Event;
 	type BridgeMessageId = (bp_messages::LaneId, bp_messages::MessageNonce);
 	type Call = Call;
 	type CallFilter = frame_support::traits::Everything;
+	type CompatibleSpecVersions = pallet_bridge_dispatch::SpecVersionRange<
+		MinCompatibleSpecVersion,
+		MaxCompatibleSpecVersion,
+	>;
 	type EncodedCall = crate::rialto_messages::FromRialtoEncodedCall;
 	type SourceChainAccountId = bp_rialto::AccountId;
 	type TargetChainAccountPublic = MultiSigner;
 	type TargetChainSignature = MultiSignature;
 	type AccountIdConverter = bp_millau::AccountIdConverter;
+	type MaxUsedNoncesPerAccount = MaxUsedNoncesPerAccount;
+	type OriginConverter = ();
+	type SourcePalletOrigin = ();
 }
 
 impl pallet_grandpa::Config for Runtime {
@@ -322,6 +345,33 @@ impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+	pub const AssetDeposit: bp_millau::Balance = 1_000_000;
+	pub const AssetAccountDeposit: bp_millau::Balance = 500;
+	pub const MetadataDepositBase: bp_millau::Balance = 1_000;
+	pub const MetadataDepositPerByte: bp_millau::Balance = 100;
+	pub const ApprovalDeposit: bp_millau::Balance = 500;
+	pub const AssetsStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = bp_millau::AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	// TODO: update me (https://github.com/paritytech/parity-bridges-common/issues/78)
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const TransactionBaseFee: Balance = 0;
 	pub const TransactionByteFee: Balance = 1;
@@ -462,8 +512,10 @@ impl pallet_bridge_messages::Config<WithRialtoMessagesInstance> for Runtime {
 			RootAccountForPayments,
 		>;
 	type OnMessageAccepted = ();
-	type OnDeliveryConfirmed =
-		pallet_bridge_token_swap::Pallet<Runtime, WithRialtoTokenSwapInstance>;
+	type OnDeliveryConfirmed = (
+		pallet_bridge_token_swap::Pallet<Runtime, WithRialtoTokenSwapInstance>,
+		pallet_bridge_token_bridge::Pallet<Runtime, WithRialtoTokenBridgeInstance>,
+	);
 
 	type SourceHeaderChain = crate::rialto_messages::Rialto;
 	type MessageDispatch = crate::rialto_messages::FromRialtoMessageDispatch;
@@ -472,6 +524,7 @@ impl pallet_bridge_messages::Config<WithRialtoMessagesInstance> for Runtime {
 
 parameter_types! {
 	pub const TokenSwapMessagesLane: bp_messages::LaneId = *b"swap";
+	pub const MaxPendingTokenSwapsPerAccount: u32 = 32;
 }
 
 /// Instance of the with-Rialto token swap pallet.
@@ -487,9 +540,47 @@ impl pallet_bridge_token_swap::Config<WithRialtoTokenSwapInstance> for Runtime {
 	type MessagesBridge = pallet_bridge_messages::Pallet<Runtime, WithRialtoMessagesInstance>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type MessagesBridge = bp_messages::source_chain::NoopMessagesBridge;
+	type MaxPendingSwapsPerAccount = MaxPendingTokenSwapsPerAccount;
 	type ThisCurrency = pallet_balances::Pallet<Runtime>;
+	type ThisAssets = pallet_assets::Pallet<Runtime>;
 	type FromSwapToThisAccountIdConverter = bp_rialto::AccountIdConverter;
 
+	type BridgedChain = bp_rialto::Rialto;
+	type BridgedAssetId = bp_rialto::AssetId;
+	type FromBridgedToThisAccountIdConverter = bp_millau::AccountIdConverter;
+}
+
+parameter_types! {
+	pub const TokenBridgePalletId: frame_support::PalletId = frame_support::PalletId(*b"py/tbrdg");
+	pub const TokenBridgeMessagesLane: bp_messages::LaneId = *b"tbrg";
+	pub const WithRialtoTokenBridgePalletIndex: u8 =
+		bp_rialto::WITH_MILLAU_TOKEN_BRIDGE_PALLET_INDEX;
+	pub const WrappedRialtoTokensAssetId: bp_millau::AssetId = WRAPPED_RIALTO_TOKENS_ASSET_ID;
+}
+
+/// Identifier of the asset that represents Rialto tokens at Millau.
+pub const WRAPPED_RIALTO_TOKENS_ASSET_ID: bp_millau::AssetId = 0;
+
+/// Instance of the with-Rialto token bridge pallet.
+pub type WithRialtoTokenBridgeInstance = ();
+
+impl pallet_bridge_token_bridge::Config<WithRialtoTokenBridgeInstance> for Runtime {
+	type Event = Event;
+	type WeightInfo = ();
+
+	type PalletId = TokenBridgePalletId;
+	type BridgedChainId = RialtoChainId;
+	type BridgedPalletIndex = WithRialtoTokenBridgePalletIndex;
+	type OutboundMessageLaneId = TokenBridgeMessagesLane;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type MessagesBridge = pallet_bridge_messages::Pallet<Runtime, WithRialtoMessagesInstance>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type MessagesBridge = bp_messages::source_chain::NoopMessagesBridge;
+
+	type ThisCurrency = pallet_balances::Pallet<Runtime>;
+	type WrappedAssets = pallet_assets::Pallet<Runtime>;
+	type WrappedAssetId = WrappedRialtoTokensAssetId;
+
 	type BridgedChain = bp_rialto::Rialto;
 	type FromBridgedToThisAccountIdConverter = bp_millau::AccountIdConverter;
 }
@@ -509,6 +600,7 @@ construct_runtime!(
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
 
 		// Consensus support.
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
@@ -529,6 +621,9 @@ construct_runtime!(
 
 		// Westend bridge modules.
 		BridgeWestendGrandpa: pallet_bridge_grandpa::<Instance1>::{Pallet, Call, Config<T>, Storage},
+
+		// Rialto token bridge. Its index is used by Rialto to encode calls, so it is fixed.
+		BridgeRialtoTokenBridge: pallet_bridge_token_bridge::{Pallet, Call, Storage, Event<T>} = 100,
 	}
 );
 
@@ -542,6 +637,11 @@ pub type Block = generic::Block<Header, UncheckedExtrinsic>;
 pub type SignedBlock = generic::SignedBlock<Block>;
 /// BlockId type as expected by this runtime.
 pub type BlockId = generic::BlockId<Block>;
+bridge_runtime_common::declare_bridge_reject_obsolete_messages!(
+	Runtime,
+	Call::BridgeRialtoMessages => WithRialtoMessagesInstance
+);
+
 /// The SignedExtension to the basic transaction logic.
 pub type SignedExtra = (
 	frame_system::CheckSpecVersion<Runtime>,
@@ -551,6 +651,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	BridgeRejectObsoleteMessages,
 );
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
@@ -565,6 +666,16 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	// swaps, started before the upgrade, won't be listed by the token swap runtime API, unless
+	// they're passed to the `v1::Migration`. They still may be claimed or cancelled
+	(
+		pallet_bridge_token_swap::migrations::v1::Migration<
+			Runtime,
+			WithRialtoTokenSwapInstance,
+			(),
+		>,
+		pallet_bridge_token_swap::migrations::v2::Migration<Runtime, WithRialtoTokenSwapInstance>,
+	),
 >;
 
 impl_runtime_apis! {
@@ -775,12 +886,51 @@ impl_runtime_apis! {
 				WithRialtoMessageBridge,
 			>(lane, begin, end)
 		}
+
+		fn underpaid_messages(
+			lane: bp_messages::LaneId,
+			max_messages: bp_messages::MessageNonce,
+		) -> Vec<bp_messages::UnderpaidMessageDetails<Balance>> {
+			bridge_runtime_common::messages_api::outbound_underpaid_messages::<
+				Runtime,
+				WithRialtoMessagesInstance,
+				WithRialtoMessageBridge,
+			>(lane, max_messages)
+		}
 	}
 
-	impl bp_rialto::FromRialtoInboundLaneApi<Block> for Runtime {
+	impl bp_rialto::FromRialtoInboundLaneApi<Block, Event> for Runtime {
 		fn unrewarded_relayers_state(lane: bp_messages::LaneId) -> bp_messages::UnrewardedRelayersState {
 			BridgeRialtoMessages::inbound_unrewarded_relayers_state(lane)
 		}
+
+		fn dry_run_message(
+			lane: bp_messages::LaneId,
+			payload: Vec<u8>,
+		) -> bp_runtime::messages::MessageDispatchDryRunResult<Event> {
+			let relayers_fund_account = pallet_bridge_messages::relayer_fund_account_id::<
+				bp_millau::AccountId,
+				bp_millau::AccountIdConverter,
+			>();
+			crate::rialto_messages::FromRialtoMessageDispatch::dry_run(&relayers_fund_account, lane, payload)
+		}
+	}
+
+	impl bp_millau::WithRialtoTokenSwapApi<
+		Block,
+		pallet_bridge_token_swap::TokenSwapDetailsOf<Runtime, WithRialtoTokenSwapInstance>,
+	> for Runtime {
+		fn swaps_by_source_account(
+			account: AccountId,
+		) -> Vec<pallet_bridge_token_swap::TokenSwapDetailsOf<Runtime, WithRialtoTokenSwapInstance>> {
+			BridgeRialtoTokenSwap::swaps_by_source_account(&account)
+		}
+
+		fn swaps_by_state(
+			state: bp_token_swap::TokenSwapState,
+		) -> Vec<pallet_bridge_token_swap::TokenSwapDetailsOf<Runtime, WithRialtoTokenSwapInstance>> {
+			BridgeRialtoTokenSwap::swaps_by_state(state)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -797,6 +947,7 @@ impl_runtime_apis! {
 			let mut list = Vec::<BenchmarkList>::new();
 
 			list_benchmark!(list, extra, pallet_bridge_token_swap, BridgeRialtoTokenSwap);
+			list_benchmark!(list, extra, pallet_bridge_token_bridge, BridgeRialtoTokenBridge);
 			list_benchmark!(list, extra, pallet_bridge_messages, MessagesBench::<Runtime, WithRialtoMessagesInstance>);
 			list_benchmark!(list, extra, pallet_bridge_grandpa, BridgeRialtoGrandpa);
 
@@ -907,6 +1058,57 @@ impl_runtime_apis! {
 						Balance::MAX / 100,
 					);
 				}
+
+				fn prepare_this_chain_asset(
+					account: &AccountId,
+					balance: Balance,
+				) -> bp_millau::AssetId {
+					use frame_support::traits::fungibles::Mutate;
+
+					const TOKEN_SWAP_ASSET_ID: bp_millau::AssetId = 1;
+
+					// the call fails if the asset already exists, but that's ok
+					let _ = pallet_assets::Pallet::<Runtime>::force_create(
+						Origin::root(),
+						TOKEN_SWAP_ASSET_ID,
+						account.clone(),
+						true,
+						1,
+					);
+					if balance != 0 {
+						pallet_assets::Pallet::<Runtime>::mint_into(
+							TOKEN_SWAP_ASSET_ID,
+							account,
+							balance,
+						)
+						.expect("failed to mint token swap asset");
+					}
+
+					TOKEN_SWAP_ASSET_ID
+				}
+			}
+
+			use pallet_bridge_token_bridge::benchmarking::Config as TokenBridgeConfig;
+
+			impl TokenBridgeConfig<WithRialtoTokenBridgeInstance> for Runtime {
+				fn initialize_environment() {
+					let pallet_account = pallet_bridge_token_bridge::pallet_account_id::<
+						Runtime,
+						WithRialtoTokenBridgeInstance,
+					>();
+					pallet_balances::Pallet::<Runtime>::make_free_balance_be(
+						&pallet_account,
+						Balance::MAX / 100,
+					);
+					// the asset is created at genesis, but let's be sure that it exists
+					let _ = pallet_assets::Pallet::<Runtime>::force_create(
+						Origin::root(),
+						WRAPPED_RIALTO_TOKENS_ASSET_ID,
+						pallet_account,
+						true,
+						1,
+					);
+				}
 			}
 
 			add_benchmark!(
@@ -917,6 +1119,7 @@ impl_runtime_apis! {
 			);
 			add_benchmark!(params, batches, pallet_bridge_grandpa, BridgeRialtoGrandpa);
 			add_benchmark!(params, batches, pallet_bridge_token_swap, BridgeRialtoTokenSwap);
+			add_benchmark!(params, batches, pallet_bridge_token_bridge, BridgeRialtoTokenBridge);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -948,6 +1151,31 @@ where
 	)
 }
 
+/// Rialto account ownership digest from Millau, protected from replay.
+///
+/// The byte vector returned by this function should be signed with a Rialto account private key.
+/// Every `nonce` may only be used once by the Rialto account.
+pub fn millau_to_rialto_account_ownership_digest_with_nonce<Call, AccountId, SpecVersion>(
+	rialto_call: &Call,
+	millau_account_id: AccountId,
+	nonce: bp_message_dispatch::TargetAccountNonce,
+	rialto_spec_version: SpecVersion,
+) -> sp_std::vec::Vec<u8>
+where
+	Call: codec::Encode,
+	AccountId: codec::Encode,
+	SpecVersion: codec::Encode,
+{
+	pallet_bridge_dispatch::account_ownership_digest_with_nonce(
+		rialto_call,
+		millau_account_id,
+		nonce,
+		rialto_spec_version,
+		bp_runtime::MILLAU_CHAIN_ID,
+		bp_runtime::RIALTO_CHAIN_ID,
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -995,6 +1223,14 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn token_bridge_pallet_index_is_correct() {
+		assert_eq!(
+			<BridgeRialtoTokenBridge as frame_support::traits::PalletInfoAccess>::index(),
+			bp_millau::WITH_RIALTO_TOKEN_BRIDGE_PALLET_INDEX as usize,
+		);
+	}
+
 	#[test]
 	fn call_size() {
 		const BRIDGES_PALLETS_MAX_CALL_SIZE: usize = 200;
@@ -18,7 +18,7 @@ use beefy_primitives::crypto::AuthorityId as BeefyId;
 use bp_rialto::derive_account_from_millau_id;
 use polkadot_primitives::v1::{AssignmentId, ValidatorId};
 use rialto_runtime::{
-	AccountId, BabeConfig, BalancesConfig, BeefyConfig, BridgeMillauMessagesConfig,
+	AccountId, AssetsConfig, BabeConfig, BalancesConfig, BeefyConfig, BridgeMillauMessagesConfig,
 	ConfigurationConfig, GenesisConfig, GrandpaConfig, SessionConfig, SessionKeys, Signature,
 	SudoConfig, SystemConfig, WASM_BINARY,
 };
@@ -165,6 +165,7 @@ fn endowed_accounts() -> Vec<AccountId> {
 			bp_rialto::AccountId,
 			bp_rialto::AccountIdConverter,
 		>(),
+		token_bridge_pallet_account(),
 		derive_account_from_millau_id(bp_runtime::SourceAccount::Account(
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
 		)),
@@ -186,6 +187,16 @@ fn endowed_accounts() -> Vec<AccountId> {
 	]
 }
 
+/// Returns account of the token bridge pallet.
+///
+/// The account must exist, because it is used to send messages. It also owns the wrapped asset.
+fn token_bridge_pallet_account() -> AccountId {
+	pallet_bridge_token_bridge::pallet_account_id::<
+		rialto_runtime::Runtime,
+		rialto_runtime::WithMillauTokenBridgeInstance,
+	>()
+}
+
 fn session_keys(
 	babe: BabeId,
 	beefy: BeefyId,
@@ -286,6 +297,16 @@ fn testnet_genesis(
 			},
 		},
 		paras: Default::default(),
+		assets: AssetsConfig {
+			assets: vec![(
+				rialto_runtime::WRAPPED_MILLAU_TOKENS_ASSET_ID,
+				token_bridge_pallet_account(),
+				true,
+				1,
+			)],
+			metadata: vec![],
+			accounts: vec![],
+		},
 		bridge_millau_messages: BridgeMillauMessagesConfig {
 			owner: Some(get_account_id_from_seed::<sr25519::Public>("MillauMessagesOwner")),
 			..Default::default()
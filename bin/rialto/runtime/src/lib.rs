@@ -253,16 +253,39 @@ impl pallet_beefy::Config for Runtime {
 	type BeefyId = BeefyId;
 }
 
+parameter_types! {
+	pub const MaxUsedNoncesPerAccount: u32 = 1024;
+	/// Oldest spec version of incoming messages that may be dispatched by this runtime.
+	///
+	/// The value is stored in the runtime storage, so it may be changed by the governance
+	/// (e.g. using `System::set_storage`) when runtime upgrade is compatible with messages that
+	/// are already in flight. Until it is changed, only messages encoded for the current runtime
+	/// are accepted. The governance must raise it again after any upgrade that changes
+	/// encoding of calls.
+	pub storage MinCompatibleSpecVersion: bp_message_dispatch::SpecVersion = VERSION.spec_version;
+	/// Newest spec version of incoming messages that may be dispatched by this runtime.
+	///
+	/// Messages may never be encoded for a newer runtime, so it is always the current version.
+	pub MaxCompatibleSpecVersion: bp_message_dispatch::SpecVersion = VERSION.spec_version;
+}
+
 impl pallet_bridge_dispatch::Config for Runtime {
 	type Event = Event;
 	type BridgeMessageId = (bp_messages::LaneId, bp_messages::MessageNonce);
 	type Call = Call;
 	type CallFilter = frame_support::traits::Everything;
+	type CompatibleSpecVersions = pallet_bridge_dispatch::SpecVersionRange<
+		MinCompatibleSpecVersion,
+		MaxCompatibleSpecVersion,
+	>;
 	type EncodedCall = crate::millau_messages::FromMillauEncodedCall;
 	type SourceChainAccountId = bp_millau::AccountId;
 	type TargetChainAccountPublic = MultiSigner;
 	type TargetChainSignature = MultiSignature;
 	type AccountIdConverter = bp_rialto::AccountIdConverter;
+	type MaxUsedNoncesPerAccount = MaxUsedNoncesPerAccount;
+	type OriginConverter = ();
+	type SourcePalletOrigin = ();
 }
 
 impl pallet_grandpa::Config for Runtime {
@@ -349,6 +372,33 @@ impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+	pub const AssetDeposit: bp_rialto::Balance = 1_000_000;
+	pub const AssetAccountDeposit: bp_rialto::Balance = 500;
+	pub const MetadataDepositBase: bp_rialto::Balance = 1_000;
+	pub const MetadataDepositPerByte: bp_rialto::Balance = 100;
+	pub const ApprovalDeposit: bp_rialto::Balance = 500;
+	pub const AssetsStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = bp_rialto::AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	// TODO: update me (https://github.com/paritytech/parity-bridges-common/issues/78)
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const TransactionBaseFee: Balance = 0;
 	pub const TransactionByteFee: Balance = 1;
@@ -463,13 +513,50 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 			RootAccountForPayments,
 		>;
 	type OnMessageAccepted = ();
-	type OnDeliveryConfirmed = ();
+	type OnDeliveryConfirmed =
+		pallet_bridge_token_bridge::Pallet<Runtime, WithMillauTokenBridgeInstance>;
 
 	type SourceHeaderChain = crate::millau_messages::Millau;
 	type MessageDispatch = crate::millau_messages::FromMillauMessageDispatch;
 	type BridgedChainId = BridgedChainId;
 }
 
+parameter_types! {
+	pub const TokenBridgePalletId: frame_support::PalletId = frame_support::PalletId(*b"py/tbrdg");
+	pub const TokenBridgeMessagesLane: bp_messages::LaneId = *b"tbrg";
+	pub const WithMillauTokenBridgePalletIndex: u8 =
+		bp_millau::WITH_RIALTO_TOKEN_BRIDGE_PALLET_INDEX;
+	pub const WrappedMillauTokensAssetId: bp_rialto::AssetId = WRAPPED_MILLAU_TOKENS_ASSET_ID;
+}
+
+/// Identifier of the asset that represents Millau tokens at Rialto.
+pub const WRAPPED_MILLAU_TOKENS_ASSET_ID: bp_rialto::AssetId = 0;
+
+/// Instance of the with-Millau token bridge pallet.
+pub type WithMillauTokenBridgeInstance = ();
+
+impl pallet_bridge_token_bridge::Config<WithMillauTokenBridgeInstance> for Runtime {
+	type Event = Event;
+	// TODO [#78] use generated weights when the pallet benchmarks are executed
+	type WeightInfo = ();
+
+	type PalletId = TokenBridgePalletId;
+	type BridgedChainId = BridgedChainId;
+	type BridgedPalletIndex = WithMillauTokenBridgePalletIndex;
+	type OutboundMessageLaneId = TokenBridgeMessagesLane;
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type MessagesBridge = pallet_bridge_messages::Pallet<Runtime, WithMillauMessagesInstance>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type MessagesBridge = bp_messages::source_chain::NoopMessagesBridge;
+
+	type ThisCurrency = pallet_balances::Pallet<Runtime>;
+	type WrappedAssets = pallet_assets::Pallet<Runtime>;
+	type WrappedAssetId = WrappedMillauTokensAssetId;
+
+	type BridgedChain = bp_millau::Millau;
+	type FromBridgedToThisAccountIdConverter = bp_rialto::AccountIdConverter;
+}
+
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -485,6 +572,7 @@ construct_runtime!(
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
 
 		// Consensus support.
 		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config},
@@ -520,6 +608,9 @@ construct_runtime!(
 		Registrar: polkadot_runtime_common::paras_registrar::{Pallet, Call, Storage, Event<T>},
 		Slots: polkadot_runtime_common::slots::{Pallet, Call, Storage, Event<T>},
 		ParasSudoWrapper: polkadot_runtime_common::paras_sudo_wrapper::{Pallet, Call},
+
+		// Millau token bridge. Its index is used by Millau to encode calls, so it is fixed.
+		BridgeMillauTokenBridge: pallet_bridge_token_bridge::{Pallet, Call, Storage, Event<T>} = 100,
 	}
 );
 
@@ -533,6 +624,11 @@ pub type Block = generic::Block<Header, UncheckedExtrinsic>;
 pub type SignedBlock = generic::SignedBlock<Block>;
 /// BlockId type as expected by this runtime.
 pub type BlockId = generic::BlockId<Block>;
+bridge_runtime_common::declare_bridge_reject_obsolete_messages!(
+	Runtime,
+	Call::BridgeMillauMessages => WithMillauMessagesInstance
+);
+
 /// The SignedExtension to the basic transaction logic.
 pub type SignedExtra = (
 	frame_system::CheckSpecVersion<Runtime>,
@@ -542,6 +638,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	BridgeRejectObsoleteMessages,
 );
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
@@ -900,12 +997,34 @@ impl_runtime_apis! {
 				WithMillauMessageBridge,
 			>(lane, begin, end)
 		}
+
+		fn underpaid_messages(
+			lane: bp_messages::LaneId,
+			max_messages: bp_messages::MessageNonce,
+		) -> Vec<bp_messages::UnderpaidMessageDetails<Balance>> {
+			bridge_runtime_common::messages_api::outbound_underpaid_messages::<
+				Runtime,
+				WithMillauMessagesInstance,
+				WithMillauMessageBridge,
+			>(lane, max_messages)
+		}
 	}
 
-	impl bp_millau::FromMillauInboundLaneApi<Block> for Runtime {
+	impl bp_millau::FromMillauInboundLaneApi<Block, Event> for Runtime {
 		fn unrewarded_relayers_state(lane: bp_messages::LaneId) -> bp_messages::UnrewardedRelayersState {
 			BridgeMillauMessages::inbound_unrewarded_relayers_state(lane)
 		}
+
+		fn dry_run_message(
+			lane: bp_messages::LaneId,
+			payload: Vec<u8>,
+		) -> bp_runtime::messages::MessageDispatchDryRunResult<Event> {
+			let relayers_fund_account = pallet_bridge_messages::relayer_fund_account_id::<
+				bp_rialto::AccountId,
+				bp_rialto::AccountIdConverter,
+			>();
+			crate::millau_messages::FromMillauMessageDispatch::dry_run(&relayers_fund_account, lane, payload)
+		}
 	}
 }
 
@@ -933,12 +1052,152 @@ where
 	)
 }
 
+/// Millau account ownership digest from Rialto, protected from replay.
+///
+/// The byte vector returned by this function should be signed with a Millau account private key.
+/// Every `nonce` may only be used once by the Millau account.
+pub fn rialto_to_millau_account_ownership_digest_with_nonce<Call, AccountId, SpecVersion>(
+	millau_call: &Call,
+	rialto_account_id: AccountId,
+	nonce: bp_message_dispatch::TargetAccountNonce,
+	millau_spec_version: SpecVersion,
+) -> sp_std::vec::Vec<u8>
+where
+	Call: codec::Encode,
+	AccountId: codec::Encode,
+	SpecVersion: codec::Encode,
+{
+	pallet_bridge_dispatch::account_ownership_digest_with_nonce(
+		millau_call,
+		rialto_account_id,
+		nonce,
+		millau_spec_version,
+		bp_runtime::RIALTO_CHAIN_ID,
+		bp_runtime::MILLAU_CHAIN_ID,
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use bp_runtime::Chain;
 	use bridge_runtime_common::messages;
 
+	fn millau_messages_proof_call(nonces_end: bp_messages::MessageNonce) -> Call {
+		Call::BridgeMillauMessages(pallet_bridge_messages::Call::<
+			Runtime,
+			WithMillauMessagesInstance,
+		>::receive_messages_proof {
+			relayer_id_at_bridged_chain: [0u8; 32].into(),
+			proof: messages::target::FromBridgedChainMessagesProof {
+				bridged_header_hash: Default::default(),
+				storage_proof: Vec::new(),
+				lane: [0, 0, 0, 0],
+				nonces_start: 1,
+				nonces_end,
+			},
+			messages_count: 1,
+			dispatch_weight: 0,
+		})
+	}
+
+	fn millau_messages_delivery_proof_call(
+		last_delivered_nonce: bp_messages::MessageNonce,
+	) -> Call {
+		Call::BridgeMillauMessages(pallet_bridge_messages::Call::<
+			Runtime,
+			WithMillauMessagesInstance,
+		>::receive_messages_delivery_proof {
+			proof: messages::source::FromBridgedChainMessagesDeliveryProof {
+				bridged_header_hash: Default::default(),
+				storage_proof: Vec::new(),
+				lane: [0, 0, 0, 0],
+			},
+			relayers_state: bp_messages::UnrewardedRelayersState {
+				last_delivered_nonce,
+				..Default::default()
+			},
+		})
+	}
+
+	fn validate_bridge_call(call: &Call) -> bool {
+		use sp_runtime::traits::SignedExtension;
+
+		BridgeRejectObsoleteMessages
+			.validate(&[0u8; 32].into(), call, &Default::default(), 0)
+			.is_ok()
+	}
+
+	#[test]
+	fn extension_rejects_obsolete_messages() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			pallet_bridge_messages::InboundLanes::<Runtime, WithMillauMessagesInstance>::insert(
+				[0, 0, 0, 0],
+				bp_messages::InboundLaneData {
+					relayers: Default::default(),
+					last_confirmed_nonce: 10,
+				},
+			);
+
+			assert!(!validate_bridge_call(&millau_messages_proof_call(5)));
+			assert!(!validate_bridge_call(&millau_messages_proof_call(10)));
+			assert!(validate_bridge_call(&millau_messages_proof_call(15)));
+		});
+	}
+
+	#[test]
+	fn extension_rejects_obsolete_confirmations() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			pallet_bridge_messages::OutboundLanes::<Runtime, WithMillauMessagesInstance>::insert(
+				[0, 0, 0, 0],
+				bp_messages::OutboundLaneData {
+					oldest_unpruned_nonce: 11,
+					latest_received_nonce: 10,
+					latest_generated_nonce: 20,
+				},
+			);
+
+			assert!(!validate_bridge_call(&millau_messages_delivery_proof_call(5)));
+			assert!(!validate_bridge_call(&millau_messages_delivery_proof_call(10)));
+			assert!(validate_bridge_call(&millau_messages_delivery_proof_call(15)));
+		});
+	}
+
+	#[test]
+	fn extension_accepts_other_calls() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			assert!(validate_bridge_call(&Call::System(frame_system::Call::remark {
+				remark: Vec::new()
+			})));
+		});
+	}
+
+	#[test]
+	fn extension_registers_lane_state_read_weight() {
+		use sp_runtime::traits::SignedExtension;
+
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let pre_dispatch_weight = |call: &Call| {
+				let weight_before = frame_system::Pallet::<Runtime>::block_weight().total();
+				BridgeRejectObsoleteMessages
+					.pre_dispatch(&[0u8; 32].into(), call, &Default::default(), 0)
+					.unwrap();
+				frame_system::Pallet::<Runtime>::block_weight().total() - weight_before
+			};
+
+			assert_eq!(
+				pre_dispatch_weight(&millau_messages_proof_call(1)),
+				DbWeight::get().reads(1)
+			);
+			assert_eq!(
+				pre_dispatch_weight(&Call::System(frame_system::Call::remark {
+					remark: Vec::new()
+				})),
+				0,
+			);
+		});
+	}
+
 	#[test]
 	fn ensure_rialto_message_lane_weights_are_correct() {
 		type Weights = pallet_bridge_messages::weights::MillauWeight<Runtime>;
@@ -980,6 +1239,14 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn token_bridge_pallet_index_is_correct() {
+		assert_eq!(
+			<BridgeMillauTokenBridge as frame_support::traits::PalletInfoAccess>::index(),
+			bp_rialto::WITH_MILLAU_TOKEN_BRIDGE_PALLET_INDEX as usize,
+		);
+	}
+
 	#[test]
 	fn call_size() {
 		const BRIDGES_PALLETS_MAX_CALL_SIZE: usize = 200;
@@ -114,6 +114,8 @@ impl messages::ChainWithMessages for Rialto {
 
 impl messages::ThisChainWithMessages for Rialto {
 	type Call = crate::Call;
+	type SourcePalletOrigin =
+		<crate::Runtime as pallet_bridge_dispatch::Config>::SourcePalletOrigin;
 
 	fn is_outbound_lane_enabled(lane: &LaneId) -> bool {
 		*lane == [0, 0, 0, 0] || *lane == [0, 0, 0, 1]
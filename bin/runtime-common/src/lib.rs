@@ -21,3 +21,5 @@
 pub mod messages;
 pub mod messages_api;
 pub mod messages_benchmarking;
+pub mod messages_extension;
+pub mod remote_calls;
@@ -27,7 +27,7 @@ use bp_messages::{
 	InboundLaneData, LaneId, Message, MessageData, MessageKey, MessageNonce, OutboundLaneData,
 };
 use bp_runtime::{
-	messages::{DispatchFeePayment, MessageDispatchResult},
+	messages::{DispatchFeePayment, MessageDispatchDryRunResult, MessageDispatchResult},
 	ChainId, Size, StorageProofChecker,
 };
 use codec::{Decode, Encode};
@@ -112,6 +112,10 @@ pub struct MessageTransaction<Weight> {
 pub trait ThisChainWithMessages: ChainWithMessages {
 	/// Call type on the chain.
 	type Call: Encode + Decode;
+	/// Origins that are allowed to send messages with `CallOrigin::SourcePallet` origin.
+	///
+	/// Normally it is the `SourcePalletOrigin` of the dispatch pallet, deployed at This chain.
+	type SourcePalletOrigin: pallet_bridge_dispatch::EnsureSourcePallet<AccountIdOf<Self>>;
 
 	/// Are we accepting any messages to the given lane?
 	fn is_outbound_lane_enabled(lane: &LaneId) -> bool;
@@ -313,8 +317,14 @@ pub mod source {
 
 			// Do the dispatch-specific check. We assume that the target chain uses
 			// `Dispatch`, so we verify the message accordingly.
-			pallet_bridge_dispatch::verify_message_origin(submitter, payload)
-				.map_err(|_| BAD_ORIGIN)?;
+			pallet_bridge_dispatch::verify_message_origin::<
+				<ThisChain<B> as ThisChainWithMessages>::SourcePalletOrigin,
+				_,
+				_,
+				_,
+				_,
+			>(submitter, payload)
+			.map_err(|_| BAD_ORIGIN)?;
 
 			let minimal_fee_in_this_tokens =
 				estimate_message_dispatch_and_delivery_fee::<B>(payload, B::RELAYER_FEE_PERCENT)?;
@@ -565,27 +575,101 @@ pub mod target {
 				message_id,
 				message.data.payload.map_err(drop),
 				|dispatch_origin, dispatch_weight| {
-					let unadjusted_weight_fee = ThisRuntime::WeightToFee::calc(&dispatch_weight);
-					let fee_multiplier =
-						pallet_transaction_payment::Pallet::<ThisRuntime>::next_fee_multiplier();
-					let adjusted_weight_fee =
-						fee_multiplier.saturating_mul_int(unadjusted_weight_fee);
-					if !adjusted_weight_fee.is_zero() {
-						ThisCurrency::transfer(
-							dispatch_origin,
-							relayer_account,
-							adjusted_weight_fee,
-							ExistenceRequirement::AllowDeath,
-						)
-						.map_err(drop)
-					} else {
-						Ok(())
-					}
+					pay_dispatch_fee::<B, ThisRuntime, ThisCurrency>(
+						relayer_account,
+						dispatch_origin,
+						dispatch_weight,
+					)
 				},
 			)
 		}
 	}
 
+	impl<B: MessageBridge, ThisRuntime, ThisCurrency, ThisDispatchInstance>
+		FromBridgedChainMessageDispatch<B, ThisRuntime, ThisCurrency, ThisDispatchInstance>
+	where
+		BalanceOf<ThisChain<B>>: Saturating + FixedPointOperand,
+		ThisDispatchInstance: 'static,
+		ThisRuntime: pallet_bridge_dispatch::Config<
+				ThisDispatchInstance,
+				BridgeMessageId = (LaneId, MessageNonce),
+				SourceChainAccountId = AccountIdOf<BridgedChain<B>>,
+				TargetChainAccountPublic = SignerOf<ThisChain<B>>,
+				TargetChainSignature = SignatureOf<ThisChain<B>>,
+				EncodedCall = FromBridgedChainEncodedMessageCall<CallOf<ThisChain<B>>>,
+			> + pallet_transaction_payment::Config,
+		<ThisRuntime as pallet_transaction_payment::Config>::OnChargeTransaction:
+			pallet_transaction_payment::OnChargeTransaction<
+				ThisRuntime,
+				Balance = BalanceOf<ThisChain<B>>,
+			>,
+		ThisCurrency: Currency<AccountIdOf<ThisChain<B>>, Balance = BalanceOf<ThisChain<B>>>,
+		ThisRuntime: frame_system::Config<AccountId = AccountIdOf<ThisChain<B>>>,
+	{
+		/// Dry run dispatch of the encoded Bridged -> This chain message.
+		///
+		/// The message is decoded and dispatched, but all storage changes are reverted. The
+		/// dispatch fee (if it is paid at This chain) is paid to the `relayer_account`. Nonce
+		/// of the message in the returned events is always zero.
+		pub fn dry_run(
+			relayer_account: &AccountIdOf<ThisChain<B>>,
+			lane: LaneId,
+			encoded_payload: Vec<u8>,
+		) -> MessageDispatchDryRunResult<
+			<ThisRuntime as pallet_bridge_dispatch::Config<ThisDispatchInstance>>::Event,
+		> {
+			let payload = FromBridgedChainMessagePayload::<B>::decode(&mut &encoded_payload[..])
+				.map_err(drop);
+			pallet_bridge_dispatch::Pallet::<ThisRuntime, ThisDispatchInstance>::dry_run_dispatch(
+				B::BRIDGED_CHAIN_ID,
+				B::THIS_CHAIN_ID,
+				(lane, 0),
+				payload,
+				|dispatch_origin, dispatch_weight| {
+					pay_dispatch_fee::<B, ThisRuntime, ThisCurrency>(
+						relayer_account,
+						dispatch_origin,
+						dispatch_weight,
+					)
+				},
+			)
+		}
+	}
+
+	/// Pay dispatch fee for the message that is dispatched at This chain.
+	fn pay_dispatch_fee<B, ThisRuntime, ThisCurrency>(
+		relayer_account: &AccountIdOf<ThisChain<B>>,
+		dispatch_origin: &AccountIdOf<ThisChain<B>>,
+		dispatch_weight: Weight,
+	) -> Result<(), ()>
+	where
+		B: MessageBridge,
+		BalanceOf<ThisChain<B>>: Saturating + FixedPointOperand,
+		ThisRuntime: pallet_transaction_payment::Config,
+		<ThisRuntime as pallet_transaction_payment::Config>::OnChargeTransaction:
+			pallet_transaction_payment::OnChargeTransaction<
+				ThisRuntime,
+				Balance = BalanceOf<ThisChain<B>>,
+			>,
+		ThisCurrency: Currency<AccountIdOf<ThisChain<B>>, Balance = BalanceOf<ThisChain<B>>>,
+	{
+		let unadjusted_weight_fee = ThisRuntime::WeightToFee::calc(&dispatch_weight);
+		let fee_multiplier =
+			pallet_transaction_payment::Pallet::<ThisRuntime>::next_fee_multiplier();
+		let adjusted_weight_fee = fee_multiplier.saturating_mul_int(unadjusted_weight_fee);
+		if !adjusted_weight_fee.is_zero() {
+			ThisCurrency::transfer(
+				dispatch_origin,
+				relayer_account,
+				adjusted_weight_fee,
+				ExistenceRequirement::AllowDeath,
+			)
+			.map_err(drop)
+		} else {
+			Ok(())
+		}
+	}
+
 	/// Return maximal dispatch weight of the message we're able to receive.
 	pub fn maximal_incoming_message_dispatch_weight(maximal_extrinsic_weight: Weight) -> Weight {
 		maximal_extrinsic_weight / 2
@@ -922,6 +1006,7 @@ mod tests {
 
 	impl ThisChainWithMessages for ThisChain {
 		type Call = ThisChainCall;
+		type SourcePalletOrigin = ();
 
 		fn is_outbound_lane_enabled(lane: &LaneId) -> bool {
 			lane == TEST_LANE_ID
@@ -982,6 +1067,7 @@ mod tests {
 
 	impl ThisChainWithMessages for BridgedChain {
 		type Call = BridgedChainCall;
+		type SourcePalletOrigin = ();
 
 		fn is_outbound_lane_enabled(_lane: &LaneId) -> bool {
 			unreachable!()
@@ -16,9 +16,12 @@
 
 //! Helpers for implementing various message-related runtime API mthods.
 
-use crate::messages::{source::FromThisChainMessagePayload, MessageBridge};
+use crate::messages::{
+	source::{estimate_message_dispatch_and_delivery_fee, FromThisChainMessagePayload},
+	BalanceOf, MessageBridge, ThisChain,
+};
 
-use bp_messages::{LaneId, MessageDetails, MessageNonce};
+use bp_messages::{LaneId, MessageDetails, MessageNonce, UnderpaidMessageDetails};
 use codec::Decode;
 use sp_std::vec::Vec;
 
@@ -49,3 +52,49 @@ where
 		})
 		.collect()
 }
+
+/// Implementation of the `To*OutboundLaneApi::underpaid_messages`.
+///
+/// Only first `max_messages` queued messages are inspected.
+pub fn outbound_underpaid_messages<Runtime, MessagesPalletInstance, BridgeConfig>(
+	lane: LaneId,
+	max_messages: MessageNonce,
+) -> Vec<UnderpaidMessageDetails<Runtime::OutboundMessageFee>>
+where
+	Runtime: pallet_bridge_messages::Config<
+		MessagesPalletInstance,
+		OutboundMessageFee = BalanceOf<ThisChain<BridgeConfig>>,
+	>,
+	MessagesPalletInstance: 'static,
+	BridgeConfig: MessageBridge,
+{
+	let lane_data =
+		pallet_bridge_messages::OutboundLanes::<Runtime, MessagesPalletInstance>::get(&lane);
+	let begin = lane_data.latest_received_nonce + 1;
+	let end = sp_std::cmp::min(
+		lane_data.latest_generated_nonce,
+		lane_data.latest_received_nonce.saturating_add(max_messages),
+	);
+	(begin..=end)
+		.filter_map(|nonce| {
+			let message_data =
+				pallet_bridge_messages::Pallet::<Runtime, MessagesPalletInstance>::outbound_message_data(lane, nonce)?;
+			let decoded_payload =
+				FromThisChainMessagePayload::<BridgeConfig>::decode(&mut &message_data.payload[..]).ok()?;
+			let estimated_fee = estimate_message_dispatch_and_delivery_fee::<BridgeConfig>(
+				&decoded_payload,
+				BridgeConfig::RELAYER_FEE_PERCENT,
+			)
+			.ok()?;
+			if message_data.fee >= estimated_fee {
+				return None
+			}
+
+			Some(UnderpaidMessageDetails {
+				nonce,
+				delivery_and_dispatch_fee: message_data.fee,
+				estimated_delivery_and_dispatch_fee: estimated_fee,
+			})
+		})
+		.collect()
+}
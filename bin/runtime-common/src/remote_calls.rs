@@ -0,0 +1,350 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Remote calls with callbacks.
+//!
+//! Pallets that are sending messages to the bridged chain often need to know whether the message
+//! has been dispatched successfully. The dispatch result is delivered back to This chain along
+//! with the delivery confirmation, so the pallet needs to remember the nonce of every sent
+//! message and to look it up in the `OnDeliveryConfirmed` handler. This module implements this
+//! bookkeeping once, so that the pallet only needs to:
+//!
+//! 1) declare the storage map `MessageNonce => CallbackId` (its type is passed as
+//! `RemoteCallsConfig::PendingCallbacks`);
+//!
+//! 2) implement `OnRemoteCallResult` for the type that is passed as `RemoteCallsConfig::OnResult`;
+//!
+//! 3) use `RemoteCalls::send` instead of the `MessagesBridge::send_message` to send messages;
+//!
+//! 4) ask runtime to register `RemoteCalls` as (one of) `OnDeliveryConfirmed` handler(s) of the
+//! messages pallet.
+//!
+//! The messages pallet only allows `OnDeliveryConfirmed` handlers to spend single DB read and
+//! single DB write per confirmed message. This budget is spent on the callbacks bookkeeping, so
+//! the weight of the `OnRemoteCallResult::on_result` call is charged up front, by the
+//! `RemoteCalls::send` call. So it is paid by the sender of the remote call and not by the relayer,
+//! which is delivering the confirmation.
+
+use bp_messages::{
+	source_chain::{MessagesBridge, OnDeliveryConfirmed, SendMessageArtifacts, Sender},
+	DeliveredMessages, LaneId, MessageNonce,
+};
+use codec::FullCodec;
+use frame_support::{
+	storage::StorageMap,
+	traits::Get,
+	weights::{RuntimeDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Handler of remote call results.
+pub trait OnRemoteCallResult<CallbackId> {
+	/// Returns maximal weight of the `on_result` call.
+	///
+	/// This weight is charged up front, when the remote call is sent (see `RemoteCalls::send`).
+	fn max_on_result_weight() -> Weight;
+
+	/// Called when the dispatch result of the remote call, identified by `callback_id`, is
+	/// delivered back to This chain. The `message_nonce` is the nonce of the message that has
+	/// been used to send the remote call.
+	///
+	/// Returns weight, consumed by the handler. It must never exceed the weight, returned by
+	/// the `max_on_result_weight`.
+	///
+	/// NOTE: the handler is called from the `OnDeliveryConfirmed::on_messages_delivered`. If it
+	/// needs more than `max_on_result_weight` to handle the result, please do it from the
+	/// `on_initialize` call(s) of the next block(s).
+	fn on_result(
+		message_nonce: MessageNonce,
+		callback_id: CallbackId,
+		is_dispatched: bool,
+	) -> Weight;
+}
+
+impl<CallbackId> OnRemoteCallResult<CallbackId> for () {
+	fn max_on_result_weight() -> Weight {
+		0
+	}
+
+	fn on_result(
+		_message_nonce: MessageNonce,
+		_callback_id: CallbackId,
+		_is_dispatched: bool,
+	) -> Weight {
+		0
+	}
+}
+
+/// Remote calls configuration.
+pub trait RemoteCallsConfig {
+	/// Account identifier of the message sender.
+	type AccountId;
+	/// Type of the message fee.
+	type Balance;
+	/// Type of the message payload.
+	type Payload;
+	/// Identifier that is passed to the `OnResult` handler when the call result is known.
+	type CallbackId: FullCodec;
+
+	/// Messages bridge that is used to send remote calls.
+	type MessagesBridge: MessagesBridge<Self::AccountId, Self::Balance, Self::Payload>;
+	/// Lane that is used to send remote calls.
+	///
+	/// The lane may be shared with other pallets, but every `RemoteCallsConfig` that is using
+	/// the same lane must have its own `PendingCallbacks` storage.
+	type OutboundLaneId: Get<LaneId>;
+	/// Storage map of callbacks that are waiting for remote calls results.
+	type PendingCallbacks: StorageMap<
+		MessageNonce,
+		Self::CallbackId,
+		Query = Option<Self::CallbackId>,
+	>;
+	/// Remote calls results handler.
+	type OnResult: OnRemoteCallResult<Self::CallbackId>;
+	/// Database weight.
+	type DbWeight: Get<RuntimeDbWeight>;
+}
+
+/// Remote calls with callbacks.
+pub struct RemoteCalls<C>(PhantomData<C>);
+
+impl<C: RemoteCallsConfig> RemoteCalls<C> {
+	/// Send remote call to the bridged chain.
+	///
+	/// The `C::OnResult` handler will be called with `callback_id` when the call dispatch
+	/// result is delivered back to This chain. Returned weight includes weight of the
+	/// `MessagesBridge::send_message` call, the weight of callback bookkeeping and the maximal
+	/// weight of the `C::OnResult` handler.
+	pub fn send(
+		sender: Sender<C::AccountId>,
+		payload: C::Payload,
+		delivery_and_dispatch_fee: C::Balance,
+		callback_id: C::CallbackId,
+	) -> Result<
+		SendMessageArtifacts,
+		<C::MessagesBridge as MessagesBridge<C::AccountId, C::Balance, C::Payload>>::Error,
+	> {
+		let artifacts = C::MessagesBridge::send_message(
+			sender,
+			C::OutboundLaneId::get(),
+			payload,
+			delivery_and_dispatch_fee,
+		)?;
+		C::PendingCallbacks::insert(artifacts.nonce, callback_id);
+
+		Ok(SendMessageArtifacts {
+			nonce: artifacts.nonce,
+			weight: artifacts.weight.saturating_add(Self::send_overhead_weight()),
+		})
+	}
+
+	/// Returns weight of the callback bookkeeping, performed by the `send` call, plus the
+	/// maximal weight of the `C::OnResult` handler, which is charged up front.
+	///
+	/// It doesn't include weight of the `MessagesBridge::send_message` call.
+	pub fn send_overhead_weight() -> Weight {
+		C::DbWeight::get().writes(1).saturating_add(C::OnResult::max_on_result_weight())
+	}
+
+	/// Returns true if we're still waiting for result of the remote call, sent in the message
+	/// with given nonce.
+	pub fn is_pending(nonce: MessageNonce) -> bool {
+		C::PendingCallbacks::contains_key(nonce)
+	}
+}
+
+impl<C: RemoteCallsConfig> OnDeliveryConfirmed for RemoteCalls<C> {
+	fn on_messages_delivered(lane: &LaneId, delivered_messages: &DeliveredMessages) -> Weight {
+		// we're only interested in our lane messages
+		if *lane != C::OutboundLaneId::get() {
+			return 0
+		}
+
+		let mut reads = 0;
+		let mut writes = 0;
+		for nonce in delivered_messages.begin..=delivered_messages.end {
+			reads += 1;
+			if let Some(callback_id) = C::PendingCallbacks::take(nonce) {
+				writes += 1;
+				let callback_weight = C::OnResult::on_result(
+					nonce,
+					callback_id,
+					delivered_messages.message_dispatch_result(nonce),
+				);
+				debug_assert!(
+					callback_weight <= C::OnResult::max_on_result_weight(),
+					"OnRemoteCallResult::on_result has spent more weight than it is allowed to",
+				);
+			}
+		}
+
+		// weight of result handlers has already been paid by senders of remote calls
+		C::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{storage::types, traits::StorageInstance, Identity};
+	use std::cell::RefCell;
+
+	const TEST_LANE_ID: LaneId = *b"test";
+	const TEST_NONCE: MessageNonce = 42;
+	const CALLBACK_WEIGHT: Weight = 1_000;
+	const SEND_MESSAGE_WEIGHT: Weight = 10_000;
+	const BAD_PAYLOAD: u64 = 0;
+
+	thread_local! {
+		static RESULTS: RefCell<Vec<(u64, bool)>> = RefCell::new(Vec::new());
+	}
+
+	struct PendingCallbacksPrefix;
+
+	impl StorageInstance for PendingCallbacksPrefix {
+		fn pallet_prefix() -> &'static str {
+			"TestPallet"
+		}
+
+		const STORAGE_PREFIX: &'static str = "PendingCallbacks";
+	}
+
+	type PendingCallbacks = types::StorageMap<PendingCallbacksPrefix, Identity, MessageNonce, u64>;
+
+	struct TestMessagesBridge;
+
+	impl MessagesBridge<u64, u64, u64> for TestMessagesBridge {
+		type Error = ();
+
+		fn send_message(
+			_sender: Sender<u64>,
+			lane: LaneId,
+			payload: u64,
+			_delivery_and_dispatch_fee: u64,
+		) -> Result<SendMessageArtifacts, Self::Error> {
+			assert_eq!(lane, TEST_LANE_ID);
+			if payload == BAD_PAYLOAD {
+				return Err(())
+			}
+
+			Ok(SendMessageArtifacts { nonce: TEST_NONCE, weight: SEND_MESSAGE_WEIGHT })
+		}
+	}
+
+	struct TestOnResult;
+
+	impl OnRemoteCallResult<u64> for TestOnResult {
+		fn max_on_result_weight() -> Weight {
+			CALLBACK_WEIGHT
+		}
+
+		fn on_result(message_nonce: MessageNonce, callback_id: u64, is_dispatched: bool) -> Weight {
+			assert_eq!(message_nonce, TEST_NONCE);
+			RESULTS.with(|results| results.borrow_mut().push((callback_id, is_dispatched)));
+			CALLBACK_WEIGHT
+		}
+	}
+
+	frame_support::parameter_types! {
+		pub const TestLaneId: LaneId = TEST_LANE_ID;
+		pub const TestDbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 10 };
+	}
+
+	struct TestRemoteCallsConfig;
+
+	impl RemoteCallsConfig for TestRemoteCallsConfig {
+		type AccountId = u64;
+		type Balance = u64;
+		type Payload = u64;
+		type CallbackId = u64;
+
+		type MessagesBridge = TestMessagesBridge;
+		type OutboundLaneId = TestLaneId;
+		type PendingCallbacks = PendingCallbacks;
+		type OnResult = TestOnResult;
+		type DbWeight = TestDbWeight;
+	}
+
+	type TestRemoteCalls = RemoteCalls<TestRemoteCallsConfig>;
+
+	fn run_test(test: impl FnOnce()) {
+		RESULTS.with(|results| results.borrow_mut().clear());
+		sp_io::TestExternalities::new(Default::default()).execute_with(test)
+	}
+
+	fn take_results() -> Vec<(u64, bool)> {
+		RESULTS.with(|results| results.borrow_mut().drain(..).collect())
+	}
+
+	#[test]
+	fn send_remembers_callback() {
+		run_test(|| {
+			assert_eq!(
+				TestRemoteCalls::send(Sender::Root, 1, 0, 100),
+				Ok(SendMessageArtifacts {
+					nonce: TEST_NONCE,
+					weight: SEND_MESSAGE_WEIGHT + TestDbWeight::get().writes(1) + CALLBACK_WEIGHT,
+				}),
+			);
+			assert!(TestRemoteCalls::is_pending(TEST_NONCE));
+		});
+	}
+
+	#[test]
+	fn send_does_not_remember_callback_if_message_is_not_sent() {
+		run_test(|| {
+			assert_eq!(TestRemoteCalls::send(Sender::Root, BAD_PAYLOAD, 0, 100), Err(()));
+			assert!(!TestRemoteCalls::is_pending(TEST_NONCE));
+		});
+	}
+
+	#[test]
+	fn delivery_confirmations_at_other_lanes_are_ignored() {
+		run_test(|| {
+			TestRemoteCalls::send(Sender::Root, 1, 0, 100).unwrap();
+
+			assert_eq!(
+				TestRemoteCalls::on_messages_delivered(
+					b"othr",
+					&DeliveredMessages::new(TEST_NONCE, true)
+				),
+				0,
+			);
+			assert!(TestRemoteCalls::is_pending(TEST_NONCE));
+			assert!(take_results().is_empty());
+		});
+	}
+
+	#[test]
+	fn callback_is_called_when_result_is_delivered() {
+		run_test(|| {
+			TestRemoteCalls::send(Sender::Root, 1, 0, 100).unwrap();
+
+			let mut delivered_messages = DeliveredMessages::new(TEST_NONCE - 1, true);
+			delivered_messages.note_dispatched_message(false);
+			assert_eq!(
+				TestRemoteCalls::on_messages_delivered(&TEST_LANE_ID, &delivered_messages),
+				TestDbWeight::get().reads_writes(2, 1),
+			);
+			assert!(!TestRemoteCalls::is_pending(TEST_NONCE));
+			assert_eq!(take_results(), vec![(100, false)]);
+
+			// callback is only called once
+			TestRemoteCalls::on_messages_delivered(&TEST_LANE_ID, &delivered_messages);
+			assert!(take_results().is_empty());
+		});
+	}
+}
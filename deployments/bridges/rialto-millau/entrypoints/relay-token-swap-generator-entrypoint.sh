@@ -33,6 +33,7 @@ do
 	echo "Initiating token-swap between Rialto and Millau"
 	/home/user/substrate-relay \
 		swap-tokens \
+		start \
 		millau-to-rialto \
 		--source-host $SOURCE_HOST \
 		--source-port $SOURCE_PORT \
@@ -18,29 +18,32 @@
 //!
 //! The messages are interpreted directly as runtime `Call`. We attempt to decode
 //! them and then dispatch as usual. To prevent compatibility issues, the Calls have
-//! to include a `spec_version`. This will be checked before dispatch. In the case of
-//! a successful dispatch an event is emitted.
+//! to include a `spec_version`. This will be checked before dispatch. Messages that are encoded
+//! using other (compatible) `spec_version` are also accepted, if the runtime is configured to do
+//! so. In the case of a successful dispatch an event is emitted.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Generated by `decl_event!`
 #![allow(clippy::unused_unit)]
 
-use bp_message_dispatch::{CallOrigin, MessageDispatch, MessagePayload, SpecVersion};
+use bp_message_dispatch::{
+	CallOrigin, MessageDispatch, MessagePayload, SpecVersion, TargetAccountNonce,
+};
 use bp_runtime::{
 	derive_account_id,
-	messages::{DispatchFeePayment, MessageDispatchResult},
-	ChainId, SourceAccount,
+	messages::{DispatchFeePayment, MessageDispatchDryRunResult, MessageDispatchResult},
+	ChainId, PalletId, SourceAccount,
 };
 use codec::Encode;
 use frame_support::{
 	dispatch::Dispatchable,
 	ensure,
 	traits::{Contains, Get},
-	weights::{extract_actual_weight, GetDispatchInfo},
+	weights::{extract_actual_weight, GetDispatchInfo, RuntimeDbWeight, Weight},
 };
 use frame_system::RawOrigin;
 use sp_runtime::traits::{BadOrigin, Convert, IdentifyAccount, MaybeDisplay, Verify};
-use sp_std::{fmt::Debug, prelude::*};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 
 pub use pallet::*;
 
@@ -82,6 +85,20 @@ pub mod pallet {
 		/// The pallet will filter all incoming calls right before they're dispatched. If this
 		/// filter rejects the call, special event (`Event::MessageCallRejected`) is emitted.
 		type CallFilter: Contains<<Self as Config<I>>::Call>;
+		/// Spec versions of messages that are compatible with the current runtime.
+		///
+		/// Messages with the current spec version are always accepted. Messages with any other
+		/// spec version are only dispatched if this filter accepts their spec version. Otherwise,
+		/// special event (`Event::MessageVersionSpecMismatch`) is emitted. Calls that are
+		/// encoded using compatible spec version must be decodable (and have the same meaning)
+		/// using the current runtime.
+		///
+		/// Weight of the call is always computed using the current runtime and checked against
+		/// the weight, declared by the message. Since the delivery and dispatch fee is computed
+		/// (at the source chain) using the declared weight, it stays correct for messages with
+		/// compatible spec versions - if the call has become heavier, the message is rejected
+		/// with `Event::MessageWeightMismatch`.
+		type CompatibleSpecVersions: Contains<SpecVersion>;
 		/// The type that is used to wrap the `Self::Call` when it is moved over bridge.
 		///
 		/// The idea behind this is to avoid `Call` conversion/decoding until we'll be sure
@@ -93,10 +110,42 @@ pub mod pallet {
 		///
 		/// Used when deriving target chain AccountIds from source chain AccountIds.
 		type AccountIdConverter: sp_runtime::traits::Convert<sp_core::hash::H256, Self::AccountId>;
+		/// A type that selects origin for dispatching the call.
+		///
+		/// By default (`()`), all calls are dispatched with `RawOrigin::Signed` origin of the
+		/// account, derived from (or proved by) the message `CallOrigin`. Runtime may use
+		/// custom converter to dispatch calls from e.g. source chain collective pallet with
+		/// the corresponding collective origin.
+		type OriginConverter: ConvertCallOrigin<
+			CallOriginOf<Self, I>,
+			Self::AccountId,
+			<Self as frame_system::Config>::Origin,
+		>;
+		/// Maximal number of recently used nonces that are tracked for every target account.
+		///
+		/// Nonces of `CallOrigin::TargetAccountWithNonce` messages may arrive out of order. The
+		/// pallet remembers up to this number of the largest used nonces. When this limit is
+		/// reached, the smallest tracked nonce is forgotten and all nonces below or equal to it
+		/// are rejected.
+		#[pallet::constant]
+		type MaxUsedNoncesPerAccount: Get<u32>;
+		/// A type that checks origin of outbound messages with `CallOrigin::SourcePallet` origin.
+		///
+		/// It is used (by `verify_message_origin`) when this chain is the source chain of the
+		/// message. Only origins that are mapped to the given `PalletId` are allowed to send
+		/// messages on behalf of this pallet. By default (`()`), such messages are rejected.
+		type SourcePalletOrigin: EnsureSourcePallet<Self::AccountId>;
 	}
 
 	type BridgeMessageIdOf<T, I> = <T as Config<I>>::BridgeMessageId;
 
+	/// Call origin of messages, dispatched by the pallet.
+	pub type CallOriginOf<T, I> = CallOrigin<
+		<T as Config<I>>::SourceChainAccountId,
+		<T as Config<I>>::TargetChainAccountPublic,
+		<T as Config<I>>::TargetChainSignature,
+	>;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
@@ -107,6 +156,24 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {}
 
+	/// Recently used nonces of `CallOrigin::TargetAccountWithNonce` messages, sorted in
+	/// ascending order.
+	#[pallet::storage]
+	pub type UsedTargetAccountNonces<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<TargetAccountNonce, T::MaxUsedNoncesPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Minimal nonce of `CallOrigin::TargetAccountWithNonce` message that may be accepted.
+	///
+	/// All nonces below this value are considered used.
+	#[pallet::storage]
+	pub type MinTargetAccountNonce<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, TargetAccountNonce, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -120,6 +187,9 @@ pub mod pallet {
 		MessageWeightMismatch(ChainId, BridgeMessageIdOf<T, I>, Weight, Weight),
 		/// Message signature mismatch.
 		MessageSignatureMismatch(ChainId, BridgeMessageIdOf<T, I>),
+		/// Message has been rejected, because its nonce has already been used by the target
+		/// account. Last argument is the nonce.
+		MessageNonceAlreadyUsed(ChainId, BridgeMessageIdOf<T, I>, TargetAccountNonce),
 		/// We have failed to decode Call from the message.
 		MessageCallDecodeFailed(ChainId, BridgeMessageIdOf<T, I>),
 		/// The call from the message has been rejected by the call filter.
@@ -177,14 +247,17 @@ impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId, T::BridgeMessageId>
 		};
 
 		// verify spec version
-		// (we want it to be the same, because otherwise we may decode Call improperly)
+		// (we want it to be the same or compatible, because otherwise we may decode Call
+		// improperly)
 		let mut dispatch_result = MessageDispatchResult {
 			dispatch_result: false,
 			unspent_weight: message.weight,
 			dispatch_fee_paid_during_dispatch: false,
 		};
 		let expected_version = <T as frame_system::Config>::Version::get().spec_version;
-		if message.spec_version != expected_version {
+		if message.spec_version != expected_version &&
+			!T::CompatibleSpecVersions::contains(&message.spec_version)
+		{
 			log::trace!(
 				"Message {:?}/{:?}: spec_version mismatch. Expected {:?}, got {:?}",
 				source_chain,
@@ -217,7 +290,8 @@ impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId, T::BridgeMessageId>
 		};
 
 		// prepare dispatch origin
-		let origin_account = match message.origin {
+		let mut target_account_nonce = None;
+		let origin_account = match message.origin.clone() {
 			CallOrigin::SourceRoot => {
 				let hex_id =
 					derive_account_id::<T::SourceChainAccountId>(source_chain, SourceAccount::Root);
@@ -251,6 +325,54 @@ impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId, T::BridgeMessageId>
 				log::trace!(target: "runtime::bridge-dispatch", "Target Account: {:?}", &target_account);
 				target_account
 			},
+			CallOrigin::TargetAccountWithNonce(
+				source_account_id,
+				target_public,
+				target_signature,
+				nonce,
+			) => {
+				let digest = account_ownership_digest_with_nonce(
+					&call,
+					source_account_id,
+					nonce,
+					message.spec_version,
+					source_chain,
+					target_chain,
+				);
+
+				let target_account = target_public.into_account();
+				if !target_signature.verify(&digest[..], &target_account) {
+					log::trace!(
+						target: "runtime::bridge-dispatch",
+						"Message {:?}/{:?}: origin proof is invalid. Expected account: {:?} from signature: {:?}",
+						source_chain,
+						id,
+						target_account,
+						target_signature,
+					);
+					Self::deposit_event(Event::MessageSignatureMismatch(source_chain, id));
+					return dispatch_result
+				}
+
+				if Self::is_target_account_nonce_used(&target_account, nonce) {
+					log::trace!(
+						target: "runtime::bridge-dispatch",
+						"Message {:?}/{:?}: nonce {} has already been used by account {:?}",
+						source_chain,
+						id,
+						nonce,
+						target_account,
+					);
+					Self::deposit_event(Event::MessageNonceAlreadyUsed(source_chain, id, nonce));
+					return dispatch_result
+				}
+
+				// the nonce is marked as used right before dispatch, when all other checks pass
+				target_account_nonce = Some(nonce);
+
+				log::trace!(target: "runtime::bridge-dispatch", "Target Account: {:?}", &target_account);
+				target_account
+			},
 			CallOrigin::SourceAccount(source_account_id) => {
 				let hex_id =
 					derive_account_id(source_chain, SourceAccount::Account(source_account_id));
@@ -258,6 +380,15 @@ impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId, T::BridgeMessageId>
 				log::trace!(target: "runtime::bridge-dispatch", "Source Account: {:?}", &target_id);
 				target_id
 			},
+			CallOrigin::SourcePallet(pallet_id) => {
+				let hex_id = derive_account_id::<T::SourceChainAccountId>(
+					source_chain,
+					SourceAccount::Pallet(pallet_id),
+				);
+				let target_id = T::AccountIdConverter::convert(hex_id);
+				log::trace!(target: "runtime::bridge-dispatch", "Pallet Account: {:?}", &target_id);
+				target_id
+			},
 		};
 
 		// filter the call
@@ -275,9 +406,15 @@ impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId, T::BridgeMessageId>
 
 		// verify weight
 		// (we want passed weight to be at least equal to pre-dispatch weight of the call
-		// because otherwise Calls may be dispatched at lower price)
+		// (plus weight of the nonce bookkeeping) because otherwise Calls may be dispatched at
+		// lower price)
 		let dispatch_info = call.get_dispatch_info();
-		let expected_weight = dispatch_info.weight;
+		let nonce_weight = if target_account_nonce.is_some() {
+			target_account_nonce_weight(T::DbWeight::get())
+		} else {
+			0
+		};
+		let expected_weight = dispatch_info.weight.saturating_add(nonce_weight);
 		if message.weight < expected_weight {
 			log::trace!(
 				target: "runtime::bridge-dispatch",
@@ -319,14 +456,21 @@ impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId, T::BridgeMessageId>
 		}
 		dispatch_result.dispatch_fee_paid_during_dispatch = pay_dispatch_fee_at_target_chain;
 
+		// mark nonce as used
+		if let Some(nonce) = target_account_nonce {
+			Self::use_target_account_nonce(&origin_account, nonce);
+		}
+
 		// finally dispatch message
-		let origin = RawOrigin::Signed(origin_account).into();
+		let origin =
+			T::OriginConverter::convert_call_origin(source_chain, &message.origin, origin_account);
 
 		log::trace!(target: "runtime::bridge-dispatch", "Message being dispatched is: {:.4096?}", &call);
 		let result = call.dispatch(origin);
 		let actual_call_weight = extract_actual_weight(&result, &dispatch_info);
 		dispatch_result.dispatch_result = result.is_ok();
-		dispatch_result.unspent_weight = message.weight.saturating_sub(actual_call_weight);
+		dispatch_result.unspent_weight =
+			message.weight.saturating_sub(actual_call_weight.saturating_add(nonce_weight));
 
 		log::trace!(
 			target: "runtime::bridge-dispatch",
@@ -349,13 +493,183 @@ impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId, T::BridgeMessageId>
 	}
 }
 
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Dry run message dispatch.
+	///
+	/// All checks are performed and the message is dispatched exactly as it is done by the
+	/// `MessageDispatch::dispatch`, but all storage changes (including deposited events) are
+	/// reverted afterwards. Returned value contains all events that would be deposited by the
+	/// dispatch.
+	pub fn dry_run_dispatch<
+		P: FnOnce(&T::AccountId, bp_message_dispatch::Weight) -> Result<(), ()>,
+	>(
+		source_chain: ChainId,
+		target_chain: ChainId,
+		id: T::BridgeMessageId,
+		message: Result<<Self as MessageDispatch<T::AccountId, T::BridgeMessageId>>::Message, ()>,
+		pay_dispatch_fee: P,
+	) -> MessageDispatchDryRunResult<<T as Config<I>>::Event> {
+		let declared_weight = message.as_ref().map(|message| message.weight).unwrap_or(0);
+		frame_support::storage::with_transaction(|| {
+			let events_before = frame_system::Pallet::<T>::event_count() as usize;
+			let dispatch_result =
+				<Self as MessageDispatch<T::AccountId, T::BridgeMessageId>>::dispatch(
+					source_chain,
+					target_chain,
+					id,
+					message,
+					pay_dispatch_fee,
+				);
+			let events = frame_system::Pallet::<T>::events()
+				.into_iter()
+				.skip(events_before)
+				.map(|record| record.event.into())
+				.collect();
+
+			sp_runtime::TransactionOutcome::Rollback(MessageDispatchDryRunResult {
+				actual_weight: declared_weight.saturating_sub(dispatch_result.unspent_weight),
+				dispatch_result,
+				events,
+			})
+		})
+	}
+
+	/// Returns true if nonce of `CallOrigin::TargetAccountWithNonce` message has already been
+	/// used by the given account.
+	fn is_target_account_nonce_used(account: &T::AccountId, nonce: TargetAccountNonce) -> bool {
+		nonce < MinTargetAccountNonce::<T, I>::get(account) ||
+			UsedTargetAccountNonces::<T, I>::get(account).binary_search(&nonce).is_ok()
+	}
+
+	/// Mark nonce of `CallOrigin::TargetAccountWithNonce` message as used.
+	///
+	/// The caller must ensure that the nonce has not been used yet.
+	fn use_target_account_nonce(account: &T::AccountId, nonce: TargetAccountNonce) {
+		UsedTargetAccountNonces::<T, I>::mutate(account, |used_nonces| {
+			let mut position = match used_nonces.binary_search(&nonce) {
+				Ok(_) => return,
+				Err(position) => position,
+			};
+
+			if used_nonces.len() >= T::MaxUsedNoncesPerAccount::get() as usize {
+				// the nonce is smaller than all tracked nonces => it is the one to forget
+				if position == 0 {
+					MinTargetAccountNonce::<T, I>::insert(account, nonce.saturating_add(1));
+					return
+				}
+
+				let forgotten_nonce = used_nonces.remove(0);
+				MinTargetAccountNonce::<T, I>::insert(account, forgotten_nonce.saturating_add(1));
+				position -= 1;
+			}
+
+			used_nonces
+				.try_insert(position, nonce)
+				.expect("we have removed element if the vector was full; qed");
+		})
+	}
+}
+
+/// Weight of the `CallOrigin::TargetAccountWithNonce` nonce bookkeeping.
+///
+/// It is added to the weight of the call when checking weight, declared by the message.
+pub fn target_account_nonce_weight(db_weight: RuntimeDbWeight) -> Weight {
+	// `MinTargetAccountNonce` and `UsedTargetAccountNonces` are read, and (in the worst case)
+	// both are written
+	db_weight.reads_writes(2, 2)
+}
+
+/// Converter of the message `CallOrigin` into the origin that is used to dispatch the call.
+pub trait ConvertCallOrigin<CallOrigin, AccountId, Origin> {
+	/// Select origin for dispatching the call.
+	///
+	/// The `source_chain` is the chain where the message came from. The `call_origin` has
+	/// already been verified by the pallet and the `origin_account` is the account that is
+	/// associated with the `call_origin` (it is also the account that has paid the dispatch fee,
+	/// if fee is paid at the target chain).
+	fn convert_call_origin(
+		source_chain: ChainId,
+		call_origin: &CallOrigin,
+		origin_account: AccountId,
+	) -> Origin;
+}
+
+impl<CallOrigin, AccountId, Origin> ConvertCallOrigin<CallOrigin, AccountId, Origin> for ()
+where
+	Origin: From<RawOrigin<AccountId>>,
+{
+	fn convert_call_origin(_: ChainId, _: &CallOrigin, origin_account: AccountId) -> Origin {
+		RawOrigin::Signed(origin_account).into()
+	}
+}
+
+/// A type that checks whether the source chain origin is allowed to send messages with
+/// `CallOrigin::SourcePallet` origin.
+pub trait EnsureSourcePallet<SourceChainAccountId> {
+	/// Ensure that the `sender_origin` is allowed to send messages on behalf of the source chain
+	/// pallet with given `pallet_id`.
+	fn ensure_source_pallet(
+		sender_origin: &RawOrigin<SourceChainAccountId>,
+		pallet_id: &PalletId,
+	) -> Result<(), BadOrigin>;
+}
+
+impl<SourceChainAccountId> EnsureSourcePallet<SourceChainAccountId> for () {
+	fn ensure_source_pallet(
+		_: &RawOrigin<SourceChainAccountId>,
+		_: &PalletId,
+	) -> Result<(), BadOrigin> {
+		Err(BadOrigin)
+	}
+}
+
+/// `EnsureSourcePallet` implementation that allows Root origin to send messages on behalf of
+/// pallets with ids, accepted by the `PalletIds` filter.
+pub struct EnsureRootSourcePallets<PalletIds>(PhantomData<PalletIds>);
+
+impl<SourceChainAccountId, PalletIds> EnsureSourcePallet<SourceChainAccountId>
+	for EnsureRootSourcePallets<PalletIds>
+where
+	SourceChainAccountId: PartialEq,
+	PalletIds: Contains<PalletId>,
+{
+	fn ensure_source_pallet(
+		sender_origin: &RawOrigin<SourceChainAccountId>,
+		pallet_id: &PalletId,
+	) -> Result<(), BadOrigin> {
+		ensure!(sender_origin == &RawOrigin::Root, BadOrigin);
+		ensure!(PalletIds::contains(pallet_id), BadOrigin);
+		Ok(())
+	}
+}
+
+/// Inclusive range of spec versions, that may be used as `Config::CompatibleSpecVersions`.
+///
+/// Bounds of the range may be backed by the runtime storage (see `parameter_types!` with
+/// `storage` keyword), so that they can be changed by the governance.
+pub struct SpecVersionRange<Min, Max>(PhantomData<(Min, Max)>);
+
+impl<Min, Max> Contains<SpecVersion> for SpecVersionRange<Min, Max>
+where
+	Min: Get<SpecVersion>,
+	Max: Get<SpecVersion>,
+{
+	fn contains(spec_version: &SpecVersion) -> bool {
+		*spec_version >= Min::get() && *spec_version <= Max::get()
+	}
+}
+
 /// Check if the message is allowed to be dispatched on the target chain given the sender's origin
 /// on the source chain.
 ///
 /// For example, if a message is sent from a "regular" account on the source chain it will not be
 /// allowed to be dispatched as Root on the target chain. This is a useful check to do on the source
 /// chain _before_ sending a message whose dispatch will be rejected on the target chain.
+///
+/// Messages with `CallOrigin::SourcePallet` origin are checked using the `SourcePalletOrigin`
+/// (see `Config::SourcePalletOrigin`).
 pub fn verify_message_origin<
+	SourcePalletOrigin,
 	SourceChainAccountId,
 	TargetChainAccountPublic,
 	TargetChainSignature,
@@ -370,6 +684,7 @@ pub fn verify_message_origin<
 	>,
 ) -> Result<Option<SourceChainAccountId>, BadOrigin>
 where
+	SourcePalletOrigin: EnsureSourcePallet<SourceChainAccountId>,
 	SourceChainAccountId: PartialEq + Clone,
 {
 	match message.origin {
@@ -377,7 +692,12 @@ where
 			ensure!(sender_origin == &RawOrigin::Root, BadOrigin);
 			Ok(None)
 		},
-		CallOrigin::TargetAccount(ref source_account_id, _, _) => {
+		CallOrigin::SourcePallet(ref pallet_id) => {
+			SourcePalletOrigin::ensure_source_pallet(sender_origin, pallet_id)?;
+			Ok(None)
+		},
+		CallOrigin::TargetAccount(ref source_account_id, _, _) |
+		CallOrigin::TargetAccountWithNonce(ref source_account_id, _, _, _) => {
 			ensure!(sender_origin == &RawOrigin::Signed(source_account_id.clone()), BadOrigin);
 			Ok(Some(source_account_id.clone()))
 		},
@@ -419,6 +739,36 @@ where
 	proof
 }
 
+/// Target account ownership digest from the source chain, protected from replay.
+///
+/// The byte vector returned by this function will be signed with a target chain account
+/// private key. This way, the owner of `source_account_id` on the source chain proves that
+/// the target chain account private key is also under his control. Every `nonce` may only be
+/// used once by the target account.
+pub fn account_ownership_digest_with_nonce<Call, AccountId, SpecVersion>(
+	call: &Call,
+	source_account_id: AccountId,
+	nonce: TargetAccountNonce,
+	target_spec_version: SpecVersion,
+	source_chain_id: ChainId,
+	target_chain_id: ChainId,
+) -> Vec<u8>
+where
+	Call: Encode,
+	AccountId: Encode,
+	SpecVersion: Encode,
+{
+	let mut proof = Vec::new();
+	call.encode_to(&mut proof);
+	source_account_id.encode_to(&mut proof);
+	nonce.encode_to(&mut proof);
+	target_spec_version.encode_to(&mut proof);
+	source_chain_id.encode_to(&mut proof);
+	target_chain_id.encode_to(&mut proof);
+
+	proof
+}
+
 #[cfg(test)]
 mod tests {
 	// From construct_runtime macro
@@ -493,6 +843,9 @@ mod tests {
 		pub const MaximumBlockWeight: Weight = 1024;
 		pub const MaximumBlockLength: u32 = 2 * 1024;
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
+		pub const MaxUsedNoncesPerAccount: u32 = 2;
+		pub const MinCompatibleSpecVersion: SpecVersion = 10;
+		pub const MaxCompatibleSpecVersion: SpecVersion = 20;
 	}
 
 	impl frame_system::Config for TestRuntime {
@@ -530,8 +883,39 @@ mod tests {
 		type TargetChainSignature = TestSignature;
 		type Call = Call;
 		type CallFilter = TestCallFilter;
+		type CompatibleSpecVersions =
+			SpecVersionRange<MinCompatibleSpecVersion, MaxCompatibleSpecVersion>;
 		type EncodedCall = EncodedCall;
 		type AccountIdConverter = AccountIdConverter;
+		type MaxUsedNoncesPerAccount = MaxUsedNoncesPerAccount;
+		type OriginConverter = TestOriginConverter;
+		type SourcePalletOrigin = EnsureRootSourcePallets<TestSourcePallets>;
+	}
+
+	const DAO_PALLET_ID: bp_runtime::PalletId = *b"test/dao";
+
+	pub struct TestSourcePallets;
+
+	impl Contains<bp_runtime::PalletId> for TestSourcePallets {
+		fn contains(pallet_id: &bp_runtime::PalletId) -> bool {
+			*pallet_id == DAO_PALLET_ID
+		}
+	}
+
+	pub struct TestOriginConverter;
+
+	impl ConvertCallOrigin<CallOriginOf<TestRuntime, ()>, AccountId, Origin> for TestOriginConverter {
+		fn convert_call_origin(
+			source_chain: ChainId,
+			call_origin: &CallOriginOf<TestRuntime, ()>,
+			origin_account: AccountId,
+		) -> Origin {
+			match *call_origin {
+				CallOrigin::SourcePallet(DAO_PALLET_ID) if source_chain == SOURCE_CHAIN_ID =>
+					RawOrigin::Root.into(),
+				_ => RawOrigin::Signed(origin_account).into(),
+			}
+		}
 	}
 
 	#[derive(Decode, Encode)]
@@ -594,6 +978,44 @@ mod tests {
 		prepare_message(origin, call)
 	}
 
+	fn prepare_target_message_with_nonce(
+		call: Call,
+		nonce: TargetAccountNonce,
+	) -> <Pallet<TestRuntime> as MessageDispatch<
+		AccountId,
+		<TestRuntime as Config>::BridgeMessageId,
+	>>::Message {
+		let origin =
+			CallOrigin::TargetAccountWithNonce(1, TestAccountPublic(1), TestSignature(1), nonce);
+		prepare_message(origin, call)
+	}
+
+	fn dispatch_target_message_with_nonce(nonce: TargetAccountNonce) -> bool {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let message = prepare_target_message_with_nonce(call, nonce);
+		Dispatch::dispatch(
+			SOURCE_CHAIN_ID,
+			TARGET_CHAIN_ID,
+			[0; 4],
+			Ok(message),
+			|_, _| unreachable!(),
+		)
+		.dispatch_result
+	}
+
+	fn verify_test_message_origin(
+		sender_origin: &RawOrigin<AccountId>,
+		message: &<Pallet<TestRuntime> as MessageDispatch<
+			AccountId,
+			<TestRuntime as Config>::BridgeMessageId,
+		>>::Message,
+	) -> Result<Option<AccountId>, BadOrigin> {
+		verify_message_origin::<<TestRuntime as Config>::SourcePalletOrigin, _, _, _, _>(
+			sender_origin,
+			message,
+		)
+	}
+
 	fn prepare_source_message(
 		call: Call,
 	) -> <Pallet<TestRuntime> as MessageDispatch<
@@ -646,14 +1068,15 @@ mod tests {
 	}
 
 	#[test]
-	fn should_fail_on_weight_mismatch() {
+	fn should_fail_on_spec_version_outside_of_compatible_range() {
 		new_test_ext().execute_with(|| {
 			let id = [0; 4];
-			let call = Call::System(frame_system::Call::set_heap_pages { pages: 42 });
-			let call_weight = call.get_dispatch_info().weight;
-			let mut message = prepare_root_message(call);
-			message.weight = 7;
-			assert!(call_weight > 7, "needed for test to actually trigger a weight mismatch");
+
+			const INCOMPATIBLE_SPEC_VERSION: SpecVersion = 21;
+			let mut message = prepare_root_message(Call::System(frame_system::Call::remark {
+				remark: vec![1, 2, 3],
+			}));
+			message.spec_version = INCOMPATIBLE_SPEC_VERSION;
 
 			System::set_block_number(1);
 			let result = Dispatch::dispatch(
@@ -663,7 +1086,6 @@ mod tests {
 				Ok(message),
 				|_, _| unreachable!(),
 			);
-			assert_eq!(result.unspent_weight, 7);
 			assert!(!result.dispatch_result);
 
 			assert_eq!(
@@ -671,11 +1093,11 @@ mod tests {
 				vec![EventRecord {
 					phase: Phase::Initialization,
 					event: Event::Dispatch(
-						call_dispatch::Event::<TestRuntime>::MessageWeightMismatch(
+						call_dispatch::Event::<TestRuntime>::MessageVersionSpecMismatch(
 							SOURCE_CHAIN_ID,
 							id,
-							call_weight,
-							7,
+							TEST_SPEC_VERSION,
+							INCOMPATIBLE_SPEC_VERSION
 						)
 					),
 					topics: vec![],
@@ -685,16 +1107,14 @@ mod tests {
 	}
 
 	#[test]
-	fn should_fail_on_signature_mismatch() {
+	fn should_dispatch_message_with_compatible_spec_version() {
 		new_test_ext().execute_with(|| {
 			let id = [0; 4];
 
-			let call_origin = CallOrigin::TargetAccount(1, TestAccountPublic(1), TestSignature(99));
-			let message = prepare_message(
-				call_origin,
-				Call::System(frame_system::Call::remark { remark: vec![1, 2, 3] }),
-			);
-			let weight = message.weight;
+			let mut message = prepare_root_message(Call::System(frame_system::Call::remark {
+				remark: vec![1, 2, 3],
+			}));
+			message.spec_version = MinCompatibleSpecVersion::get();
 
 			System::set_block_number(1);
 			let result = Dispatch::dispatch(
@@ -704,19 +1124,17 @@ mod tests {
 				Ok(message),
 				|_, _| unreachable!(),
 			);
-			assert_eq!(result.unspent_weight, weight);
-			assert!(!result.dispatch_result);
+			assert!(result.dispatch_result);
 
 			assert_eq!(
 				System::events(),
 				vec![EventRecord {
 					phase: Phase::Initialization,
-					event: Event::Dispatch(
-						call_dispatch::Event::<TestRuntime>::MessageSignatureMismatch(
-							SOURCE_CHAIN_ID,
-							id
-						)
-					),
+					event: Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageDispatched(
+						SOURCE_CHAIN_ID,
+						id,
+						Ok(())
+					)),
 					topics: vec![],
 				}],
 			);
@@ -724,27 +1142,39 @@ mod tests {
 	}
 
 	#[test]
-	fn should_emit_event_for_rejected_messages() {
+	fn should_fail_on_weight_mismatch_of_message_with_compatible_spec_version() {
 		new_test_ext().execute_with(|| {
 			let id = [0; 4];
+			let call = Call::System(frame_system::Call::set_heap_pages { pages: 42 });
+			let call_weight = call.get_dispatch_info().weight;
+			let mut message = prepare_root_message(call);
+			message.spec_version = MinCompatibleSpecVersion::get();
+			message.weight = 7;
+			assert!(call_weight > 7, "needed for test to actually trigger a weight mismatch");
 
 			System::set_block_number(1);
-			Dispatch::dispatch(
+			let result = Dispatch::dispatch(
 				SOURCE_CHAIN_ID,
 				TARGET_CHAIN_ID,
 				id,
-				Err(()),
+				Ok(message),
 				|_, _| unreachable!(),
 			);
+			assert_eq!(result.unspent_weight, 7);
+			assert!(!result.dispatch_result);
 
 			assert_eq!(
 				System::events(),
 				vec![EventRecord {
 					phase: Phase::Initialization,
-					event: Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageRejected(
-						SOURCE_CHAIN_ID,
-						id
-					)),
+					event: Event::Dispatch(
+						call_dispatch::Event::<TestRuntime>::MessageWeightMismatch(
+							SOURCE_CHAIN_ID,
+							id,
+							call_weight,
+							7,
+						)
+					),
 					topics: vec![],
 				}],
 			);
@@ -752,15 +1182,14 @@ mod tests {
 	}
 
 	#[test]
-	fn should_fail_on_call_decode() {
+	fn should_fail_on_weight_mismatch() {
 		new_test_ext().execute_with(|| {
 			let id = [0; 4];
-
-			let mut message = prepare_root_message(Call::System(frame_system::Call::remark {
-				remark: vec![1, 2, 3],
-			}));
-			let weight = message.weight;
-			message.call.0 = vec![];
+			let call = Call::System(frame_system::Call::set_heap_pages { pages: 42 });
+			let call_weight = call.get_dispatch_info().weight;
+			let mut message = prepare_root_message(call);
+			message.weight = 7;
+			assert!(call_weight > 7, "needed for test to actually trigger a weight mismatch");
 
 			System::set_block_number(1);
 			let result = Dispatch::dispatch(
@@ -770,7 +1199,7 @@ mod tests {
 				Ok(message),
 				|_, _| unreachable!(),
 			);
-			assert_eq!(result.unspent_weight, weight);
+			assert_eq!(result.unspent_weight, 7);
 			assert!(!result.dispatch_result);
 
 			assert_eq!(
@@ -778,9 +1207,11 @@ mod tests {
 				vec![EventRecord {
 					phase: Phase::Initialization,
 					event: Event::Dispatch(
-						call_dispatch::Event::<TestRuntime>::MessageCallDecodeFailed(
+						call_dispatch::Event::<TestRuntime>::MessageWeightMismatch(
 							SOURCE_CHAIN_ID,
-							id
+							id,
+							call_weight,
+							7,
 						)
 					),
 					topics: vec![],
@@ -790,15 +1221,16 @@ mod tests {
 	}
 
 	#[test]
-	fn should_emit_event_for_rejected_calls() {
+	fn should_fail_on_signature_mismatch() {
 		new_test_ext().execute_with(|| {
 			let id = [0; 4];
 
-			let call =
-				Call::System(frame_system::Call::fill_block { ratio: Perbill::from_percent(75) });
-			let weight = call.get_dispatch_info().weight;
-			let mut message = prepare_root_message(call);
-			message.weight = weight;
+			let call_origin = CallOrigin::TargetAccount(1, TestAccountPublic(1), TestSignature(99));
+			let message = prepare_message(
+				call_origin,
+				Call::System(frame_system::Call::remark { remark: vec![1, 2, 3] }),
+			);
+			let weight = message.weight;
 
 			System::set_block_number(1);
 			let result = Dispatch::dispatch(
@@ -816,7 +1248,7 @@ mod tests {
 				vec![EventRecord {
 					phase: Phase::Initialization,
 					event: Event::Dispatch(
-						call_dispatch::Event::<TestRuntime>::MessageCallRejected(
+						call_dispatch::Event::<TestRuntime>::MessageSignatureMismatch(
 							SOURCE_CHAIN_ID,
 							id
 						)
@@ -828,13 +1260,117 @@ mod tests {
 	}
 
 	#[test]
-	fn should_emit_event_for_unpaid_calls() {
+	fn should_emit_event_for_rejected_messages() {
 		new_test_ext().execute_with(|| {
 			let id = [0; 4];
 
-			let mut message = prepare_root_message(Call::System(frame_system::Call::remark {
-				remark: vec![1, 2, 3],
-			}));
+			System::set_block_number(1);
+			Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Err(()),
+				|_, _| unreachable!(),
+			);
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageRejected(
+						SOURCE_CHAIN_ID,
+						id
+					)),
+					topics: vec![],
+				}],
+			);
+		});
+	}
+
+	#[test]
+	fn should_fail_on_call_decode() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let mut message = prepare_root_message(Call::System(frame_system::Call::remark {
+				remark: vec![1, 2, 3],
+			}));
+			let weight = message.weight;
+			message.call.0 = vec![];
+
+			System::set_block_number(1);
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert_eq!(result.unspent_weight, weight);
+			assert!(!result.dispatch_result);
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Dispatch(
+						call_dispatch::Event::<TestRuntime>::MessageCallDecodeFailed(
+							SOURCE_CHAIN_ID,
+							id
+						)
+					),
+					topics: vec![],
+				}],
+			);
+		});
+	}
+
+	#[test]
+	fn should_emit_event_for_rejected_calls() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let call =
+				Call::System(frame_system::Call::fill_block { ratio: Perbill::from_percent(75) });
+			let weight = call.get_dispatch_info().weight;
+			let mut message = prepare_root_message(call);
+			message.weight = weight;
+
+			System::set_block_number(1);
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert_eq!(result.unspent_weight, weight);
+			assert!(!result.dispatch_result);
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Dispatch(
+						call_dispatch::Event::<TestRuntime>::MessageCallRejected(
+							SOURCE_CHAIN_ID,
+							id
+						)
+					),
+					topics: vec![],
+				}],
+			);
+		});
+	}
+
+	#[test]
+	fn should_emit_event_for_unpaid_calls() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let mut message = prepare_root_message(Call::System(frame_system::Call::remark {
+				remark: vec![1, 2, 3],
+			}));
 			let weight = message.weight;
 			message.dispatch_fee_payment = DispatchFeePayment::AtTargetChain;
 
@@ -1005,6 +1541,195 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn should_dispatch_bridge_message_from_target_origin_with_nonce() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let call = Call::System(frame_system::Call::remark { remark: vec![] });
+			let message = prepare_target_message_with_nonce(call, 42);
+
+			System::set_block_number(1);
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(!result.dispatch_fee_paid_during_dispatch);
+			assert!(result.dispatch_result);
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageDispatched(
+						SOURCE_CHAIN_ID,
+						id,
+						Ok(())
+					)),
+					topics: vec![],
+				}],
+			);
+			assert_eq!(UsedTargetAccountNonces::<TestRuntime>::get(1).into_inner(), vec![42]);
+		})
+	}
+
+	#[test]
+	fn should_fail_on_nonce_reuse() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			assert!(dispatch_target_message_with_nonce(42));
+
+			let call = Call::System(frame_system::Call::remark { remark: vec![] });
+			let message = prepare_target_message_with_nonce(call, 42);
+
+			System::set_block_number(1);
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert_eq!(result.unspent_weight, TEST_WEIGHT);
+			assert!(!result.dispatch_result);
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Dispatch(
+						call_dispatch::Event::<TestRuntime>::MessageNonceAlreadyUsed(
+							SOURCE_CHAIN_ID,
+							id,
+							42,
+						)
+					),
+					topics: vec![],
+				}],
+			);
+		})
+	}
+
+	#[test]
+	fn should_not_consume_nonce_on_signature_mismatch() {
+		new_test_ext().execute_with(|| {
+			let call = Call::System(frame_system::Call::remark { remark: vec![] });
+			let call_origin =
+				CallOrigin::TargetAccountWithNonce(1, TestAccountPublic(1), TestSignature(99), 42);
+			let message = prepare_message(call_origin, call);
+
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				[0; 4],
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(!result.dispatch_result);
+			assert!(UsedTargetAccountNonces::<TestRuntime>::get(1).is_empty());
+
+			assert!(dispatch_target_message_with_nonce(42));
+		})
+	}
+
+	#[test]
+	fn should_not_consume_nonce_on_filtered_call() {
+		new_test_ext().execute_with(|| {
+			let call =
+				Call::System(frame_system::Call::fill_block { ratio: Perbill::from_percent(75) });
+			let message = prepare_target_message_with_nonce(call, 42);
+
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				[0; 4],
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(!result.dispatch_result);
+			assert!(UsedTargetAccountNonces::<TestRuntime>::get(1).is_empty());
+
+			assert!(dispatch_target_message_with_nonce(42));
+		})
+	}
+
+	#[test]
+	fn should_not_consume_nonce_on_weight_mismatch() {
+		new_test_ext().execute_with(|| {
+			let call = Call::System(frame_system::Call::remark { remark: vec![] });
+			let mut message = prepare_target_message_with_nonce(call, 42);
+			message.weight = 0;
+
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				[0; 4],
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(!result.dispatch_result);
+			assert!(UsedTargetAccountNonces::<TestRuntime>::get(1).is_empty());
+
+			assert!(dispatch_target_message_with_nonce(42));
+		})
+	}
+
+	#[test]
+	fn should_not_consume_nonce_if_dispatch_fee_payment_fails() {
+		new_test_ext().execute_with(|| {
+			let call = Call::System(frame_system::Call::remark { remark: vec![] });
+			let mut message = prepare_target_message_with_nonce(call, 42);
+			message.dispatch_fee_payment = DispatchFeePayment::AtTargetChain;
+
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				[0; 4],
+				Ok(message),
+				|_, _| Err(()),
+			);
+			assert!(!result.dispatch_result);
+			assert!(UsedTargetAccountNonces::<TestRuntime>::get(1).is_empty());
+
+			assert!(dispatch_target_message_with_nonce(42));
+		})
+	}
+
+	#[test]
+	fn should_accept_out_of_order_nonces_within_tracked_window() {
+		new_test_ext().execute_with(|| {
+			assert!(dispatch_target_message_with_nonce(5));
+			assert!(dispatch_target_message_with_nonce(3));
+			assert_eq!(UsedTargetAccountNonces::<TestRuntime>::get(1).into_inner(), vec![3, 5]);
+			assert_eq!(MinTargetAccountNonce::<TestRuntime>::get(1), 0);
+
+			// the window is full and nonce is below all tracked nonces => it is accepted, but
+			// all nonces <= 1 are rejected from now on
+			assert!(dispatch_target_message_with_nonce(1));
+			assert_eq!(UsedTargetAccountNonces::<TestRuntime>::get(1).into_inner(), vec![3, 5]);
+			assert_eq!(MinTargetAccountNonce::<TestRuntime>::get(1), 2);
+			assert!(!dispatch_target_message_with_nonce(0));
+			assert!(!dispatch_target_message_with_nonce(1));
+
+			// the window is full => smallest nonce (3) is forgotten and all nonces <= 3 are
+			// rejected from now on
+			assert!(dispatch_target_message_with_nonce(4));
+			assert_eq!(UsedTargetAccountNonces::<TestRuntime>::get(1).into_inner(), vec![4, 5]);
+			assert_eq!(MinTargetAccountNonce::<TestRuntime>::get(1), 4);
+			assert!(!dispatch_target_message_with_nonce(3));
+			assert!(!dispatch_target_message_with_nonce(4));
+			assert!(!dispatch_target_message_with_nonce(5));
+
+			assert!(dispatch_target_message_with_nonce(10));
+			assert_eq!(UsedTargetAccountNonces::<TestRuntime>::get(1).into_inner(), vec![5, 10]);
+			assert_eq!(MinTargetAccountNonce::<TestRuntime>::get(1), 5);
+		})
+	}
+
 	#[test]
 	fn should_dispatch_bridge_message_from_source_origin() {
 		new_test_ext().execute_with(|| {
@@ -1039,16 +1764,197 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn should_dispatch_bridge_message_from_source_pallet_with_custom_origin() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let call = Call::System(frame_system::Call::set_heap_pages { pages: 64 });
+			let message = prepare_message(CallOrigin::SourcePallet(DAO_PALLET_ID), call);
+
+			System::set_block_number(1);
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(result.dispatch_result);
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageDispatched(
+						SOURCE_CHAIN_ID,
+						id,
+						Ok(())
+					)),
+					topics: vec![],
+				}],
+			);
+		})
+	}
+
+	#[test]
+	fn should_dispatch_bridge_message_from_source_pallet_with_default_origin() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let call = Call::System(frame_system::Call::set_heap_pages { pages: 64 });
+			let message = prepare_message(CallOrigin::SourcePallet(*b"test/bad"), call);
+
+			System::set_block_number(1);
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(!result.dispatch_result);
+
+			assert_eq!(
+				System::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageDispatched(
+						SOURCE_CHAIN_ID,
+						id,
+						Err(sp_runtime::DispatchError::BadOrigin)
+					)),
+					topics: vec![],
+				}],
+			);
+		})
+	}
+
+	#[test]
+	fn should_pay_dispatch_fee_from_source_pallet_account() {
+		new_test_ext().execute_with(|| {
+			let call = Call::System(frame_system::Call::remark { remark: vec![] });
+			let mut message = prepare_message(CallOrigin::SourcePallet(DAO_PALLET_ID), call);
+			message.dispatch_fee_payment = DispatchFeePayment::AtTargetChain;
+
+			let expected_account = AccountIdConverter::convert(derive_account_id::<AccountId>(
+				SOURCE_CHAIN_ID,
+				SourceAccount::Pallet(DAO_PALLET_ID),
+			));
+			let result = Dispatch::dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				[0; 4],
+				Ok(message),
+				|account, _| {
+					assert_eq!(*account, expected_account);
+					Ok(())
+				},
+			);
+			assert!(result.dispatch_fee_paid_during_dispatch);
+			assert!(result.dispatch_result);
+		})
+	}
+
+	#[test]
+	fn dry_run_dispatch_returns_events_and_reverts_changes() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let message = prepare_target_message_with_nonce(
+				Call::System(frame_system::Call::remark { remark: vec![] }),
+				42,
+			);
+
+			System::set_block_number(1);
+			let result = Dispatch::dry_run_dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(result.dispatch_result.dispatch_result);
+			assert_eq!(result.actual_weight, TEST_WEIGHT - result.dispatch_result.unspent_weight);
+			assert_eq!(
+				result.events,
+				vec![Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageDispatched(
+					SOURCE_CHAIN_ID,
+					id,
+					Ok(())
+				))],
+			);
+
+			// nothing is changed => events are not deposited and nonce is not used
+			assert!(System::events().is_empty());
+			assert!(UsedTargetAccountNonces::<TestRuntime>::get(1).is_empty());
+		})
+	}
+
+	#[test]
+	fn dry_run_dispatch_returns_rejection_event() {
+		new_test_ext().execute_with(|| {
+			let id = [0; 4];
+
+			let call =
+				Call::System(frame_system::Call::fill_block { ratio: Perbill::from_percent(75) });
+			let message = prepare_root_message(call);
+
+			System::set_block_number(1);
+			let result = Dispatch::dry_run_dispatch(
+				SOURCE_CHAIN_ID,
+				TARGET_CHAIN_ID,
+				id,
+				Ok(message),
+				|_, _| unreachable!(),
+			);
+			assert!(!result.dispatch_result.dispatch_result);
+			assert_eq!(result.actual_weight, 0);
+			assert_eq!(
+				result.events,
+				vec![Event::Dispatch(call_dispatch::Event::<TestRuntime>::MessageCallRejected(
+					SOURCE_CHAIN_ID,
+					id
+				))],
+			);
+			assert!(System::events().is_empty());
+		})
+	}
+
+	#[test]
+	fn origin_is_checked_when_verifying_sending_message_using_source_pallet() {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let message = prepare_message(CallOrigin::SourcePallet(DAO_PALLET_ID), call);
+
+		// When message is sent by Root, CallOrigin::SourcePallet is allowed
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Root, &message), Ok(None)));
+
+		// when message is sent by some real account, CallOrigin::SourcePallet is not allowed
+		assert!(matches!(
+			verify_test_message_origin(&RawOrigin::Signed(1), &message),
+			Err(BadOrigin)
+		));
+
+		// when message is sent on behalf of pallet, which is not allowed by the
+		// `SourcePalletOrigin`, it is rejected even if it is sent by Root
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let message = prepare_message(CallOrigin::SourcePallet(*b"test/bad"), call);
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Root, &message), Err(BadOrigin)));
+	}
+
 	#[test]
 	fn origin_is_checked_when_verifying_sending_message_using_source_root_account() {
 		let call = Call::System(frame_system::Call::remark { remark: vec![] });
 		let message = prepare_root_message(call);
 
 		// When message is sent by Root, CallOrigin::SourceRoot is allowed
-		assert!(matches!(verify_message_origin(&RawOrigin::Root, &message), Ok(None)));
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Root, &message), Ok(None)));
 
 		// when message is sent by some real account, CallOrigin::SourceRoot is not allowed
-		assert!(matches!(verify_message_origin(&RawOrigin::Signed(1), &message), Err(BadOrigin)));
+		assert!(matches!(
+			verify_test_message_origin(&RawOrigin::Signed(1), &message),
+			Err(BadOrigin)
+		));
 	}
 
 	#[test]
@@ -1057,14 +1963,30 @@ mod tests {
 		let message = prepare_target_message(call);
 
 		// When message is sent by Root, CallOrigin::TargetAccount is not allowed
-		assert!(matches!(verify_message_origin(&RawOrigin::Root, &message), Err(BadOrigin)));
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Root, &message), Err(BadOrigin)));
 
 		// When message is sent by some other account, it is rejected
-		assert!(matches!(verify_message_origin(&RawOrigin::Signed(2), &message), Err(BadOrigin)));
+		assert!(matches!(
+			verify_test_message_origin(&RawOrigin::Signed(2), &message),
+			Err(BadOrigin)
+		));
 
 		// When message is sent by a real account, it is allowed to have origin
 		// CallOrigin::TargetAccount
-		assert!(matches!(verify_message_origin(&RawOrigin::Signed(1), &message), Ok(Some(1))));
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Signed(1), &message), Ok(Some(1))));
+	}
+
+	#[test]
+	fn origin_is_checked_when_verifying_sending_message_using_target_account_with_nonce() {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let message = prepare_target_message_with_nonce(call, 0);
+
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Root, &message), Err(BadOrigin)));
+		assert!(matches!(
+			verify_test_message_origin(&RawOrigin::Signed(2), &message),
+			Err(BadOrigin)
+		));
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Signed(1), &message), Ok(Some(1))));
 	}
 
 	#[test]
@@ -1073,12 +1995,15 @@ mod tests {
 		let message = prepare_source_message(call);
 
 		// Sending a message from the expected origin account works
-		assert!(matches!(verify_message_origin(&RawOrigin::Signed(1), &message), Ok(Some(1))));
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Signed(1), &message), Ok(Some(1))));
 
 		// If we send a message from a different account, it is rejected
-		assert!(matches!(verify_message_origin(&RawOrigin::Signed(2), &message), Err(BadOrigin)));
+		assert!(matches!(
+			verify_test_message_origin(&RawOrigin::Signed(2), &message),
+			Err(BadOrigin)
+		));
 
 		// The Root account is allowed to assume any expected origin account
-		assert!(matches!(verify_message_origin(&RawOrigin::Root, &message), Ok(Some(1))));
+		assert!(matches!(verify_test_message_origin(&RawOrigin::Root, &message), Ok(Some(1))));
 	}
 }
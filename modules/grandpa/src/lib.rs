@@ -1161,5 +1161,15 @@ mod tests {
 			BestFinalized::<TestRuntime>::storage_value_final_key().to_vec(),
 			bp_header_chain::storage_keys::best_finalized_hash_key("Grandpa").0,
 		);
+
+		assert_eq!(
+			CurrentAuthoritySet::<TestRuntime>::storage_value_final_key().to_vec(),
+			bp_header_chain::storage_keys::current_authority_set_key("Grandpa").0,
+		);
+
+		assert_eq!(
+			InitialHash::<TestRuntime>::storage_value_final_key().to_vec(),
+			bp_header_chain::storage_keys::initial_hash_key("Grandpa").0,
+		);
 	}
 }
@@ -282,6 +282,36 @@ benchmarks_instance_pallet! {
 		assert_eq!(T::account_balance(&sender), 0.into());
 	}
 
+	// Benchmark `increase_messages_fee` with following conditions:
+	// * number of messages varies from 1 to 64;
+	// * every message has minimal size.
+	//
+	// Message size overhead is computed using results of `increase_message_fee` benchmark.
+	increase_messages_fee {
+		let i in 1..64;
+
+		let relayers_fund_id = crate::relayer_fund_account_id::<T::AccountId, T::AccountIdConverter>();
+		let sender = account("sender", 42, SEED);
+		T::endow_account(&sender);
+		T::endow_account(&relayers_fund_id);
+
+		let initial_balance = T::account_balance(&sender);
+		let additional_fee_per_message = T::message_fee();
+		let lane_id = T::bench_lane_id();
+
+		for _ in 0..i {
+			send_regular_message::<T, I>();
+		}
+	}: increase_messages_fee(
+		RawOrigin::Signed(sender.clone()),
+		lane_id,
+		1..=i as MessageNonce,
+		additional_fee_per_message
+	)
+	verify {
+		assert!(T::account_balance(&sender) < initial_balance);
+	}
+
 	// Benchmark `receive_messages_proof` extrinsic with single minimal-weight message and following conditions:
 	// * proof does not include outbound lane state proof;
 	// * inbound lane already has state, so it needs to be read and decoded;
@@ -507,6 +537,7 @@ benchmarks_instance_pallet! {
 			unrewarded_relayer_entries: 1,
 			messages_in_oldest_entry: 1,
 			total_messages: 1,
+			last_delivered_nonce: 1,
 		};
 		let proof = T::prepare_message_delivery_proof(MessageDeliveryProofParams {
 			lane: T::bench_lane_id(),
@@ -548,6 +579,7 @@ benchmarks_instance_pallet! {
 			unrewarded_relayer_entries: 1,
 			messages_in_oldest_entry: 2,
 			total_messages: 2,
+			last_delivered_nonce: 2,
 		};
 		let mut delivered_messages = DeliveredMessages::new(1, true);
 		delivered_messages.note_dispatched_message(true);
@@ -590,6 +622,7 @@ benchmarks_instance_pallet! {
 			unrewarded_relayer_entries: 2,
 			messages_in_oldest_entry: 1,
 			total_messages: 2,
+			last_delivered_nonce: 2,
 		};
 		let proof = T::prepare_message_delivery_proof(MessageDeliveryProofParams {
 			lane: T::bench_lane_id(),
@@ -71,7 +71,9 @@ use frame_system::RawOrigin;
 use num_traits::{SaturatingAdd, Zero};
 use sp_core::H256;
 use sp_runtime::traits::{BadOrigin, Convert};
-use sp_std::{cell::RefCell, cmp::PartialOrd, marker::PhantomData, prelude::*};
+use sp_std::{
+	cell::RefCell, cmp::PartialOrd, marker::PhantomData, ops::RangeInclusive, prelude::*,
+};
 
 mod inbound_lane;
 mod outbound_lane;
@@ -547,18 +549,20 @@ pub mod pallet {
 
 			// verify that the relayer has declared correct `lane_data::relayers` state
 			// (we only care about total number of entries and messages, because this affects call
-			// weight)
+			// weight, and about the last delivered nonce, because it is used to reject obsolete
+			// transactions before they're included in the block)
+			let last_delivered_nonce = lane_data.last_delivered_nonce();
 			ensure!(
 				total_unrewarded_messages(&lane_data.relayers).unwrap_or(MessageNonce::MAX) ==
 					relayers_state.total_messages &&
 					lane_data.relayers.len() as MessageNonce ==
-						relayers_state.unrewarded_relayer_entries,
+						relayers_state.unrewarded_relayer_entries &&
+					last_delivered_nonce == relayers_state.last_delivered_nonce,
 				Error::<T, I>::InvalidUnrewardedRelayersState
 			);
 
 			// mark messages as delivered
 			let mut lane = outbound_lane::<T, I>(lane_id);
-			let last_delivered_nonce = lane_data.last_delivered_nonce();
 			let confirmed_messages = match lane.confirm_delivery(
 				relayers_state.total_messages,
 				last_delivered_nonce,
@@ -649,6 +653,86 @@ pub mod pallet {
 
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
+
+		/// Pay additional fee for every message in the given inclusive range of nonces.
+		///
+		/// All messages in the range must be sent and not yet delivered. The total additional fee
+		/// (`additional_fee_per_message` multiplied by the number of messages) is withdrawn from
+		/// the submitter at once.
+		#[pallet::weight(T::WeightInfo::maximal_increase_messages_fee_weight(messages_in_range(
+			nonces
+		)))]
+		pub fn increase_messages_fee(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			nonces: RangeInclusive<MessageNonce>,
+			additional_fee_per_message: T::OutboundMessageFee,
+		) -> DispatchResultWithPostInfo {
+			ensure_not_halted::<T, I>()?;
+			ensure!(!nonces.is_empty(), Error::<T, I>::EmptyMessagesRange);
+			// the same rules as in `increase_message_fee` are applied to every message in the range
+			let lane = outbound_lane::<T, I>(lane_id);
+			ensure!(
+				*nonces.start() > lane.data().latest_received_nonce,
+				Error::<T, I>::MessageIsAlreadyDelivered
+			);
+			ensure!(
+				*nonces.end() <= lane.data().latest_generated_nonce,
+				Error::<T, I>::MessageIsNotYetSent
+			);
+
+			// withdraw additional fee for all messages from submitter
+			//
+			// saturating_add is fine here - overflow here means that someone controls all chain
+			// funds, which shouldn't ever happen + `pay_delivery_and_dispatch_fee` will fail
+			let messages_count = messages_in_range(&nonces);
+			let additional_fee = (0..messages_count)
+				.fold(T::OutboundMessageFee::zero(), |additional_fee, _| {
+					additional_fee.saturating_add(&additional_fee_per_message)
+				});
+			let submitter = origin.into().map_err(|_| BadOrigin)?;
+			T::MessageDeliveryAndDispatchPayment::pay_delivery_and_dispatch_fee(
+				&submitter,
+				&additional_fee,
+				&relayer_fund_account_id::<T::AccountId, T::AccountIdConverter>(),
+			)
+			.map_err(|err| {
+				log::trace!(
+					target: "runtime::bridge-messages",
+					"Submitter {:?} can't pay additional fee {:?} for the messages {:?}/{:?} to {:?}: {:?}",
+					submitter,
+					additional_fee,
+					lane_id,
+					nonces,
+					relayer_fund_account_id::<T::AccountId, T::AccountIdConverter>(),
+					err,
+				);
+
+				Error::<T, I>::FailedToWithdrawMessageFee
+			})?;
+
+			// and finally update fees in the storage
+			let mut total_messages_size: u32 = 0;
+			for nonce in nonces {
+				let message_key = MessageKey { lane_id, nonce };
+				let message_size = OutboundMessages::<T, I>::mutate(message_key, |message_data| {
+					let message_data = message_data.as_mut().expect(
+						"the message is sent and not yet delivered; so it is in the storage; qed",
+					);
+					message_data.fee = message_data.fee.saturating_add(&additional_fee_per_message);
+					message_data.payload.len()
+				});
+				total_messages_size = total_messages_size.saturating_add(message_size as _);
+			}
+
+			// compute actual dispatch weight that depends on the stored messages size
+			let actual_weight = sp_std::cmp::min(
+				T::WeightInfo::maximal_increase_messages_fee_weight(messages_count),
+				T::WeightInfo::increase_messages_fee_weight(messages_count, total_messages_size),
+			);
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
 	}
 
 	#[pallet::event]
@@ -690,6 +774,8 @@ pub mod pallet {
 		/// The number of actually confirmed messages is going to be larger than the number of
 		/// messages in the proof. This may mean that this or bridged chain storage is corrupted.
 		TryingToConfirmMoreMessagesThanExpected,
+		/// The range of messages someone is trying to work with (i.e. increase fee) is empty.
+		EmptyMessagesRange,
 	}
 
 	/// Optional pallet owner.
@@ -769,7 +855,9 @@ pub mod pallet {
 		pub fn inbound_unrewarded_relayers_state(
 			lane: bp_messages::LaneId,
 		) -> bp_messages::UnrewardedRelayersState {
-			let relayers = InboundLanes::<T, I>::get(&lane).relayers;
+			let lane_data = InboundLanes::<T, I>::get(&lane);
+			let last_delivered_nonce = lane_data.last_delivered_nonce();
+			let relayers = lane_data.relayers;
 			bp_messages::UnrewardedRelayersState {
 				unrewarded_relayer_entries: relayers.len() as _,
 				messages_in_oldest_entry: relayers
@@ -777,6 +865,7 @@ pub mod pallet {
 					.map(|entry| 1 + entry.messages.end - entry.messages.begin)
 					.unwrap_or(0),
 				total_messages: total_unrewarded_messages(&relayers).unwrap_or(MessageNonce::MAX),
+				last_delivered_nonce,
 			}
 		}
 	}
@@ -815,6 +904,15 @@ where
 	}
 }
 
+/// Returns number of messages in the inclusive range of nonces.
+fn messages_in_range(nonces: &RangeInclusive<MessageNonce>) -> MessageNonce {
+	if nonces.is_empty() {
+		return 0
+	}
+
+	nonces.end().saturating_sub(*nonces.start()).saturating_add(1)
+}
+
 /// Function that actually sends message.
 fn send_message<T: Config<I>, I: 'static>(
 	submitter: bp_messages::source_chain::Sender<T::AccountId>,
@@ -1182,6 +1280,7 @@ mod tests {
 			UnrewardedRelayersState {
 				unrewarded_relayer_entries: 1,
 				total_messages: 1,
+				last_delivered_nonce: 1,
 				..Default::default()
 			},
 		));
@@ -1397,6 +1496,16 @@ mod tests {
 				Error::<TestRuntime, ()>::Halted,
 			);
 
+			assert_noop!(
+				Pallet::<TestRuntime>::increase_messages_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					1..=1,
+					1,
+				),
+				Error::<TestRuntime, ()>::Halted,
+			);
+
 			assert_noop!(
 				Pallet::<TestRuntime>::receive_messages_proof(
 					Origin::signed(1),
@@ -1424,6 +1533,7 @@ mod tests {
 						unrewarded_relayer_entries: 1,
 						messages_in_oldest_entry: 1,
 						total_messages: 1,
+						last_delivered_nonce: 1,
 					},
 				),
 				Error::<TestRuntime, ()>::Halted,
@@ -1456,6 +1566,13 @@ mod tests {
 				1,
 			));
 
+			assert_ok!(Pallet::<TestRuntime>::increase_messages_fee(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				1..=1,
+				1,
+			));
+
 			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
 				Origin::signed(1),
 				TEST_RELAYER_A,
@@ -1479,6 +1596,7 @@ mod tests {
 					unrewarded_relayer_entries: 1,
 					messages_in_oldest_entry: 1,
 					total_messages: 1,
+					last_delivered_nonce: 1,
 				},
 			));
 		});
@@ -1576,6 +1694,7 @@ mod tests {
 					unrewarded_relayer_entries: 2,
 					messages_in_oldest_entry: 1,
 					total_messages: 2,
+					last_delivered_nonce: 10,
 				},
 			);
 
@@ -1611,6 +1730,7 @@ mod tests {
 					unrewarded_relayer_entries: 2,
 					messages_in_oldest_entry: 1,
 					total_messages: 2,
+					last_delivered_nonce: 11,
 				},
 			);
 		});
@@ -1706,6 +1826,7 @@ mod tests {
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
 					total_messages: 1,
+					last_delivered_nonce: 1,
 					..Default::default()
 				},
 			));
@@ -1731,6 +1852,7 @@ mod tests {
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 2,
 					total_messages: 2,
+					last_delivered_nonce: 2,
 					..Default::default()
 				},
 			));
@@ -1800,6 +1922,33 @@ mod tests {
 					UnrewardedRelayersState {
 						unrewarded_relayer_entries: 2,
 						total_messages: 1,
+						last_delivered_nonce: 2,
+						..Default::default()
+					},
+				),
+				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			);
+
+			// when last delivered nonce is invalid
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							relayers: vec![
+								unrewarded_relayer(1, 1, TEST_RELAYER_A),
+								unrewarded_relayer(2, 2, TEST_RELAYER_B)
+							]
+							.into_iter()
+							.collect(),
+							..Default::default()
+						}
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 2,
+						total_messages: 2,
+						last_delivered_nonce: 1,
 						..Default::default()
 					},
 				),
@@ -1933,6 +2082,110 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn increase_messages_fee_fails_if_range_is_empty() {
+		run_test(|| {
+			send_regular_message();
+
+			#[allow(clippy::reversed_empty_ranges)]
+			let empty_range = 1..=0;
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::increase_messages_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					empty_range,
+					100,
+				),
+				Error::<TestRuntime, ()>::EmptyMessagesRange,
+			);
+		});
+	}
+
+	#[test]
+	fn increase_messages_fee_fails_if_some_message_is_already_delivered() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			receive_messages_delivery_proof();
+
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::increase_messages_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					1..=2,
+					100,
+				),
+				Error::<TestRuntime, ()>::MessageIsAlreadyDelivered,
+			);
+		});
+	}
+
+	#[test]
+	fn increase_messages_fee_fails_if_some_message_is_not_yet_sent() {
+		run_test(|| {
+			send_regular_message();
+
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::increase_messages_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					1..=2,
+					100,
+				),
+				Error::<TestRuntime, ()>::MessageIsNotYetSent,
+			);
+		});
+	}
+
+	#[test]
+	fn increase_messages_fee_fails_if_submitter_cant_pay_additional_fee() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+
+			TestMessageDeliveryAndDispatchPayment::reject_payments();
+
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::increase_messages_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					1..=2,
+					100,
+				),
+				Error::<TestRuntime, ()>::FailedToWithdrawMessageFee,
+			);
+		});
+	}
+
+	#[test]
+	fn increase_messages_fee_succeeds() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			send_regular_message();
+
+			assert_ok!(Pallet::<TestRuntime, ()>::increase_messages_fee(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				2..=3,
+				100,
+			));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_fee_paid(1, 200));
+
+			let message_fee = |nonce| {
+				OutboundMessages::<TestRuntime, ()>::get(MessageKey {
+					lane_id: TEST_LANE_ID,
+					nonce,
+				})
+				.expect("message is in the storage")
+				.fee
+			};
+			assert_eq!(message_fee(1), REGULAR_PAYLOAD.declared_weight);
+			assert_eq!(message_fee(2), REGULAR_PAYLOAD.declared_weight + 100);
+			assert_eq!(message_fee(3), REGULAR_PAYLOAD.declared_weight + 100);
+		});
+	}
+
 	#[test]
 	fn weight_refund_from_receive_messages_proof_works() {
 		run_test(|| {
@@ -2036,6 +2289,7 @@ mod tests {
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
 					total_messages: 2,
+					last_delivered_nonce: 2,
 					..Default::default()
 				},
 			));
@@ -2046,6 +2300,7 @@ mod tests {
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
 					total_messages: 1,
+					last_delivered_nonce: 3,
 					..Default::default()
 				},
 			));
@@ -2073,6 +2328,7 @@ mod tests {
 		let relayers_state = UnrewardedRelayersState {
 			unrewarded_relayer_entries: 1,
 			total_messages: 3,
+			last_delivered_nonce: 3,
 			..Default::default()
 		};
 		let pre_dispatch_weight =
@@ -2147,7 +2403,7 @@ mod tests {
 						TEST_LANE_ID,
 						InboundLaneData { last_confirmed_nonce: 1, relayers: Default::default() },
 					))),
-					UnrewardedRelayersState::default(),
+					UnrewardedRelayersState { last_delivered_nonce: 1, ..Default::default() },
 				),
 				Error::<TestRuntime, ()>::TryingToConfirmMoreMessagesThanExpected,
 			);
@@ -2196,6 +2452,42 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn increase_messages_fee_weight_depends_on_messages_count_and_size() {
+		run_test(|| {
+			let mut large_payload = message_payload(0, 100);
+			large_payload.extra = vec![2; 16_384];
+
+			send_regular_message();
+			send_regular_message();
+			assert_ok!(Pallet::<TestRuntime>::send_message(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				large_payload,
+				100,
+			));
+
+			let actual_weight = |nonces| {
+				Pallet::<TestRuntime>::increase_messages_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					nonces,
+					1,
+				)
+				.expect("increase_messages_fee has failed")
+				.actual_weight
+				.expect("increase_messages_fee always returns Some")
+			};
+
+			let single_message_weight = actual_weight(1..=1);
+			let two_messages_weight = actual_weight(1..=2);
+			let two_messages_with_large_weight = actual_weight(2..=3);
+
+			assert!(two_messages_weight > single_message_weight);
+			assert!(two_messages_with_large_weight > two_messages_weight);
+		});
+	}
+
 	#[test]
 	fn weight_is_refunded_for_messages_that_are_not_pruned() {
 		run_test(|| {
@@ -2226,6 +2518,7 @@ mod tests {
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
 					total_messages: max_messages_to_prune,
+					last_delivered_nonce: max_messages_to_prune,
 					..Default::default()
 				},
 			));
@@ -271,6 +271,29 @@ pub trait WeightInfoExt: WeightInfo {
 			.saturating_add(callback_overhead)
 	}
 
+	/// Weight of the extrinsic that increases fee of all messages in the range
+	/// (`increase_messages_fee`).
+	fn increase_messages_fee_weight(
+		messages_count: MessageNonce,
+		total_messages_size: u32,
+	) -> Weight {
+		let transaction_overhead = Self::increase_messages_fee_overhead(messages_count);
+		let messages_size_overhead = Self::increase_message_fee_size_overhead(total_messages_size);
+
+		transaction_overhead.saturating_add(messages_size_overhead)
+	}
+
+	/// Maximal weight of the extrinsic that increases fee of all messages in the range
+	/// (`increase_messages_fee`). This weight is used when all messages have maximal size.
+	fn maximal_increase_messages_fee_weight(messages_count: MessageNonce) -> Weight {
+		let transaction_overhead = Self::increase_messages_fee_overhead(messages_count);
+		let single_maximal_message_size_overhead =
+			Self::maximal_increase_message_fee().saturating_sub(Self::increase_message_fee(0));
+
+		transaction_overhead
+			.saturating_add(messages_count.saturating_mul(single_maximal_message_size_overhead))
+	}
+
 	// Functions that are used by extrinsics weights formulas.
 
 	/// Returns weight of message send transaction (`send_message`).
@@ -287,6 +310,21 @@ pub trait WeightInfoExt: WeightInfo {
 		message_size_in_kb * single_kb_weight
 	}
 
+	/// Returns weight overhead of the transaction that increases fee of all messages in the range
+	/// (`increase_messages_fee`).
+	fn increase_messages_fee_overhead(messages_count: MessageNonce) -> Weight {
+		// TODO [#78] use results of the `increase_messages_fee` benchmark when weights are
+		// regenerated. For now, increasing fee of every message in the range is assumed to be as
+		// expensive as the separate `increase_message_fee` transaction
+		Self::increase_message_fee(0).saturating_mul(messages_count)
+	}
+
+	/// Returns weight that needs to be accounted when fee of message of given size is increased
+	/// (`increase_message_fee` and `increase_messages_fee`).
+	fn increase_message_fee_size_overhead(message_size: u32) -> Weight {
+		Self::increase_message_fee(message_size).saturating_sub(Self::increase_message_fee(0))
+	}
+
 	/// Returns weight overhead of message delivery transaction (`receive_messages_proof`).
 	fn receive_messages_proof_overhead() -> Weight {
 		let weight_of_two_messages_and_two_tx_overheads =
@@ -0,0 +1,191 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Token-bridge pallet benchmarking.
+
+use crate::{
+	bridged_sovereign_account, BridgedAccountIdOf, BridgedBalanceOf, Call, Pallet,
+	ThisChainBalance, Transfer, TransferKind,
+};
+
+use bp_messages::MessageNonce;
+use frame_benchmarking::{account, benchmarks_instance_pallet};
+use frame_support::traits::{fungibles, Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, SaturatedConversion};
+
+const SEED: u32 = 0;
+
+/// Trait that must be implemented by runtime.
+pub trait Config<I: 'static>: crate::Config<I> {
+	/// Initialize environment for token bridge.
+	///
+	/// The pallet account must be endowed and the wrapped asset must be created here.
+	fn initialize_environment();
+}
+
+benchmarks_instance_pallet! {
+	//
+	// Benchmarks that are used directly by the runtime.
+	//
+
+	// Benchmark `lock` extrinsic.
+	//
+	// This benchmark assumes that message is **NOT** actually sent. Instead we're using `send_message_weight`
+	// from the `WeightInfoExt` trait.
+	lock {
+		T::initialize_environment();
+
+		let sender = funded_account::<T, I>("sender", 0);
+		let recipient: BridgedAccountIdOf<T, I> = account("recipient", 0, SEED);
+	}: lock(
+		RawOrigin::Signed(sender),
+		recipient,
+		transfer_amount::<T, I>(),
+		0,
+		delivery_and_dispatch_fee::<T, I>()
+	)
+	verify {
+		assert!(crate::PendingTransfers::<T, I>::contains_key(TEST_MESSAGE_NONCE));
+	}
+
+	// Benchmark `burn` extrinsic.
+	//
+	// This benchmark assumes that message is **NOT** actually sent. Instead we're using `send_message_weight`
+	// from the `WeightInfoExt` trait.
+	burn {
+		T::initialize_environment();
+
+		let sender = funded_account::<T, I>("sender", 0);
+		let recipient: BridgedAccountIdOf<T, I> = account("recipient", 0, SEED);
+		mint_wrapped_tokens::<T, I>(&sender);
+	}: burn(
+		RawOrigin::Signed(sender),
+		recipient,
+		transfer_amount::<T, I>(),
+		0,
+		delivery_and_dispatch_fee::<T, I>()
+	)
+	verify {
+		assert!(crate::PendingTransfers::<T, I>::contains_key(TEST_MESSAGE_NONCE));
+	}
+
+	// Benchmark `mint` extrinsic with the worst possible conditions:
+	//
+	// * recipient account doesn't hold wrapped tokens yet.
+	mint {
+		T::initialize_environment();
+
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+	}: mint(
+		RawOrigin::Signed(bridged_sovereign_account::<T, I>()),
+		recipient.clone(),
+		transfer_amount::<T, I>()
+	)
+	verify {
+		assert_eq!(wrapped_balance::<T, I>(&recipient), transfer_amount::<T, I>());
+	}
+
+	// Benchmark `unlock` extrinsic with the worst possible conditions:
+	//
+	// * recipient account doesn't exist yet.
+	unlock {
+		T::initialize_environment();
+
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		T::ThisCurrency::make_free_balance_be(
+			&bridged_sovereign_account::<T, I>(),
+			ThisChainBalance::<T, I>::max_value(),
+		);
+	}: unlock(
+		RawOrigin::Signed(bridged_sovereign_account::<T, I>()),
+		recipient.clone(),
+		transfer_amount::<T, I>()
+	)
+	verify {
+		assert_eq!(T::ThisCurrency::free_balance(&recipient), transfer_amount::<T, I>());
+	}
+
+	// Benchmark `refund` extrinsic with the worst possible conditions:
+	//
+	// * failed transfer is the `burn` transfer, so wrapped tokens are minted back to the sender;
+	// * sender account doesn't hold wrapped tokens anymore.
+	refund {
+		T::initialize_environment();
+
+		let sender: T::AccountId = account("sender", 0, SEED);
+		let recipient: BridgedAccountIdOf<T, I> = account("recipient", 0, SEED);
+		crate::FailedTransfers::<T, I>::insert(
+			TEST_MESSAGE_NONCE,
+			Transfer {
+				kind: TransferKind::Burn,
+				sender: sender.clone(),
+				recipient,
+				amount: transfer_amount::<T, I>(),
+			},
+		);
+
+		let refunder = funded_account::<T, I>("refunder", 0);
+	}: refund(RawOrigin::Signed(refunder), TEST_MESSAGE_NONCE)
+	verify {
+		assert!(!crate::FailedTransfers::<T, I>::contains_key(TEST_MESSAGE_NONCE));
+		assert_eq!(wrapped_balance::<T, I>(&sender), transfer_amount::<T, I>());
+	}
+}
+
+/// Nonce of the message that is "sent" by the `NoopMessagesBridge`.
+const TEST_MESSAGE_NONCE: MessageNonce = 0;
+
+/// Account that has some balance.
+fn funded_account<T: Config<I>, I: 'static>(name: &'static str, index: u32) -> T::AccountId {
+	let account: T::AccountId = account(name, index, SEED);
+	T::ThisCurrency::make_free_balance_be(&account, ThisChainBalance::<T, I>::max_value());
+	account
+}
+
+/// Mint wrapped tokens to the given account.
+fn mint_wrapped_tokens<T: Config<I>, I: 'static>(account: &T::AccountId) {
+	<T::WrappedAssets as fungibles::Mutate<T::AccountId>>::mint_into(
+		T::WrappedAssetId::get(),
+		account,
+		transfer_amount::<T, I>(),
+	)
+	.expect("failed to mint wrapped tokens");
+}
+
+/// Returns wrapped tokens balance of the given account.
+fn wrapped_balance<T: Config<I>, I: 'static>(account: &T::AccountId) -> ThisChainBalance<T, I> {
+	<T::WrappedAssets as fungibles::Inspect<T::AccountId>>::balance(
+		T::WrappedAssetId::get(),
+		account,
+	)
+}
+
+/// Amount of tokens that we're going to transfer.
+///
+/// The amount must be representable at both chains, or the transfer message won't be sent.
+fn transfer_amount<T: Config<I>, I: 'static>() -> ThisChainBalance<T, I> {
+	let this_chain_amount: u128 =
+		(ThisChainBalance::<T, I>::max_value() / 4u32.into()).saturated_into();
+	let bridged_chain_amount: u128 =
+		(BridgedBalanceOf::<T, I>::max_value() / 4u32.into()).saturated_into();
+	this_chain_amount.min(bridged_chain_amount).saturated_into()
+}
+
+/// Transfer message fee.
+fn delivery_and_dispatch_fee<T: Config<I>, I: 'static>() -> ThisChainBalance<T, I> {
+	ThisChainBalance::<T, I>::max_value() / 4u32.into()
+}
@@ -0,0 +1,1078 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime module that allows transferring native tokens between two chains, bridged using
+//! messages pallet.
+//!
+//! The pallet must be deployed at both chains. The native tokens of This chain are represented by
+//! the wrapped asset (`pallet-assets` or any other `fungibles` implementation) at the Bridged
+//! chain and vice versa. The transfer goes as follows:
+//!
+//! 1) the sender submits the `lock` transaction at This chain. The tokens are transferred to the
+//! sovereign account of the Bridged chain pallet, and the message that would `mint` wrapped tokens
+//! to the recipient account is sent to the Bridged chain;
+//!
+//! 2) to transfer tokens back, the owner of wrapped tokens submits the `burn` transaction at the
+//! Bridged chain. The wrapped tokens are burned, and the message that would `unlock` tokens from
+//! the sovereign account is sent to This chain;
+//!
+//! 3) if the `mint` or `unlock` call dispatch has failed, the pallet receives notification and
+//! the sender (or anyone else) may submit the `refund` transaction to return tokens to the sender.
+//!
+//! The sovereign account of the Bridged chain pallet is derived (using `derive_account_id`) from
+//! the Bridged chain pallet account. This is the same account that the `mint` and `unlock` calls,
+//! sent by the Bridged chain pallet, are dispatched with. So only the Bridged chain pallet may
+//! unlock tokens and mint wrapped tokens at This chain.
+//!
+//! The messages are sent by This chain pallet account and are paid by the sender of the `lock` or
+//! `burn` transaction. The pallet account must exist (i.e. it must hold existential deposit)
+//! at both chains.
+//!
+//! Transfer messages are sent using `bridge_runtime_common::remote_calls`, so the weight of
+//! transfer result handling is paid by the transfer sender.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use bp_messages::{
+	source_chain::{MessagesBridge, OnDeliveryConfirmed},
+	DeliveredMessages, LaneId, MessageNonce,
+};
+use bp_runtime::{messages::DispatchFeePayment, ChainId};
+use bridge_runtime_common::remote_calls::{OnRemoteCallResult, RemoteCalls, RemoteCallsConfig};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{fungibles, Currency, ExistenceRequirement},
+	weights::PostDispatchInfo,
+	PalletId, RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::traits::{AccountIdConversion, Convert, Zero};
+use sp_std::{convert::TryInto, marker::PhantomData, vec::Vec};
+use weights::WeightInfo;
+
+pub use weights_ext::WeightInfoExt;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+pub mod weights;
+pub mod weights_ext;
+
+pub use pallet::*;
+
+/// Index of the `mint` call in the pallet calls enum.
+const MINT_CALL_INDEX: u8 = 2;
+/// Index of the `unlock` call in the pallet calls enum.
+const UNLOCK_CALL_INDEX: u8 = 3;
+
+/// SCALE-encoded call of the token bridge pallet at the Bridged chain.
+pub type RawBridgedCall = Vec<u8>;
+
+/// Type of the tokens transfer.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub enum TransferKind {
+	/// This chain native tokens have been locked and wrapped tokens are minted at the Bridged
+	/// chain.
+	Lock,
+	/// Wrapped tokens have been burned and the Bridged chain native tokens are unlocked at the
+	/// Bridged chain.
+	Burn,
+}
+
+/// Tokens transfer, initiated at This chain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct Transfer<ThisAccountId, ThisBalance, BridgedAccountId> {
+	/// Type of the transfer.
+	pub kind: TransferKind,
+	/// Account at This chain that has initiated the transfer.
+	pub sender: ThisAccountId,
+	/// Account at the Bridged chain that is receiving tokens.
+	pub recipient: BridgedAccountId,
+	/// Amount of This chain tokens (native or wrapped) that have been locked or burned.
+	pub amount: ThisBalance,
+}
+
+// comes from #[pallet::event]
+#[allow(clippy::unused_unit)]
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Benchmarks results from runtime we're plugged into.
+		type WeightInfo: WeightInfoExt;
+
+		/// Id of the pallet.
+		///
+		/// It is used to derive the pallet account, which is sending messages to the Bridged
+		/// chain. The token bridge pallet at the Bridged chain must use the same identifier.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Id of the bridge with the Bridged chain.
+		type BridgedChainId: Get<ChainId>;
+		/// Index of the token bridge pallet in the Bridged chain runtime.
+		#[pallet::constant]
+		type BridgedPalletIndex: Get<u8>;
+		/// The identifier of outbound message lane on This chain used to send token transfer
+		/// messages to the Bridged chain.
+		///
+		/// It is highly recommended to use dedicated lane for every instance of token bridge
+		/// pallet.
+		type OutboundMessageLaneId: Get<LaneId>;
+		/// Messages bridge with Bridged chain.
+		type MessagesBridge: MessagesBridge<
+			Self::AccountId,
+			<Self::ThisCurrency as Currency<Self::AccountId>>::Balance,
+			MessagePayloadOf<Self, I>,
+		>;
+
+		/// This chain Currency that may be transferred to the Bridged chain.
+		type ThisCurrency: Currency<Self::AccountId>;
+		/// Assets that are used to represent the Bridged chain tokens at This chain.
+		///
+		/// The wrapped asset should be sufficient (i.e. it should be possible to hold it
+		/// without holding native tokens). Otherwise the recipient account must exist before
+		/// tokens are minted.
+		type WrappedAssets: fungibles::Mutate<
+			Self::AccountId,
+			Balance = <Self::ThisCurrency as Currency<Self::AccountId>>::Balance,
+		>;
+		/// Identifier of the asset that represents the Bridged chain tokens at This chain.
+		type WrappedAssetId: Get<
+			<Self::WrappedAssets as fungibles::Inspect<Self::AccountId>>::AssetId,
+		>;
+
+		/// The chain we're bridged to.
+		type BridgedChain: bp_runtime::Chain;
+		/// Converter from raw hash (derived from Bridged chain account) to This chain account.
+		type FromBridgedToThisAccountIdConverter: Convert<H256, Self::AccountId>;
+	}
+
+	/// Tokens balance at This chain.
+	pub type ThisChainBalance<T, I> = <<T as Config<I>>::ThisCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	/// Type of the Bridged chain.
+	pub type BridgedChainOf<T, I> = <T as Config<I>>::BridgedChain;
+	/// Tokens balance type at the Bridged chain.
+	pub type BridgedBalanceOf<T, I> = bp_runtime::BalanceOf<BridgedChainOf<T, I>>;
+	/// Account identifier type at the Bridged chain.
+	pub type BridgedAccountIdOf<T, I> = bp_runtime::AccountIdOf<BridgedChainOf<T, I>>;
+	/// Account public key type at the Bridged chain.
+	pub type BridgedAccountPublicOf<T, I> = bp_runtime::AccountPublicOf<BridgedChainOf<T, I>>;
+	/// Account signature type at the Bridged chain.
+	pub type BridgedAccountSignatureOf<T, I> = bp_runtime::SignatureOf<BridgedChainOf<T, I>>;
+
+	/// Bridge message payload used by the pallet.
+	pub type MessagePayloadOf<T, I> = bp_message_dispatch::MessagePayload<
+		<T as frame_system::Config>::AccountId,
+		BridgedAccountPublicOf<T, I>,
+		BridgedAccountSignatureOf<T, I>,
+		RawBridgedCall,
+	>;
+	/// Type of `Transfer` used by the pallet.
+	pub type TransferOf<T, I> = Transfer<
+		<T as frame_system::Config>::AccountId,
+		ThisChainBalance<T, I>,
+		BridgedAccountIdOf<T, I>,
+	>;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+
+	// **IMPORTANT NOTE**: the Bridged chain pallet encodes `mint` and `unlock` calls using their
+	// indices (`MINT_CALL_INDEX` and `UNLOCK_CALL_INDEX`). So the order of calls MUST NOT be
+	// changed.
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Lock This chain tokens and send message that would mint the same amount of wrapped
+		/// tokens to the `recipient` account at the Bridged chain.
+		///
+		/// The sender MUST have enough balance to cover both the `amount` and the
+		/// `delivery_and_dispatch_fee`. Message fee may be estimated using corresponding
+		/// `OutboundLaneApi` of This runtime.
+		#[pallet::weight(
+			T::WeightInfo::lock()
+				.saturating_add(send_message_weight::<T, I>(recipient))
+		)]
+		pub fn lock(
+			origin: OriginFor<T>,
+			recipient: BridgedAccountIdOf<T, I>,
+			amount: ThisChainBalance<T, I>,
+			bridged_chain_spec_version: u32,
+			delivery_and_dispatch_fee: ThisChainBalance<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let transfer = Transfer { kind: TransferKind::Lock, sender, recipient, amount };
+			let base_weight = T::WeightInfo::lock();
+
+			let actual_send_message_weight = frame_support::storage::with_transaction(|| {
+				// tokens are transferred to the sovereign account of the Bridged chain pallet
+				let lock_result = T::ThisCurrency::transfer(
+					&transfer.sender,
+					&bridged_sovereign_account::<T, I>(),
+					transfer.amount,
+					ExistenceRequirement::KeepAlive,
+				);
+				if let Err(err) = lock_result {
+					log::error!(
+						target: "runtime::bridge-token-bridge",
+						"Failed to lock This chain tokens of transfer {:?}: {:?}",
+						transfer,
+						err,
+					);
+
+					return sp_runtime::TransactionOutcome::Rollback(Err(
+						Error::<T, I>::FailedToLockTokens,
+					))
+				}
+
+				send_transfer_message::<T, I>(
+					transfer,
+					bridged_chain_spec_version,
+					delivery_and_dispatch_fee,
+				)
+			})?;
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(base_weight.saturating_add(actual_send_message_weight)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Burn wrapped tokens and send message that would unlock the same amount of native
+		/// tokens to the `recipient` account at the Bridged chain.
+		///
+		/// The sender MUST have enough wrapped tokens to cover the `amount` and enough native
+		/// tokens to cover the `delivery_and_dispatch_fee`.
+		#[pallet::weight(
+			T::WeightInfo::burn()
+				.saturating_add(send_message_weight::<T, I>(recipient))
+		)]
+		pub fn burn(
+			origin: OriginFor<T>,
+			recipient: BridgedAccountIdOf<T, I>,
+			amount: ThisChainBalance<T, I>,
+			bridged_chain_spec_version: u32,
+			delivery_and_dispatch_fee: ThisChainBalance<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let transfer = Transfer { kind: TransferKind::Burn, sender, recipient, amount };
+			let base_weight = T::WeightInfo::burn();
+
+			let actual_send_message_weight = frame_support::storage::with_transaction(|| {
+				let burn_result = <T::WrappedAssets as fungibles::Mutate<T::AccountId>>::burn_from(
+					T::WrappedAssetId::get(),
+					&transfer.sender,
+					transfer.amount,
+				);
+				if let Err(err) = burn_result {
+					log::error!(
+						target: "runtime::bridge-token-bridge",
+						"Failed to burn wrapped tokens of transfer {:?}: {:?}",
+						transfer,
+						err,
+					);
+
+					return sp_runtime::TransactionOutcome::Rollback(Err(
+						Error::<T, I>::FailedToBurnTokens,
+					))
+				}
+
+				send_transfer_message::<T, I>(
+					transfer,
+					bridged_chain_spec_version,
+					delivery_and_dispatch_fee,
+				)
+			})?;
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(base_weight.saturating_add(actual_send_message_weight)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Mint wrapped tokens, representing the Bridged chain tokens, to the `recipient`.
+		///
+		/// The dispatch origin for this call must be the sovereign account of the Bridged chain
+		/// pallet. So it may only be called over the messages bridge by the Bridged chain pallet.
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			amount: ThisChainBalance<T, I>,
+		) -> DispatchResult {
+			ensure_bridged_pallet_origin::<T, I>(origin)?;
+
+			<T::WrappedAssets as fungibles::Mutate<T::AccountId>>::mint_into(
+				T::WrappedAssetId::get(),
+				&recipient,
+				amount,
+			)
+			.map_err(|err| {
+				log::error!(
+					target: "runtime::bridge-token-bridge",
+					"Failed to mint {:?} wrapped tokens to {:?}: {:?}",
+					amount,
+					recipient,
+					err,
+				);
+
+				Error::<T, I>::FailedToMintTokens
+			})?;
+
+			Self::deposit_event(Event::TokensMinted(recipient, amount));
+			Ok(())
+		}
+
+		/// Unlock This chain tokens, previously locked by the `lock` call, to the `recipient`.
+		///
+		/// The dispatch origin for this call must be the sovereign account of the Bridged chain
+		/// pallet. So it may only be called over the messages bridge by the Bridged chain pallet.
+		#[pallet::weight(T::WeightInfo::unlock())]
+		pub fn unlock(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			amount: ThisChainBalance<T, I>,
+		) -> DispatchResult {
+			let sovereign_account = ensure_bridged_pallet_origin::<T, I>(origin)?;
+
+			T::ThisCurrency::transfer(
+				&sovereign_account,
+				&recipient,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)
+			.map_err(|err| {
+				log::error!(
+					target: "runtime::bridge-token-bridge",
+					"Failed to unlock {:?} tokens to {:?}: {:?}",
+					amount,
+					recipient,
+					err,
+				);
+
+				Error::<T, I>::FailedToUnlockTokens
+			})?;
+
+			Self::deposit_event(Event::TokensUnlocked(recipient, amount));
+			Ok(())
+		}
+
+		/// Refund tokens of the transfer that has failed at the Bridged chain.
+		///
+		/// The dispatch origin for this call may be any signed account. Tokens are always
+		/// returned to the transfer sender.
+		#[pallet::weight(T::WeightInfo::refund())]
+		pub fn refund(origin: OriginFor<T>, message_nonce: MessageNonce) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let transfer = FailedTransfers::<T, I>::get(message_nonce)
+				.ok_or(Error::<T, I>::TransferIsNotFailed)?;
+			let refund_result = match transfer.kind {
+				TransferKind::Lock => T::ThisCurrency::transfer(
+					&bridged_sovereign_account::<T, I>(),
+					&transfer.sender,
+					transfer.amount,
+					ExistenceRequirement::AllowDeath,
+				),
+				TransferKind::Burn =>
+					<T::WrappedAssets as fungibles::Mutate<T::AccountId>>::mint_into(
+						T::WrappedAssetId::get(),
+						&transfer.sender,
+						transfer.amount,
+					),
+			};
+			refund_result.map_err(|err| {
+				log::error!(
+					target: "runtime::bridge-token-bridge",
+					"Failed to refund failed transfer {:?}: {:?}",
+					transfer,
+					err,
+				);
+
+				Error::<T, I>::FailedToRefundTokens
+			})?;
+
+			FailedTransfers::<T, I>::remove(message_nonce);
+
+			Self::deposit_event(Event::TransferRefunded(
+				message_nonce,
+				transfer.sender,
+				transfer.amount,
+			));
+			Ok(())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// This chain tokens have been locked and the message has been sent to the Bridged chain.
+		///
+		/// The payload is the message nonce and the transfer itself.
+		TokensLocked(MessageNonce, TransferOf<T, I>),
+		/// Wrapped tokens have been burned and the message has been sent to the Bridged chain.
+		///
+		/// The payload is the message nonce and the transfer itself.
+		TokensBurned(MessageNonce, TransferOf<T, I>),
+		/// Wrapped tokens have been minted to the account.
+		TokensMinted(T::AccountId, ThisChainBalance<T, I>),
+		/// This chain tokens have been unlocked to the account.
+		TokensUnlocked(T::AccountId, ThisChainBalance<T, I>),
+		/// Tokens transfer has been successfully dispatched at the Bridged chain.
+		TransferConfirmed(MessageNonce),
+		/// Tokens transfer has failed at the Bridged chain and may be refunded.
+		TransferFailed(MessageNonce),
+		/// Tokens of the failed transfer have been refunded to the sender.
+		TransferRefunded(MessageNonce, T::AccountId, ThisChainBalance<T, I>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The transfer amount can't be represented at the Bridged chain.
+		FailedToConvertAmount,
+		/// Transfer from the sender account to the sovereign account has failed.
+		FailedToLockTokens,
+		/// Wrapped tokens can't be burned.
+		FailedToBurnTokens,
+		/// Transfer of the message fee to the pallet account has failed.
+		FailedToPayMessageFee,
+		/// The tokens transfer message can't be sent.
+		FailedToSendTransferMessage,
+		/// The call origin is not the sovereign account of the Bridged chain pallet.
+		InvalidBridgedPalletOrigin,
+		/// Wrapped tokens can't be minted.
+		FailedToMintTokens,
+		/// Transfer from the sovereign account to the recipient account has failed.
+		FailedToUnlockTokens,
+		/// Someone is trying to refund transfer that is either not failed or already refunded.
+		TransferIsNotFailed,
+		/// Tokens of the failed transfer can't be refunded.
+		FailedToRefundTokens,
+	}
+
+	/// Transfers that are waiting for the message delivery confirmation.
+	///
+	/// This map is managed by the `TransferCalls`.
+	#[pallet::storage]
+	pub type PendingTransfers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, MessageNonce, TransferOf<T, I>>;
+
+	/// Transfers that have failed at the Bridged chain and may be refunded.
+	#[pallet::storage]
+	pub type FailedTransfers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, MessageNonce, TransferOf<T, I>>;
+
+	impl<T: Config<I>, I: 'static> OnDeliveryConfirmed for Pallet<T, I> {
+		fn on_messages_delivered(lane: &LaneId, delivered_messages: &DeliveredMessages) -> Weight {
+			<TransferCalls<T, I> as OnDeliveryConfirmed>::on_messages_delivered(
+				lane,
+				delivered_messages,
+			)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> OnRemoteCallResult<TransferOf<T, I>> for Pallet<T, I> {
+		fn max_on_result_weight() -> Weight {
+			// failed transfer is inserted into the `FailedTransfers` map and every event also
+			// writes to the `frame_system::Events` storage
+			<T as frame_system::Config>::DbWeight::get().writes(2)
+		}
+
+		fn on_result(
+			message_nonce: MessageNonce,
+			transfer: TransferOf<T, I>,
+			is_dispatched: bool,
+		) -> Weight {
+			log::trace!(
+				target: "runtime::bridge-token-bridge",
+				"Dispatch of transfer {:?} (message {}) has completed with {} status",
+				transfer,
+				message_nonce,
+				if is_dispatched { "success" } else { "failure" },
+			);
+
+			let db_weight = <T as frame_system::Config>::DbWeight::get();
+			if is_dispatched {
+				Self::deposit_event(Event::TransferConfirmed(message_nonce));
+				db_weight.writes(1)
+			} else {
+				FailedTransfers::<T, I>::insert(message_nonce, transfer);
+				Self::deposit_event(Event::TransferFailed(message_nonce));
+				db_weight.writes(2)
+			}
+		}
+	}
+
+	/// Returns account of This chain pallet.
+	///
+	/// This account is sending transfer messages to the Bridged chain.
+	pub fn pallet_account_id<T: Config<I>, I: 'static>() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Returns sovereign account of the Bridged chain pallet at This chain.
+	///
+	/// This account holds locked tokens and it is the dispatch origin of all calls, sent by
+	/// the Bridged chain pallet.
+	pub fn bridged_sovereign_account<T: Config<I>, I: 'static>() -> T::AccountId {
+		let bridged_pallet_account: BridgedAccountIdOf<T, I> = T::PalletId::get().into_account();
+		T::FromBridgedToThisAccountIdConverter::convert(bp_runtime::derive_account_id(
+			T::BridgedChainId::get(),
+			bp_runtime::SourceAccount::Account(bridged_pallet_account),
+		))
+	}
+
+	/// Ensure that the origin is the sovereign account of the Bridged chain pallet.
+	fn ensure_bridged_pallet_origin<T: Config<I>, I: 'static>(
+		origin: OriginFor<T>,
+	) -> Result<T::AccountId, DispatchError> {
+		let origin_account = ensure_signed(origin)?;
+		let sovereign_account = bridged_sovereign_account::<T, I>();
+		ensure!(origin_account == sovereign_account, Error::<T, I>::InvalidBridgedPalletOrigin);
+		Ok(sovereign_account)
+	}
+
+	/// Send message that would `mint` (if `transfer.kind` is `Lock`) or `unlock` (if
+	/// `transfer.kind` is `Burn`) tokens at the Bridged chain.
+	///
+	/// Returns actual weight of the send message call.
+	fn send_transfer_message<T: Config<I>, I: 'static>(
+		transfer: TransferOf<T, I>,
+		bridged_chain_spec_version: u32,
+		delivery_and_dispatch_fee: ThisChainBalance<T, I>,
+	) -> sp_runtime::TransactionOutcome<Result<Weight, Error<T, I>>> {
+		let bridged_amount = match convert_amount::<_, BridgedBalanceOf<T, I>>(transfer.amount) {
+			Some(bridged_amount) => bridged_amount,
+			None =>
+				return sp_runtime::TransactionOutcome::Rollback(Err(
+					Error::<T, I>::FailedToConvertAmount,
+				)),
+		};
+		let (bridged_call, bridged_call_weight) = match transfer.kind {
+			TransferKind::Lock => (MINT_CALL_INDEX, T::WeightInfo::mint()),
+			TransferKind::Burn => (UNLOCK_CALL_INDEX, T::WeightInfo::unlock()),
+		};
+
+		// message fee is paid by the transfer sender
+		let pallet_account = pallet_account_id::<T, I>();
+		let pay_fee_result = T::ThisCurrency::transfer(
+			&transfer.sender,
+			&pallet_account,
+			delivery_and_dispatch_fee,
+			ExistenceRequirement::KeepAlive,
+		);
+		if let Err(err) = pay_fee_result {
+			log::error!(
+				target: "runtime::bridge-token-bridge",
+				"Failed to pay message fee for transfer {:?}: {:?}",
+				transfer,
+				err,
+			);
+
+			return sp_runtime::TransactionOutcome::Rollback(Err(
+				Error::<T, I>::FailedToPayMessageFee,
+			))
+		}
+
+		// we assume that the weight of the Bridged chain pallet calls is the same as weight of
+		// This chain pallet calls
+		let send_message_result = TransferCalls::<T, I>::send(
+			bp_messages::source_chain::Sender::from(Some(pallet_account.clone())),
+			bp_message_dispatch::MessagePayload {
+				spec_version: bridged_chain_spec_version,
+				weight: bridged_call_weight,
+				origin: bp_message_dispatch::CallOrigin::SourceAccount(pallet_account),
+				dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+				call: encode_bridged_call::<T, I>(
+					bridged_call,
+					transfer.recipient.clone(),
+					bridged_amount,
+				),
+			},
+			delivery_and_dispatch_fee,
+			transfer.clone(),
+		);
+		let sent_message = match send_message_result {
+			Ok(sent_message) => sent_message,
+			Err(err) => {
+				log::error!(
+					target: "runtime::bridge-token-bridge",
+					"Failed to send message for transfer {:?} to the Bridged chain: {:?}",
+					transfer,
+					err,
+				);
+
+				return sp_runtime::TransactionOutcome::Rollback(Err(
+					Error::<T, I>::FailedToSendTransferMessage,
+				))
+			},
+		};
+
+		log::trace!(
+			target: "runtime::bridge-token-bridge",
+			"The transfer {:?} has been started with message {}",
+			transfer,
+			sent_message.nonce,
+		);
+
+		// finally - emit the event
+		Pallet::<T, I>::deposit_event(match transfer.kind {
+			TransferKind::Lock => Event::TokensLocked(sent_message.nonce, transfer),
+			TransferKind::Burn => Event::TokensBurned(sent_message.nonce, transfer),
+		});
+
+		sp_runtime::TransactionOutcome::Commit(Ok(sent_message.weight))
+	}
+}
+
+/// Remote calls configuration of the token bridge pallet.
+pub struct TransferCallsConfig<T, I>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> RemoteCallsConfig for TransferCallsConfig<T, I> {
+	type AccountId = T::AccountId;
+	type Balance = ThisChainBalance<T, I>;
+	type Payload = MessagePayloadOf<T, I>;
+	type CallbackId = TransferOf<T, I>;
+
+	type MessagesBridge = T::MessagesBridge;
+	type OutboundLaneId = T::OutboundMessageLaneId;
+	type PendingCallbacks = PendingTransfers<T, I>;
+	type OnResult = Pallet<T, I>;
+	type DbWeight = <T as frame_system::Config>::DbWeight;
+}
+
+/// Remote calls that are used to send transfer messages.
+pub type TransferCalls<T, I> = RemoteCalls<TransferCallsConfig<T, I>>;
+
+/// Convert This chain amount to the Bridged chain amount.
+///
+/// Returns `None` if the amount can't be represented at the Bridged chain.
+fn convert_amount<ThisBalance, BridgedBalance>(amount: ThisBalance) -> Option<BridgedBalance>
+where
+	ThisBalance: TryInto<u128>,
+	u128: TryInto<BridgedBalance>,
+{
+	let amount: u128 = amount.try_into().ok()?;
+	amount.try_into().ok()
+}
+
+/// Encode call of the token bridge pallet, deployed at the Bridged chain.
+///
+/// Both `mint` and `unlock` calls have the same arguments, so they only differ in call index.
+fn encode_bridged_call<T: Config<I>, I: 'static>(
+	call_index: u8,
+	recipient: BridgedAccountIdOf<T, I>,
+	amount: BridgedBalanceOf<T, I>,
+) -> RawBridgedCall {
+	(T::BridgedPalletIndex::get(), call_index, recipient, amount).encode()
+}
+
+/// Returns weight of sending the transfer message to the `recipient`.
+///
+/// It includes the weight of transfer result handling, which is charged up front.
+fn send_message_weight<T: Config<I>, I: 'static>(recipient: &BridgedAccountIdOf<T, I>) -> Weight {
+	T::WeightInfo::send_message_weight(
+		&&encode_bridged_call::<T, I>(MINT_CALL_INDEX, recipient.clone(), Zero::zero())[..],
+		T::DbWeight::get(),
+	)
+	.saturating_add(TransferCalls::<T, I>::send_overhead_weight())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use frame_support::{assert_noop, assert_ok};
+
+	const BRIDGED_CHAIN_ACCOUNT: BridgedAccountId = 3;
+	const BRIDGED_CHAIN_SPEC_VERSION: u32 = 4;
+	const TRANSFER_AMOUNT: Balance = 100;
+
+	fn test_transfer(kind: TransferKind) -> TransferOf<TestRuntime, ()> {
+		Transfer {
+			kind,
+			sender: THIS_CHAIN_ACCOUNT,
+			recipient: BRIDGED_CHAIN_ACCOUNT,
+			amount: TRANSFER_AMOUNT,
+		}
+	}
+
+	fn lock_tokens() {
+		assert_ok!(Pallet::<TestRuntime>::lock(
+			Origin::signed(THIS_CHAIN_ACCOUNT),
+			BRIDGED_CHAIN_ACCOUNT,
+			TRANSFER_AMOUNT,
+			BRIDGED_CHAIN_SPEC_VERSION,
+			DELIVERY_AND_DISPATCH_FEE,
+		));
+	}
+
+	fn burn_tokens() {
+		assert_ok!(Pallet::<TestRuntime>::burn(
+			Origin::signed(THIS_CHAIN_ACCOUNT),
+			BRIDGED_CHAIN_ACCOUNT,
+			TRANSFER_AMOUNT,
+			BRIDGED_CHAIN_SPEC_VERSION,
+			DELIVERY_AND_DISPATCH_FEE,
+		));
+	}
+
+	fn receive_transfer_confirmation(success: bool) {
+		Pallet::<TestRuntime, ()>::on_messages_delivered(
+			&OutboundMessageLaneId::get(),
+			&DeliveredMessages::new(MESSAGE_NONCE, success),
+		);
+	}
+
+	fn native_balance(account: AccountId) -> Balance {
+		pallet_balances::Pallet::<TestRuntime>::free_balance(&account)
+	}
+
+	fn wrapped_balance(account: AccountId) -> Balance {
+		pallet_assets::Pallet::<TestRuntime>::balance(WRAPPED_ASSET, account)
+	}
+
+	#[test]
+	fn bridged_calls_are_encoded_properly() {
+		run_test(|| {
+			assert_eq!(
+				encode_bridged_call::<TestRuntime, ()>(MINT_CALL_INDEX, 1, 2),
+				Call::TokenBridge(crate::Call::mint { recipient: 1, amount: 2 }).encode(),
+			);
+			assert_eq!(
+				encode_bridged_call::<TestRuntime, ()>(UNLOCK_CALL_INDEX, 1, 2),
+				Call::TokenBridge(crate::Call::unlock { recipient: 1, amount: 2 }).encode(),
+			);
+		});
+	}
+
+	#[test]
+	fn convert_amount_fails_if_amount_can_not_be_represented_at_bridged_chain() {
+		assert_eq!(convert_amount::<u128, u64>(u64::MAX as u128), Some(u64::MAX));
+		assert_eq!(convert_amount::<u128, u64>(u64::MAX as u128 + 1), None);
+	}
+
+	#[test]
+	fn lock_fails_if_balance_is_too_low() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::lock(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					BRIDGED_CHAIN_ACCOUNT,
+					THIS_CHAIN_ACCOUNT_BALANCE,
+					BRIDGED_CHAIN_SPEC_VERSION,
+					DELIVERY_AND_DISPATCH_FEE,
+				),
+				Error::<TestRuntime, ()>::FailedToLockTokens
+			);
+		});
+	}
+
+	#[test]
+	fn lock_fails_if_message_fee_can_not_be_paid() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::lock(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					BRIDGED_CHAIN_ACCOUNT,
+					TRANSFER_AMOUNT,
+					BRIDGED_CHAIN_SPEC_VERSION,
+					THIS_CHAIN_ACCOUNT_BALANCE - TRANSFER_AMOUNT,
+				),
+				Error::<TestRuntime, ()>::FailedToPayMessageFee
+			);
+		});
+	}
+
+	#[test]
+	fn lock_fails_if_send_message_fails() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::lock(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					BRIDGED_CHAIN_ACCOUNT,
+					TRANSFER_AMOUNT,
+					BRIDGED_CHAIN_SPEC_VERSION,
+					BAD_DELIVERY_AND_DISPATCH_FEE,
+				),
+				Error::<TestRuntime, ()>::FailedToSendTransferMessage
+			);
+		});
+	}
+
+	#[test]
+	fn lock_succeeds() {
+		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+
+			let initial_pallet_balance = native_balance(pallet_account_id::<TestRuntime, ()>());
+			lock_tokens();
+
+			assert_eq!(
+				native_balance(THIS_CHAIN_ACCOUNT),
+				THIS_CHAIN_ACCOUNT_BALANCE - TRANSFER_AMOUNT - DELIVERY_AND_DISPATCH_FEE,
+			);
+			assert_eq!(
+				native_balance(bridged_sovereign_account::<TestRuntime, ()>()),
+				TRANSFER_AMOUNT
+			);
+			assert_eq!(
+				native_balance(pallet_account_id::<TestRuntime, ()>()),
+				initial_pallet_balance + DELIVERY_AND_DISPATCH_FEE,
+			);
+			assert_eq!(
+				PendingTransfers::<TestRuntime>::get(MESSAGE_NONCE),
+				Some(test_transfer(TransferKind::Lock)),
+			);
+			assert!(
+				frame_system::Pallet::<TestRuntime>::events().iter().any(|e| e.event ==
+					crate::mock::Event::TokenBridge(crate::Event::TokensLocked(
+						MESSAGE_NONCE,
+						test_transfer(TransferKind::Lock),
+					))),
+				"Missing TokensLocked event: {:?}",
+				frame_system::Pallet::<TestRuntime>::events(),
+			);
+		});
+	}
+
+	#[test]
+	fn burn_fails_if_wrapped_balance_is_too_low() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::burn(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					BRIDGED_CHAIN_ACCOUNT,
+					THIS_CHAIN_ACCOUNT_WRAPPED_BALANCE + 1,
+					BRIDGED_CHAIN_SPEC_VERSION,
+					DELIVERY_AND_DISPATCH_FEE,
+				),
+				Error::<TestRuntime, ()>::FailedToBurnTokens
+			);
+		});
+	}
+
+	#[test]
+	fn burn_succeeds() {
+		run_test(|| {
+			burn_tokens();
+
+			assert_eq!(
+				wrapped_balance(THIS_CHAIN_ACCOUNT),
+				THIS_CHAIN_ACCOUNT_WRAPPED_BALANCE - TRANSFER_AMOUNT,
+			);
+			assert_eq!(
+				native_balance(THIS_CHAIN_ACCOUNT),
+				THIS_CHAIN_ACCOUNT_BALANCE - DELIVERY_AND_DISPATCH_FEE,
+			);
+			assert_eq!(
+				PendingTransfers::<TestRuntime>::get(MESSAGE_NONCE),
+				Some(test_transfer(TransferKind::Burn)),
+			);
+		});
+	}
+
+	#[test]
+	fn mint_fails_if_origin_is_incorrect() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::mint(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					THIS_CHAIN_ACCOUNT,
+					TRANSFER_AMOUNT,
+				),
+				Error::<TestRuntime, ()>::InvalidBridgedPalletOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn mint_succeeds() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::mint(
+				Origin::signed(bridged_sovereign_account::<TestRuntime, ()>()),
+				THIS_CHAIN_ACCOUNT,
+				TRANSFER_AMOUNT,
+			));
+
+			assert_eq!(
+				wrapped_balance(THIS_CHAIN_ACCOUNT),
+				THIS_CHAIN_ACCOUNT_WRAPPED_BALANCE + TRANSFER_AMOUNT,
+			);
+		});
+	}
+
+	#[test]
+	fn unlock_fails_if_origin_is_incorrect() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::unlock(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					THIS_CHAIN_ACCOUNT,
+					TRANSFER_AMOUNT,
+				),
+				Error::<TestRuntime, ()>::InvalidBridgedPalletOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn unlock_fails_if_tokens_are_not_locked() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::unlock(
+					Origin::signed(bridged_sovereign_account::<TestRuntime, ()>()),
+					THIS_CHAIN_ACCOUNT,
+					TRANSFER_AMOUNT,
+				),
+				Error::<TestRuntime, ()>::FailedToUnlockTokens
+			);
+		});
+	}
+
+	#[test]
+	fn unlock_succeeds() {
+		run_test(|| {
+			lock_tokens();
+
+			assert_ok!(Pallet::<TestRuntime>::unlock(
+				Origin::signed(bridged_sovereign_account::<TestRuntime, ()>()),
+				THIS_CHAIN_ACCOUNT,
+				TRANSFER_AMOUNT,
+			));
+
+			assert_eq!(native_balance(bridged_sovereign_account::<TestRuntime, ()>()), 0);
+			assert_eq!(
+				native_balance(THIS_CHAIN_ACCOUNT),
+				THIS_CHAIN_ACCOUNT_BALANCE - DELIVERY_AND_DISPATCH_FEE,
+			);
+		});
+	}
+
+	#[test]
+	fn messages_delivery_confirmations_are_accepted() {
+		run_test(|| {
+			lock_tokens();
+
+			// when unrelated messages are delivered
+			Pallet::<TestRuntime, ()>::on_messages_delivered(
+				&OutboundMessageLaneId::get(),
+				&DeliveredMessages::new(MESSAGE_NONCE - 1, false),
+			);
+			assert!(PendingTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+
+			// when our message is delivered
+			receive_transfer_confirmation(true);
+			assert!(!PendingTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+			assert!(!FailedTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+		});
+	}
+
+	#[test]
+	fn messages_delivery_confirmations_from_other_lanes_are_ignored() {
+		run_test(|| {
+			lock_tokens();
+
+			assert_eq!(
+				Pallet::<TestRuntime, ()>::on_messages_delivered(
+					b"othr",
+					&DeliveredMessages::new(MESSAGE_NONCE, false),
+				),
+				0,
+			);
+			assert!(PendingTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+			assert!(!FailedTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+		});
+	}
+
+	#[test]
+	fn failed_transfers_are_remembered() {
+		run_test(|| {
+			lock_tokens();
+			receive_transfer_confirmation(false);
+
+			assert!(!PendingTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+			assert_eq!(
+				FailedTransfers::<TestRuntime>::get(MESSAGE_NONCE),
+				Some(test_transfer(TransferKind::Lock)),
+			);
+		});
+	}
+
+	#[test]
+	fn refund_fails_if_transfer_is_not_failed() {
+		run_test(|| {
+			lock_tokens();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::refund(Origin::signed(THIS_CHAIN_ACCOUNT), MESSAGE_NONCE),
+				Error::<TestRuntime, ()>::TransferIsNotFailed
+			);
+		});
+	}
+
+	#[test]
+	fn refund_of_lock_succeeds() {
+		run_test(|| {
+			lock_tokens();
+			receive_transfer_confirmation(false);
+
+			assert_ok!(Pallet::<TestRuntime>::refund(
+				Origin::signed(THIS_CHAIN_ACCOUNT + 1),
+				MESSAGE_NONCE
+			));
+
+			assert!(!FailedTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+			assert_eq!(
+				native_balance(THIS_CHAIN_ACCOUNT),
+				THIS_CHAIN_ACCOUNT_BALANCE - DELIVERY_AND_DISPATCH_FEE,
+			);
+		});
+	}
+
+	#[test]
+	fn refund_of_burn_succeeds() {
+		run_test(|| {
+			burn_tokens();
+			receive_transfer_confirmation(false);
+
+			assert_ok!(Pallet::<TestRuntime>::refund(
+				Origin::signed(THIS_CHAIN_ACCOUNT + 1),
+				MESSAGE_NONCE
+			));
+
+			assert!(!FailedTransfers::<TestRuntime>::contains_key(MESSAGE_NONCE));
+			assert_eq!(wrapped_balance(THIS_CHAIN_ACCOUNT), THIS_CHAIN_ACCOUNT_WRAPPED_BALANCE);
+		});
+	}
+}
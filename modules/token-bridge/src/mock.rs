@@ -0,0 +1,243 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate as pallet_bridge_token_bridge;
+use crate::MessagePayloadOf;
+
+use bp_messages::{
+	source_chain::{MessagesBridge, SendMessageArtifacts},
+	LaneId, MessageNonce,
+};
+use bp_runtime::ChainId;
+use frame_support::{weights::Weight, PalletId};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header as SubstrateHeader,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+pub type AccountId = u64;
+pub type AssetId = u32;
+pub type Balance = u64;
+pub type Block = frame_system::mocking::MockBlock<TestRuntime>;
+pub type BridgedAccountId = u64;
+pub type BridgedAccountSignature = sp_runtime::testing::TestSignature;
+pub type BridgedBalance = u64;
+pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+pub const MESSAGE_NONCE: MessageNonce = 3;
+
+pub const THIS_CHAIN_ACCOUNT: AccountId = 1;
+pub const THIS_CHAIN_ACCOUNT_BALANCE: Balance = 100_000;
+pub const THIS_CHAIN_ACCOUNT_WRAPPED_BALANCE: Balance = 100_000;
+
+pub const WRAPPED_ASSET: AssetId = 1;
+pub const WRAPPED_ASSET_MIN_BALANCE: Balance = 1;
+
+pub const DELIVERY_AND_DISPATCH_FEE: Balance = 1;
+pub const BAD_DELIVERY_AND_DISPATCH_FEE: Balance = 2;
+
+frame_support::construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		TokenBridge: pallet_bridge_token_bridge::{Pallet, Call, Event<T>},
+	}
+}
+
+frame_support::parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for TestRuntime {
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = SubstrateHeader;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+frame_support::parameter_types! {
+	pub const ExistentialDeposit: u64 = 10;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for TestRuntime {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<TestRuntime>;
+	type WeightInfo = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+}
+
+frame_support::parameter_types! {
+	pub const AssetDeposit: Balance = 1;
+	pub const AssetAccountDeposit: Balance = 1;
+	pub const MetadataDepositBase: Balance = 1;
+	pub const MetadataDepositPerByte: Balance = 1;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const StringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for TestRuntime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = pallet_balances::Pallet<TestRuntime>;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+frame_support::parameter_types! {
+	pub const TokenBridgePalletId: PalletId = PalletId(*b"py/tbrdg");
+	pub const BridgedChainId: ChainId = *b"inst";
+	pub const BridgedPalletIndex: u8 = 3;
+	pub const OutboundMessageLaneId: LaneId = *b"lane";
+	pub const WrappedAssetId: AssetId = WRAPPED_ASSET;
+}
+
+impl pallet_bridge_token_bridge::Config for TestRuntime {
+	type Event = Event;
+	type WeightInfo = ();
+
+	type PalletId = TokenBridgePalletId;
+	type BridgedChainId = BridgedChainId;
+	type BridgedPalletIndex = BridgedPalletIndex;
+	type OutboundMessageLaneId = OutboundMessageLaneId;
+	type MessagesBridge = TestMessagesBridge;
+
+	type ThisCurrency = pallet_balances::Pallet<TestRuntime>;
+	type WrappedAssets = pallet_assets::Pallet<TestRuntime>;
+	type WrappedAssetId = WrappedAssetId;
+
+	type BridgedChain = BridgedChain;
+	type FromBridgedToThisAccountIdConverter = TestAccountConverter;
+}
+
+pub struct BridgedChain;
+
+impl bp_runtime::Chain for BridgedChain {
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hasher = BlakeTwo256;
+	type Header = sp_runtime::generic::Header<u64, BlakeTwo256>;
+
+	type AccountId = BridgedAccountId;
+	type Balance = BridgedBalance;
+	type Index = u64;
+	type Signature = BridgedAccountSignature;
+
+	fn max_extrinsic_size() -> u32 {
+		unreachable!()
+	}
+	fn max_extrinsic_weight() -> Weight {
+		unreachable!()
+	}
+}
+
+pub struct TestMessagesBridge;
+
+impl MessagesBridge<AccountId, Balance, MessagePayloadOf<TestRuntime, ()>> for TestMessagesBridge {
+	type Error = ();
+
+	fn send_message(
+		sender: frame_system::RawOrigin<AccountId>,
+		lane: LaneId,
+		message: MessagePayloadOf<TestRuntime, ()>,
+		delivery_and_dispatch_fee: Balance,
+	) -> Result<SendMessageArtifacts, Self::Error> {
+		let pallet_account = crate::pallet_account_id::<TestRuntime, ()>();
+		assert_eq!(sender, frame_system::RawOrigin::Signed(pallet_account));
+		assert_eq!(message.origin, bp_message_dispatch::CallOrigin::SourceAccount(pallet_account));
+		assert_eq!(lane, OutboundMessageLaneId::get());
+		match delivery_and_dispatch_fee {
+			DELIVERY_AND_DISPATCH_FEE =>
+				Ok(SendMessageArtifacts { nonce: MESSAGE_NONCE, weight: 0 }),
+			BAD_DELIVERY_AND_DISPATCH_FEE => Err(()),
+			_ => unreachable!(),
+		}
+	}
+}
+
+pub struct TestAccountConverter;
+
+impl sp_runtime::traits::Convert<H256, AccountId> for TestAccountConverter {
+	fn convert(hash: H256) -> AccountId {
+		hash.to_low_u64_ne()
+	}
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+	pallet_balances::GenesisConfig::<TestRuntime> {
+		balances: vec![
+			(THIS_CHAIN_ACCOUNT, THIS_CHAIN_ACCOUNT_BALANCE),
+			(crate::pallet_account_id::<TestRuntime, ()>(), ExistentialDeposit::get()),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_assets::GenesisConfig::<TestRuntime> {
+		assets: vec![(WRAPPED_ASSET, THIS_CHAIN_ACCOUNT, true, WRAPPED_ASSET_MIN_BALANCE)],
+		metadata: vec![],
+		accounts: vec![(WRAPPED_ASSET, THIS_CHAIN_ACCOUNT, THIS_CHAIN_ACCOUNT_WRAPPED_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(test)
+}
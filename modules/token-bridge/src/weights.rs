@@ -0,0 +1,63 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_bridge_token_bridge`.
+//!
+//! The pallet benchmarks haven't been executed yet, so there are no generated weights. Until then,
+//! runtimes should use the `()` implementation, which composes weights of the pallet calls from
+//! the generated weights of balances and assets pallets calls.
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_bridge_token_bridge`.
+pub trait WeightInfo {
+	fn lock() -> Weight;
+	fn burn() -> Weight;
+	fn mint() -> Weight;
+	fn unlock() -> Weight;
+	fn refund() -> Weight;
+}
+
+// TODO [#78] replace with weights, generated using the pallet benchmarks. Every call deposits event
+// (one write to the `frame_system::Events`) and `lock` and `burn` additionally transfer the message
+// fee. The weight of message sending is computed separately (see `WeightInfoExt`).
+impl WeightInfo for () {
+	fn lock() -> Weight {
+		<() as pallet_balances::WeightInfo>::transfer_keep_alive()
+			.saturating_add(<() as pallet_balances::WeightInfo>::transfer_keep_alive())
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn burn() -> Weight {
+		<() as pallet_assets::WeightInfo>::burn()
+			.saturating_add(<() as pallet_balances::WeightInfo>::transfer_keep_alive())
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn mint() -> Weight {
+		<() as pallet_assets::WeightInfo>::mint().saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn unlock() -> Weight {
+		<() as pallet_balances::WeightInfo>::transfer()
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn refund() -> Weight {
+		<() as pallet_balances::WeightInfo>::transfer()
+			.max(<() as pallet_assets::WeightInfo>::mint())
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 2))
+	}
+}
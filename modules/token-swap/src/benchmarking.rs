@@ -18,18 +18,17 @@
 
 use crate::{
 	swap_account_id, target_account_at_this_chain, BridgedAccountIdOf, BridgedAccountPublicOf,
-	BridgedAccountSignatureOf, BridgedBalanceOf, Call, Pallet, ThisChainBalance,
+	BridgedAccountSignatureOf, BridgedBalanceOf, Call, Pallet, ThisChainAssetId, ThisChainBalance,
 	TokenSwapCreationOf, TokenSwapOf,
 };
 
 use bp_token_swap::{TokenSwap, TokenSwapCreation, TokenSwapState, TokenSwapType};
-use codec::{Decode, Encode};
+use codec::Decode;
 use frame_benchmarking::{account, benchmarks_instance_pallet};
 use frame_support::{traits::Currency, Parameter};
 use frame_system::RawOrigin;
 use sp_core::H256;
-use sp_io::hashing::blake2_256;
-use sp_runtime::traits::{Bounded, TrailingZeroInput};
+use sp_runtime::traits::{Bounded, TrailingZeroInput, Zero};
 use sp_std::{boxed::Box, vec::Vec};
 
 const SEED: u32 = 0;
@@ -38,6 +37,14 @@ const SEED: u32 = 0;
 pub trait Config<I: 'static>: crate::Config<I> {
 	/// Initialize environment for token swap.
 	fn initialize_environment();
+	/// Prepare This chain asset that may be used in token swaps and mint `balance` of this asset
+	/// to the `account`. The `account` is guaranteed to have some native tokens.
+	///
+	/// Returns identifier of the prepared asset.
+	fn prepare_this_chain_asset(
+		account: &T::AccountId,
+		balance: ThisChainBalance<T, I>,
+	) -> ThisChainAssetId<T, I>;
 }
 
 benchmarks_instance_pallet! {
@@ -62,7 +69,7 @@ benchmarks_instance_pallet! {
 		T::initialize_environment();
 
 		let sender = funded_account::<T, I>("source_account_at_this_chain", 0);
-		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), true);
+		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), true, None);
 		let swap_creation: TokenSwapCreationOf<T, I> = test_swap_creation::<T, I>();
 	}: create_swap(
 		RawOrigin::Signed(sender.clone()),
@@ -70,7 +77,9 @@ benchmarks_instance_pallet! {
 		Box::new(swap_creation)
 	)
 	verify {
-		assert!(crate::PendingSwaps::<T, I>::contains_key(test_swap_hash::<T, I>(sender, true)));
+		assert!(crate::PendingSwaps::<T, I>::contains_key(
+			test_swap_hash::<T, I>(sender, true, None),
+		));
 	}
 
 	// Benchmark `claim_swap` extrinsic with the worst possible conditions:
@@ -81,17 +90,19 @@ benchmarks_instance_pallet! {
 
 		let sender: T::AccountId = account("source_account_at_this_chain", 0, SEED);
 		crate::PendingSwaps::<T, I>::insert(
-			test_swap_hash::<T, I>(sender.clone(), false),
+			test_swap_hash::<T, I>(sender.clone(), false, None),
 			TokenSwapState::Confirmed,
 		);
 
-		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), false);
+		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), false, None);
 		let claimer = target_account_at_this_chain::<T, I>(&swap);
 		let token_swap_account = swap_account_id::<T, I>(&swap);
 		T::ThisCurrency::make_free_balance_be(&token_swap_account, ThisChainBalance::<T, I>::max_value());
 	}: claim_swap(RawOrigin::Signed(claimer), swap)
 	verify {
-		assert!(!crate::PendingSwaps::<T, I>::contains_key(test_swap_hash::<T, I>(sender, false)));
+		assert!(!crate::PendingSwaps::<T, I>::contains_key(
+			test_swap_hash::<T, I>(sender, false, None),
+		));
 	}
 
 	// Benchmark `cancel_swap` extrinsic with the worst possible conditions:
@@ -102,37 +113,125 @@ benchmarks_instance_pallet! {
 
 		let sender: T::AccountId = account("source_account_at_this_chain", 0, SEED);
 		crate::PendingSwaps::<T, I>::insert(
-			test_swap_hash::<T, I>(sender.clone(), false),
+			test_swap_hash::<T, I>(sender.clone(), false, None),
 			TokenSwapState::Failed,
 		);
 
-		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), false);
+		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), false, None);
 		let token_swap_account = swap_account_id::<T, I>(&swap);
 		T::ThisCurrency::make_free_balance_be(&token_swap_account, ThisChainBalance::<T, I>::max_value());
 
 	}: cancel_swap(RawOrigin::Signed(sender.clone()), swap)
 	verify {
-		assert!(!crate::PendingSwaps::<T, I>::contains_key(test_swap_hash::<T, I>(sender, false)));
+		assert!(!crate::PendingSwaps::<T, I>::contains_key(
+			test_swap_hash::<T, I>(sender, false, None),
+		));
+	}
+
+	// Benchmark `create_swap` extrinsic when This chain asset is swapped.
+	//
+	// Comparing to the `create_swap` benchmark, it includes the cost of the asset transfer.
+	create_asset_swap {
+		T::initialize_environment();
+
+		let sender = funded_account::<T, I>("source_account_at_this_chain", 0);
+		let asset = T::prepare_this_chain_asset(&sender, source_balance_to_swap::<T, I>());
+		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), true, Some(asset.clone()));
+		let swap_creation: TokenSwapCreationOf<T, I> = test_swap_creation::<T, I>();
+	}: create_swap(
+		RawOrigin::Signed(sender.clone()),
+		swap,
+		Box::new(swap_creation)
+	)
+	verify {
+		assert!(crate::PendingSwaps::<T, I>::contains_key(
+			test_swap_hash::<T, I>(sender, true, Some(asset)),
+		));
+	}
+
+	// Benchmark `claim_swap` extrinsic when This chain asset is swapped.
+	//
+	// Comparing to the `claim_swap` benchmark, it includes the cost of the asset transfer.
+	claim_asset_swap {
+		T::initialize_environment();
+
+		let sender = funded_account::<T, I>("source_account_at_this_chain", 0);
+		let asset = T::prepare_this_chain_asset(&sender, Zero::zero());
+		crate::PendingSwaps::<T, I>::insert(
+			test_swap_hash::<T, I>(sender.clone(), false, Some(asset.clone())),
+			TokenSwapState::Confirmed,
+		);
+
+		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), false, Some(asset.clone()));
+		let claimer = target_account_at_this_chain::<T, I>(&swap);
+		let token_swap_account = swap_account_id::<T, I>(&swap);
+		T::ThisCurrency::make_free_balance_be(
+			&token_swap_account,
+			T::ThisCurrency::minimum_balance(),
+		);
+		T::prepare_this_chain_asset(&token_swap_account, source_balance_to_swap::<T, I>());
+	}: claim_swap(RawOrigin::Signed(claimer), swap)
+	verify {
+		assert!(!crate::PendingSwaps::<T, I>::contains_key(
+			test_swap_hash::<T, I>(sender, false, Some(asset)),
+		));
+	}
+
+	// Benchmark `cancel_swap` extrinsic when This chain asset is swapped.
+	//
+	// Comparing to the `cancel_swap` benchmark, it includes the cost of the asset transfer.
+	cancel_asset_swap {
+		T::initialize_environment();
+
+		let sender = funded_account::<T, I>("source_account_at_this_chain", 0);
+		let asset = T::prepare_this_chain_asset(&sender, Zero::zero());
+		crate::PendingSwaps::<T, I>::insert(
+			test_swap_hash::<T, I>(sender.clone(), false, Some(asset.clone())),
+			TokenSwapState::Failed,
+		);
+
+		let swap: TokenSwapOf<T, I> = test_swap::<T, I>(sender.clone(), false, Some(asset.clone()));
+		let token_swap_account = swap_account_id::<T, I>(&swap);
+		T::ThisCurrency::make_free_balance_be(
+			&token_swap_account,
+			T::ThisCurrency::minimum_balance(),
+		);
+		T::prepare_this_chain_asset(&token_swap_account, source_balance_to_swap::<T, I>());
+	}: cancel_swap(RawOrigin::Signed(sender.clone()), swap)
+	verify {
+		assert!(!crate::PendingSwaps::<T, I>::contains_key(
+			test_swap_hash::<T, I>(sender, false, Some(asset)),
+		));
 	}
 }
 
 /// Returns test token swap.
-fn test_swap<T: Config<I>, I: 'static>(sender: T::AccountId, is_create: bool) -> TokenSwapOf<T, I> {
+fn test_swap<T: Config<I>, I: 'static>(
+	sender: T::AccountId,
+	is_create: bool,
+	source_asset: Option<ThisChainAssetId<T, I>>,
+) -> TokenSwapOf<T, I> {
 	TokenSwap {
 		swap_type: TokenSwapType::LockClaimUntilBlock(
 			if is_create { 10u32.into() } else { 0u32.into() },
 			0.into(),
 		),
+		source_asset_at_this_chain: source_asset,
 		source_balance_at_this_chain: source_balance_to_swap::<T, I>(),
 		source_account_at_this_chain: sender,
+		target_asset_at_bridged_chain: None,
 		target_balance_at_bridged_chain: target_balance_to_swap::<T, I>(),
 		target_account_at_bridged_chain: target_account_at_bridged_chain::<T, I>(),
 	}
 }
 
 /// Returns test token swap hash.
-fn test_swap_hash<T: Config<I>, I: 'static>(sender: T::AccountId, is_create: bool) -> H256 {
-	test_swap::<T, I>(sender, is_create).using_encoded(blake2_256).into()
+fn test_swap_hash<T: Config<I>, I: 'static>(
+	sender: T::AccountId,
+	is_create: bool,
+	source_asset: Option<ThisChainAssetId<T, I>>,
+) -> H256 {
+	test_swap::<T, I>(sender, is_create, source_asset).hash()
 }
 
 /// Returns test token swap creation params.
@@ -147,6 +246,7 @@ where
 		bridged_chain_spec_version: 0,
 		bridged_currency_transfer: Vec::new(),
 		bridged_currency_transfer_weight: 0,
+		bridged_currency_transfer_nonce: 0,
 		bridged_currency_transfer_signature: bridged_currency_transfer_signature::<T, I>(),
 	}
 }
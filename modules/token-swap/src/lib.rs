@@ -57,8 +57,22 @@
 //! 5) if message dispatch has failed, the `source_account_at_this_chain` may submit the
 //! `cancel_swap` transaction and return his `source_balance_at_this_chain` back to his account.
 //!
+//! There's no way to cancel the swap if the transfer message is never delivered to the Bridged
+//! chain. The proof of message non-delivery isn't enough here, because the message may still be
+//! delivered and dispatched after the swap is canceled. So it'll only be possible when the
+//! transfer message will become undeliverable after some Bridged chain block.
+//!
 //! While swap is pending, the `source_balance_at_this_chain` tokens are owned by the special
 //! temporary `swap_account_at_this_chain` account. It is destroyed upon swap completion.
+//!
+//! The swapped tokens may either be native This chain tokens (`ThisCurrency`), or some This chain
+//! asset (`ThisAssets`). Similarly, the `target_balance_at_bridged_chain` may be either native
+//! Bridged chain tokens, or some asset of the Bridged chain. When This chain asset is swapped, the
+//! `source_account_at_this_chain` additionally pays existential deposit (in native tokens) to the
+//! `swap_account_at_this_chain`. The deposit is returned back when the swap is completed.
+//!
+//! Transfer messages are sent using `bridge_runtime_common::remote_calls`, so the weight of
+//! transfer result handling is paid by the swap creator.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -68,18 +82,18 @@ use bp_messages::{
 };
 use bp_runtime::{messages::DispatchFeePayment, ChainId};
 use bp_token_swap::{
-	RawBridgedTransferCall, TokenSwap, TokenSwapCreation, TokenSwapState, TokenSwapType,
+	RawBridgedTransferCall, TokenSwap, TokenSwapCreation, TokenSwapDetails, TokenSwapState,
+	TokenSwapType,
 };
-use codec::Encode;
+use bridge_runtime_common::remote_calls::{OnRemoteCallResult, RemoteCalls, RemoteCallsConfig};
 use frame_support::{
 	fail,
-	traits::{Currency, ExistenceRequirement},
+	traits::{fungibles, Currency, ExistenceRequirement},
 	weights::PostDispatchInfo,
 };
 use sp_core::H256;
-use sp_io::hashing::blake2_256;
 use sp_runtime::traits::{Convert, Saturating};
-use sp_std::boxed::Box;
+use sp_std::{boxed::Box, marker::PhantomData, vec::Vec};
 use weights::WeightInfo;
 
 pub use weights_ext::WeightInfoExt;
@@ -90,6 +104,7 @@ mod mock;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+pub mod migrations;
 pub mod weights;
 pub mod weights_ext;
 
@@ -127,14 +142,24 @@ pub mod pallet {
 			<Self::ThisCurrency as Currency<Self::AccountId>>::Balance,
 			MessagePayloadOf<Self, I>,
 		>;
+		/// Maximal number of pending swaps that may be started by the single account.
+		#[pallet::constant]
+		type MaxPendingSwapsPerAccount: Get<u32>;
 
 		/// This chain Currency used in the tokens swap.
 		type ThisCurrency: Currency<Self::AccountId>;
+		/// This chain assets that may be used in the tokens swap.
+		type ThisAssets: fungibles::Inspect<
+				Self::AccountId,
+				Balance = <Self::ThisCurrency as Currency<Self::AccountId>>::Balance,
+			> + fungibles::Transfer<Self::AccountId>;
 		/// Converter from raw hash (derived from swap) to This chain account.
 		type FromSwapToThisAccountIdConverter: Convert<H256, Self::AccountId>;
 
 		/// The chain we're bridged to.
 		type BridgedChain: bp_runtime::Chain;
+		/// Identifier of assets at the Bridged chain.
+		type BridgedAssetId: Parameter;
 		/// Converter from raw hash (derived from Bridged chain account) to This chain account.
 		type FromBridgedToThisAccountIdConverter: Convert<H256, Self::AccountId>;
 	}
@@ -143,11 +168,17 @@ pub mod pallet {
 	pub type ThisChainBalance<T, I> = <<T as Config<I>>::ThisCurrency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
+	/// Asset identifier at This chain.
+	pub type ThisChainAssetId<T, I> = <<T as Config<I>>::ThisAssets as fungibles::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::AssetId;
 
 	/// Type of the Bridged chain.
 	pub type BridgedChainOf<T, I> = <T as Config<I>>::BridgedChain;
 	/// Tokens balance type at the Bridged chain.
 	pub type BridgedBalanceOf<T, I> = bp_runtime::BalanceOf<BridgedChainOf<T, I>>;
+	/// Asset identifier type at the Bridged chain.
+	pub type BridgedAssetIdOf<T, I> = <T as Config<I>>::BridgedAssetId;
 	/// Account identifier type at the Bridged chain.
 	pub type BridgedAccountIdOf<T, I> = bp_runtime::AccountIdOf<BridgedChainOf<T, I>>;
 	/// Account public key type at the Bridged chain.
@@ -165,8 +196,10 @@ pub mod pallet {
 	/// Type of `TokenSwap` used by the pallet.
 	pub type TokenSwapOf<T, I> = TokenSwap<
 		BlockNumberFor<T>,
+		ThisChainAssetId<T, I>,
 		ThisChainBalance<T, I>,
 		<T as frame_system::Config>::AccountId,
+		BridgedAssetIdOf<T, I>,
 		BridgedBalanceOf<T, I>,
 		BridgedAccountIdOf<T, I>,
 	>;
@@ -176,9 +209,23 @@ pub mod pallet {
 		ThisChainBalance<T, I>,
 		BridgedAccountSignatureOf<T, I>,
 	>;
+	/// Type of `TokenSwapDetails` used by the pallet.
+	pub type TokenSwapDetailsOf<T, I> = TokenSwapDetails<
+		BlockNumberFor<T>,
+		ThisChainAssetId<T, I>,
+		ThisChainBalance<T, I>,
+		<T as frame_system::Config>::AccountId,
+		BridgedAssetIdOf<T, I>,
+		BridgedBalanceOf<T, I>,
+		BridgedAccountIdOf<T, I>,
+	>;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
@@ -201,13 +248,15 @@ pub mod pallet {
 		/// - `swap_creation_params` - additional parameters required to start tokens swap.
 		///
 		/// The `source_account_at_this_chain` MUST have enough balance to cover both token swap and
-		/// message transfer. Message fee may be estimated using corresponding `OutboundLaneApi` of
-		/// This runtime.
+		/// message transfer. If `swap.source_asset_at_this_chain` is set, it also MUST have enough
+		/// native tokens to pay existential deposit of the temporary swap account. Message fee may
+		/// be estimated using corresponding `OutboundLaneApi` of This runtime.
 		///
 		/// **WARNING**: the submitter of this transaction is responsible for verifying:
 		///
 		/// 1) that the `swap_creation_params.bridged_currency_transfer` represents a valid token
-		/// transfer call that transfers `swap.target_balance_at_bridged_chain` to his
+		/// transfer call that transfers `swap.target_balance_at_bridged_chain` (of the
+		/// `swap.target_asset_at_bridged_chain` asset, if it is set) to his
 		/// `swap.source_account_at_bridged_chain` account;
 		///
 		/// 2) that either the `swap.source_account_at_bridged_chain` already exists, or the
@@ -219,19 +268,27 @@ pub mod pallet {
 		///
 		/// 4) the `bridged_currency_transfer_signature` is valid and generated by the owner of
 		/// the `swap_creation_params.target_public_at_bridged_chain` account (read more
-		/// about [`CallOrigin::TargetAccount`]).
+		/// about [`CallOrigin::TargetAccountWithNonce`]);
+		///
+		/// 5) if the `swap.source_asset_at_this_chain` is set and the asset is not sufficient
+		/// (i.e. it can't be held by account without native tokens), the
+		/// `target_account_at_this_chain` exists at the moment when swap is claimed.
 		///
 		/// Violating rule#1 will lead to losing your `source_balance_at_this_chain` tokens.
 		/// Violating other rules will lead to losing message fees for this and other transactions +
 		/// losing fees for message transfer.
 		#[allow(clippy::boxed_local)]
 		#[pallet::weight(
-			T::WeightInfo::create_swap()
-				.saturating_add(T::WeightInfo::send_message_weight(
-					&&swap_creation_params.bridged_currency_transfer[..],
-					T::DbWeight::get(),
-				))
-			)]
+			T::WeightInfo::create_swap_weight(
+				swap.source_asset_at_this_chain.is_some(),
+				T::DbWeight::get(),
+			)
+			.saturating_add(T::WeightInfo::send_message_weight(
+				&&swap_creation_params.bridged_currency_transfer[..],
+				T::DbWeight::get(),
+			))
+			.saturating_add(SwapTransferCalls::<T, I>::send_overhead_weight())
+		)]
 		pub fn create_swap(
 			origin: OriginFor<T>,
 			swap: TokenSwapOf<T, I>,
@@ -243,6 +300,7 @@ pub mod pallet {
 				bridged_chain_spec_version,
 				bridged_currency_transfer,
 				bridged_currency_transfer_weight,
+				bridged_currency_transfer_nonce,
 				bridged_currency_transfer_signature,
 			} = *swap_creation_params;
 
@@ -255,7 +313,10 @@ pub mod pallet {
 			);
 
 			// remember weight components
-			let base_weight = T::WeightInfo::create_swap();
+			let base_weight = T::WeightInfo::create_swap_weight(
+				swap.source_asset_at_this_chain.is_some(),
+				T::DbWeight::get(),
+			);
 
 			// we can't exchange less than existential deposit (the temporary `swap_account` account
 			// won't be created then)
@@ -264,7 +325,8 @@ pub mod pallet {
 			// here (without additional knowledge of the Bridged chain). So it is the `origin`
 			// responsibility to check that the swap is valid.
 			ensure!(
-				swap.source_balance_at_this_chain >= T::ThisCurrency::minimum_balance(),
+				swap.source_balance_at_this_chain >=
+					this_chain_minimum_balance::<T, I>(swap.source_asset_at_this_chain),
 				Error::<T, I>::TooLowBalanceOnThisChain,
 			);
 
@@ -280,18 +342,34 @@ pub mod pallet {
 
 			let swap_account = swap_account_id::<T, I>(&swap);
 			let actual_send_message_weight = frame_support::storage::with_transaction(|| {
-				// funds are transferred from This account to the temporary Swap account
+				// funds are transferred from This account to the temporary Swap account. If we're
+				// swapping assets, the Swap account also receives existential deposit, so that it
+				// is able to hold (maybe insufficient) assets
+				let native_balance_to_transfer = match swap.source_asset_at_this_chain {
+					Some(_) => T::ThisCurrency::minimum_balance(),
+					None => swap.source_balance_at_this_chain,
+				};
 				let transfer_result = T::ThisCurrency::transfer(
 					&swap.source_account_at_this_chain,
 					&swap_account,
 					// saturating_add is ok, or we have the chain where single holder owns all
 					// tokens
-					swap.source_balance_at_this_chain
-						.saturating_add(swap_delivery_and_dispatch_fee),
+					native_balance_to_transfer.saturating_add(swap_delivery_and_dispatch_fee),
 					// if we'll allow account to die, then he'll be unable to `cancel_claim`
 					// if something won't work
 					ExistenceRequirement::KeepAlive,
-				);
+				)
+				.and_then(|_| match swap.source_asset_at_this_chain {
+					Some(asset) => <T::ThisAssets as fungibles::Transfer<T::AccountId>>::transfer(
+						asset,
+						&swap.source_account_at_this_chain,
+						&swap_account,
+						swap.source_balance_at_this_chain,
+						true,
+					)
+					.map(drop),
+					None => Ok(()),
+				});
 				if let Err(err) = transfer_result {
 					log::error!(
 						target: "runtime::bridge-token-swap",
@@ -307,23 +385,26 @@ pub mod pallet {
 				}
 
 				// the transfer message is sent over the bridge. The message is supposed to be a
-				// `Currency::transfer` call on the bridged chain, but no checks are made - it is
-				// the transaction submitter to ensure it is valid.
-				let send_message_result = T::MessagesBridge::send_message(
+				// `Currency::transfer` (or `pallet_assets::Call::transfer`) call on the bridged
+				// chain, but no checks are made - it is the transaction submitter to ensure it is
+				// valid.
+				let swap_hash = swap.hash();
+				let send_message_result = SwapTransferCalls::<T, I>::send(
 					bp_messages::source_chain::Sender::from(Some(swap_account.clone())),
-					T::OutboundMessageLaneId::get(),
 					bp_message_dispatch::MessagePayload {
 						spec_version: bridged_chain_spec_version,
 						weight: bridged_currency_transfer_weight,
-						origin: bp_message_dispatch::CallOrigin::TargetAccount(
+						origin: bp_message_dispatch::CallOrigin::TargetAccountWithNonce(
 							swap_account,
 							target_public_at_bridged_chain,
 							bridged_currency_transfer_signature,
+							bridged_currency_transfer_nonce,
 						),
 						dispatch_fee_payment: DispatchFeePayment::AtTargetChain,
 						call: bridged_currency_transfer,
 					},
 					swap_delivery_and_dispatch_fee,
+					swap_hash,
 				);
 				let sent_message = match send_message_result {
 					Ok(sent_message) => sent_message,
@@ -342,7 +423,6 @@ pub mod pallet {
 				};
 
 				// remember that we have started the swap
-				let swap_hash = swap.using_encoded(blake2_256).into();
 				let insert_swap_result =
 					PendingSwaps::<T, I>::try_mutate(swap_hash, |maybe_state| {
 						if maybe_state.is_some() {
@@ -364,6 +444,25 @@ pub mod pallet {
 					))
 				}
 
+				// remember swap details
+				let insert_account_swap_result = SwapsBySourceAccount::<T, I>::try_mutate(
+					&swap.source_account_at_this_chain,
+					|account_swaps| account_swaps.try_push(swap_hash),
+				);
+				if insert_account_swap_result.is_err() {
+					log::error!(
+						target: "runtime::bridge-token-swap",
+						"Failed to start token swap {:?}: too many pending swaps of {:?}",
+						swap,
+						swap.source_account_at_this_chain,
+					);
+
+					return sp_runtime::TransactionOutcome::Rollback(Err(
+						Error::<T, I>::TooManyPendingSwaps,
+					))
+				}
+				Swaps::<T, I>::insert(swap_hash, &swap);
+
 				log::trace!(
 					target: "runtime::bridge-token-swap",
 					"The swap {:?} (hash {:?}) has been started",
@@ -371,11 +470,14 @@ pub mod pallet {
 					swap_hash,
 				);
 
-				// remember that we're waiting for the transfer message delivery confirmation
-				PendingMessages::<T, I>::insert(sent_message.nonce, swap_hash);
+				SwapTransferMessages::<T, I>::insert(swap_hash, sent_message.nonce);
 
 				// finally - emit the event
-				Self::deposit_event(Event::SwapStarted(swap_hash, sent_message.nonce));
+				Self::deposit_event(Event::SwapStarted(
+					swap_hash,
+					sent_message.nonce,
+					swap.clone(),
+				));
 
 				sp_runtime::TransactionOutcome::Commit(Ok(sent_message.weight))
 			})?;
@@ -394,7 +496,12 @@ pub mod pallet {
 		/// `pallet_bridge_dispatch::CallOrigin::SourceAccount(target_account_at_bridged_chain)`.
 		///
 		/// This should be called only when successful transfer confirmation has been received.
-		#[pallet::weight(T::WeightInfo::claim_swap())]
+		#[pallet::weight(
+			T::WeightInfo::claim_swap_weight(
+				swap.source_asset_at_this_chain.is_some(),
+				T::DbWeight::get(),
+			)
+		)]
 		pub fn claim_swap(
 			origin: OriginFor<T>,
 			swap: TokenSwapOf<T, I>,
@@ -405,7 +512,7 @@ pub mod pallet {
 			ensure!(origin_account == target_account_at_this_chain, Error::<T, I>::InvalidClaimant,);
 
 			// ensure that the swap is confirmed
-			let swap_hash = swap.using_encoded(blake2_256).into();
+			let swap_hash = swap.hash();
 			let swap_state = PendingSwaps::<T, I>::get(swap_hash);
 			match swap_state {
 				Some(TokenSwapState::Started) => fail!(Error::<T, I>::SwapIsPending),
@@ -422,7 +529,7 @@ pub mod pallet {
 				None => fail!(Error::<T, I>::SwapIsInactive),
 			}
 
-			complete_claim::<T, I>(swap, swap_hash, origin_account, Event::SwapClaimed(swap_hash))
+			complete_claim::<T, I>(swap, swap_hash, origin_account, Event::SwapClaimed)
 		}
 
 		/// Return previously reserved `source_balance_at_this_chain` back to the
@@ -430,7 +537,12 @@ pub mod pallet {
 		///
 		/// This should be called only when transfer has failed at Bridged chain and we have
 		/// received notification about that.
-		#[pallet::weight(T::WeightInfo::cancel_swap())]
+		#[pallet::weight(
+			T::WeightInfo::cancel_swap_weight(
+				swap.source_asset_at_this_chain.is_some(),
+				T::DbWeight::get(),
+			)
+		)]
 		pub fn cancel_swap(
 			origin: OriginFor<T>,
 			swap: TokenSwapOf<T, I>,
@@ -444,7 +556,7 @@ pub mod pallet {
 			);
 
 			// ensure that the swap has failed
-			let swap_hash = swap.using_encoded(blake2_256).into();
+			let swap_hash = swap.hash();
 			let swap_state = PendingSwaps::<T, I>::get(swap_hash);
 			match swap_state {
 				Some(TokenSwapState::Started) => fail!(Error::<T, I>::SwapIsPending),
@@ -457,7 +569,7 @@ pub mod pallet {
 				None => fail!(Error::<T, I>::SwapIsInactive),
 			}
 
-			complete_claim::<T, I>(swap, swap_hash, origin_account, Event::SwapCanceled(swap_hash))
+			complete_claim::<T, I>(swap, swap_hash, origin_account, Event::SwapCanceled)
 		}
 	}
 
@@ -466,12 +578,21 @@ pub mod pallet {
 	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// Tokens swap has been started and message has been sent to the bridged message.
 		///
-		/// The payload is the swap hash and the transfer message nonce.
-		SwapStarted(H256, MessageNonce),
+		/// The payload is the swap hash, the transfer message nonce and the swap itself.
+		SwapStarted(H256, MessageNonce, TokenSwapOf<T, I>),
+		/// Tokens transfer has been successfully dispatched at the Bridged chain and the swap may
+		/// be claimed.
+		SwapConfirmed(H256),
+		/// Tokens transfer has failed at the Bridged chain and the swap may be canceled.
+		SwapFailed(H256),
 		/// Token swap has been claimed.
-		SwapClaimed(H256),
+		///
+		/// The payload is the swap hash, the account that has received tokens and the amount.
+		SwapClaimed(H256, T::AccountId, ThisChainBalance<T, I>),
 		/// Token swap has been canceled.
-		SwapCanceled(H256),
+		///
+		/// The payload is the swap hash, the account that has received tokens and the amount.
+		SwapCanceled(H256, T::AccountId, ThisChainBalance<T, I>),
 	}
 
 	#[pallet::error]
@@ -479,7 +600,8 @@ pub mod pallet {
 		/// The account that has submitted the `start_claim` doesn't match the
 		/// `TokenSwap::source_account_at_this_chain`.
 		MismatchedSwapSourceOrigin,
-		/// The swap balance in This chain tokens is below existential deposit and can't be made.
+		/// The swap balance in This chain tokens (or assets) is below existential deposit (or
+		/// minimal asset balance) and can't be made.
 		TooLowBalanceOnThisChain,
 		/// Transfer from This chain account to temporary Swap account has failed.
 		FailedToTransferToSwapAccount,
@@ -511,6 +633,8 @@ pub mod pallet {
 		SwapIsInactive,
 		/// The swap claimant is invalid.
 		InvalidClaimant,
+		/// The account has too many pending swaps.
+		TooManyPendingSwaps,
 	}
 
 	/// Pending token swaps states.
@@ -519,46 +643,66 @@ pub mod pallet {
 		StorageMap<_, Identity, H256, TokenSwapState>;
 
 	/// Pending transfer messages.
+	///
+	/// This map is managed by the `SwapTransferCalls`.
 	#[pallet::storage]
 	pub type PendingMessages<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Identity, MessageNonce, H256>;
 
+	/// Pending token swaps.
+	#[pallet::storage]
+	pub type Swaps<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, H256, TokenSwapOf<T, I>>;
+
+	/// Hashes of pending token swaps, started by given `source_account_at_this_chain`.
+	#[pallet::storage]
+	pub type SwapsBySourceAccount<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<H256, T::MaxPendingSwapsPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Transfer message nonces of pending token swaps.
+	#[pallet::storage]
+	pub type SwapTransferMessages<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, H256, MessageNonce>;
+
 	impl<T: Config<I>, I: 'static> OnDeliveryConfirmed for Pallet<T, I> {
 		fn on_messages_delivered(lane: &LaneId, delivered_messages: &DeliveredMessages) -> Weight {
-			// we're only interested in our lane messages
-			if *lane != T::OutboundMessageLaneId::get() {
-				return 0
-			}
+			<SwapTransferCalls<T, I> as OnDeliveryConfirmed>::on_messages_delivered(
+				lane,
+				delivered_messages,
+			)
+		}
+	}
 
-			// so now we're dealing with our lane messages. Ideally we'll have dedicated lane
-			// and every message from `delivered_messages` is actually our transfer message.
-			// But it may be some shared lane (which is not recommended).
-			let mut reads = 0;
-			let mut writes = 0;
-			for message_nonce in delivered_messages.begin..=delivered_messages.end {
-				reads += 1;
-				if let Some(swap_hash) = PendingMessages::<T, I>::take(message_nonce) {
-					writes += 1;
-
-					let token_swap_state =
-						if delivered_messages.message_dispatch_result(message_nonce) {
-							TokenSwapState::Confirmed
-						} else {
-							TokenSwapState::Failed
-						};
-
-					log::trace!(
-						target: "runtime::bridge-token-swap",
-						"The dispatch of swap {:?} has been completed with {:?} status",
-						swap_hash,
-						token_swap_state,
-					);
+	impl<T: Config<I>, I: 'static> OnRemoteCallResult<H256> for Pallet<T, I> {
+		fn max_on_result_weight() -> Weight {
+			// swap state is updated in the `PendingSwaps` map and every event also writes to the
+			// `frame_system::Events` storage
+			<T as frame_system::Config>::DbWeight::get().writes(2)
+		}
 
-					PendingSwaps::<T, I>::insert(swap_hash, token_swap_state);
-				}
-			}
+		fn on_result(_message_nonce: MessageNonce, swap_hash: H256, is_dispatched: bool) -> Weight {
+			let token_swap_state =
+				if is_dispatched { TokenSwapState::Confirmed } else { TokenSwapState::Failed };
 
-			<T as frame_system::Config>::DbWeight::get().reads_writes(reads, writes)
+			log::trace!(
+				target: "runtime::bridge-token-swap",
+				"The dispatch of swap {:?} has been completed with {:?} status",
+				swap_hash,
+				token_swap_state,
+			);
+
+			PendingSwaps::<T, I>::insert(swap_hash, token_swap_state.clone());
+			Self::deposit_event(match token_swap_state {
+				TokenSwapState::Confirmed => Event::SwapConfirmed(swap_hash),
+				_ => Event::SwapFailed(swap_hash),
+			});
+
+			Self::max_on_result_weight()
 		}
 	}
 
@@ -566,7 +710,18 @@ pub mod pallet {
 	pub(crate) fn swap_account_id<T: Config<I>, I: 'static>(
 		swap: &TokenSwapOf<T, I>,
 	) -> T::AccountId {
-		T::FromSwapToThisAccountIdConverter::convert(swap.using_encoded(blake2_256).into())
+		T::FromSwapToThisAccountIdConverter::convert(swap.hash())
+	}
+
+	/// Returns minimal balance of This chain tokens that may be swapped.
+	pub(crate) fn this_chain_minimum_balance<T: Config<I>, I: 'static>(
+		asset: Option<ThisChainAssetId<T, I>>,
+	) -> ThisChainBalance<T, I> {
+		match asset {
+			Some(asset) =>
+				<T::ThisAssets as fungibles::Inspect<T::AccountId>>::minimum_balance(asset),
+			None => T::ThisCurrency::minimum_balance(),
+		}
 	}
 
 	/// Expected target account representation on This chain (aka `target_account_at_this_chain`).
@@ -580,21 +735,42 @@ pub mod pallet {
 	}
 
 	/// Complete claim with given outcome.
+	///
+	/// The `event` is constructed from the swap hash, the `destination_account` and the amount of
+	/// transferred tokens.
 	pub(crate) fn complete_claim<T: Config<I>, I: 'static>(
 		swap: TokenSwapOf<T, I>,
 		swap_hash: H256,
 		destination_account: T::AccountId,
-		event: Event<T, I>,
+		event: fn(H256, T::AccountId, ThisChainBalance<T, I>) -> Event<T, I>,
 	) -> DispatchResultWithPostInfo {
 		let swap_account = swap_account_id::<T, I>(&swap);
 		frame_support::storage::with_transaction(|| {
 			// funds are transferred from the temporary Swap account to the destination account
-			let transfer_result = T::ThisCurrency::transfer(
-				&swap_account,
-				&destination_account,
-				swap.source_balance_at_this_chain,
-				ExistenceRequirement::AllowDeath,
-			);
+			let transfer_result = match swap.source_asset_at_this_chain {
+				Some(asset) => <T::ThisAssets as fungibles::Transfer<T::AccountId>>::transfer(
+					asset,
+					&swap_account,
+					&destination_account,
+					swap.source_balance_at_this_chain,
+					false,
+				)
+				.and_then(|_| {
+					// existential deposit is returned to the account that has paid it
+					T::ThisCurrency::transfer(
+						&swap_account,
+						&swap.source_account_at_this_chain,
+						T::ThisCurrency::free_balance(&swap_account),
+						ExistenceRequirement::AllowDeath,
+					)
+				}),
+				None => T::ThisCurrency::transfer(
+					&swap_account,
+					&destination_account,
+					swap.source_balance_at_this_chain,
+					ExistenceRequirement::AllowDeath,
+				),
+			};
 			if let Err(err) = transfer_result {
 				log::error!(
 					target: "runtime::bridge-token-swap",
@@ -610,20 +786,34 @@ pub mod pallet {
 				))
 			}
 
+			let event = event(swap_hash, destination_account, swap.source_balance_at_this_chain);
 			log::trace!(
 				target: "runtime::bridge-token-swap",
 				"The swap {:?} (hash {:?}) has been completed with {} status",
 				swap,
 				swap_hash,
 				match event {
-					Event::SwapClaimed(_) => "claimed",
-					Event::SwapCanceled(_) => "canceled",
+					Event::SwapClaimed(..) => "claimed",
+					Event::SwapCanceled(..) => "canceled",
 					_ => "<unknown>",
 				},
 			);
 
 			// forget about swap
 			PendingSwaps::<T, I>::remove(swap_hash);
+			Swaps::<T, I>::remove(swap_hash);
+			SwapTransferMessages::<T, I>::remove(swap_hash);
+			let mut account_swaps =
+				SwapsBySourceAccount::<T, I>::get(&swap.source_account_at_this_chain);
+			account_swaps.retain(|account_swap_hash| *account_swap_hash != swap_hash);
+			if account_swaps.is_empty() {
+				SwapsBySourceAccount::<T, I>::remove(&swap.source_account_at_this_chain);
+			} else {
+				SwapsBySourceAccount::<T, I>::insert(
+					&swap.source_account_at_this_chain,
+					account_swaps,
+				);
+			}
 
 			// finally - emit the event
 			Pallet::<T, I>::deposit_event(event);
@@ -633,6 +823,57 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Returns details of all pending token swaps, started by given account.
+	pub fn swaps_by_source_account(account: &T::AccountId) -> Vec<TokenSwapDetailsOf<T, I>> {
+		SwapsBySourceAccount::<T, I>::get(account)
+			.into_iter()
+			.filter_map(swap_details::<T, I>)
+			.collect()
+	}
+
+	/// Returns details of all pending token swaps with given state.
+	///
+	/// This method iterates over all pending swaps, so it should only be used by runtime APIs.
+	pub fn swaps_by_state(state: TokenSwapState) -> Vec<TokenSwapDetailsOf<T, I>> {
+		PendingSwaps::<T, I>::iter()
+			.filter(|(_, swap_state)| *swap_state == state)
+			.filter_map(|(swap_hash, _)| swap_details::<T, I>(swap_hash))
+			.collect()
+	}
+}
+
+/// Returns details of the pending token swap.
+fn swap_details<T: Config<I>, I: 'static>(swap_hash: H256) -> Option<TokenSwapDetailsOf<T, I>> {
+	let state = PendingSwaps::<T, I>::get(swap_hash)?;
+	let swap = Swaps::<T, I>::get(swap_hash)?;
+	Some(TokenSwapDetails {
+		hash: swap_hash,
+		swap,
+		state,
+		transfer_message_nonce: SwapTransferMessages::<T, I>::get(swap_hash),
+	})
+}
+
+/// Remote calls configuration of the token swap pallet.
+pub struct SwapTransferCallsConfig<T, I>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> RemoteCallsConfig for SwapTransferCallsConfig<T, I> {
+	type AccountId = T::AccountId;
+	type Balance = ThisChainBalance<T, I>;
+	type Payload = MessagePayloadOf<T, I>;
+	type CallbackId = H256;
+
+	type MessagesBridge = T::MessagesBridge;
+	type OutboundLaneId = T::OutboundMessageLaneId;
+	type PendingCallbacks = PendingMessages<T, I>;
+	type OnResult = Pallet<T, I>;
+	type DbWeight = <T as frame_system::Config>::DbWeight;
+}
+
+/// Remote calls that are used to send swap transfer messages.
+pub type SwapTransferCalls<T, I> = RemoteCalls<SwapTransferCallsConfig<T, I>>;
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -657,13 +898,22 @@ mod tests {
 	fn test_swap() -> TokenSwapOf<TestRuntime, ()> {
 		bp_token_swap::TokenSwap {
 			swap_type: TokenSwapType::LockClaimUntilBlock(CAN_START_BLOCK_NUMBER, 0.into()),
+			source_asset_at_this_chain: None,
 			source_balance_at_this_chain: 100,
 			source_account_at_this_chain: THIS_CHAIN_ACCOUNT,
+			target_asset_at_bridged_chain: None,
 			target_balance_at_bridged_chain: 200,
 			target_account_at_bridged_chain: BRIDGED_CHAIN_ACCOUNT,
 		}
 	}
 
+	fn test_asset_swap() -> TokenSwapOf<TestRuntime, ()> {
+		bp_token_swap::TokenSwap {
+			source_asset_at_this_chain: Some(THIS_CHAIN_ASSET),
+			..test_swap()
+		}
+	}
+
 	fn test_swap_creation() -> TokenSwapCreationOf<TestRuntime, ()> {
 		TokenSwapCreation {
 			target_public_at_bridged_chain: bridged_chain_account_public(),
@@ -671,12 +921,13 @@ mod tests {
 			bridged_chain_spec_version: BRIDGED_CHAIN_SPEC_VERSION,
 			bridged_currency_transfer: test_transfer(),
 			bridged_currency_transfer_weight: BRIDGED_CHAIN_CALL_WEIGHT,
+			bridged_currency_transfer_nonce: 0,
 			bridged_currency_transfer_signature: bridged_chain_account_signature(),
 		}
 	}
 
 	fn test_swap_hash() -> H256 {
-		test_swap().using_encoded(blake2_256).into()
+		test_swap().hash()
 	}
 
 	fn test_transfer() -> RawBridgedTransferCall {
@@ -693,6 +944,7 @@ mod tests {
 				bridged_chain_spec_version: BRIDGED_CHAIN_SPEC_VERSION,
 				bridged_currency_transfer: test_transfer(),
 				bridged_currency_transfer_weight: BRIDGED_CHAIN_CALL_WEIGHT,
+				bridged_currency_transfer_nonce: 0,
 				bridged_currency_transfer_signature: bridged_chain_account_signature(),
 			}),
 		));
@@ -735,6 +987,22 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn create_swap_fails_if_this_chain_asset_balance_is_below_minimal_balance() {
+		run_test(|| {
+			let mut swap = test_asset_swap();
+			swap.source_balance_at_this_chain = THIS_CHAIN_ASSET_MIN_BALANCE - 1;
+			assert_noop!(
+				Pallet::<TestRuntime>::create_swap(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					swap,
+					Box::new(test_swap_creation()),
+				),
+				Error::<TestRuntime, ()>::TooLowBalanceOnThisChain
+			);
+		});
+	}
+
 	#[test]
 	fn create_swap_fails_if_currency_transfer_to_swap_account_fails() {
 		run_test(|| {
@@ -751,6 +1019,22 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn create_swap_fails_if_asset_transfer_to_swap_account_fails() {
+		run_test(|| {
+			let mut swap = test_asset_swap();
+			swap.source_balance_at_this_chain = THIS_CHAIN_ACCOUNT_ASSET_BALANCE + 1;
+			assert_noop!(
+				Pallet::<TestRuntime>::create_swap(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					swap,
+					Box::new(test_swap_creation()),
+				),
+				Error::<TestRuntime, ()>::FailedToTransferToSwapAccount
+			);
+		});
+	}
+
 	#[test]
 	fn create_swap_fails_if_send_message_fails() {
 		run_test(|| {
@@ -789,6 +1073,24 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn create_swap_fails_if_account_has_too_many_pending_swaps() {
+		run_test(|| {
+			start_test_swap();
+
+			let mut another_swap = test_swap();
+			another_swap.source_balance_at_this_chain += 1;
+			assert_noop!(
+				Pallet::<TestRuntime>::create_swap(
+					Origin::signed(THIS_CHAIN_ACCOUNT),
+					another_swap,
+					Box::new(test_swap_creation()),
+				),
+				Error::<TestRuntime, ()>::TooManyPendingSwaps
+			);
+		});
+	}
+
 	#[test]
 	fn create_swap_fails_if_trying_to_start_swap_after_lock_period_is_finished() {
 		run_test(|| {
@@ -831,6 +1133,11 @@ mod tests {
 			let swap_hash = test_swap_hash();
 			assert_eq!(PendingSwaps::<TestRuntime>::get(swap_hash), Some(TokenSwapState::Started));
 			assert_eq!(PendingMessages::<TestRuntime>::get(MESSAGE_NONCE), Some(swap_hash));
+			assert_eq!(Swaps::<TestRuntime>::get(swap_hash), Some(test_swap()));
+			assert_eq!(
+				SwapsBySourceAccount::<TestRuntime>::get(THIS_CHAIN_ACCOUNT).into_inner(),
+				vec![swap_hash],
+			);
 			assert_eq!(
 				pallet_balances::Pallet::<TestRuntime>::free_balance(&swap_account_id::<
 					TestRuntime,
@@ -843,6 +1150,7 @@ mod tests {
 					crate::mock::Event::TokenSwap(crate::Event::SwapStarted(
 						swap_hash,
 						MESSAGE_NONCE,
+						test_swap(),
 					))),
 				"Missing SwapStarted event: {:?}",
 				frame_system::Pallet::<TestRuntime>::events(),
@@ -850,6 +1158,31 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn create_asset_swap_succeeds() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::create_swap(
+				Origin::signed(THIS_CHAIN_ACCOUNT),
+				test_asset_swap(),
+				Box::new(test_swap_creation()),
+			));
+
+			let swap_account = swap_account_id::<TestRuntime, ()>(&test_asset_swap());
+			assert_eq!(
+				PendingSwaps::<TestRuntime>::get(test_asset_swap().hash()),
+				Some(TokenSwapState::Started),
+			);
+			assert_eq!(
+				pallet_assets::Pallet::<TestRuntime>::balance(THIS_CHAIN_ASSET, swap_account),
+				test_asset_swap().source_balance_at_this_chain,
+			);
+			assert_eq!(
+				pallet_balances::Pallet::<TestRuntime>::free_balance(&swap_account),
+				ExistentialDeposit::get() + SWAP_DELIVERY_AND_DISPATCH_FEE,
+			);
+		});
+	}
+
 	#[test]
 	fn claim_swap_fails_if_origin_is_incorrect() {
 		run_test(|| {
@@ -973,13 +1306,56 @@ mod tests {
 			);
 			assert!(
 				frame_system::Pallet::<TestRuntime>::events().iter().any(|e| e.event ==
-					crate::mock::Event::TokenSwap(crate::Event::SwapClaimed(swap_hash,))),
+					crate::mock::Event::TokenSwap(crate::Event::SwapClaimed(
+						swap_hash,
+						target_account_at_this_chain::<TestRuntime, ()>(&test_swap()),
+						test_swap().source_balance_at_this_chain,
+					))),
 				"Missing SwapClaimed event: {:?}",
 				frame_system::Pallet::<TestRuntime>::events(),
 			);
 		});
 	}
 
+	#[test]
+	fn claim_asset_swap_succeeds() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::create_swap(
+				Origin::signed(THIS_CHAIN_ACCOUNT),
+				test_asset_swap(),
+				Box::new(test_swap_creation()),
+			));
+			receive_test_swap_confirmation(true);
+
+			frame_system::Pallet::<TestRuntime>::set_block_number(CAN_CLAIM_BLOCK_NUMBER);
+
+			let target_account =
+				target_account_at_this_chain::<TestRuntime, ()>(&test_asset_swap());
+			assert_ok!(Pallet::<TestRuntime>::claim_swap(
+				Origin::signed(target_account),
+				test_asset_swap(),
+			));
+
+			let swap_account = swap_account_id::<TestRuntime, ()>(&test_asset_swap());
+			assert_eq!(PendingSwaps::<TestRuntime>::get(test_asset_swap().hash()), None);
+			assert_eq!(
+				pallet_assets::Pallet::<TestRuntime>::balance(THIS_CHAIN_ASSET, swap_account),
+				0,
+			);
+			assert_eq!(
+				pallet_assets::Pallet::<TestRuntime>::balance(THIS_CHAIN_ASSET, target_account),
+				test_asset_swap().source_balance_at_this_chain,
+			);
+			assert_eq!(pallet_balances::Pallet::<TestRuntime>::free_balance(&swap_account), 0);
+			// test messages bridge doesn't withdraw the fee, so the whole native balance of the
+			// swap account is returned back
+			assert_eq!(
+				pallet_balances::Pallet::<TestRuntime>::free_balance(&THIS_CHAIN_ACCOUNT),
+				THIS_CHAIN_ACCOUNT_BALANCE,
+			);
+		});
+	}
+
 	#[test]
 	fn cancel_swap_fails_if_origin_is_incorrect() {
 		run_test(|| {
@@ -1077,16 +1453,83 @@ mod tests {
 			);
 			assert!(
 				frame_system::Pallet::<TestRuntime>::events().iter().any(|e| e.event ==
-					crate::mock::Event::TokenSwap(crate::Event::SwapCanceled(swap_hash,))),
+					crate::mock::Event::TokenSwap(crate::Event::SwapCanceled(
+						swap_hash,
+						THIS_CHAIN_ACCOUNT,
+						test_swap().source_balance_at_this_chain,
+					))),
 				"Missing SwapCanceled event: {:?}",
 				frame_system::Pallet::<TestRuntime>::events(),
 			);
 		});
 	}
 
+	#[test]
+	fn create_swap_remembers_transfer_message_nonce() {
+		run_test(|| {
+			start_test_swap();
+
+			assert_eq!(
+				SwapTransferMessages::<TestRuntime>::get(test_swap_hash()),
+				Some(MESSAGE_NONCE),
+			);
+		});
+	}
+
+	#[test]
+	fn completed_swap_details_are_removed() {
+		run_test(|| {
+			start_test_swap();
+			receive_test_swap_confirmation(false);
+
+			assert_ok!(Pallet::<TestRuntime>::cancel_swap(
+				Origin::signed(THIS_CHAIN_ACCOUNT),
+				test_swap()
+			));
+
+			assert_eq!(Swaps::<TestRuntime>::get(test_swap_hash()), None);
+			assert!(!SwapsBySourceAccount::<TestRuntime>::contains_key(THIS_CHAIN_ACCOUNT));
+		});
+	}
+
+	#[test]
+	fn swaps_are_listed_by_source_account_and_state() {
+		run_test(|| {
+			start_test_swap();
+
+			let expected_details = TokenSwapDetails {
+				hash: test_swap_hash(),
+				swap: test_swap(),
+				state: TokenSwapState::Started,
+				transfer_message_nonce: Some(MESSAGE_NONCE),
+			};
+			assert_eq!(
+				Pallet::<TestRuntime>::swaps_by_source_account(&THIS_CHAIN_ACCOUNT),
+				vec![expected_details.clone()],
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::swaps_by_source_account(&(THIS_CHAIN_ACCOUNT + 1)),
+				vec![]
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::swaps_by_state(TokenSwapState::Started),
+				vec![expected_details.clone()],
+			);
+			assert_eq!(Pallet::<TestRuntime>::swaps_by_state(TokenSwapState::Confirmed), vec![]);
+
+			receive_test_swap_confirmation(true);
+			assert_eq!(Pallet::<TestRuntime>::swaps_by_state(TokenSwapState::Started), vec![]);
+			assert_eq!(
+				Pallet::<TestRuntime>::swaps_by_state(TokenSwapState::Confirmed),
+				vec![TokenSwapDetails { state: TokenSwapState::Confirmed, ..expected_details }],
+			);
+		});
+	}
+
 	#[test]
 	fn messages_delivery_confirmations_are_accepted() {
 		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
 			start_test_swap();
 			assert_eq!(
 				PendingMessages::<TestRuntime, ()>::get(MESSAGE_NONCE),
@@ -1126,6 +1569,14 @@ mod tests {
 				PendingSwaps::<TestRuntime, ()>::get(test_swap_hash()),
 				Some(TokenSwapState::Confirmed)
 			);
+			assert!(
+				frame_system::Pallet::<TestRuntime>::events().iter().any(|e| e.event ==
+					crate::mock::Event::TokenSwap(
+						crate::Event::SwapConfirmed(test_swap_hash())
+					)),
+				"Missing SwapConfirmed event: {:?}",
+				frame_system::Pallet::<TestRuntime>::events(),
+			);
 		});
 	}
 
@@ -0,0 +1,388 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the token swap pallet.
+//!
+//! Migrations must be applied in order. E.g. if the pallet storage is at version 0, both
+//! `v1::Migration` and `v2::Migration` must be executed (in this order) during runtime upgrade.
+
+/// Migration to the storage version 1.
+///
+/// Before version 1, the pallet has only been storing hashes of pending swaps. Version 1 adds
+/// the `Swaps` and `SwapsBySourceAccount` maps, which are used to provide swap details to the
+/// pallet clients. Swaps can't be restored from their hashes, so the migration fills these maps
+/// using the list of swaps, provided by the runtime. Pending swaps that are missing from this
+/// list may still be claimed and cancelled, but they won't be returned by the runtime API.
+pub mod v1 {
+	use crate::{
+		BridgedAccountIdOf, BridgedBalanceOf, Config, Pallet, PendingSwaps, Swaps,
+		SwapsBySourceAccount, ThisChainBalance,
+	};
+
+	use bp_token_swap::TokenSwapType;
+	use codec::{Decode, Encode};
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+		weights::Weight,
+		RuntimeDebug,
+	};
+	use frame_system::pallet_prelude::BlockNumberFor;
+	use sp_core::H256;
+	use sp_io::hashing::blake2_256;
+	use sp_std::{marker::PhantomData, vec::Vec};
+
+	/// Storage version, set by this migration.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// Token swap, as it has been stored before version 2 (when only native tokens have been
+	/// swapped).
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+	pub struct TokenSwap<
+		ThisBlockNumber,
+		ThisBalance,
+		ThisAccountId,
+		BridgedBalance,
+		BridgedAccountId,
+	> {
+		/// The type of the swap.
+		pub swap_type: TokenSwapType<ThisBlockNumber>,
+		/// This chain balance to be swapped with `target_balance_at_bridged_chain`.
+		pub source_balance_at_this_chain: ThisBalance,
+		/// Account id of the party acting at This chain and owning the
+		/// `source_account_at_this_chain`.
+		pub source_account_at_this_chain: ThisAccountId,
+		/// Bridged chain balance to be swapped with `source_balance_at_this_chain`.
+		pub target_balance_at_bridged_chain: BridgedBalance,
+		/// Account id of the party acting at the Bridged chain and owning the
+		/// `target_balance_at_bridged_chain`.
+		pub target_account_at_bridged_chain: BridgedAccountId,
+	}
+
+	/// Type of `TokenSwap` that has been used by the pallet before version 2.
+	pub type TokenSwapOf<T, I> = TokenSwap<
+		BlockNumberFor<T>,
+		ThisChainBalance<T, I>,
+		<T as frame_system::Config>::AccountId,
+		BridgedBalanceOf<T, I>,
+		BridgedAccountIdOf<T, I>,
+	>;
+
+	/// Migration that fills `Swaps` and `SwapsBySourceAccount` maps using the `KnownSwaps` list.
+	pub struct Migration<T, I, KnownSwaps>(PhantomData<(T, I, KnownSwaps)>);
+
+	impl<T, I, KnownSwaps> OnRuntimeUpgrade for Migration<T, I, KnownSwaps>
+	where
+		T: Config<I>,
+		I: 'static,
+		KnownSwaps: Get<Vec<TokenSwapOf<T, I>>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let db_weight = T::DbWeight::get();
+			if Pallet::<T, I>::on_chain_storage_version() >= STORAGE_VERSION {
+				return db_weight.reads(1)
+			}
+
+			let pallet_name = <Pallet<T, I> as PalletInfoAccess>::name();
+			let mut reads = 1;
+			let mut writes = 1;
+			for swap in KnownSwaps::get() {
+				let swap_hash: H256 = swap.using_encoded(blake2_256).into();
+				reads += 2;
+				if !PendingSwaps::<T, I>::contains_key(swap_hash) ||
+					Swaps::<T, I>::contains_key(swap_hash)
+				{
+					continue
+				}
+
+				reads += 1;
+				writes += 2;
+				let insert_account_swap_result = SwapsBySourceAccount::<T, I>::try_mutate(
+					&swap.source_account_at_this_chain,
+					|account_swaps| account_swaps.try_push(swap_hash),
+				);
+				if insert_account_swap_result.is_err() {
+					log::warn!(
+						target: "runtime::bridge-token-swap",
+						"Failed to migrate swap {:?}: too many pending swaps of {:?}",
+						swap_hash,
+						swap.source_account_at_this_chain,
+					);
+				}
+				// `Swaps` map is using the `Identity` hasher, so the hashed key is the swap hash
+				put_storage_value(pallet_name.as_bytes(), b"Swaps", swap_hash.as_bytes(), swap);
+			}
+
+			STORAGE_VERSION.put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::bridge-token-swap",
+				"Migrated token swap pallet storage to version {:?}",
+				STORAGE_VERSION,
+			);
+
+			db_weight.reads_writes(reads, writes)
+		}
+	}
+}
+
+/// Migration to the storage version 2.
+///
+/// Version 2 adds This and Bridged chain assets to the `TokenSwap` structure. Native token swaps
+/// are hashed without asset fields (see `TokenSwap::hash`), so all pending swaps keep their hashes
+/// and swap accounts. The migration only converts values of the `Swaps` map to the new layout.
+pub mod v2 {
+	use super::v1;
+	use crate::{Config, Pallet, Swaps};
+
+	use bp_token_swap::TokenSwap;
+	use frame_support::{
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+	use sp_std::marker::PhantomData;
+
+	/// Storage version, set by this migration.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+	/// Migration that converts values of the `Swaps` map to the new `TokenSwap` layout.
+	pub struct Migration<T, I>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let db_weight = T::DbWeight::get();
+			if Pallet::<T, I>::on_chain_storage_version() >= STORAGE_VERSION {
+				return db_weight.reads(1)
+			}
+
+			let mut translated_swaps = 0;
+			Swaps::<T, I>::translate::<v1::TokenSwapOf<T, I>, _>(|_, legacy_swap| {
+				translated_swaps += 1;
+				Some(TokenSwap {
+					swap_type: legacy_swap.swap_type,
+					source_asset_at_this_chain: None,
+					source_balance_at_this_chain: legacy_swap.source_balance_at_this_chain,
+					source_account_at_this_chain: legacy_swap.source_account_at_this_chain,
+					target_asset_at_bridged_chain: None,
+					target_balance_at_bridged_chain: legacy_swap.target_balance_at_bridged_chain,
+					target_account_at_bridged_chain: legacy_swap.target_account_at_bridged_chain,
+				})
+			});
+
+			STORAGE_VERSION.put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::bridge-token-swap",
+				"Migrated token swap pallet storage to version {:?}",
+				STORAGE_VERSION,
+			);
+
+			db_weight.reads_writes(translated_swaps + 1, translated_swaps + 1)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		mock::*, swap_account_id, target_account_at_this_chain, PendingMessages, PendingSwaps,
+		SwapTransferMessages, Swaps, SwapsBySourceAccount, TokenSwapOf,
+	};
+
+	use bp_token_swap::{TokenSwap, TokenSwapState, TokenSwapType};
+	use codec::Encode;
+	use frame_support::{
+		assert_ok,
+		storage::migration::get_storage_value,
+		traits::{Currency, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+	use sp_core::H256;
+	use sp_io::hashing::blake2_256;
+	use sp_runtime::traits::Convert;
+
+	const SWAP_ACCOUNT_BALANCE: Balance = 1_000;
+
+	frame_support::parameter_types! {
+		pub KnownSwaps: Vec<v1::TokenSwapOf<TestRuntime, ()>> =
+			vec![legacy_swap(100), legacy_swap(200)];
+	}
+
+	type MigrationV1 = v1::Migration<TestRuntime, (), KnownSwaps>;
+	type MigrationV2 = v2::Migration<TestRuntime, ()>;
+
+	fn legacy_swap(source_balance_at_this_chain: Balance) -> v1::TokenSwapOf<TestRuntime, ()> {
+		v1::TokenSwap {
+			swap_type: TokenSwapType::TemporaryTargetAccountAtBridgedChain,
+			source_balance_at_this_chain,
+			source_account_at_this_chain: THIS_CHAIN_ACCOUNT,
+			target_balance_at_bridged_chain: 200,
+			target_account_at_bridged_chain: 3,
+		}
+	}
+
+	fn migrated_swap(source_balance_at_this_chain: Balance) -> TokenSwapOf<TestRuntime, ()> {
+		TokenSwap {
+			swap_type: TokenSwapType::TemporaryTargetAccountAtBridgedChain,
+			source_asset_at_this_chain: None,
+			source_balance_at_this_chain,
+			source_account_at_this_chain: THIS_CHAIN_ACCOUNT,
+			target_asset_at_bridged_chain: None,
+			target_balance_at_bridged_chain: 200,
+			target_account_at_bridged_chain: 3,
+		}
+	}
+
+	fn swap_hash(swap: &impl Encode) -> H256 {
+		swap.using_encoded(blake2_256).into()
+	}
+
+	fn legacy_swap_details(swap_hash: H256) -> Option<v1::TokenSwapOf<TestRuntime, ()>> {
+		get_storage_value(b"TokenSwap", b"Swaps", swap_hash.as_bytes())
+	}
+
+	fn storage_version() -> StorageVersion {
+		crate::Pallet::<TestRuntime, ()>::on_chain_storage_version()
+	}
+
+	#[test]
+	fn migration_v1_fills_details_of_known_pending_swaps() {
+		run_test(|| {
+			StorageVersion::new(0).put::<crate::Pallet<TestRuntime, ()>>();
+			let swap_hash = swap_hash(&legacy_swap(100));
+			PendingSwaps::<TestRuntime, ()>::insert(swap_hash, TokenSwapState::Started);
+
+			MigrationV1::on_runtime_upgrade();
+
+			assert_eq!(legacy_swap_details(swap_hash), Some(legacy_swap(100)));
+			assert_eq!(
+				SwapsBySourceAccount::<TestRuntime, ()>::get(THIS_CHAIN_ACCOUNT).into_inner(),
+				vec![swap_hash],
+			);
+			assert_eq!(legacy_swap_details(self::swap_hash(&legacy_swap(200))), None);
+			assert_eq!(storage_version(), v1::STORAGE_VERSION);
+		});
+	}
+
+	#[test]
+	fn migration_v1_stores_details_even_if_account_swaps_list_is_full() {
+		run_test(|| {
+			StorageVersion::new(0).put::<crate::Pallet<TestRuntime, ()>>();
+			let swap_hashes = [swap_hash(&legacy_swap(100)), swap_hash(&legacy_swap(200))];
+			for swap_hash in &swap_hashes {
+				PendingSwaps::<TestRuntime, ()>::insert(swap_hash, TokenSwapState::Started);
+			}
+
+			MigrationV1::on_runtime_upgrade();
+
+			assert_eq!(legacy_swap_details(swap_hashes[0]), Some(legacy_swap(100)));
+			assert_eq!(legacy_swap_details(swap_hashes[1]), Some(legacy_swap(200)));
+			assert_eq!(
+				SwapsBySourceAccount::<TestRuntime, ()>::get(THIS_CHAIN_ACCOUNT).into_inner(),
+				vec![swap_hashes[0]],
+			);
+		});
+	}
+
+	#[test]
+	fn migration_v1_does_nothing_if_storage_is_already_migrated() {
+		run_test(|| {
+			v1::STORAGE_VERSION.put::<crate::Pallet<TestRuntime, ()>>();
+			let swap_hash = swap_hash(&legacy_swap(100));
+			PendingSwaps::<TestRuntime, ()>::insert(swap_hash, TokenSwapState::Started);
+
+			MigrationV1::on_runtime_upgrade();
+
+			assert_eq!(legacy_swap_details(swap_hash), None);
+		});
+	}
+
+	#[test]
+	fn migration_v2_converts_swaps_and_keeps_their_hashes() {
+		run_test(|| {
+			StorageVersion::new(0).put::<crate::Pallet<TestRuntime, ()>>();
+			let legacy_swap_hash = swap_hash(&legacy_swap(100));
+			let legacy_swap_account = TestAccountConverter::convert(legacy_swap_hash);
+			PendingSwaps::<TestRuntime, ()>::insert(legacy_swap_hash, TokenSwapState::Started);
+			PendingMessages::<TestRuntime, ()>::insert(MESSAGE_NONCE, legacy_swap_hash);
+			SwapTransferMessages::<TestRuntime, ()>::insert(legacy_swap_hash, MESSAGE_NONCE);
+
+			MigrationV1::on_runtime_upgrade();
+			MigrationV2::on_runtime_upgrade();
+
+			assert_eq!(migrated_swap(100).hash(), legacy_swap_hash);
+			assert_eq!(
+				swap_account_id::<TestRuntime, ()>(&migrated_swap(100)),
+				legacy_swap_account
+			);
+			assert_eq!(Swaps::<TestRuntime, ()>::get(legacy_swap_hash), Some(migrated_swap(100)));
+			assert_eq!(
+				PendingSwaps::<TestRuntime, ()>::get(legacy_swap_hash),
+				Some(TokenSwapState::Started),
+			);
+			assert_eq!(
+				PendingMessages::<TestRuntime, ()>::get(MESSAGE_NONCE),
+				Some(legacy_swap_hash)
+			);
+			assert_eq!(
+				SwapTransferMessages::<TestRuntime, ()>::get(legacy_swap_hash),
+				Some(MESSAGE_NONCE)
+			);
+			assert_eq!(
+				SwapsBySourceAccount::<TestRuntime, ()>::get(THIS_CHAIN_ACCOUNT).into_inner(),
+				vec![legacy_swap_hash],
+			);
+			assert_eq!(storage_version(), v2::STORAGE_VERSION);
+		});
+	}
+
+	#[test]
+	fn unknown_swap_may_be_claimed_after_migrations() {
+		run_test(|| {
+			StorageVersion::new(0).put::<crate::Pallet<TestRuntime, ()>>();
+			let legacy_swap_hash = swap_hash(&legacy_swap(SWAP_ACCOUNT_BALANCE));
+			let legacy_swap_account = TestAccountConverter::convert(legacy_swap_hash);
+			PendingSwaps::<TestRuntime, ()>::insert(legacy_swap_hash, TokenSwapState::Confirmed);
+			Balances::make_free_balance_be(&legacy_swap_account, SWAP_ACCOUNT_BALANCE);
+
+			MigrationV1::on_runtime_upgrade();
+			MigrationV2::on_runtime_upgrade();
+
+			let swap = migrated_swap(SWAP_ACCOUNT_BALANCE);
+			let target_account = target_account_at_this_chain::<TestRuntime, ()>(&swap);
+			assert_ok!(crate::Pallet::<TestRuntime, ()>::claim_swap(
+				Origin::signed(target_account),
+				swap,
+			));
+			assert_eq!(PendingSwaps::<TestRuntime, ()>::get(legacy_swap_hash), None);
+			assert_eq!(Balances::free_balance(&legacy_swap_account), 0);
+			assert_eq!(Balances::free_balance(&target_account), SWAP_ACCOUNT_BALANCE);
+		});
+	}
+
+	#[test]
+	fn migration_v2_does_nothing_if_storage_is_already_migrated() {
+		run_test(|| {
+			v2::STORAGE_VERSION.put::<crate::Pallet<TestRuntime, ()>>();
+			Swaps::<TestRuntime, ()>::insert(migrated_swap(100).hash(), migrated_swap(100));
+
+			MigrationV2::on_runtime_upgrade();
+
+			assert_eq!(
+				Swaps::<TestRuntime, ()>::get(migrated_swap(100).hash()),
+				Some(migrated_swap(100)),
+			);
+		});
+	}
+}
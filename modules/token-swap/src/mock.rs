@@ -31,11 +31,13 @@ use sp_runtime::{
 };
 
 pub type AccountId = u64;
+pub type AssetId = u32;
 pub type Balance = u64;
 pub type Block = frame_system::mocking::MockBlock<TestRuntime>;
 pub type BridgedAccountId = u64;
 pub type BridgedAccountPublic = sp_runtime::testing::UintAuthorityId;
 pub type BridgedAccountSignature = sp_runtime::testing::TestSignature;
+pub type BridgedAssetId = u32;
 pub type BridgedBalance = u64;
 pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
 
@@ -46,6 +48,10 @@ pub const MESSAGE_NONCE: MessageNonce = 3;
 pub const THIS_CHAIN_ACCOUNT: AccountId = 1;
 pub const THIS_CHAIN_ACCOUNT_BALANCE: Balance = 100_000;
 
+pub const THIS_CHAIN_ASSET: AssetId = 1;
+pub const THIS_CHAIN_ASSET_MIN_BALANCE: Balance = 20;
+pub const THIS_CHAIN_ACCOUNT_ASSET_BALANCE: Balance = 100_000;
+
 pub const SWAP_DELIVERY_AND_DISPATCH_FEE: Balance = 1;
 
 frame_support::construct_runtime! {
@@ -56,6 +62,7 @@ frame_support::construct_runtime! {
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		TokenSwap: pallet_bridge_token_swap::{Pallet, Call, Event<T>},
 	}
 }
@@ -111,9 +118,36 @@ impl pallet_balances::Config for TestRuntime {
 	type ReserveIdentifier = [u8; 8];
 }
 
+frame_support::parameter_types! {
+	pub const AssetDeposit: Balance = 1;
+	pub const AssetAccountDeposit: Balance = 1;
+	pub const MetadataDepositBase: Balance = 1;
+	pub const MetadataDepositPerByte: Balance = 1;
+	pub const ApprovalDeposit: Balance = 1;
+	pub const StringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for TestRuntime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = pallet_balances::Pallet<TestRuntime>;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 frame_support::parameter_types! {
 	pub const BridgedChainId: ChainId = *b"inst";
 	pub const OutboundMessageLaneId: LaneId = *b"lane";
+	pub const MaxPendingSwapsPerAccount: u32 = 1;
 }
 
 impl pallet_bridge_token_swap::Config for TestRuntime {
@@ -123,11 +157,14 @@ impl pallet_bridge_token_swap::Config for TestRuntime {
 	type BridgedChainId = BridgedChainId;
 	type OutboundMessageLaneId = OutboundMessageLaneId;
 	type MessagesBridge = TestMessagesBridge;
+	type MaxPendingSwapsPerAccount = MaxPendingSwapsPerAccount;
 
 	type ThisCurrency = pallet_balances::Pallet<TestRuntime>;
+	type ThisAssets = pallet_assets::Pallet<TestRuntime>;
 	type FromSwapToThisAccountIdConverter = TestAccountConverter;
 
 	type BridgedChain = BridgedChain;
+	type BridgedAssetId = BridgedAssetId;
 	type FromBridgedToThisAccountIdConverter = TestAccountConverter;
 }
 
@@ -190,6 +227,13 @@ pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
+	pallet_assets::GenesisConfig::<TestRuntime> {
+		assets: vec![(THIS_CHAIN_ASSET, THIS_CHAIN_ACCOUNT, true, THIS_CHAIN_ASSET_MIN_BALANCE)],
+		metadata: vec![],
+		accounts: vec![(THIS_CHAIN_ASSET, THIS_CHAIN_ACCOUNT, THIS_CHAIN_ACCOUNT_ASSET_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(test)
 }
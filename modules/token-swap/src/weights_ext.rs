@@ -27,16 +27,74 @@ pub trait WeightInfoExt: WeightInfo {
 
 	/// Weight of message send extrinsic.
 	fn send_message_weight(message: &impl Size, db_weight: RuntimeDbWeight) -> Weight;
+
+	// Functions that are used by extrinsics weights formulas.
+	//
+	// TODO [#78] use results of `*_asset_swap` benchmarks when weights are regenerated. For now,
+	// every asset swap call is a native tokens swap call plus single asset transfer.
+
+	/// Weight of single This chain asset transfer.
+	fn this_chain_asset_transfer_weight() -> Weight;
+
+	/// Weight of `create_swap` extrinsic, excluding weight of the message send.
+	fn create_swap_weight(is_asset_swap: bool, db_weight: RuntimeDbWeight) -> Weight {
+		let weight = Self::create_swap().saturating_add(Self::swap_details_weight(db_weight));
+		if is_asset_swap {
+			weight.saturating_add(Self::this_chain_asset_transfer_weight())
+		} else {
+			weight
+		}
+	}
+
+	/// Weight of `claim_swap` extrinsic.
+	fn claim_swap_weight(is_asset_swap: bool, db_weight: RuntimeDbWeight) -> Weight {
+		let weight = Self::claim_swap().saturating_add(Self::swap_details_weight(db_weight));
+		if is_asset_swap {
+			weight.saturating_add(Self::this_chain_asset_transfer_weight())
+		} else {
+			weight
+		}
+	}
+
+	/// Weight of `cancel_swap` extrinsic.
+	fn cancel_swap_weight(is_asset_swap: bool, db_weight: RuntimeDbWeight) -> Weight {
+		let weight = Self::cancel_swap().saturating_add(Self::swap_details_weight(db_weight));
+		if is_asset_swap {
+			weight.saturating_add(Self::this_chain_asset_transfer_weight())
+		} else {
+			weight
+		}
+	}
+
+	// TODO [#78] remove when weights are regenerated using the pallet benchmarks
+	/// Weight of updating `Swaps`, `SwapsBySourceAccount` and `SwapTransferMessages` maps when
+	/// swap is created or completed.
+	///
+	/// These maps have been added after `create_swap`, `claim_swap` and `cancel_swap` benchmarks
+	/// results have been generated.
+	fn swap_details_weight(db_weight: RuntimeDbWeight) -> Weight {
+		// `SwapsBySourceAccount` is read and written, `Swaps` and `SwapTransferMessages` are
+		// written
+		db_weight.reads_writes(1, 3)
+	}
 }
 
 impl WeightInfoExt for () {
 	fn send_message_weight(message: &impl Size, db_weight: RuntimeDbWeight) -> Weight {
 		<() as pallet_bridge_messages::WeightInfoExt>::send_message_weight(message, db_weight)
 	}
+
+	fn this_chain_asset_transfer_weight() -> Weight {
+		<() as pallet_assets::WeightInfo>::transfer()
+	}
 }
 
 impl<T: frame_system::Config> WeightInfoExt for crate::weights::MillauWeight<T> {
 	fn send_message_weight(message: &impl Size, db_weight: RuntimeDbWeight) -> Weight {
 		<() as pallet_bridge_messages::WeightInfoExt>::send_message_weight(message, db_weight)
 	}
+
+	fn this_chain_asset_transfer_weight() -> Weight {
+		<pallet_assets::weights::SubstrateWeight<T> as pallet_assets::WeightInfo>::transfer()
+	}
 }
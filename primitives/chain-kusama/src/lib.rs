@@ -149,6 +149,12 @@ sp_api::decl_runtime_apis! {
 	///
 	/// This API is implemented by runtimes that are receiving messages from Kusama chain, not the
 	/// Kusama runtime itself.
+	///
+	/// API versions:
+	///
+	/// - 2: `UnrewardedRelayersState::last_delivered_nonce` field is returned by the
+	/// `unrewarded_relayers_state` method.
+	#[api_version(2)]
 	pub trait FromKusamaInboundLaneApi {
 		/// State of the unrewarded relayers set at given lane.
 		fn unrewarded_relayers_state(lane: LaneId) -> UnrewardedRelayersState;
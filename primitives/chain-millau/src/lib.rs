@@ -22,8 +22,11 @@
 
 mod millau_hash;
 
-use bp_messages::{LaneId, MessageDetails, MessageNonce, UnrewardedRelayersState};
-use bp_runtime::Chain;
+use bp_messages::{
+	LaneId, MessageDetails, MessageNonce, UnderpaidMessageDetails, UnrewardedRelayersState,
+};
+use bp_runtime::{messages::MessageDispatchDryRunResult, Chain};
+use bp_token_swap::TokenSwapState;
 use frame_support::{
 	weights::{constants::WEIGHT_PER_SECOND, DispatchClass, IdentityFee, Weight},
 	Parameter, RuntimeDebug,
@@ -152,6 +155,9 @@ pub type AccountSigner = MultiSigner;
 /// Balance of an account.
 pub type Balance = u64;
 
+/// Identifier of an asset, managed by the `pallet-assets`.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -274,6 +280,9 @@ pub const RIALTO_TO_MILLAU_CONVERSION_RATE_PARAMETER_NAME: &str = "RialtoToMilla
 /// Name of the With-Rialto token swap pallet instance in the Millau runtime.
 pub const WITH_RIALTO_TOKEN_SWAP_PALLET_NAME: &str = "BridgeRialtoTokenSwap";
 
+/// Index of the With-Rialto token bridge pallet in the Millau runtime.
+pub const WITH_RIALTO_TOKEN_BRIDGE_PALLET_INDEX: u8 = 100;
+
 /// Name of the `MillauFinalityApi::best_finalized` runtime method.
 pub const BEST_FINALIZED_MILLAU_HEADER_METHOD: &str = "MillauFinalityApi_best_finalized";
 
@@ -283,10 +292,20 @@ pub const TO_MILLAU_ESTIMATE_MESSAGE_FEE_METHOD: &str =
 	"ToMillauOutboundLaneApi_estimate_message_delivery_and_dispatch_fee";
 /// Name of the `ToMillauOutboundLaneApi::message_details` runtime method.
 pub const TO_MILLAU_MESSAGE_DETAILS_METHOD: &str = "ToMillauOutboundLaneApi_message_details";
+/// Name of the `ToMillauOutboundLaneApi::underpaid_messages` runtime method.
+pub const TO_MILLAU_UNDERPAID_MESSAGES_METHOD: &str = "ToMillauOutboundLaneApi_underpaid_messages";
 
 /// Name of the `FromMillauInboundLaneApi::unrewarded_relayers_state` runtime method.
 pub const FROM_MILLAU_UNREWARDED_RELAYERS_STATE: &str =
 	"FromMillauInboundLaneApi_unrewarded_relayers_state";
+/// Name of the `FromMillauInboundLaneApi::dry_run_message` runtime method.
+pub const FROM_MILLAU_DRY_RUN_MESSAGE_METHOD: &str = "FromMillauInboundLaneApi_dry_run_message";
+
+/// Name of the `WithRialtoTokenSwapApi::swaps_by_source_account` runtime method.
+pub const WITH_RIALTO_TOKEN_SWAPS_BY_SOURCE_ACCOUNT_METHOD: &str =
+	"WithRialtoTokenSwapApi_swaps_by_source_account";
+/// Name of the `WithRialtoTokenSwapApi::swaps_by_state` runtime method.
+pub const WITH_RIALTO_TOKEN_SWAPS_BY_STATE_METHOD: &str = "WithRialtoTokenSwapApi_swaps_by_state";
 
 sp_api::decl_runtime_apis! {
 	/// API for querying information about the finalized Millau headers.
@@ -302,6 +321,11 @@ sp_api::decl_runtime_apis! {
 	///
 	/// This API is implemented by runtimes that are sending messages to Millau chain, not the
 	/// Millau runtime itself.
+	///
+	/// API versions:
+	///
+	/// - 2: `underpaid_messages` method is added.
+	#[api_version(2)]
 	pub trait ToMillauOutboundLaneApi<OutboundMessageFee: Parameter, OutboundPayload: Parameter> {
 		/// Estimate message delivery and dispatch fee that needs to be paid by the sender on
 		/// this chain.
@@ -326,15 +350,52 @@ sp_api::decl_runtime_apis! {
 			begin: MessageNonce,
 			end: MessageNonce,
 		) -> Vec<MessageDetails<OutboundMessageFee>>;
+		/// Returns nonces, paid and estimated delivery+dispatch fees of queued (sent, but not
+		/// yet delivered) messages whose fee is below the current fee estimate.
+		///
+		/// Only first `max_messages` queued messages are inspected.
+		///
+		/// Submitters of such messages may want to top up their fees using the
+		/// `increase_message_fee` or `increase_messages_fee` calls. The vector is ordered by the
+		/// nonce.
+		fn underpaid_messages(
+			lane: LaneId,
+			max_messages: MessageNonce,
+		) -> Vec<UnderpaidMessageDetails<OutboundMessageFee>>;
 	}
 
 	/// Inbound message lane API for messages sent by Millau chain.
 	///
 	/// This API is implemented by runtimes that are receiving messages from Millau chain, not the
 	/// Millau runtime itself.
-	pub trait FromMillauInboundLaneApi {
+	///
+	/// API versions:
+	///
+	/// - 2: `UnrewardedRelayersState::last_delivered_nonce` field is returned by the
+	/// `unrewarded_relayers_state` method.
+	/// - 3: `dry_run_message` method is added.
+	#[api_version(3)]
+	pub trait FromMillauInboundLaneApi<DispatchEvent: Parameter> {
 		/// State of the unrewarded relayers set at given lane.
 		fn unrewarded_relayers_state(lane: LaneId) -> UnrewardedRelayersState;
+		/// Dry run dispatch of the encoded message payload, sent by Millau over given lane.
+		///
+		/// The message is dispatched, but all changes are reverted afterwards. Returns dispatch
+		/// result, actual dispatch weight and events that would be deposited by the dispatch.
+		fn dry_run_message(
+			lane: LaneId,
+			payload: Vec<u8>,
+		) -> MessageDispatchDryRunResult<DispatchEvent>;
+	}
+
+	/// API for querying pending token swaps of the with-Rialto token swap pallet.
+	///
+	/// This API is implemented by the Millau runtime itself.
+	pub trait WithRialtoTokenSwapApi<TokenSwapDetails: Parameter> {
+		/// Returns details of all pending token swaps, started by given account.
+		fn swaps_by_source_account(account: AccountId) -> Vec<TokenSwapDetails>;
+		/// Returns details of all pending token swaps with given state.
+		fn swaps_by_state(state: TokenSwapState) -> Vec<TokenSwapDetails>;
 	}
 }
 
@@ -20,8 +20,10 @@
 // Runtime-generated DecodeLimit::decode_all_With_depth_limit
 #![allow(clippy::unnecessary_mut_passed)]
 
-use bp_messages::{LaneId, MessageDetails, MessageNonce, UnrewardedRelayersState};
-use bp_runtime::Chain;
+use bp_messages::{
+	LaneId, MessageDetails, MessageNonce, UnderpaidMessageDetails, UnrewardedRelayersState,
+};
+use bp_runtime::{messages::MessageDispatchDryRunResult, Chain};
 use frame_support::{
 	weights::{constants::WEIGHT_PER_SECOND, DispatchClass, IdentityFee, Weight},
 	Parameter, RuntimeDebug,
@@ -151,6 +153,9 @@ pub type Balance = u128;
 /// An instant or duration in time.
 pub type Moment = u64;
 
+/// Identifier of an asset, managed by the `pallet-assets`.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -234,6 +239,9 @@ pub const WITH_RIALTO_MESSAGES_PALLET_NAME: &str = "BridgeRialtoMessages";
 /// Name of the Millau->Rialto (actually KSM->DOT) conversion rate stored in the Rialto runtime.
 pub const MILLAU_TO_RIALTO_CONVERSION_RATE_PARAMETER_NAME: &str = "MillauToRialtoConversionRate";
 
+/// Index of the With-Millau token bridge pallet in the Rialto runtime.
+pub const WITH_MILLAU_TOKEN_BRIDGE_PALLET_INDEX: u8 = 100;
+
 /// Name of the parachain registrar pallet in the Rialto runtime.
 pub const PARAS_REGISTRAR_PALLET_NAME: &str = "Registrar";
 
@@ -249,10 +257,14 @@ pub const TO_RIALTO_ESTIMATE_MESSAGE_FEE_METHOD: &str =
 	"ToRialtoOutboundLaneApi_estimate_message_delivery_and_dispatch_fee";
 /// Name of the `ToRialtoOutboundLaneApi::message_details` runtime method.
 pub const TO_RIALTO_MESSAGE_DETAILS_METHOD: &str = "ToRialtoOutboundLaneApi_message_details";
+/// Name of the `ToRialtoOutboundLaneApi::underpaid_messages` runtime method.
+pub const TO_RIALTO_UNDERPAID_MESSAGES_METHOD: &str = "ToRialtoOutboundLaneApi_underpaid_messages";
 
 /// Name of the `FromRialtoInboundLaneApi::unrewarded_relayers_state` runtime method.
 pub const FROM_RIALTO_UNREWARDED_RELAYERS_STATE: &str =
 	"FromRialtoInboundLaneApi_unrewarded_relayers_state";
+/// Name of the `FromRialtoInboundLaneApi::dry_run_message` runtime method.
+pub const FROM_RIALTO_DRY_RUN_MESSAGE_METHOD: &str = "FromRialtoInboundLaneApi_dry_run_message";
 
 sp_api::decl_runtime_apis! {
 	/// API for querying information about the finalized Rialto headers.
@@ -268,6 +280,11 @@ sp_api::decl_runtime_apis! {
 	///
 	/// This API is implemented by runtimes that are sending messages to Rialto chain, not the
 	/// Rialto runtime itself.
+	///
+	/// API versions:
+	///
+	/// - 2: `underpaid_messages` method is added.
+	#[api_version(2)]
 	pub trait ToRialtoOutboundLaneApi<OutboundMessageFee: Parameter, OutboundPayload: Parameter> {
 		/// Estimate message delivery and dispatch fee that needs to be paid by the sender on
 		/// this chain.
@@ -292,15 +309,42 @@ sp_api::decl_runtime_apis! {
 			begin: MessageNonce,
 			end: MessageNonce,
 		) -> Vec<MessageDetails<OutboundMessageFee>>;
+		/// Returns nonces, paid and estimated delivery+dispatch fees of queued (sent, but not
+		/// yet delivered) messages whose fee is below the current fee estimate.
+		///
+		/// Only first `max_messages` queued messages are inspected.
+		///
+		/// Submitters of such messages may want to top up their fees using the
+		/// `increase_message_fee` or `increase_messages_fee` calls. The vector is ordered by the
+		/// nonce.
+		fn underpaid_messages(
+			lane: LaneId,
+			max_messages: MessageNonce,
+		) -> Vec<UnderpaidMessageDetails<OutboundMessageFee>>;
 	}
 
 	/// Inbound message lane API for messages sent by Rialto chain.
 	///
 	/// This API is implemented by runtimes that are receiving messages from Rialto chain, not the
 	/// Rialto runtime itself.
-	pub trait FromRialtoInboundLaneApi {
+	///
+	/// API versions:
+	///
+	/// - 2: `UnrewardedRelayersState::last_delivered_nonce` field is returned by the
+	/// `unrewarded_relayers_state` method.
+	/// - 3: `dry_run_message` method is added.
+	#[api_version(3)]
+	pub trait FromRialtoInboundLaneApi<DispatchEvent: Parameter> {
 		/// State of the unrewarded relayers set at given lane.
 		fn unrewarded_relayers_state(lane: LaneId) -> UnrewardedRelayersState;
+		/// Dry run dispatch of the encoded message payload, sent by Rialto over given lane.
+		///
+		/// The message is dispatched, but all changes are reverted afterwards. Returns dispatch
+		/// result, actual dispatch weight and events that would be deposited by the dispatch.
+		fn dry_run_message(
+			lane: LaneId,
+			payload: Vec<u8>,
+		) -> MessageDispatchDryRunResult<DispatchEvent>;
 	}
 }
 
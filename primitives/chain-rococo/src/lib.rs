@@ -150,6 +150,12 @@ sp_api::decl_runtime_apis! {
 	///
 	/// This API is implemented by runtimes that are receiving messages from Rococo chain, not the
 	/// Rococo runtime itself.
+	///
+	/// API versions:
+	///
+	/// - 2: `UnrewardedRelayersState::last_delivered_nonce` field is returned by the
+	/// `unrewarded_relayers_state` method.
+	#[api_version(2)]
 	pub trait FromRococoInboundLaneApi {
 		/// State of the unrewarded relayers set at given lane.
 		fn unrewarded_relayers_state(lane: LaneId) -> UnrewardedRelayersState;
@@ -20,6 +20,10 @@
 pub const IS_HALTED_VALUE_NAME: &str = "IsHalted";
 /// Name of the `BestFinalized` storage value.
 pub const BEST_FINALIZED_VALUE_NAME: &str = "BestFinalized";
+/// Name of the `CurrentAuthoritySet` storage value.
+pub const CURRENT_AUTHORITY_SET_VALUE_NAME: &str = "CurrentAuthoritySet";
+/// Name of the `InitialHash` storage value.
+pub const INITIAL_HASH_VALUE_NAME: &str = "InitialHash";
 
 use sp_core::storage::StorageKey;
 
@@ -45,6 +49,28 @@ pub fn best_finalized_hash_key(pallet_prefix: &str) -> StorageKey {
 	)
 }
 
+/// Storage key of the current authority set value in the runtime storage.
+pub fn current_authority_set_key(pallet_prefix: &str) -> StorageKey {
+	StorageKey(
+		bp_runtime::storage_value_final_key(
+			pallet_prefix.as_bytes(),
+			CURRENT_AUTHORITY_SET_VALUE_NAME.as_bytes(),
+		)
+		.to_vec(),
+	)
+}
+
+/// Storage key of the initial header hash value in the runtime storage.
+pub fn initial_hash_key(pallet_prefix: &str) -> StorageKey {
+	StorageKey(
+		bp_runtime::storage_value_final_key(
+			pallet_prefix.as_bytes(),
+			INITIAL_HASH_VALUE_NAME.as_bytes(),
+		)
+		.to_vec(),
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -75,4 +101,30 @@ mod tests {
 			hex::encode(&storage_key),
 		);
 	}
+
+	#[test]
+	fn current_authority_set_key_computed_properly() {
+		// If this test fails, then something has been changed in module storage that is breaking
+		// compatibility with previous pallet.
+		let storage_key = current_authority_set_key("BridgeGrandpa").0;
+		assert_eq!(
+			storage_key,
+			hex!("0b06f475eddb98cf933a12262e0388de24a7b8b5717ea33346fa595a66ccbcb0").to_vec(),
+			"Unexpected storage key: {}",
+			hex::encode(&storage_key),
+		);
+	}
+
+	#[test]
+	fn initial_hash_key_computed_properly() {
+		// If this test fails, then something has been changed in module storage that is breaking
+		// compatibility with previous pallet.
+		let storage_key = initial_hash_key("BridgeGrandpa").0;
+		assert_eq!(
+			storage_key,
+			hex!("0b06f475eddb98cf933a12262e0388deccab258509f2075fdbc5ad77225c3915").to_vec(),
+			"Unexpected storage key: {}",
+			hex::encode(&storage_key),
+		);
+	}
 }
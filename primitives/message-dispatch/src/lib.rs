@@ -21,7 +21,7 @@
 
 use bp_runtime::{
 	messages::{DispatchFeePayment, MessageDispatchResult},
-	ChainId, Size,
+	ChainId, PalletId, Size,
 };
 use codec::{Decode, Encode};
 use frame_support::RuntimeDebug;
@@ -34,6 +34,11 @@ pub type Weight = u64;
 /// Spec version type.
 pub type SpecVersion = u32;
 
+/// Nonce of the message, signed by the target account owner.
+///
+/// Used to protect `CallOrigin::TargetAccountWithNonce` messages from replay.
+pub type TargetAccountNonce = u64;
+
 /// A generic trait to dispatch arbitrary messages delivered over the bridge.
 pub trait MessageDispatch<AccountId, BridgeMessageId> {
 	/// A type of the message to be dispatched.
@@ -89,7 +94,10 @@ pub enum CallOrigin<SourceChainAccountId, TargetChainAccountPublic, TargetChainS
 	/// `SourceChainAccountId` controls `TargetChainAccountPublic` is the `TargetChainSignature`
 	/// over `(Call, SourceChainAccountId, TargetChainSpecVersion, SourceChainBridgeId).encode()`.
 	///
-	/// NOTE sending messages using this origin (or any other) does not have replay protection!
+	/// NOTE sending messages using this origin does not have replay protection! It is kept
+	/// for compatibility with messages that have been signed before `TargetAccountWithNonce`
+	/// has been introduced. New messages should use `TargetAccountWithNonce` instead.
+	///
 	/// The assumption is that both the source account and the target account is controlled by
 	/// the same entity, so source-chain replay protection is sufficient.
 	/// As a consequence, it's extremely important for the target chain user to never produce
@@ -109,6 +117,30 @@ pub enum CallOrigin<SourceChainAccountId, TargetChainAccountPublic, TargetChainS
 	/// Note that the derived account does not need to have a private key on the target chain. This
 	/// origin can therefore represent proxies, pallets, etc. as well as "regular" accounts.
 	SourceAccount(SourceChainAccountId),
+
+	/// Call is sent by `SourceChainAccountId` on the source chain. On the target chain it is
+	/// dispatched from an account controlled by a private key on the target chain.
+	///
+	/// This is the same as `TargetAccount`, but the `TargetChainSignature` is computed over
+	/// `(Call, SourceChainAccountId, TargetAccountNonce, TargetChainSpecVersion,
+	/// SourceChainBridgeId, TargetChainBridgeId).encode()`. The target chain tracks nonces that
+	/// have been used by every target account, so the same signed message can't be dispatched
+	/// twice.
+	TargetAccountWithNonce(
+		SourceChainAccountId,
+		TargetChainAccountPublic,
+		TargetChainSignature,
+		TargetAccountNonce,
+	),
+
+	/// Call is sent by the pallet (e.g. multisig or collective) with given `PalletId` on the
+	/// source chain. On the target chain it is dispatched from a derived account ID, or with the
+	/// custom origin, selected by the dispatch module configuration.
+	///
+	/// The source chain runtime decides which origins are allowed to send messages on behalf of
+	/// the pallet with given `PalletId`. It is the source chain pallet responsibility to ensure
+	/// that the message is approved by its members.
+	SourcePallet(PalletId),
 }
 
 /// Message payload type used by dispatch module.
@@ -193,6 +193,18 @@ pub struct MessageDetails<OutboundMessageFee> {
 	pub dispatch_fee_payment: DispatchFeePayment,
 }
 
+/// Details of the queued message whose fee is below the current fee estimate, returned by runtime
+/// APIs.
+#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq)]
+pub struct UnderpaidMessageDetails<OutboundMessageFee> {
+	/// Nonce assigned to the message.
+	pub nonce: MessageNonce,
+	/// Delivery+dispatch fee paid by the message submitter at the source chain.
+	pub delivery_and_dispatch_fee: OutboundMessageFee,
+	/// Current estimate of the delivery+dispatch fee of the same message.
+	pub estimated_delivery_and_dispatch_fee: OutboundMessageFee,
+}
+
 /// Bit vector of message dispatch results.
 pub type DispatchResultsBitVec = BitVec<Msb0, u8>;
 
@@ -278,6 +290,11 @@ pub struct UnrewardedRelayersState {
 	pub messages_in_oldest_entry: MessageNonce,
 	/// Total number of messages in the relayers vector.
 	pub total_messages: MessageNonce,
+	/// Nonce of the latest message that has been delivered to the target chain.
+	///
+	/// This corresponds to the result of the `InboundLaneData::last_delivered_nonce` call
+	/// at the bridged chain.
+	pub last_delivered_nonce: MessageNonce,
 }
 
 /// Outbound lane data.
@@ -69,6 +69,9 @@ pub const ACCOUNT_DERIVATION_PREFIX: &[u8] = b"pallet-bridge/account-derivation/
 /// A unique prefix for entropy when generating a cross-chain account ID for the Root account.
 pub const ROOT_ACCOUNT_DERIVATION_PREFIX: &[u8] = b"pallet-bridge/account-derivation/root";
 
+/// A unique prefix for entropy when generating a cross-chain account ID for the pallet account.
+pub const PALLET_ACCOUNT_DERIVATION_PREFIX: &[u8] = b"pallet-bridge/account-derivation/pallet";
+
 /// Generic header Id.
 #[derive(RuntimeDebug, Default, Clone, Copy, Eq, Hash, PartialEq)]
 pub struct HeaderId<Hash, Number>(pub Number, pub Hash);
@@ -82,6 +85,10 @@ pub struct HeaderId<Hash, Number>(pub Number, pub Hash);
 /// used for that.
 pub type ChainId = [u8; 4];
 
+/// Identifier of the pallet. This is the same value that is wrapped by the
+/// `frame_support::PalletId`.
+pub type PalletId = [u8; 8];
+
 /// Type of accounts on the source chain.
 pub enum SourceAccount<T> {
 	/// An account that belongs to Root (privileged origin).
@@ -91,6 +98,8 @@ pub enum SourceAccount<T> {
 	/// The embedded account ID may or may not have a private key depending on the "owner" of the
 	/// account (private key, pallet, proxy, etc.).
 	Account(T),
+	/// An account that belongs to the pallet (e.g. multisig or collective) with given id.
+	Pallet(PalletId),
 }
 
 /// Derive an account ID from a foreign account ID.
@@ -113,6 +122,8 @@ where
 			(ROOT_ACCOUNT_DERIVATION_PREFIX, bridge_id).using_encoded(blake2_256),
 		SourceAccount::Account(id) =>
 			(ACCOUNT_DERIVATION_PREFIX, bridge_id, id).using_encoded(blake2_256),
+		SourceAccount::Pallet(id) =>
+			(PALLET_ACCOUNT_DERIVATION_PREFIX, bridge_id, id).using_encoded(blake2_256),
 	}
 	.into()
 }
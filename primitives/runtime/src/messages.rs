@@ -19,6 +19,7 @@
 use codec::{Decode, Encode};
 use frame_support::{weights::Weight, RuntimeDebug};
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
 /// Where message dispatch fee is paid?
 #[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo)]
@@ -55,3 +56,17 @@ pub struct MessageDispatchResult {
 	/// enabled this option.
 	pub dispatch_fee_paid_during_dispatch: bool,
 }
+
+/// Result of the message dispatch dry run.
+///
+/// The dry run performs all checks that are performed during actual message dispatch and then
+/// dispatches the message. All changes (including deposited events) are reverted afterwards.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct MessageDispatchDryRunResult<Event> {
+	/// Message dispatch result, as it would be returned by the dispatcher.
+	pub dispatch_result: MessageDispatchResult,
+	/// Actual dispatch weight of the message (declared weight minus unspent weight).
+	pub actual_weight: Weight,
+	/// Events that would be deposited by the message dispatch.
+	pub events: Vec<Event>,
+}
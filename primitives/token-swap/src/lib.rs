@@ -73,14 +73,27 @@ pub enum TokenSwapType<ThisBlockNumber> {
 /// This chain is always the chain where swap has been started. And the Bridged chain is the other
 /// chain.
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
-pub struct TokenSwap<ThisBlockNumber, ThisBalance, ThisAccountId, BridgedBalance, BridgedAccountId>
-{
+pub struct TokenSwap<
+	ThisBlockNumber,
+	ThisAssetId,
+	ThisBalance,
+	ThisAccountId,
+	BridgedAssetId,
+	BridgedBalance,
+	BridgedAccountId,
+> {
 	/// The type of the swap.
 	pub swap_type: TokenSwapType<ThisBlockNumber>,
+	/// This chain asset to be swapped with `target_balance_at_bridged_chain`. If `None`, This
+	/// chain native tokens are swapped.
+	pub source_asset_at_this_chain: Option<ThisAssetId>,
 	/// This chain balance to be swapped with `target_balance_at_bridged_chain`.
 	pub source_balance_at_this_chain: ThisBalance,
 	/// Account id of the party acting at This chain and owning the `source_account_at_this_chain`.
 	pub source_account_at_this_chain: ThisAccountId,
+	/// Bridged chain asset to be swapped with `source_balance_at_this_chain`. If `None`, the
+	/// Bridged chain native tokens are swapped.
+	pub target_asset_at_bridged_chain: Option<BridgedAssetId>,
 	/// Bridged chain balance to be swapped with `source_balance_at_this_chain`.
 	pub target_balance_at_bridged_chain: BridgedBalance,
 	/// Account id of the party acting at the Bridged chain and owning the
@@ -88,19 +101,87 @@ pub struct TokenSwap<ThisBlockNumber, ThisBalance, ThisAccountId, BridgedBalance
 	pub target_account_at_bridged_chain: BridgedAccountId,
 }
 
-impl<ThisBlockNumber, ThisBalance, ThisAccountId, BridgedBalance, BridgedAccountId>
-	TokenSwap<ThisBlockNumber, ThisBalance, ThisAccountId, BridgedBalance, BridgedAccountId>
+impl<
+		ThisBlockNumber,
+		ThisAssetId,
+		ThisBalance,
+		ThisAccountId,
+		BridgedAssetId,
+		BridgedBalance,
+		BridgedAccountId,
+	>
+	TokenSwap<
+		ThisBlockNumber,
+		ThisAssetId,
+		ThisBalance,
+		ThisAccountId,
+		BridgedAssetId,
+		BridgedBalance,
+		BridgedAccountId,
+	>
 where
-	TokenSwap<ThisBlockNumber, ThisBalance, ThisAccountId, BridgedBalance, BridgedAccountId>:
-		Encode,
+	ThisBlockNumber: Encode,
+	ThisAssetId: Encode,
+	ThisBalance: Encode,
+	ThisAccountId: Encode,
+	BridgedAssetId: Encode,
+	BridgedBalance: Encode,
+	BridgedAccountId: Encode,
 {
 	/// Returns hash, used to identify this token swap.
+	///
+	/// Swaps of native tokens are hashed without asset fields, so their hashes (and accounts,
+	/// derived from these hashes) are the same as before assets have been added to the swap.
+	/// Swaps of assets are hashed using their full encoding. Since both encodings start with the
+	/// swap type, they can't collide as long as all other fields have fixed size: encoded asset
+	/// swap is then always longer than encoded native swap of the same type.
 	pub fn hash(&self) -> H256 {
-		self.using_encoded(blake2_256).into()
+		match (&self.source_asset_at_this_chain, &self.target_asset_at_bridged_chain) {
+			(None, None) => (
+				&self.swap_type,
+				&self.source_balance_at_this_chain,
+				&self.source_account_at_this_chain,
+				&self.target_balance_at_bridged_chain,
+				&self.target_account_at_bridged_chain,
+			)
+				.using_encoded(blake2_256)
+				.into(),
+			_ => self.using_encoded(blake2_256).into(),
+		}
 	}
 }
 
-/// SCALE-encoded `Currency::transfer` call on the bridged chain.
+/// Pending token swap details, returned by runtime APIs.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct TokenSwapDetails<
+	ThisBlockNumber,
+	ThisAssetId,
+	ThisBalance,
+	ThisAccountId,
+	BridgedAssetId,
+	BridgedBalance,
+	BridgedAccountId,
+> {
+	/// Hash of the swap.
+	pub hash: H256,
+	/// The swap itself.
+	pub swap: TokenSwap<
+		ThisBlockNumber,
+		ThisAssetId,
+		ThisBalance,
+		ThisAccountId,
+		BridgedAssetId,
+		BridgedBalance,
+		BridgedAccountId,
+	>,
+	/// Current state of the swap.
+	pub state: TokenSwapState,
+	/// Nonce of the tokens transfer message.
+	pub transfer_message_nonce: Option<u64>,
+}
+
+/// SCALE-encoded `Currency::transfer` (or `pallet_assets::Call::transfer` if the
+/// `TokenSwap::target_asset_at_bridged_chain` is set) call on the bridged chain.
 pub type RawBridgedTransferCall = Vec<u8>;
 
 /// Token swap creation parameters.
@@ -118,7 +199,11 @@ pub struct TokenSwapCreation<BridgedAccountPublic, ThisChainBalance, BridgedAcco
 	pub bridged_currency_transfer: RawBridgedTransferCall,
 	/// Dispatch weight of the tokens transfer call at the Bridged chain.
 	pub bridged_currency_transfer_weight: Weight,
+	/// Nonce of the tokens transfer message. Every nonce may only be used once by the
+	/// `target_account_at_bridged_chain`.
+	pub bridged_currency_transfer_nonce: u64,
 	/// The signature of the `target_account_at_bridged_chain` for the message
-	/// returned by the `pallet_bridge_dispatch::account_ownership_digest()` function call.
+	/// returned by the `pallet_bridge_dispatch::account_ownership_digest_with_nonce()` function
+	/// call.
 	pub bridged_currency_transfer_signature: BridgedAccountSignature,
 }
@@ -20,7 +20,10 @@ use codec::Encode;
 use frame_support::weights::Weight;
 use sp_core::{Bytes, Pair};
 
-use messages_relay::relay_strategy::MixStrategy;
+use messages_relay::{
+	relay_coordination::SlotCoordination,
+	relay_strategy::{CoordinatedStrategy, MixStrategy},
+};
 use relay_kusama_client::Kusama;
 use relay_polkadot_client::Polkadot;
 use relay_substrate_client::{Client, SignParam, TransactionSignScheme, UnsignedTransaction};
@@ -58,7 +61,7 @@ impl SubstrateMessageLane for KusamaMessagesToPolkadot {
 	type ReceiveMessagesDeliveryProofCallBuilder =
 		KusamaMessagesToPolkadotReceiveMessagesDeliveryProofCallBuilder;
 
-	type RelayStrategy = MixStrategy;
+	type RelayStrategy = CoordinatedStrategy<MixStrategy, Option<SlotCoordination>>;
 }
 
 /// Update Polkadot -> Kusama conversion rate, stored in Kusama runtime storage.
@@ -72,12 +75,12 @@ pub(crate) async fn update_polkadot_to_kusama_conversion_rate(
 	let (spec_version, transaction_version) = client.simple_runtime_version().await?;
 	client
 		.submit_signed_extrinsic(signer_id, move |_, transaction_nonce| {
-			Bytes(
+			Ok(Bytes(
 				Kusama::sign_transaction(SignParam {
 					spec_version,
 					transaction_version,
 					genesis_hash,
-					signer,
+					signer: signer.into(),
 					era: relay_substrate_client::TransactionEra::immortal(),
 					unsigned: UnsignedTransaction::new(
 						relay_kusama_client::runtime::Call::BridgePolkadotMessages(
@@ -89,9 +92,9 @@ pub(crate) async fn update_polkadot_to_kusama_conversion_rate(
 						),
 						transaction_nonce,
 					),
-				})
+				})?
 				.encode(),
-			)
+			))
 		})
 		.await
 		.map(drop)
@@ -21,10 +21,12 @@ use crate::cli::{
 	encode_call::{self, Call, CliEncodeCall},
 	encode_message,
 	send_message::{self, DispatchFeePayment},
+	trace_message::{self, MessageDispatchEvent},
 	CliChain,
 };
 use anyhow::anyhow;
 use bp_message_dispatch::{CallOrigin, MessagePayload};
+use bp_messages::{LaneId, MessageNonce};
 use codec::Decode;
 use frame_support::weights::{DispatchInfo, GetDispatchInfo};
 use relay_millau_client::Millau;
@@ -115,3 +117,13 @@ impl CliChain for Millau {
 		}
 	}
 }
+
+impl MessageDispatchEvent for millau_runtime::Event {
+	fn message_dispatch_event(&self, message_id: &(LaneId, MessageNonce)) -> Option<String> {
+		match *self {
+			millau_runtime::Event::BridgeDispatch(ref event) =>
+				trace_message::describe_dispatch_event(event, message_id),
+			_ => None,
+		}
+	}
+}
@@ -19,7 +19,10 @@
 use codec::Encode;
 use sp_core::{Bytes, Pair};
 
-use messages_relay::relay_strategy::MixStrategy;
+use messages_relay::{
+	relay_coordination::SlotCoordination,
+	relay_strategy::{CoordinatedStrategy, MixStrategy},
+};
 use relay_millau_client::Millau;
 use relay_rialto_client::Rialto;
 use relay_substrate_client::{Client, SignParam, TransactionSignScheme, UnsignedTransaction};
@@ -55,7 +58,7 @@ impl SubstrateMessageLane for MillauMessagesToRialto {
 		millau_runtime::WithRialtoMessagesInstance,
 	>;
 
-	type RelayStrategy = MixStrategy;
+	type RelayStrategy = CoordinatedStrategy<MixStrategy, Option<SlotCoordination>>;
 }
 
 /// Update Rialto -> Millau conversion rate, stored in Millau runtime storage.
@@ -69,12 +72,12 @@ pub(crate) async fn update_rialto_to_millau_conversion_rate(
 	let (spec_version, transaction_version) = client.simple_runtime_version().await?;
 	client
 		.submit_signed_extrinsic(signer_id, move |_, transaction_nonce| {
-			Bytes(
+			Ok(Bytes(
 				Millau::sign_transaction(SignParam {
 					spec_version,
 					transaction_version,
 					genesis_hash,
-					signer,
+					signer: signer.into(),
 					era: relay_substrate_client::TransactionEra::immortal(),
 					unsigned: UnsignedTransaction::new(
 						millau_runtime::MessagesCall::update_pallet_parameter {
@@ -85,9 +88,9 @@ pub(crate) async fn update_rialto_to_millau_conversion_rate(
 							.into(),
 						transaction_nonce,
 					),
-				})
+				})?
 				.encode(),
-			)
+			))
 		})
 		.await
 		.map(drop)
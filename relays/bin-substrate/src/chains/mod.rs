@@ -208,10 +208,11 @@ mod tests {
 			spec_version: 1,
 			transaction_version: 1,
 			genesis_hash: Default::default(),
-			signer: sp_keyring::AccountKeyring::Alice.pair(),
+			signer: sp_keyring::AccountKeyring::Alice.pair().into(),
 			era: relay_substrate_client::TransactionEra::immortal(),
 			unsigned: UnsignedTransaction::new(rialto_call.clone(), 0),
-		});
+		})
+		.unwrap();
 		let extra_bytes_in_transaction = rialto_tx.encode().len() - rialto_call.encode().len();
 		assert!(
 			bp_rialto::TX_EXTRA_BYTES as usize >= extra_bytes_in_transaction,
@@ -229,10 +230,11 @@ mod tests {
 			spec_version: 0,
 			transaction_version: 0,
 			genesis_hash: Default::default(),
-			signer: sp_keyring::AccountKeyring::Alice.pair(),
+			signer: sp_keyring::AccountKeyring::Alice.pair().into(),
 			era: relay_substrate_client::TransactionEra::immortal(),
 			unsigned: UnsignedTransaction::new(millau_call.clone(), 0),
-		});
+		})
+		.unwrap();
 		let extra_bytes_in_transaction = millau_tx.encode().len() - millau_call.encode().len();
 		assert!(
 			bp_millau::TX_EXTRA_BYTES as usize >= extra_bytes_in_transaction,
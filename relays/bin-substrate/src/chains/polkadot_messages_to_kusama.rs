@@ -20,7 +20,10 @@ use codec::Encode;
 use sp_core::{Bytes, Pair};
 
 use frame_support::weights::Weight;
-use messages_relay::relay_strategy::MixStrategy;
+use messages_relay::{
+	relay_coordination::SlotCoordination,
+	relay_strategy::{CoordinatedStrategy, MixStrategy},
+};
 use relay_kusama_client::Kusama;
 use relay_polkadot_client::Polkadot;
 use relay_substrate_client::{Client, SignParam, TransactionSignScheme, UnsignedTransaction};
@@ -58,7 +61,7 @@ impl SubstrateMessageLane for PolkadotMessagesToKusama {
 	type ReceiveMessagesDeliveryProofCallBuilder =
 		PolkadotMessagesToKusamaReceiveMessagesDeliveryProofCallBuilder;
 
-	type RelayStrategy = MixStrategy;
+	type RelayStrategy = CoordinatedStrategy<MixStrategy, Option<SlotCoordination>>;
 }
 
 /// Update Kusama -> Polkadot conversion rate, stored in Polkadot runtime storage.
@@ -72,12 +75,12 @@ pub(crate) async fn update_kusama_to_polkadot_conversion_rate(
 	let (spec_version, transaction_version) = client.simple_runtime_version().await?;
 	client
 		.submit_signed_extrinsic(signer_id, move |_, transaction_nonce| {
-			Bytes(
+			Ok(Bytes(
 				Polkadot::sign_transaction(SignParam {
 					spec_version,
 					transaction_version,
 					genesis_hash,
-					signer,
+					signer: signer.into(),
 					era: relay_substrate_client::TransactionEra::immortal(),
 					unsigned: UnsignedTransaction::new(
 						relay_polkadot_client::runtime::Call::BridgeKusamaMessages(
@@ -89,9 +92,9 @@ pub(crate) async fn update_kusama_to_polkadot_conversion_rate(
 						),
 						transaction_nonce,
 					)
-				})
+				})?
 				.encode(),
-			)
+			))
 		})
 		.await
 		.map(drop)
@@ -21,10 +21,12 @@ use crate::cli::{
 	encode_call::{self, Call, CliEncodeCall},
 	encode_message,
 	send_message::{self, DispatchFeePayment},
+	trace_message::{self, MessageDispatchEvent},
 	CliChain,
 };
 use anyhow::anyhow;
 use bp_message_dispatch::{CallOrigin, MessagePayload};
+use bp_messages::{LaneId, MessageNonce};
 use codec::Decode;
 use frame_support::weights::{DispatchInfo, GetDispatchInfo};
 use relay_rialto_client::Rialto;
@@ -114,3 +116,13 @@ impl CliChain for Rialto {
 		}
 	}
 }
+
+impl MessageDispatchEvent for rialto_runtime::Event {
+	fn message_dispatch_event(&self, message_id: &(LaneId, MessageNonce)) -> Option<String> {
+		match *self {
+			rialto_runtime::Event::BridgeDispatch(ref event) =>
+				trace_message::describe_dispatch_event(event, message_id),
+			_ => None,
+		}
+	}
+}
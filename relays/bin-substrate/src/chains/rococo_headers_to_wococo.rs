@@ -19,6 +19,7 @@
 use crate::chains::wococo_headers_to_rococo::MAXIMAL_BALANCE_DECREASE_PER_DAY;
 
 use async_trait::async_trait;
+use relay_substrate_client::guard::GuardPolicy;
 use relay_wococo_client::Wococo;
 use substrate_relay_helper::{finality_pipeline::SubstrateFinalitySyncPipeline, TransactionParams};
 
@@ -44,12 +45,14 @@ impl SubstrateFinalitySyncPipeline for RococoFinalityToWococo {
 		target_client: &relay_substrate_client::Client<Wococo>,
 		transaction_params: &TransactionParams<sp_core::sr25519::Pair>,
 		enable_version_guard: bool,
+		guard_policy: &GuardPolicy,
 	) -> relay_substrate_client::Result<()> {
 		substrate_relay_helper::finality_guards::start::<Wococo, Wococo>(
 			target_client,
 			transaction_params,
 			enable_version_guard,
 			MAXIMAL_BALANCE_DECREASE_PER_DAY,
+			guard_policy,
 		)
 		.await
 	}
@@ -17,7 +17,10 @@
 //! Rococo-to-Wococo messages sync entrypoint.
 
 use frame_support::weights::Weight;
-use messages_relay::relay_strategy::MixStrategy;
+use messages_relay::{
+	relay_coordination::SlotCoordination,
+	relay_strategy::{CoordinatedStrategy, MixStrategy},
+};
 use relay_rococo_client::Rococo;
 use relay_wococo_client::Wococo;
 use substrate_relay_helper::messages_lane::SubstrateMessageLane;
@@ -52,5 +55,5 @@ impl SubstrateMessageLane for RococoMessagesToWococo {
 	type ReceiveMessagesDeliveryProofCallBuilder =
 		RococoMessagesToWococoReceiveMessagesDeliveryProofCallBuilder;
 
-	type RelayStrategy = MixStrategy;
+	type RelayStrategy = CoordinatedStrategy<MixStrategy, Option<SlotCoordination>>;
 }
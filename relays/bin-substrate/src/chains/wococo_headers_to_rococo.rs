@@ -18,6 +18,7 @@
 
 use async_trait::async_trait;
 use relay_rococo_client::Rococo;
+use relay_substrate_client::guard::GuardPolicy;
 use substrate_relay_helper::{finality_pipeline::SubstrateFinalitySyncPipeline, TransactionParams};
 
 /// Maximal saturating difference between `balance(now)` and `balance(now-24h)` to treat
@@ -49,12 +50,14 @@ impl SubstrateFinalitySyncPipeline for WococoFinalityToRococo {
 		target_client: &relay_substrate_client::Client<Rococo>,
 		transaction_params: &TransactionParams<sp_core::sr25519::Pair>,
 		enable_version_guard: bool,
+		guard_policy: &GuardPolicy,
 	) -> relay_substrate_client::Result<()> {
 		substrate_relay_helper::finality_guards::start::<Rococo, Rococo>(
 			target_client,
 			transaction_params,
 			enable_version_guard,
 			MAXIMAL_BALANCE_DECREASE_PER_DAY,
+			guard_policy,
 		)
 		.await
 	}
@@ -18,7 +18,10 @@
 
 use frame_support::weights::Weight;
 
-use messages_relay::relay_strategy::MixStrategy;
+use messages_relay::{
+	relay_coordination::SlotCoordination,
+	relay_strategy::{CoordinatedStrategy, MixStrategy},
+};
 use relay_rococo_client::Rococo;
 use relay_wococo_client::Wococo;
 use substrate_relay_helper::messages_lane::SubstrateMessageLane;
@@ -53,5 +56,5 @@ impl SubstrateMessageLane for WococoMessagesToRococo {
 	type ReceiveMessagesDeliveryProofCallBuilder =
 		WococoMessagesToRococoReceiveMessagesDeliveryProofCallBuilder;
 
-	type RelayStrategy = MixStrategy;
+	type RelayStrategy = CoordinatedStrategy<MixStrategy, Option<SlotCoordination>>;
 }
@@ -40,6 +40,25 @@ impl FullBridge {
 			Self::PolkadotToKusama => POLKADOT_TO_KUSAMA_INDEX,
 		}
 	}
+
+	/// Return weight of `CallOrigin::TargetAccountWithNonce` bookkeeping at the target chain.
+	///
+	/// Returns `None` if target chain does not support this origin.
+	pub fn target_account_nonce_weight(&self) -> Option<frame_support::weights::Weight> {
+		use frame_support::traits::Get;
+		match self {
+			Self::MillauToRialto => Some(pallet_bridge_dispatch::target_account_nonce_weight(
+				rialto_runtime::DbWeight::get(),
+			)),
+			Self::RialtoToMillau => Some(pallet_bridge_dispatch::target_account_nonce_weight(
+				millau_runtime::DbWeight::get(),
+			)),
+			Self::RococoToWococo |
+			Self::WococoToRococo |
+			Self::KusamaToPolkadot |
+			Self::PolkadotToKusama => None,
+		}
+	}
 }
 
 pub const RIALTO_TO_MILLAU_INDEX: u8 = 0;
@@ -76,12 +95,28 @@ macro_rules! select_full_bridge {
 				// Send-message
 				#[allow(unused_imports)]
 				use millau_runtime::millau_to_rialto_account_ownership_digest as account_ownership_digest;
+				#[allow(unused_imports)]
+				use millau_runtime::millau_to_rialto_account_ownership_digest_with_nonce as account_ownership_digest_with_nonce;
+
+				// Check-bridge
+				#[allow(unused_imports)]
+				use bp_millau::AccountIdConverter as SourceAccountIdConverter;
+
+				// Prove-finality / Submit-proof
+				#[allow(unused_imports)]
+				use crate::chains::millau_headers_to_rialto::MillauFinalityToRialto as Finality;
 
 				#[allow(dead_code)]
 				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(millau_runtime::VERSION);
 				#[allow(dead_code)]
 				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(rialto_runtime::VERSION);
 
+				// Send-message (dry run)
+				#[allow(dead_code)]
+				const DRY_RUN_MESSAGE_METHOD: Option<&str> = Some(bp_millau::FROM_MILLAU_DRY_RUN_MESSAGE_METHOD);
+				#[allow(dead_code)]
+				type TargetDispatchEvent = rialto_runtime::Event;
+
 				$generic
 			}
 			FullBridge::RialtoToMillau => {
@@ -104,12 +139,28 @@ macro_rules! select_full_bridge {
 				// Send-message
 				#[allow(unused_imports)]
 				use rialto_runtime::rialto_to_millau_account_ownership_digest as account_ownership_digest;
+				#[allow(unused_imports)]
+				use rialto_runtime::rialto_to_millau_account_ownership_digest_with_nonce as account_ownership_digest_with_nonce;
+
+				// Check-bridge
+				#[allow(unused_imports)]
+				use bp_rialto::AccountIdConverter as SourceAccountIdConverter;
+
+				// Prove-finality / Submit-proof
+				#[allow(unused_imports)]
+				use crate::chains::rialto_headers_to_millau::RialtoFinalityToMillau as Finality;
 
 				#[allow(dead_code)]
 				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(rialto_runtime::VERSION);
 				#[allow(dead_code)]
 				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(millau_runtime::VERSION);
 
+				// Send-message (dry run)
+				#[allow(dead_code)]
+				const DRY_RUN_MESSAGE_METHOD: Option<&str> = Some(bp_rialto::FROM_RIALTO_DRY_RUN_MESSAGE_METHOD);
+				#[allow(dead_code)]
+				type TargetDispatchEvent = millau_runtime::Event;
+
 				$generic
 			}
 			FullBridge::RococoToWococo => {
@@ -131,12 +182,28 @@ macro_rules! select_full_bridge {
 				// Send-message
 				#[allow(unused_imports)]
 				use relay_rococo_client::runtime::rococo_to_wococo_account_ownership_digest as account_ownership_digest;
+				#[allow(unused_imports)]
+				use relay_rococo_client::runtime::rococo_to_wococo_account_ownership_digest_with_nonce as account_ownership_digest_with_nonce;
+
+				// Check-bridge
+				#[allow(unused_imports)]
+				use bp_rococo::AccountIdConverter as SourceAccountIdConverter;
+
+				// Prove-finality / Submit-proof
+				#[allow(unused_imports)]
+				use crate::chains::rococo_headers_to_wococo::RococoFinalityToWococo as Finality;
 
 				#[allow(dead_code)]
 				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_rococo::VERSION);
 				#[allow(dead_code)]
 				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_wococo::VERSION);
 
+				// Send-message (dry run)
+				#[allow(dead_code)]
+				const DRY_RUN_MESSAGE_METHOD: Option<&str> = None;
+				#[allow(dead_code)]
+				type TargetDispatchEvent = ();
+
 				$generic
 			}
 			FullBridge::WococoToRococo => {
@@ -158,12 +225,28 @@ macro_rules! select_full_bridge {
 				// Send-message
 				#[allow(unused_imports)]
 				use relay_wococo_client::runtime::wococo_to_rococo_account_ownership_digest as account_ownership_digest;
+				#[allow(unused_imports)]
+				use relay_wococo_client::runtime::wococo_to_rococo_account_ownership_digest_with_nonce as account_ownership_digest_with_nonce;
+
+				// Check-bridge
+				#[allow(unused_imports)]
+				use bp_wococo::AccountIdConverter as SourceAccountIdConverter;
+
+				// Prove-finality / Submit-proof
+				#[allow(unused_imports)]
+				use crate::chains::wococo_headers_to_rococo::WococoFinalityToRococo as Finality;
 
 				#[allow(dead_code)]
 				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_wococo::VERSION);
 				#[allow(dead_code)]
 				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_rococo::VERSION);
 
+				// Send-message (dry run)
+				#[allow(dead_code)]
+				const DRY_RUN_MESSAGE_METHOD: Option<&str> = None;
+				#[allow(dead_code)]
+				type TargetDispatchEvent = ();
+
 				$generic
 			}
 			FullBridge::KusamaToPolkadot => {
@@ -185,12 +268,28 @@ macro_rules! select_full_bridge {
 				// Send-message
 				#[allow(unused_imports)]
 				use relay_kusama_client::runtime::kusama_to_polkadot_account_ownership_digest as account_ownership_digest;
+				#[allow(unused_imports)]
+				use relay_kusama_client::runtime::kusama_to_polkadot_account_ownership_digest_with_nonce as account_ownership_digest_with_nonce;
+
+				// Check-bridge
+				#[allow(unused_imports)]
+				use bp_kusama::AccountIdConverter as SourceAccountIdConverter;
+
+				// Prove-finality / Submit-proof
+				#[allow(unused_imports)]
+				use crate::chains::kusama_headers_to_polkadot::KusamaFinalityToPolkadot as Finality;
 
 				#[allow(dead_code)]
 				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_kusama::VERSION);
 				#[allow(dead_code)]
 				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_polkadot::VERSION);
 
+				// Send-message (dry run)
+				#[allow(dead_code)]
+				const DRY_RUN_MESSAGE_METHOD: Option<&str> = None;
+				#[allow(dead_code)]
+				type TargetDispatchEvent = ();
+
 				$generic
 			}
 			FullBridge::PolkadotToKusama => {
@@ -212,12 +311,28 @@ macro_rules! select_full_bridge {
 				// Send-message
 				#[allow(unused_imports)]
 				use relay_polkadot_client::runtime::polkadot_to_kusama_account_ownership_digest as account_ownership_digest;
+				#[allow(unused_imports)]
+				use relay_polkadot_client::runtime::polkadot_to_kusama_account_ownership_digest_with_nonce as account_ownership_digest_with_nonce;
+
+				// Check-bridge
+				#[allow(unused_imports)]
+				use bp_polkadot::AccountIdConverter as SourceAccountIdConverter;
+
+				// Prove-finality / Submit-proof
+				#[allow(unused_imports)]
+				use crate::chains::polkadot_headers_to_kusama::PolkadotFinalityToKusama as Finality;
 
 				#[allow(dead_code)]
 				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_polkadot::VERSION);
 				#[allow(dead_code)]
 				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> = Some(bp_kusama::VERSION);
 
+				// Send-message (dry run)
+				#[allow(dead_code)]
+				const DRY_RUN_MESSAGE_METHOD: Option<&str> = None;
+				#[allow(dead_code)]
+				type TargetDispatchEvent = ();
+
 				$generic
 			}
 		}
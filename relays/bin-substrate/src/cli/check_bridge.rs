@@ -0,0 +1,781 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bridge health checker.
+//!
+//! Connects to both chains of the bridge and reports state of bridge pallets, lanes and
+//! relayer-related accounts.
+
+use crate::{
+	cli::{
+		bridge::FullBridge, Balance, HexLaneId, SourceConnectionParams, SourcePriceOracleParams,
+		TargetConnectionParams, TargetPriceOracleParams,
+	},
+	select_full_bridge,
+};
+
+use bp_header_chain::{
+	storage_keys::{best_finalized_hash_key, current_authority_set_key, is_halted_key},
+	AuthoritySet,
+};
+use bp_messages::{
+	storage_keys::{inbound_lane_data_key, operating_mode_key, outbound_lane_data_key},
+	InboundLaneData, LaneId, MessageNonce, OperatingMode, OutboundLaneData,
+	UnrewardedRelayersState,
+};
+use codec::{Decode, Encode};
+use relay_substrate_client::{
+	AccountIdOf, Chain, ChainWithGrandpa, ChainWithMessages, Client, HashOf,
+};
+use relay_utils::price_oracle::SharedPriceOracle;
+use serde_json::{json, Value};
+use sp_core::Bytes;
+use sp_runtime::{
+	traits::{Header as HeaderT, UniqueSaturatedInto},
+	FixedU128,
+};
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+use substrate_relay_helper::messages_lane::SubstrateMessageLane;
+
+/// Health report output format.
+#[derive(Debug, EnumString, EnumVariantNames, Clone, Copy, PartialEq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum OutputFormat {
+	/// Human-readable table.
+	Table,
+	/// JSON document.
+	Json,
+}
+
+/// Check bridge health.
+#[derive(StructOpt)]
+pub struct CheckBridge {
+	/// A bridge instance to check.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded identifiers of lanes that should be checked.
+	#[structopt(long, default_value = "00000000")]
+	lane: Vec<HexLaneId>,
+	/// Health report output format.
+	#[structopt(long, possible_values = OutputFormat::VARIANTS, case_insensitive = true, default_value = "table")]
+	output: OutputFormat,
+	/// Maximal number of finalized source headers, that are not yet known to the target chain.
+	#[structopt(long, default_value = "64")]
+	max_finality_lag: u64,
+	/// Maximal allowed difference ratio between stored conversion rate and the rate, computed
+	/// from token prices.
+	#[structopt(long, default_value = "0.05")]
+	max_conversion_rate_drift: f64,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	source_price: SourcePriceOracleParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_price: TargetPriceOracleParams,
+}
+
+impl CheckBridge {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		let bridge_name = format!("{:?}", self.bridge);
+		select_full_bridge!(self.bridge, {
+			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+			let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
+
+			let mut report = HealthReport::default();
+			let result = check_finality::<Source, Target>(
+				&source_client,
+				&target_client,
+				self.max_finality_lag,
+				&mut report,
+			)
+			.await;
+			report.fail_on_error("finality", result);
+			let result = check_messages_pallets::<Source, Target>(
+				&source_client,
+				&target_client,
+				&mut report,
+			)
+			.await;
+			report.fail_on_error("messages pallets", result);
+			for lane in &self.lane {
+				let result = check_lane::<Source, Target>(
+					&source_client,
+					&target_client,
+					lane.0,
+					&mut report,
+				)
+				.await;
+				report.fail_on_error(&format!("lane {}", hex::encode(lane.0)), result);
+			}
+
+			let relayers_fund_account_id = pallet_bridge_messages::relayer_fund_account_id::<
+				AccountIdOf<Source>,
+				SourceAccountIdConverter,
+			>();
+			let result = source_client
+				.free_native_balance(relayers_fund_account_id)
+				.await
+				.map(|balance| report.relayers_fund_balance(Source::NAME, Balance(balance as _)));
+			report.fail_on_error("relayers fund", result.map_err(Into::into));
+
+			let result = check_conversion_rates::<MessagesLane>(
+				&source_client,
+				&target_client,
+				self.source_price.to_price_oracle()?,
+				self.target_price.to_price_oracle()?,
+				self.max_conversion_rate_drift,
+				&mut report,
+			)
+			.await;
+			report.fail_on_error("conversion rates", result);
+
+			match self.output {
+				OutputFormat::Table => println!("{}", report.to_table()),
+				OutputFormat::Json => println!("{}", report.to_json(&bridge_name)),
+			}
+
+			if report.is_healthy() {
+				Ok(())
+			} else {
+				Err(anyhow::format_err!("Bridge {} is unhealthy", bridge_name))
+			}
+		})
+	}
+}
+
+/// Status of single health check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HealthStatus {
+	/// Everything is fine.
+	Ok,
+	/// Something requires attention, but bridge is operational.
+	Warning,
+	/// Bridge is not operational.
+	Error,
+	/// Status can't be determined.
+	Unknown,
+}
+
+impl HealthStatus {
+	/// Return status name.
+	fn as_str(&self) -> &'static str {
+		match *self {
+			HealthStatus::Ok => "ok",
+			HealthStatus::Warning => "warning",
+			HealthStatus::Error => "error",
+			HealthStatus::Unknown => "unknown",
+		}
+	}
+}
+
+/// Result of single health check.
+#[derive(Debug)]
+struct HealthCheck {
+	/// Check name.
+	name: String,
+	/// Check status.
+	status: HealthStatus,
+	/// Structured check value.
+	value: Value,
+	/// Human-readable details.
+	details: String,
+}
+
+/// Bridge health report.
+#[derive(Debug, Default)]
+struct HealthReport {
+	checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+	/// Add check result to the report.
+	fn push(
+		&mut self,
+		name: impl Into<String>,
+		status: HealthStatus,
+		value: Value,
+		details: String,
+	) {
+		self.checks.push(HealthCheck { name: name.into(), status, value, details });
+	}
+
+	/// Add failed check to the report if group of checks has failed.
+	fn fail_on_error(&mut self, name: &str, result: anyhow::Result<()>) {
+		if let Err(error) = result {
+			self.push(
+				name,
+				HealthStatus::Error,
+				Value::Null,
+				format!("Check has failed: {}", error),
+			);
+		}
+	}
+
+	/// Add relayers fund balance check to the report.
+	fn relayers_fund_balance(&mut self, chain: &str, balance: Balance) {
+		let status = if balance.0 == 0 { HealthStatus::Warning } else { HealthStatus::Ok };
+		self.push(
+			format!("relayers fund at {}", chain),
+			status,
+			json!(balance.0.to_string()),
+			format!("balance: {}", balance),
+		);
+	}
+
+	/// Returns true if there are no failed checks.
+	fn is_healthy(&self) -> bool {
+		self.checks.iter().all(|check| check.status != HealthStatus::Error)
+	}
+
+	/// Format report as JSON document.
+	fn to_json(&self, bridge: &str) -> Value {
+		json!({
+			"bridge": bridge,
+			"healthy": self.is_healthy(),
+			"checks": self.checks.iter().map(|check| json!({
+				"name": check.name,
+				"status": check.status.as_str(),
+				"value": check.value,
+				"details": check.details,
+			})).collect::<Vec<_>>(),
+		})
+	}
+
+	/// Format report as human-readable table.
+	fn to_table(&self) -> String {
+		const CHECK_HEADER: &str = "CHECK";
+		const STATUS_HEADER: &str = "STATUS";
+		const DETAILS_HEADER: &str = "DETAILS";
+
+		let name_width = self
+			.checks
+			.iter()
+			.map(|check| check.name.len())
+			.chain(std::iter::once(CHECK_HEADER.len()))
+			.max()
+			.unwrap_or_default();
+		let status_width = STATUS_HEADER.len().max(HealthStatus::Unknown.as_str().len());
+
+		let mut table = format!(
+			"{:name_width$}  {:status_width$}  {}",
+			CHECK_HEADER,
+			STATUS_HEADER,
+			DETAILS_HEADER,
+			name_width = name_width,
+			status_width = status_width,
+		);
+		for check in &self.checks {
+			table.push_str(&format!(
+				"\n{:name_width$}  {:status_width$}  {}",
+				check.name,
+				check.status.as_str(),
+				check.details,
+				name_width = name_width,
+				status_width = status_width,
+			));
+		}
+		table
+	}
+}
+
+/// Check state of the bridge GRANDPA pallet at the target chain.
+async fn check_finality<SC: ChainWithGrandpa, TC: Chain>(
+	source_client: &Client<SC>,
+	target_client: &Client<TC>,
+	max_finality_lag: u64,
+	report: &mut HealthReport,
+) -> anyhow::Result<()> {
+	let pallet_name = SC::WITH_CHAIN_GRANDPA_PALLET_NAME;
+
+	let best_finalized_hash = target_client
+		.storage_value::<HashOf<SC>>(best_finalized_hash_key(pallet_name), None)
+		.await?
+		.filter(|best_finalized_hash| *best_finalized_hash != Default::default());
+	let best_finalized_hash = match best_finalized_hash {
+		Some(best_finalized_hash) => {
+			report.push(
+				"bridge initialized",
+				HealthStatus::Ok,
+				json!(true),
+				format!("{} headers are known to {}", SC::NAME, TC::NAME),
+			);
+			best_finalized_hash
+		},
+		None => {
+			report.push(
+				"bridge initialized",
+				HealthStatus::Error,
+				json!(false),
+				format!("{} pallet at {} is not initialized", pallet_name, TC::NAME),
+			);
+			return Ok(())
+		},
+	};
+
+	let is_halted = target_client
+		.storage_value::<bool>(is_halted_key(pallet_name), None)
+		.await?
+		.unwrap_or(false);
+	report.push(
+		"finality pallet halted",
+		if is_halted { HealthStatus::Error } else { HealthStatus::Ok },
+		json!(is_halted),
+		format!(
+			"{} pallet at {} is {}",
+			pallet_name,
+			TC::NAME,
+			if is_halted { "halted" } else { "active" }
+		),
+	);
+
+	let bridged_best_finalized_number: u64 =
+		(*source_client.header_by_hash(best_finalized_hash).await?.number())
+			.unique_saturated_into();
+	let best_finalized_number: u64 =
+		source_client.best_finalized_header_number().await?.unique_saturated_into();
+	let finality_lag = best_finalized_number.saturating_sub(bridged_best_finalized_number);
+	report.push(
+		"finality lag",
+		finality_lag_status(finality_lag, max_finality_lag),
+		json!(finality_lag),
+		format!(
+			"best finalized {} header: {}, known to {}: {}",
+			SC::NAME,
+			best_finalized_number,
+			TC::NAME,
+			bridged_best_finalized_number,
+		),
+	);
+
+	let bridged_authority_set = target_client
+		.storage_value::<AuthoritySet>(current_authority_set_key(pallet_name), None)
+		.await?
+		.unwrap_or_default();
+	let authority_set_id = source_client.grandpa_current_set_id(None).await?;
+	report.push(
+		"authority set id",
+		authority_set_id_status(authority_set_id, bridged_authority_set.set_id),
+		json!({ "source": authority_set_id, "target": bridged_authority_set.set_id }),
+		format!(
+			"{} authority set id: {}, known to {}: {}",
+			SC::NAME,
+			authority_set_id,
+			TC::NAME,
+			bridged_authority_set.set_id,
+		),
+	);
+
+	Ok(())
+}
+
+/// Check operating mode of messages pallets at both chains.
+async fn check_messages_pallets<SC: ChainWithMessages, TC: ChainWithMessages>(
+	source_client: &Client<SC>,
+	target_client: &Client<TC>,
+	report: &mut HealthReport,
+) -> anyhow::Result<()> {
+	check_messages_pallet(source_client, TC::WITH_CHAIN_MESSAGES_PALLET_NAME, true, report).await?;
+	check_messages_pallet(target_client, SC::WITH_CHAIN_MESSAGES_PALLET_NAME, false, report).await
+}
+
+/// Check operating mode of messages pallet at given chain.
+async fn check_messages_pallet<C: Chain>(
+	client: &Client<C>,
+	pallet_name: &str,
+	is_source: bool,
+	report: &mut HealthReport,
+) -> anyhow::Result<()> {
+	let operating_mode = client
+		.storage_value::<OperatingMode>(operating_mode_key(pallet_name), None)
+		.await?
+		.unwrap_or_default();
+	report.push(
+		format!("messages pallet at {}", C::NAME),
+		operating_mode_status(operating_mode, is_source),
+		json!(format!("{:?}", operating_mode)),
+		format!("{} pallet operating mode: {:?}", pallet_name, operating_mode),
+	);
+	Ok(())
+}
+
+/// Check state of the lane at both chains.
+async fn check_lane<SC: ChainWithMessages, TC: ChainWithMessages>(
+	source_client: &Client<SC>,
+	target_client: &Client<TC>,
+	lane: LaneId,
+	report: &mut HealthReport,
+) -> anyhow::Result<()> {
+	let outbound_lane_data = source_client
+		.storage_value::<OutboundLaneData>(
+			outbound_lane_data_key(TC::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+			None,
+		)
+		.await?
+		.unwrap_or_default();
+	let inbound_lane_data = target_client
+		.storage_value::<InboundLaneData<AccountIdOf<SC>>>(
+			inbound_lane_data_key(SC::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+			None,
+		)
+		.await?
+		.unwrap_or_default();
+	let generated_nonce = outbound_lane_data.latest_generated_nonce;
+	let received_nonce = inbound_lane_data.last_delivered_nonce();
+	let confirmed_nonce = outbound_lane_data.latest_received_nonce;
+	report.push(
+		format!("lane {} nonces", hex::encode(lane)),
+		lane_nonces_status(generated_nonce, received_nonce, confirmed_nonce),
+		json!({
+			"generated": generated_nonce,
+			"received": received_nonce,
+			"confirmed": confirmed_nonce,
+		}),
+		format!(
+			"generated: {}, received: {}, confirmed: {}",
+			generated_nonce, received_nonce, confirmed_nonce,
+		),
+	);
+
+	let encoded_response = target_client
+		.state_call(SC::FROM_CHAIN_UNREWARDED_RELAYERS_STATE.into(), Bytes(lane.encode()), None)
+		.await?;
+	let unrewarded_relayers = UnrewardedRelayersState::decode(&mut &encoded_response.0[..])
+		.map_err(relay_substrate_client::Error::ResponseParseFailed)?;
+	let max_unrewarded_relayers = SC::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
+	let max_unconfirmed_messages = SC::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	report.push(
+		format!("lane {} unrewarded relayers", hex::encode(lane)),
+		unrewarded_relayers_status(
+			&unrewarded_relayers,
+			max_unrewarded_relayers,
+			max_unconfirmed_messages,
+		),
+		json!({
+			"unrewarded_relayer_entries": unrewarded_relayers.unrewarded_relayer_entries,
+			"max_unrewarded_relayer_entries": max_unrewarded_relayers,
+			"unconfirmed_messages": unrewarded_relayers.total_messages,
+			"max_unconfirmed_messages": max_unconfirmed_messages,
+		}),
+		format!(
+			"entries: {}/{}, unconfirmed messages: {}/{}",
+			unrewarded_relayers.unrewarded_relayer_entries,
+			max_unrewarded_relayers,
+			unrewarded_relayers.total_messages,
+			max_unconfirmed_messages,
+		),
+	);
+
+	Ok(())
+}
+
+/// Check drift of conversion rates, stored at both chains.
+///
+/// Actual conversion rates are computed using configured token price oracles only. If oracle
+/// is not configured for some chain, then the rate is reported as unknown.
+async fn check_conversion_rates<P: SubstrateMessageLane>(
+	source_client: &Client<P::SourceChain>,
+	target_client: &Client<P::TargetChain>,
+	source_token_price_oracle: Option<SharedPriceOracle>,
+	target_token_price_oracle: Option<SharedPriceOracle>,
+	max_conversion_rate_drift: f64,
+	report: &mut HealthReport,
+) -> anyhow::Result<()> {
+	let source_token_price = read_token_price(source_token_price_oracle.as_ref()).await;
+	let target_token_price = read_token_price(target_token_price_oracle.as_ref()).await;
+
+	if let Some(parameter_name) = P::TARGET_TO_SOURCE_CONVERSION_RATE_PARAMETER_NAME {
+		let stored_rate = read_conversion_rate(source_client, parameter_name).await?;
+		let actual_rate = compute_conversion_rate(target_token_price, source_token_price);
+		push_conversion_rate(
+			report,
+			P::TargetChain::NAME,
+			P::SourceChain::NAME,
+			stored_rate,
+			actual_rate,
+			max_conversion_rate_drift,
+		);
+	}
+
+	if let Some(parameter_name) = P::SOURCE_TO_TARGET_CONVERSION_RATE_PARAMETER_NAME {
+		let stored_rate = read_conversion_rate(target_client, parameter_name).await?;
+		let actual_rate = compute_conversion_rate(source_token_price, target_token_price);
+		push_conversion_rate(
+			report,
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+			stored_rate,
+			actual_rate,
+			max_conversion_rate_drift,
+		);
+	}
+
+	Ok(())
+}
+
+/// Read token price from given oracle.
+async fn read_token_price(oracle: Option<&SharedPriceOracle>) -> Option<f64> {
+	match oracle?.price().await {
+		Ok(price) => Some(price),
+		Err(error) => {
+			log::trace!(target: "bridge", "Failed to read token price: {:?}", error);
+			None
+		},
+	}
+}
+
+/// Return conversion rate from `from` to `to` tokens, given prices of both tokens.
+///
+/// Matches the way the relayer computes the rate when updating conversion rate parameters.
+fn compute_conversion_rate(
+	from_token_price: Option<f64>,
+	to_token_price: Option<f64>,
+) -> Option<f64> {
+	Some(to_token_price? / from_token_price?)
+}
+
+/// Read conversion rate parameter from the runtime storage.
+async fn read_conversion_rate<C: Chain>(
+	client: &Client<C>,
+	parameter_name: &str,
+) -> anyhow::Result<Option<f64>> {
+	Ok(client
+		.storage_value::<FixedU128>(bp_runtime::storage_parameter_key(parameter_name), None)
+		.await?
+		.map(|rate| rate.to_float()))
+}
+
+/// Add conversion rate drift check to the report.
+fn push_conversion_rate(
+	report: &mut HealthReport,
+	from_chain: &str,
+	to_chain: &str,
+	stored_rate: Option<f64>,
+	actual_rate: Option<f64>,
+	max_conversion_rate_drift: f64,
+) {
+	let name = format!("{} to {} conversion rate", from_chain, to_chain);
+	match (stored_rate, actual_rate) {
+		(Some(stored_rate), Some(actual_rate)) if !stored_rate.is_normal() => report.push(
+			name,
+			HealthStatus::Error,
+			json!({ "stored": stored_rate, "actual": actual_rate }),
+			format!("stored: {}, actual: {}, stored rate is invalid", stored_rate, actual_rate),
+		),
+		(Some(stored_rate), Some(actual_rate)) => {
+			let drift = (stored_rate - actual_rate).abs() / stored_rate;
+			report.push(
+				name,
+				if drift > max_conversion_rate_drift {
+					HealthStatus::Warning
+				} else {
+					HealthStatus::Ok
+				},
+				json!({ "stored": stored_rate, "actual": actual_rate, "drift": drift }),
+				format!(
+					"stored: {}, actual: {}, drift: {:.2}%",
+					stored_rate,
+					actual_rate,
+					drift * 100.0
+				),
+			);
+		},
+		_ => report.push(
+			name,
+			HealthStatus::Unknown,
+			json!({ "stored": stored_rate, "actual": actual_rate }),
+			format!(
+				"stored: {}, actual: {}",
+				stored_rate.map(|rate| rate.to_string()).unwrap_or_else(|| "unknown".into()),
+				actual_rate.map(|rate| rate.to_string()).unwrap_or_else(|| "unknown".into()),
+			),
+		),
+	}
+}
+
+/// Return status of the finality lag check.
+fn finality_lag_status(finality_lag: u64, max_finality_lag: u64) -> HealthStatus {
+	if finality_lag > max_finality_lag {
+		HealthStatus::Warning
+	} else {
+		HealthStatus::Ok
+	}
+}
+
+/// Return status of the authority set id check.
+fn authority_set_id_status(source_set_id: u64, bridged_set_id: u64) -> HealthStatus {
+	if source_set_id == bridged_set_id {
+		HealthStatus::Ok
+	} else {
+		HealthStatus::Warning
+	}
+}
+
+/// Return status of the messages pallet operating mode check.
+fn operating_mode_status(operating_mode: OperatingMode, is_source: bool) -> HealthStatus {
+	match operating_mode {
+		OperatingMode::Normal => HealthStatus::Ok,
+		OperatingMode::RejectingOutboundMessages if is_source => HealthStatus::Error,
+		OperatingMode::RejectingOutboundMessages => HealthStatus::Ok,
+		OperatingMode::Halted => HealthStatus::Error,
+	}
+}
+
+/// Return status of the lane nonces check.
+fn lane_nonces_status(
+	generated_nonce: MessageNonce,
+	received_nonce: MessageNonce,
+	confirmed_nonce: MessageNonce,
+) -> HealthStatus {
+	if received_nonce > generated_nonce || confirmed_nonce > received_nonce {
+		HealthStatus::Error
+	} else {
+		HealthStatus::Ok
+	}
+}
+
+/// Return status of the unrewarded relayers check.
+fn unrewarded_relayers_status(
+	unrewarded_relayers: &UnrewardedRelayersState,
+	max_unrewarded_relayers: MessageNonce,
+	max_unconfirmed_messages: MessageNonce,
+) -> HealthStatus {
+	if unrewarded_relayers.unrewarded_relayer_entries >= max_unrewarded_relayers ||
+		unrewarded_relayers.total_messages >= max_unconfirmed_messages
+	{
+		HealthStatus::Warning
+	} else {
+		HealthStatus::Ok
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn test_report() -> HealthReport {
+		let mut report = HealthReport::default();
+		report.push("bridge initialized", HealthStatus::Ok, json!(true), "initialized".into());
+		report.push("finality lag", HealthStatus::Warning, json!(100), "lag: 100".into());
+		report
+	}
+
+	#[test]
+	fn report_is_formatted_as_table() {
+		assert_eq!(
+			test_report().to_table(),
+			"CHECK               STATUS   DETAILS\n\
+			bridge initialized  ok       initialized\n\
+			finality lag        warning  lag: 100",
+		);
+	}
+
+	#[test]
+	fn report_is_formatted_as_json() {
+		assert_eq!(
+			test_report().to_json("millau-to-rialto"),
+			json!({
+				"bridge": "millau-to-rialto",
+				"healthy": true,
+				"checks": [
+					{ "name": "bridge initialized", "status": "ok", "value": true, "details": "initialized" },
+					{ "name": "finality lag", "status": "warning", "value": 100, "details": "lag: 100" },
+				],
+			}),
+		);
+	}
+
+	#[test]
+	fn report_is_unhealthy_when_check_fails() {
+		let mut report = test_report();
+		assert!(report.is_healthy());
+
+		report.fail_on_error("lane 00000000", Err(anyhow::format_err!("connection lost")));
+		assert!(!report.is_healthy());
+	}
+
+	#[test]
+	fn conversion_rate_drift_is_detected() {
+		let mut report = HealthReport::default();
+		push_conversion_rate(&mut report, "Rialto", "Millau", Some(1.0), Some(1.01), 0.05);
+		push_conversion_rate(&mut report, "Millau", "Rialto", Some(1.0), Some(1.5), 0.05);
+		push_conversion_rate(&mut report, "Millau", "Rialto", Some(1.0), None, 0.05);
+		assert_eq!(
+			report.checks.iter().map(|check| check.status).collect::<Vec<_>>(),
+			vec![HealthStatus::Ok, HealthStatus::Warning, HealthStatus::Unknown],
+		);
+	}
+
+	#[test]
+	fn zero_stored_conversion_rate_is_reported_as_error() {
+		let mut report = HealthReport::default();
+		push_conversion_rate(&mut report, "Rialto", "Millau", Some(0.0), Some(1.0), 0.05);
+		assert_eq!(report.checks[0].status, HealthStatus::Error);
+		assert_eq!(report.checks[0].value, json!({ "stored": 0.0, "actual": 1.0 }));
+	}
+
+	#[test]
+	fn conversion_rate_is_computed_from_token_prices() {
+		assert_eq!(compute_conversion_rate(Some(2.0), Some(10.0)), Some(5.0));
+		assert_eq!(compute_conversion_rate(None, Some(10.0)), None);
+		assert_eq!(compute_conversion_rate(Some(2.0), None), None);
+	}
+
+	#[test]
+	fn finality_checks_work() {
+		assert_eq!(finality_lag_status(64, 64), HealthStatus::Ok);
+		assert_eq!(finality_lag_status(65, 64), HealthStatus::Warning);
+		assert_eq!(authority_set_id_status(1, 1), HealthStatus::Ok);
+		assert_eq!(authority_set_id_status(2, 1), HealthStatus::Warning);
+	}
+
+	#[test]
+	fn operating_mode_check_works() {
+		assert_eq!(operating_mode_status(OperatingMode::Normal, true), HealthStatus::Ok);
+		assert_eq!(
+			operating_mode_status(OperatingMode::RejectingOutboundMessages, true),
+			HealthStatus::Error,
+		);
+		assert_eq!(
+			operating_mode_status(OperatingMode::RejectingOutboundMessages, false),
+			HealthStatus::Ok,
+		);
+		assert_eq!(operating_mode_status(OperatingMode::Halted, false), HealthStatus::Error);
+	}
+
+	#[test]
+	fn lane_nonces_check_works() {
+		assert_eq!(lane_nonces_status(10, 5, 3), HealthStatus::Ok);
+		assert_eq!(lane_nonces_status(10, 10, 10), HealthStatus::Ok);
+		assert_eq!(lane_nonces_status(5, 10, 3), HealthStatus::Error);
+		assert_eq!(lane_nonces_status(10, 5, 7), HealthStatus::Error);
+	}
+
+	#[test]
+	fn unrewarded_relayers_check_works() {
+		let state = |entries, messages| UnrewardedRelayersState {
+			unrewarded_relayer_entries: entries,
+			total_messages: messages,
+			..Default::default()
+		};
+		assert_eq!(unrewarded_relayers_status(&state(1, 1), 4, 8), HealthStatus::Ok);
+		assert_eq!(unrewarded_relayers_status(&state(4, 1), 4, 8), HealthStatus::Warning);
+		assert_eq!(unrewarded_relayers_status(&state(1, 8), 4, 8), HealthStatus::Warning);
+	}
+}
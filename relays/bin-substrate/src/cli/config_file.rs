@@ -0,0 +1,457 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Declarative relayer configuration.
+//!
+//! The configuration file is a TOML file that describes one or more bridges, served by the
+//! `relay-headers-and-messages` command:
+//!
+//! ```toml
+//! [bridges.millau-rialto]
+//! lane = ["00000000", "73776170"]
+//! relayer-mode = "rational"
+//! prometheus-port = 9616
+//!
+//! [bridges.millau-rialto.millau]
+//! host = "127.0.0.1"
+//! port = 9945
+//! signer = { file = "/keys/millau-relayer" }
+//! transactions-mortality = 64
+//!
+//! [bridges.millau-rialto.rialto]
+//! host = "127.0.0.1"
+//! port = 9944
+//! signer = { env = "RIALTO_RELAYER_SURI" }
+//! ```
+//!
+//! Every key corresponds to the command line option: bridge-level keys are mapped to options
+//! with the same name and chain-level keys are mapped to options, prefixed with the chain name
+//! (e.g. `port` in the `millau` section becomes `--millau-port`). Options that are explicitly
+//! specified in the command line override values from the configuration file.
+
+use std::{ffi::OsString, path::Path};
+use structopt::StructOpt;
+
+/// Name of the command line argument that is used to specify path to the configuration file.
+const CONFIG_ARG: &str = "config";
+/// Command that supports configuration file.
+const CONFIG_COMMAND: &str = "relay-headers-and-messages";
+
+/// Bridge-level keys of the configuration file.
+const BRIDGE_KEYS: &[&str] = &[
+	"lane",
+	"relayer-mode",
+	"left-to-right-headers-relay-costs",
+	"right-to-left-headers-relay-costs",
+	"relay-mode",
+	"create-relayers-fund-accounts",
+	"only-mandatory-headers",
+	"no-prometheus",
+	"prometheus-host",
+	"prometheus-port",
+	"admin-host",
+	"admin-port",
+	"admin-token",
+	"coordination-relayer",
+	"coordination-slot-length",
+];
+/// Chain-level keys of the configuration file.
+const CHAIN_KEYS: &[&str] = &[
+	"host",
+	"port",
+	"secure",
+	"version-mode",
+	"spec-version",
+	"transaction-version",
+	"signer",
+	"signer-password",
+	"signer-env",
+	"signer-keystore",
+	"signer-public",
+	"signer-url",
+	"transactions-mortality",
+	"messages-pallet-owner",
+	"messages-pallet-owner-password",
+	"price-file",
+	"price-url",
+	"price-json-path",
+];
+/// Chain-level keys that may reference secrets, stored in files or environment variables.
+const SECRET_KEYS: &[&str] =
+	&["signer", "signer-password", "messages-pallet-owner", "messages-pallet-owner-password"];
+
+/// Expand `--config <path>` option of the `relay-headers-and-messages` command into the
+/// list of command line options.
+///
+/// Returns unmodified arguments if configuration file is not specified or if arguments can't be
+/// parsed - in the latter case the error is reported by the actual parser.
+pub fn expand_config_args(args: Vec<OsString>) -> anyhow::Result<Vec<OsString>> {
+	let matches = match super::Command::clap().get_matches_from_safe(args.iter()) {
+		Ok(matches) => matches,
+		Err(_) => return Ok(args),
+	};
+	let (bridge, bridge_matches) = match matches.subcommand() {
+		(CONFIG_COMMAND, Some(command_matches)) => match command_matches.subcommand() {
+			(bridge, Some(bridge_matches)) => (bridge, bridge_matches),
+			_ => return Ok(args),
+		},
+		_ => return Ok(args),
+	};
+	let config_path = match bridge_matches.value_of_os(CONFIG_ARG) {
+		Some(config_path) => config_path,
+		None => return Ok(args),
+	};
+
+	let config = std::fs::read_to_string(config_path).map_err(|e| {
+		anyhow::format_err!("Failed to read configuration file {:?}: {}", config_path, e)
+	})?;
+	let config_args = bridge_config_args(&config, bridge, &|name| std::env::var(name).ok())?;
+
+	let is_overridden = |option: &str| bridge_matches.occurrences_of(option_id(option)) != 0;
+	let mut expanded_args = args.clone();
+	expanded_args
+		.extend(filter_overridden_args(config_args, &is_overridden).into_iter().map(Into::into));
+	Ok(expanded_args)
+}
+
+/// Return identifier of the argument, that corresponds to given long option.
+fn option_id(option: &str) -> String {
+	option.trim_start_matches("--").replace('-', "_")
+}
+
+/// Remove options that have been specified in the command line from the list of arguments.
+///
+/// Configuration arguments are always `--option` or `--option value` pairs.
+fn filter_overridden_args(
+	config_args: Vec<String>,
+	is_overridden_option: &dyn Fn(&str) -> bool,
+) -> Vec<String> {
+	let mut filtered_args = Vec::with_capacity(config_args.len());
+	let mut is_overridden = false;
+	for arg in config_args {
+		if arg.starts_with("--") {
+			is_overridden = is_overridden_option(&arg);
+		}
+		if !is_overridden {
+			filtered_args.push(arg);
+		}
+	}
+	filtered_args
+}
+
+/// Convert configuration of given bridge into the list of command line arguments.
+fn bridge_config_args(
+	config: &str,
+	bridge: &str,
+	read_env: &dyn Fn(&str) -> Option<String>,
+) -> anyhow::Result<Vec<String>> {
+	let config: toml::Value = config
+		.parse()
+		.map_err(|e| anyhow::format_err!("Failed to parse configuration file: {}", e))?;
+	let bridges = config
+		.get("bridges")
+		.and_then(|bridges| bridges.as_table())
+		.ok_or_else(|| anyhow::format_err!("Configuration file has no [bridges] section"))?;
+	if let Some(unknown_key) =
+		config.as_table().into_iter().flatten().find(|(k, _)| *k != "bridges")
+	{
+		return Err(anyhow::format_err!("Unknown configuration key: {}", unknown_key.0))
+	}
+	let bridge_config = bridges
+		.get(bridge)
+		.and_then(|bridge_config| bridge_config.as_table())
+		.ok_or_else(|| {
+			anyhow::format_err!("Bridge {} is missing from the configuration file", bridge)
+		})?;
+
+	let chains: Vec<&str> = bridge.split('-').collect();
+	let mut args = Vec::new();
+	for (key, value) in bridge_config {
+		if chains.contains(&key.as_str()) {
+			let chain_config = value.as_table().ok_or_else(|| {
+				anyhow::format_err!("Configuration of {} in bridge {} must be a table", key, bridge)
+			})?;
+			for (chain_key, chain_value) in chain_config {
+				let context = format!("bridges.{}.{}.{}", bridge, key, chain_key);
+				if !CHAIN_KEYS.contains(&chain_key.as_str()) {
+					return Err(anyhow::format_err!("Unknown configuration key: {}", context))
+				}
+				let option = format!("--{}-{}", key, chain_key);
+				if SECRET_KEYS.contains(&chain_key.as_str()) {
+					args.extend(secret_args(&context, &option, chain_value, read_env)?);
+				} else {
+					args.extend(value_args(&context, &option, chain_value)?);
+				}
+			}
+		} else if BRIDGE_KEYS.contains(&key.as_str()) {
+			let context = format!("bridges.{}.{}", bridge, key);
+			args.extend(value_args(&context, &format!("--{}", key), value)?);
+		} else {
+			return Err(anyhow::format_err!("Unknown configuration key: bridges.{}.{}", bridge, key))
+		}
+	}
+
+	Ok(args)
+}
+
+/// Convert plain configuration value into command line arguments.
+fn value_args(context: &str, option: &str, value: &toml::Value) -> anyhow::Result<Vec<String>> {
+	Ok(match value {
+		toml::Value::Boolean(true) => vec![option.into()],
+		toml::Value::Boolean(false) => vec![],
+		toml::Value::Integer(value) => vec![option.into(), value.to_string()],
+		toml::Value::String(value) => vec![option.into(), value.clone()],
+		toml::Value::Array(values) => values
+			.iter()
+			.map(|value| match value {
+				toml::Value::Integer(_) | toml::Value::String(_) =>
+					value_args(context, option, value),
+				_ => Err(anyhow::format_err!("Unsupported value of {}: {}", context, value)),
+			})
+			.collect::<anyhow::Result<Vec<_>>>()?
+			.into_iter()
+			.flatten()
+			.collect(),
+		_ => return Err(anyhow::format_err!("Unsupported value of {}: {}", context, value)),
+	})
+}
+
+/// Convert secret configuration value into command line arguments.
+///
+/// Secrets may be specified explicitly (`signer = "//Alice"`), be read from the file
+/// (`signer = { file = "/path" }`) or be read from the environment variable
+/// (`signer = { env = "NAME" }`).
+fn secret_args(
+	context: &str,
+	option: &str,
+	value: &toml::Value,
+	read_env: &dyn Fn(&str) -> Option<String>,
+) -> anyhow::Result<Vec<String>> {
+	let reference = match value {
+		toml::Value::String(_) => return value_args(context, option, value),
+		toml::Value::Table(reference) if reference.len() == 1 => reference,
+		_ => return Err(anyhow::format_err!("Unsupported value of {}: {}", context, value)),
+	};
+
+	match reference.iter().next() {
+		Some((kind, toml::Value::String(name))) if kind == "env" => {
+			let secret = read_env(name).ok_or_else(|| {
+				anyhow::format_err!(
+					"Environment variable {} (used by {}) is not set",
+					name,
+					context
+				)
+			})?;
+			Ok(vec![option.into(), secret])
+		},
+		Some((kind, toml::Value::String(path))) if kind == "file" => {
+			let secret = std::fs::read_to_string(Path::new(path)).map_err(|e| {
+				anyhow::format_err!("Failed to read {} (used by {}): {}", path, context, e)
+			})?;
+			Ok(vec![option.into(), secret.trim_end().into()])
+		},
+		_ => Err(anyhow::format_err!(
+			"Unsupported value of {}: {}. Expected string, {{ env = \"NAME\" }} or {{ file = \"PATH\" }}",
+			context,
+			value,
+		)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CONFIG: &str = r#"
+		[bridges.millau-rialto]
+		lane = ["00000000", "73776170"]
+		relayer-mode = "altruistic"
+		only-mandatory-headers = true
+		create-relayers-fund-accounts = false
+
+		[bridges.millau-rialto.millau]
+		port = 9945
+		signer = "//Alice"
+		transactions-mortality = 64
+
+		[bridges.millau-rialto.rialto]
+		port = 9944
+		signer = { env = "RIALTO_SIGNER" }
+	"#;
+
+	fn read_test_env(name: &str) -> Option<String> {
+		if name == "RIALTO_SIGNER" {
+			Some("//Bob".into())
+		} else {
+			None
+		}
+	}
+
+	fn args(args: &[&str]) -> Vec<String> {
+		args.iter().map(|arg| arg.to_string()).collect()
+	}
+
+	#[test]
+	fn bridge_config_is_converted_to_args() {
+		assert_eq!(
+			bridge_config_args(CONFIG, "millau-rialto", &read_test_env).unwrap(),
+			args(&[
+				"--lane",
+				"00000000",
+				"--lane",
+				"73776170",
+				"--millau-port",
+				"9945",
+				"--millau-signer",
+				"//Alice",
+				"--millau-transactions-mortality",
+				"64",
+				"--only-mandatory-headers",
+				"--relayer-mode",
+				"altruistic",
+				"--rialto-port",
+				"9944",
+				"--rialto-signer",
+				"//Bob",
+			]),
+		);
+	}
+
+	#[test]
+	fn signer_is_read_from_file() {
+		let mut signer_file = tempfile::NamedTempFile::new().unwrap();
+		std::io::Write::write_all(&mut signer_file, b"//Charlie\n").unwrap();
+		let config = format!(
+			"[bridges.millau-rialto.millau]\nsigner = {{ file = {:?} }}",
+			signer_file.path().to_str().unwrap(),
+		);
+		assert_eq!(
+			bridge_config_args(&config, "millau-rialto", &read_test_env).unwrap(),
+			args(&["--millau-signer", "//Charlie"]),
+		);
+	}
+
+	#[test]
+	fn missing_environment_variable_is_rejected() {
+		let config = "[bridges.millau-rialto.millau]\nsigner = { env = \"MISSING\" }";
+		assert!(bridge_config_args(config, "millau-rialto", &read_test_env).is_err());
+	}
+
+	#[test]
+	fn unknown_keys_are_rejected() {
+		let config = "[bridges.millau-rialto]\nunknown = 1";
+		assert!(bridge_config_args(config, "millau-rialto", &read_test_env).is_err());
+
+		let config = "[bridges.millau-rialto.millau]\nunknown = 1";
+		assert!(bridge_config_args(config, "millau-rialto", &read_test_env).is_err());
+
+		let config = "[bridges.millau-rialto.rococo]\nport = 1";
+		assert!(bridge_config_args(config, "millau-rialto", &read_test_env).is_err());
+
+		let config = "[bridges.millau-rialto]\n[unknown]\nport = 1";
+		assert!(bridge_config_args(config, "millau-rialto", &read_test_env).is_err());
+	}
+
+	#[test]
+	fn missing_bridge_is_rejected() {
+		assert!(bridge_config_args(CONFIG, "rococo-wococo", &read_test_env).is_err());
+	}
+
+	#[test]
+	fn command_line_options_override_config() {
+		let is_overridden = |option: &str| option == "--lane" || option == "--millau-port";
+		assert_eq!(
+			filter_overridden_args(
+				bridge_config_args(CONFIG, "millau-rialto", &read_test_env).unwrap(),
+				&is_overridden,
+			),
+			args(&[
+				"--millau-signer",
+				"//Alice",
+				"--millau-transactions-mortality",
+				"64",
+				"--only-mandatory-headers",
+				"--relayer-mode",
+				"altruistic",
+				"--rialto-port",
+				"9944",
+				"--rialto-signer",
+				"//Bob",
+			]),
+		);
+	}
+
+	#[test]
+	fn expanded_args_are_accepted_by_command() {
+		let mut config_file = tempfile::NamedTempFile::new().unwrap();
+		std::io::Write::write_all(
+			&mut config_file,
+			b"[bridges.millau-rialto]\nlane = [\"00000000\"]\n\n\
+			[bridges.millau-rialto.millau]\nport = 9945\nsigner = \"//Alice\"\n\n\
+			[bridges.millau-rialto.rialto]\nport = 9944\nsigner = \"//Bob\"\n",
+		)
+		.unwrap();
+
+		let expanded_args = expand_config_args(
+			vec![
+				"substrate-relay",
+				"relay-headers-and-messages",
+				"millau-rialto",
+				"--config",
+				config_file.path().to_str().unwrap(),
+				"--millau-port=10000",
+			]
+			.into_iter()
+			.map(Into::into)
+			.collect(),
+		)
+		.unwrap();
+		assert!(crate::cli::Command::from_iter_safe(expanded_args.clone()).is_ok());
+		assert_eq!(
+			expanded_args
+				.iter()
+				.filter(|arg| arg.to_str().unwrap().contains("--millau-port"))
+				.count(),
+			1,
+		);
+	}
+
+	#[test]
+	fn config_option_inside_values_is_ignored() {
+		let args: Vec<OsString> = vec![
+			"substrate-relay",
+			"relay-headers-and-messages",
+			"millau-rialto",
+			"--millau-signer=--config",
+			"--rialto-signer=--config=relay.toml",
+		]
+		.into_iter()
+		.map(Into::into)
+		.collect();
+		assert_eq!(expand_config_args(args.clone()).unwrap(), args);
+	}
+
+	#[test]
+	fn config_is_rejected_by_other_commands() {
+		let args: Vec<OsString> =
+			vec!["substrate-relay", "relay-messages", "millau-to-rialto", "--config", "relay.toml"]
+				.into_iter()
+				.map(Into::into)
+				.collect();
+		assert_eq!(expand_config_args(args.clone()).unwrap(), args);
+		assert!(crate::cli::Command::from_iter_safe(args).is_err());
+	}
+}
@@ -19,7 +19,9 @@ use bp_header_chain::InitializationData;
 use bp_runtime::Chain as ChainBase;
 use codec::Encode;
 use relay_substrate_client::{Chain, SignParam, TransactionSignScheme, UnsignedTransaction};
-use sp_core::{Bytes, Pair};
+use sp_core::Bytes;
+use sp_runtime::{traits::Header as HeaderT, DeserializeOwned};
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 use strum::{EnumString, EnumVariantNames, VariantNames};
 
@@ -29,6 +31,11 @@ pub struct InitBridge {
 	/// A bridge instance to initialize.
 	#[structopt(possible_values = InitBridgeName::VARIANTS, case_insensitive = true)]
 	bridge: InitBridgeName,
+	/// Path to the checkpoint file, exported by the `export-checkpoint` command. If specified,
+	/// the bridge is initialized with the checkpoint data instead of the current state of the
+	/// source node.
+	#[structopt(long)]
+	from_checkpoint: Option<PathBuf>,
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	#[structopt(flatten)]
@@ -50,6 +57,7 @@ pub enum InitBridgeName {
 	PolkadotToKusama,
 }
 
+/// Select source and target chains of the bridge and the bridge initialization call.
 macro_rules! select_bridge {
 	($bridge: expr, $generic: tt) => {
 		match $bridge {
@@ -207,13 +215,153 @@ macro_rules! select_bridge {
 	};
 }
 
+/// Select source and target chains of the bridge.
+macro_rules! select_bridge_chains {
+	($bridge: expr, $generic: tt) => {
+		match $bridge {
+			InitBridgeName::MillauToRialto => {
+				type Source = relay_millau_client::Millau;
+				type Target = relay_rialto_client::Rialto;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(millau_runtime::VERSION);
+				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(rialto_runtime::VERSION);
+
+				$generic
+			},
+			InitBridgeName::RialtoToMillau => {
+				type Source = relay_rialto_client::Rialto;
+				type Target = relay_millau_client::Millau;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(rialto_runtime::VERSION);
+				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(millau_runtime::VERSION);
+
+				$generic
+			},
+			InitBridgeName::WestendToMillau => {
+				type Source = relay_westend_client::Westend;
+				type Target = relay_millau_client::Millau;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_westend::VERSION);
+				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(millau_runtime::VERSION);
+
+				$generic
+			},
+			InitBridgeName::RococoToWococo => {
+				type Source = relay_rococo_client::Rococo;
+				type Target = relay_wococo_client::Wococo;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_rococo::VERSION);
+				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_wococo::VERSION);
+
+				$generic
+			},
+			InitBridgeName::WococoToRococo => {
+				type Source = relay_wococo_client::Wococo;
+				type Target = relay_rococo_client::Rococo;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_wococo::VERSION);
+				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_rococo::VERSION);
+
+				$generic
+			},
+			InitBridgeName::KusamaToPolkadot => {
+				type Source = relay_kusama_client::Kusama;
+				type Target = relay_polkadot_client::Polkadot;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_kusama::VERSION);
+				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_polkadot::VERSION);
+
+				$generic
+			},
+			InitBridgeName::PolkadotToKusama => {
+				type Source = relay_polkadot_client::Polkadot;
+				type Target = relay_kusama_client::Kusama;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_polkadot::VERSION);
+				const TARGET_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_kusama::VERSION);
+
+				$generic
+			},
+		}
+	};
+}
+
+/// Select source chain of the bridge.
+macro_rules! select_source {
+	($bridge: expr, $generic: tt) => {
+		match $bridge {
+			InitBridgeName::MillauToRialto => {
+				type Source = relay_millau_client::Millau;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(millau_runtime::VERSION);
+
+				$generic
+			},
+			InitBridgeName::RialtoToMillau => {
+				type Source = relay_rialto_client::Rialto;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(rialto_runtime::VERSION);
+
+				$generic
+			},
+			InitBridgeName::WestendToMillau => {
+				type Source = relay_westend_client::Westend;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_westend::VERSION);
+
+				$generic
+			},
+			InitBridgeName::RococoToWococo => {
+				type Source = relay_rococo_client::Rococo;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_rococo::VERSION);
+
+				$generic
+			},
+			InitBridgeName::WococoToRococo => {
+				type Source = relay_wococo_client::Wococo;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_wococo::VERSION);
+
+				$generic
+			},
+			InitBridgeName::KusamaToPolkadot => {
+				type Source = relay_kusama_client::Kusama;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_kusama::VERSION);
+
+				$generic
+			},
+			InitBridgeName::PolkadotToKusama => {
+				type Source = relay_polkadot_client::Polkadot;
+				const SOURCE_RUNTIME_VERSION: Option<sp_version::RuntimeVersion> =
+					Some(bp_polkadot::VERSION);
+
+				$generic
+			},
+		}
+	};
+}
+
 impl InitBridge {
 	/// Run the command.
 	pub async fn run(self) -> anyhow::Result<()> {
 		select_bridge!(self.bridge, {
 			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
 			let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
-			let target_sign = self.target_sign.to_keypair::<Target>()?;
+			let target_sign = self.target_sign.to_signer::<Target>()?;
+			let checkpoint = self
+				.from_checkpoint
+				.as_ref()
+				.map(|path| read_checkpoint::<<Source as ChainBase>::Header>(path))
+				.transpose()?;
 
 			let (spec_version, transaction_version) =
 				target_client.simple_runtime_version().await?;
@@ -221,8 +369,9 @@ impl InitBridge {
 				source_client,
 				target_client.clone(),
 				target_sign.public().into(),
+				checkpoint,
 				move |transaction_nonce, initialization_data| {
-					Bytes(
+					Ok(Bytes(
 						Target::sign_transaction(SignParam {
 							spec_version,
 							transaction_version,
@@ -233,9 +382,9 @@ impl InitBridge {
 								encode_init_bridge(initialization_data),
 								transaction_nonce,
 							),
-						})
+						})?
 						.encode(),
-					)
+					))
 				},
 			)
 			.await;
@@ -244,3 +393,134 @@ impl InitBridge {
 		})
 	}
 }
+
+/// Export bridge initialization data (checkpoint) to the file.
+///
+/// The checkpoint is prepared from the current state of the source node. It may then be audited
+/// and used to initialize bridge with the `init-bridge --from-checkpoint` command.
+#[derive(StructOpt)]
+pub struct ExportCheckpoint {
+	/// A bridge instance to export checkpoint for.
+	#[structopt(possible_values = InitBridgeName::VARIANTS, case_insensitive = true)]
+	bridge: InitBridgeName,
+	/// Path to the file where checkpoint will be written.
+	#[structopt(long)]
+	output: PathBuf,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+}
+
+impl ExportCheckpoint {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_source!(self.bridge, {
+			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+			let checkpoint =
+				substrate_relay_helper::headers_initialize::prepare_initialization_data(
+					source_client,
+				)
+				.await?;
+
+			log::info!(
+				target: "bridge",
+				"Writing {} checkpoint at header {}/{:?} to {:?}",
+				Source::NAME,
+				checkpoint.header.number(),
+				checkpoint.header.hash(),
+				self.output,
+			);
+			write_checkpoint(&self.output, &checkpoint)
+		})
+	}
+}
+
+/// Verify that the bridge pallet has been initialized with header and authorities set that are
+/// known to the source node.
+#[derive(StructOpt)]
+pub struct VerifyInit {
+	/// A bridge instance to verify.
+	#[structopt(possible_values = InitBridgeName::VARIANTS, case_insensitive = true)]
+	bridge: InitBridgeName,
+	/// Path to the checkpoint file. If specified, the command also checks that the bridge has been
+	/// initialized with the checkpoint header.
+	#[structopt(long)]
+	checkpoint: Option<PathBuf>,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+}
+
+impl VerifyInit {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_bridge_chains!(self.bridge, {
+			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+			let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
+			let checkpoint = self
+				.checkpoint
+				.as_ref()
+				.map(|path| read_checkpoint::<<Source as ChainBase>::Header>(path))
+				.transpose()?;
+
+			substrate_relay_helper::headers_initialize::verify_initialization(
+				&source_client,
+				&target_client,
+				checkpoint.as_ref(),
+			)
+			.await?;
+
+			println!("{}-headers bridge at {} is initialized properly", Source::NAME, Target::NAME);
+			Ok(())
+		})
+	}
+}
+
+/// Read bridge initialization data from the checkpoint file.
+fn read_checkpoint<Header: HeaderT + DeserializeOwned>(
+	path: &Path,
+) -> anyhow::Result<InitializationData<Header>> {
+	let checkpoint = std::fs::read_to_string(path)
+		.map_err(|e| anyhow::format_err!("Failed to read checkpoint from {:?}: {}", path, e))?;
+	serde_json::from_str(&checkpoint)
+		.map_err(|e| anyhow::format_err!("Failed to parse checkpoint from {:?}: {}", path, e))
+}
+
+/// Write bridge initialization data to the checkpoint file.
+fn write_checkpoint<Header: HeaderT>(
+	path: &Path,
+	checkpoint: &InitializationData<Header>,
+) -> anyhow::Result<()> {
+	let checkpoint = serde_json::to_string_pretty(checkpoint)?;
+	std::fs::write(path, checkpoint)
+		.map_err(|e| anyhow::format_err!("Failed to write checkpoint to {:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_keyring::Ed25519Keyring;
+
+	#[test]
+	fn checkpoint_is_written_and_read() {
+		let checkpoint = InitializationData {
+			header: Box::new(bp_millau::Header::new(
+				42,
+				Default::default(),
+				Default::default(),
+				[1u8; 32].into(),
+				Default::default(),
+			)),
+			authority_list: vec![(Ed25519Keyring::Alice.public().into(), 1)],
+			set_id: 5,
+			is_halted: false,
+		};
+
+		let temp_dir = tempfile::tempdir().unwrap();
+		let path = temp_dir.path().join("checkpoint.json");
+		write_checkpoint(&path, &checkpoint).unwrap();
+		let read_checkpoint = read_checkpoint::<bp_millau::Header>(&path);
+
+		assert_eq!(read_checkpoint.unwrap(), checkpoint);
+	}
+}
@@ -16,10 +16,15 @@
 
 //! Deal with CLI args of substrate-to-substrate relay.
 
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 use codec::{Decode, Encode};
-use relay_substrate_client::ChainRuntimeVersion;
+use messages_relay::relay_coordination::SlotCoordination;
+use relay_substrate_client::{
+	guard::{GuardConditionPolicy, GuardMetrics, GuardPolicy},
+	ChainRuntimeVersion,
+};
+use relay_utils::metrics::{Metric, Registry};
 use sp_runtime::app_crypto::Ss58Codec;
 use structopt::{clap::arg_enum, StructOpt};
 use strum::{EnumString, EnumVariantNames};
@@ -31,9 +36,13 @@ pub(crate) mod encode_call;
 pub(crate) mod encode_message;
 pub(crate) mod estimate_fee;
 pub(crate) mod send_message;
+pub(crate) mod trace_message;
 
+mod check_bridge;
+mod config_file;
 mod derive_account;
 mod init_bridge;
+mod proofs;
 mod register_parachain;
 mod relay_headers;
 mod relay_headers_and_messages;
@@ -43,7 +52,15 @@ mod swap_tokens;
 
 /// Parse relay CLI args.
 pub fn parse_args() -> Command {
-	Command::from_args()
+	let args = match config_file::expand_config_args(std::env::args_os().collect()) {
+		Ok(args) => args,
+		Err(e) => structopt::clap::Error::with_description(
+			&e.to_string(),
+			structopt::clap::ErrorKind::InvalidValue,
+		)
+		.exit(),
+	};
+	Command::from_iter(args)
 }
 
 /// Substrate-to-Substrate bridge utilities.
@@ -98,6 +115,28 @@ pub enum Command {
 	SwapTokens(swap_tokens::SwapTokens),
 	/// Register parachain.
 	RegisterParachain(register_parachain::RegisterParachain),
+	/// Check bridge health.
+	///
+	/// Connects to both chains and reports state of the bridge pallets, lanes and relayers
+	/// fund as a human-readable table or JSON document.
+	CheckBridge(check_bridge::CheckBridge),
+	/// Trace message, sent over the bridge.
+	///
+	/// Reports when the message has been accepted, delivered and dispatched, which relayer has
+	/// delivered it and whether the delivery has been confirmed at the source chain.
+	TraceMessage(trace_message::TraceMessage),
+	/// Generate finality proof of the source chain header and write it to the file.
+	ProveFinality(proofs::ProveFinality),
+	/// Generate proof of messages, sent by the source chain, and write it to the file.
+	ProveMessages(proofs::ProveMessages),
+	/// Generate proof of messages delivery to the target chain and write it to the file.
+	ProveDelivery(proofs::ProveDelivery),
+	/// Sign and submit proof, generated by one of `prove-*` commands.
+	SubmitProof(proofs::SubmitProof),
+	/// Export bridge initialization data (checkpoint) to the file.
+	ExportCheckpoint(init_bridge::ExportCheckpoint),
+	/// Verify that the bridge pallet has been initialized with data, known to the source node.
+	VerifyInit(init_bridge::VerifyInit),
 }
 
 impl Command {
@@ -134,6 +173,14 @@ impl Command {
 			Self::ResubmitTransactions(arg) => arg.run().await?,
 			Self::SwapTokens(arg) => arg.run().await?,
 			Self::RegisterParachain(arg) => arg.run().await?,
+			Self::CheckBridge(arg) => arg.run().await?,
+			Self::TraceMessage(arg) => arg.run().await?,
+			Self::ProveFinality(arg) => arg.run().await?,
+			Self::ProveMessages(arg) => arg.run().await?,
+			Self::ProveDelivery(arg) => arg.run().await?,
+			Self::SubmitProof(arg) => arg.run().await?,
+			Self::ExportCheckpoint(arg) => arg.run().await?,
+			Self::VerifyInit(arg) => arg.run().await?,
 		}
 		Ok(())
 	}
@@ -313,6 +360,59 @@ impl HexBytes {
 	}
 }
 
+/// Select nonce of the message, dispatched on behalf of the target chain account.
+///
+/// If nonce is not explicitly specified, current UNIX time (in milliseconds) is used, so that
+/// nonces of subsequent messages are (most likely) unique.
+pub(crate) fn target_account_nonce(
+	explicit_nonce: Option<bp_message_dispatch::TargetAccountNonce>,
+) -> bp_message_dispatch::TargetAccountNonce {
+	explicit_nonce.unwrap_or_else(|| {
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.map(|duration| duration.as_millis() as _)
+			.unwrap_or_default()
+	})
+}
+
+/// Read SURI of the account key from the Substrate keystore directory.
+///
+/// The keystore file name is the hex-encoded key type, followed by the hex-encoded public key.
+/// The file contains JSON string with the SURI. If public key is not specified, the keystore must
+/// contain exactly one account key.
+pub(crate) fn read_keystore_suri(
+	keystore: &std::path::Path,
+	public: Option<&[u8]>,
+) -> anyhow::Result<String> {
+	let key_type_prefix = hex::encode(sp_core::crypto::key_types::ACCOUNT.0);
+	let read_error = |e| anyhow::format_err!("Failed to read keystore {:?}: {}", keystore, e);
+	let mut key_files = Vec::new();
+	for entry in std::fs::read_dir(keystore).map_err(read_error)? {
+		let entry = entry.map_err(read_error)?;
+		let file_name = entry.file_name().to_string_lossy().into_owned();
+		let key_public = match file_name.strip_prefix(&key_type_prefix).map(hex::decode) {
+			Some(Ok(key_public)) => key_public,
+			_ => continue,
+		};
+		if public.map(|public| public == &key_public[..]).unwrap_or(true) {
+			key_files.push(entry.path());
+		}
+	}
+
+	let key_file = match key_files.len() {
+		1 => key_files.remove(0),
+		0 => return Err(anyhow::format_err!("No matching account key in keystore {:?}", keystore)),
+		_ =>
+			return Err(anyhow::format_err!(
+				"Multiple account keys in keystore {:?}. Select one using public key",
+				keystore,
+			)),
+	};
+	let key_file_contents = std::fs::read_to_string(&key_file).map_err(read_error)?;
+	serde_json::from_str(&key_file_contents)
+		.map_err(|e| anyhow::format_err!("Invalid keystore file {:?}: {}", key_file, e))
+}
+
 /// Prometheus metrics params.
 #[derive(StructOpt)]
 pub struct PrometheusParams {
@@ -341,6 +441,107 @@ impl From<PrometheusParams> for relay_utils::metrics::MetricsParams {
 	}
 }
 
+/// Relay admin server params.
+#[derive(StructOpt)]
+pub struct AdminServerParams {
+	/// Expose admin server (relay status and control commands) at given port. The server is
+	/// not started if port is not specified.
+	#[structopt(long)]
+	pub admin_port: Option<u16>,
+	/// Expose admin server at given interface. Non-loopback interfaces may only be used if
+	/// the access token is specified.
+	#[structopt(long, default_value = "127.0.0.1")]
+	pub admin_host: String,
+	/// Access token that must be provided by admin server clients in the
+	/// `Authorization: Bearer <token>` header.
+	#[structopt(long)]
+	pub admin_token: Option<String>,
+}
+
+impl From<AdminServerParams> for relay_utils::admin::AdminParams {
+	fn from(cli_params: AdminServerParams) -> relay_utils::admin::AdminParams {
+		let admin_host = cli_params.admin_host;
+		relay_utils::admin::AdminParams::from(
+			cli_params
+				.admin_port
+				.map(|port| relay_utils::admin::AdminAddress { host: admin_host, port }),
+		)
+		.with_token(cli_params.admin_token)
+	}
+}
+
+/// Relay guards params.
+#[derive(StructOpt)]
+pub struct GuardParams {
+	/// Actions that are performed when guard condition fails, in the
+	/// `<condition>=<action>[,<action>...]` format. Supported conditions are:
+	/// `spec-version-changed`, `balance-decreased`, `finality-stalled`, `pallet-halted` and
+	/// `unrewarded-relayers-near-limit`. Supported actions are: `log`, `metric`,
+	/// `webhook:<url>`, `pause` and `abort`. The `pause` action pauses message lanes that are
+	/// affected by the failed condition, so it is only supported by relays that are serving
+	/// message lanes. By default, balance guard is aborting the relay and all other guards are
+	/// only logging errors.
+	#[structopt(long)]
+	pub guard: Vec<GuardConditionPolicy>,
+	/// Maximal time (in seconds) the best finalized header of the bridged chain may stay the
+	/// same before the `finality-stalled` guard fails.
+	#[structopt(long, default_value = "3600")]
+	pub guard_finality_stall_timeout: u64,
+}
+
+impl GuardParams {
+	/// Convert CLI params into guard policy. Guard metrics are registered in the given registry
+	/// if any guard condition needs them.
+	pub fn into_policy(self, metrics_registry: &Registry) -> anyhow::Result<GuardPolicy> {
+		let mut policy = GuardPolicy::default()
+			.with_finality_stall_timeout(Duration::from_secs(self.guard_finality_stall_timeout));
+		for condition_policy in self.guard {
+			policy = policy.with_condition_policy(condition_policy);
+		}
+		if policy.uses_metrics() {
+			let metrics = GuardMetrics::new(None)?;
+			metrics.register(metrics_registry)?;
+			policy = policy.with_metrics(metrics);
+		}
+		Ok(policy)
+	}
+}
+
+/// Relayers coordination params.
+#[derive(StructOpt)]
+pub struct CoordinationParams {
+	/// Hex-encoded accounts of all relayers that are serving the lane. If specified, target chain
+	/// blocks are split into slots and every slot is assigned to a single relayer. The relayer
+	/// only delivers messages during its own slots. All relayers must be started with the same
+	/// set of accounts and the same slot length.
+	#[structopt(long)]
+	pub coordination_relayer: Vec<HexBytes>,
+	/// Number of target chain blocks in a single coordination slot.
+	#[structopt(long, default_value = "4")]
+	pub coordination_slot_length: u64,
+}
+
+impl CoordinationParams {
+	/// Convert CLI params into slot coordination of the relayer with given encoded account.
+	///
+	/// Returns `None` if coordination is not configured.
+	pub fn into_coordination(self, relayer_id: &[u8]) -> anyhow::Result<Option<SlotCoordination>> {
+		if self.coordination_relayer.is_empty() {
+			return Ok(None)
+		}
+
+		let relayers = self.coordination_relayer.into_iter().map(|relayer| relayer.0).collect();
+		SlotCoordination::new(relayer_id, relayers, self.coordination_slot_length)
+			.map(Some)
+			.ok_or_else(|| {
+				anyhow::format_err!(
+					"Relayer account {} is not in the coordination set or slot length is zero",
+					hex::encode(relayer_id),
+				)
+			})
+	}
+}
+
 /// Either explicit or maximal allowed value.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExplicitOrMaximal<V> {
@@ -432,11 +633,62 @@ macro_rules! declare_chain_options {
 				#[structopt(long)]
 				pub [<$chain_prefix _signer_password_file>]: Option<std::path::PathBuf>,
 
+				#[doc = "Name of the environment variable, that contains SURI of secret key to use when transactions are submitted to the " $chain " node."]
+				#[structopt(long)]
+				pub [<$chain_prefix _signer_env>]: Option<String>,
+				#[doc = "Path to the Substrate keystore directory, that contains secret key to use when transactions are submitted to the " $chain " node. The key must have `acco` key type. If keystore contains multiple account keys, the " $chain_prefix "_signer_public option must be specified."]
+				#[structopt(long)]
+				pub [<$chain_prefix _signer_keystore>]: Option<std::path::PathBuf>,
+				#[doc = "Hex-encoded public key of the " $chain " transactions signer. Used to select key from the keystore and required by the remote signer."]
+				#[structopt(long)]
+				pub [<$chain_prefix _signer_public>]: Option<String>,
+				#[doc = "URL of the remote service that signs " $chain " transactions. If specified, other signer options (except " $chain_prefix "_signer_public) are ignored."]
+				#[structopt(long)]
+				pub [<$chain_prefix _signer_url>]: Option<String>,
+
 				#[doc = "Transactions mortality period, in blocks. MUST be a power of two in [4; 65536] range. MAY NOT be larger than `BlockHashCount` parameter of the chain system module."]
 				#[structopt(long)]
 				pub [<$chain_prefix _transactions_mortality>]: Option<u32>,
 			}
 
+			#[doc = $chain " token price oracle params."]
+			#[derive(StructOpt, Debug, PartialEq, Eq, Clone)]
+			pub struct [<$chain PriceOracleParams>] {
+				#[doc = "Path to the JSON file, that contains " $chain " token price. The file is re-read periodically, so the price may be changed without restarting the relay."]
+				#[structopt(long)]
+				pub [<$chain_prefix _price_file>]: Option<std::path::PathBuf>,
+				#[doc = "URL of the HTTP service that responds with JSON, containing " $chain " token price."]
+				#[structopt(long)]
+				pub [<$chain_prefix _price_url>]: Option<String>,
+				#[doc = "JSON path of the " $chain " token price in the price file or HTTP service response."]
+				#[structopt(long, default_value = "$")]
+				pub [<$chain_prefix _price_json_path>]: String,
+			}
+
+			impl [<$chain PriceOracleParams>] {
+				/// Create token price oracle. Returns `None` if neither price file, nor price URL is specified.
+				#[allow(dead_code)]
+				pub fn to_price_oracle(&self) -> anyhow::Result<Option<relay_utils::price_oracle::SharedPriceOracle>> {
+					use relay_utils::price_oracle::{FilePriceOracle, HttpJsonPriceOracle};
+
+					let json_path = self.[<$chain_prefix _price_json_path>].clone();
+					match (self.[<$chain_prefix _price_file>].as_ref(), self.[<$chain_prefix _price_url>].as_ref()) {
+						(Some(_), Some(_)) => Err(anyhow::format_err!(
+							"Only one of options may be specified: '{}' or '{}'",
+							stringify!([<$chain_prefix _price_file>]),
+							stringify!([<$chain_prefix _price_url>]),
+						)),
+						(Some(price_file), None) => Ok(Some(std::sync::Arc::new(
+							FilePriceOracle::new(price_file.clone(), json_path),
+						))),
+						(None, Some(price_url)) => Ok(Some(std::sync::Arc::new(
+							HttpJsonPriceOracle::new(price_url.clone(), json_path),
+						))),
+						(None, None) => Ok(None),
+					}
+				}
+			}
+
 			#[doc = "Parameters required to sign transaction on behalf of owner of the messages pallet at " $chain "."]
 			#[derive(StructOpt, Debug, PartialEq, Eq)]
 			pub struct [<$chain MessagesPalletOwnerSigningParams>] {
@@ -468,21 +720,64 @@ macro_rules! declare_chain_options {
 						.transpose()
 				}
 
+				/// Parse signing params into chain-specific transactions signer.
+				///
+				/// If remote signer URL is specified, the signer is using remote service to sign
+				/// transactions. Otherwise the key pair is read using `to_keypair`.
+				#[allow(dead_code)]
+				pub fn to_signer<Chain: CliChain>(
+					&self,
+				) -> anyhow::Result<relay_substrate_client::Signer<Chain::KeyPair>>
+				where
+					<Chain::KeyPair as sp_core::crypto::Pair>::Public: codec::Decode + codec::Encode,
+					<Chain::KeyPair as sp_core::crypto::Pair>::Signature: codec::Decode,
+				{
+					let signer_url = match self.[<$chain_prefix _signer_url>] {
+						Some(ref signer_url) => signer_url.clone(),
+						None => return self.to_keypair::<Chain>().map(Into::into),
+					};
+					let public = self.signer_public()?.ok_or_else(|| anyhow::format_err!(
+						"Option '{}' must be specified when '{}' is used",
+						stringify!([<$chain_prefix _signer_public>]),
+						stringify!([<$chain_prefix _signer_url>]),
+					))?;
+					let public = codec::Decode::decode(&mut &public[..])
+						.map_err(|e| anyhow::format_err!("Invalid signer public key: {:?}", e))?;
+					Ok(relay_substrate_client::Signer::remote(public, signer_url))
+				}
+
 				/// Parse signing params into chain-specific KeyPair.
 				#[allow(dead_code)]
 				pub fn to_keypair<Chain: CliChain>(&self) -> anyhow::Result<Chain::KeyPair> {
-					let suri = match (self.[<$chain_prefix _signer>].as_ref(), self.[<$chain_prefix _signer_file>].as_ref()) {
-						(Some(suri), _) => suri.to_owned(),
-						(None, Some(suri_file)) => std::fs::read_to_string(suri_file)
+					let suri = match (
+						self.[<$chain_prefix _signer>].as_ref(),
+						self.[<$chain_prefix _signer_file>].as_ref(),
+						self.[<$chain_prefix _signer_env>].as_ref(),
+						self.[<$chain_prefix _signer_keystore>].as_ref(),
+					) {
+						(Some(suri), _, _, _) => suri.to_owned(),
+						(None, Some(suri_file), _, _) => std::fs::read_to_string(suri_file)
 							.map_err(|err| anyhow::format_err!(
 								"Failed to read SURI from file {:?}: {}",
 								suri_file,
 								err,
 							))?,
-						(None, None) => return Err(anyhow::format_err!(
-							"One of options must be specified: '{}' or '{}'",
+						(None, None, Some(suri_env), _) => std::env::var(suri_env)
+							.map_err(|err| anyhow::format_err!(
+								"Failed to read SURI from environment variable {}: {}",
+								suri_env,
+								err,
+							))?,
+						(None, None, None, Some(keystore)) => crate::cli::read_keystore_suri(
+							keystore,
+							self.signer_public()?.as_deref(),
+						)?,
+						(None, None, None, None) => return Err(anyhow::format_err!(
+							"One of options must be specified: '{}', '{}', '{}' or '{}'",
 							stringify!([<$chain_prefix _signer>]),
 							stringify!([<$chain_prefix _signer_file>]),
+							stringify!([<$chain_prefix _signer_env>]),
+							stringify!([<$chain_prefix _signer_keystore>]),
 						)),
 					};
 
@@ -508,6 +803,18 @@ macro_rules! declare_chain_options {
 						suri_password.as_deref()
 					).map_err(|e| anyhow::format_err!("{:?}", e))
 				}
+
+				/// Return decoded public key of the signer, if it is specified.
+				fn signer_public(&self) -> anyhow::Result<Option<Vec<u8>>> {
+					self.[<$chain_prefix _signer_public>]
+						.as_ref()
+						.map(|public| hex::decode(public.trim_start_matches("0x")).map_err(|e| anyhow::format_err!(
+							"Invalid signer public key {}: {}",
+							public,
+							e,
+						)))
+						.transpose()
+				}
 			}
 
 			#[allow(dead_code)]
@@ -683,6 +990,10 @@ mod tests {
 
 				target_signer_file: None,
 				target_signer_password_file: None,
+				target_signer_env: None,
+				target_signer_keystore: None,
+				target_signer_public: None,
+				target_signer_url: None,
 
 				target_transactions_mortality: None,
 			}
@@ -700,6 +1011,10 @@ mod tests {
 
 				target_signer_file: Some(suri_file_path.clone()),
 				target_signer_password_file: Some(password_file_path.clone()),
+				target_signer_env: None,
+				target_signer_keystore: None,
+				target_signer_public: None,
+				target_signer_url: None,
 
 				target_transactions_mortality: None,
 			}
@@ -717,6 +1032,10 @@ mod tests {
 
 				target_signer_file: Some(suri_file_path.clone()),
 				target_signer_password_file: Some(password_file_path.clone()),
+				target_signer_env: None,
+				target_signer_keystore: None,
+				target_signer_public: None,
+				target_signer_url: None,
 
 				target_transactions_mortality: None,
 			}
@@ -734,6 +1053,10 @@ mod tests {
 
 				target_signer_file: Some(suri_file_path),
 				target_signer_password_file: Some(password_file_path),
+				target_signer_env: None,
+				target_signer_keystore: None,
+				target_signer_public: None,
+				target_signer_url: None,
 
 				target_transactions_mortality: None,
 			}
@@ -743,4 +1066,92 @@ mod tests {
 			Ok(alice.public()),
 		);
 	}
+
+	fn empty_target_signing_params() -> TargetSigningParams {
+		TargetSigningParams {
+			target_signer: None,
+			target_signer_password: None,
+
+			target_signer_file: None,
+			target_signer_password_file: None,
+			target_signer_env: None,
+			target_signer_keystore: None,
+			target_signer_public: None,
+			target_signer_url: None,
+
+			target_transactions_mortality: None,
+		}
+	}
+
+	#[test]
+	fn reads_suri_from_env() {
+		const SURI_ENV: &str = "BRIDGE_TEST_TARGET_SIGNER_SURI";
+		std::env::set_var(SURI_ENV, "//Alice");
+
+		let alice = sp_core::sr25519::Pair::from_string("//Alice", None).unwrap();
+		let mut params = empty_target_signing_params();
+		params.target_signer_env = Some(SURI_ENV.into());
+		assert_eq!(
+			params
+				.to_keypair::<relay_rialto_client::Rialto>()
+				.map(|p| p.public())
+				.map_err(drop),
+			Ok(alice.public()),
+		);
+	}
+
+	#[test]
+	fn reads_suri_from_keystore() {
+		let alice = sp_core::sr25519::Pair::from_string("//Alice", None).unwrap();
+		let bob = sp_core::sr25519::Pair::from_string("//Bob", None).unwrap();
+
+		let temp_dir = tempfile::tempdir().unwrap();
+		let write_key = |suri: &str, public: sp_core::sr25519::Public| {
+			let file_name = format!("{}{}", hex::encode(b"acco"), hex::encode(public.encode()));
+			std::fs::write(temp_dir.path().join(file_name), format!("\"{}\"", suri)).unwrap();
+		};
+		write_key("//Alice", alice.public());
+
+		// when keystore has single account key, it is selected
+		let mut params = empty_target_signing_params();
+		params.target_signer_keystore = Some(temp_dir.path().to_path_buf());
+		assert_eq!(
+			params
+				.to_keypair::<relay_rialto_client::Rialto>()
+				.map(|p| p.public())
+				.map_err(drop),
+			Ok(alice.public()),
+		);
+
+		// when keystore has multiple account keys, public key is required
+		write_key("//Bob", bob.public());
+		assert!(params.to_keypair::<relay_rialto_client::Rialto>().is_err());
+
+		params.target_signer_public = Some(hex::encode(bob.public().encode()));
+		assert_eq!(
+			params
+				.to_keypair::<relay_rialto_client::Rialto>()
+				.map(|p| p.public())
+				.map_err(drop),
+			Ok(bob.public()),
+		);
+	}
+
+	#[test]
+	fn remote_signer_requires_public_key() {
+		let alice = sp_core::sr25519::Pair::from_string("//Alice", None).unwrap();
+
+		let mut params = empty_target_signing_params();
+		params.target_signer_url = Some("http://127.0.0.1:9999".into());
+		assert!(params.to_signer::<relay_rialto_client::Rialto>().is_err());
+
+		params.target_signer_public = Some(hex::encode(alice.public().encode()));
+		assert_eq!(
+			params
+				.to_signer::<relay_rialto_client::Rialto>()
+				.map(|s| s.public())
+				.map_err(drop),
+			Ok(alice.public()),
+		);
+	}
 }
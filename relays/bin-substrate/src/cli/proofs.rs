@@ -0,0 +1,497 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Manual bridge operation: generating proofs and submitting them separately.
+//!
+//! Proofs are written to files as hex-encoded SCALE-encoded `BridgeProof` structure. The same
+//! file may later be passed to the `submit-proof` command, which will sign and submit the
+//! proof to the right chain.
+
+use crate::{
+	cli::{
+		bridge::FullBridge, HexLaneId, SourceConnectionParams, SourceSigningParams,
+		TargetConnectionParams, TargetSigningParams,
+	},
+	select_full_bridge,
+};
+
+use bp_header_chain::justification::GrandpaJustification;
+use bp_messages::{MessageNonce, UnrewardedRelayersState};
+use bridge_runtime_common::messages::{
+	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+};
+use codec::{Decode, Encode};
+use finality_relay::{SourceClient as FinalitySourceClient, TargetClient as FinalityTargetClient};
+use frame_support::weights::Weight;
+use futures::StreamExt;
+use messages_relay::message_lane_loop::{
+	MessageProofParameters, SourceClient as MessagesSourceClient,
+	TargetClient as MessagesTargetClient,
+};
+use relay_substrate_client::{AccountIdOf, BlockNumberOf, Chain, Client, HashOf, HeaderOf};
+use relay_utils::HeaderId;
+use sp_runtime::traits::{Header as HeaderT, UniqueSaturatedFrom};
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+use strum::VariantNames;
+use substrate_relay_helper::{
+	finality_source::SubstrateFinalitySource, finality_target::SubstrateFinalityTarget,
+	messages_source::SubstrateMessagesSource, messages_target::SubstrateMessagesTarget,
+	TransactionParams,
+};
+
+/// Proof, generated by one of `prove-*` commands.
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum BridgeProof<SourceHeader: HeaderT, SourceHash, TargetHash> {
+	/// Source chain header and its GRANDPA justification.
+	Finality(SourceHeader, GrandpaJustification<SourceHeader>),
+	/// Cumulative dispatch weight and proof of messages, sent by the source chain.
+	Messages(Weight, FromBridgedChainMessagesProof<SourceHash>),
+	/// State of unrewarded relayers and proof of messages delivery to the target chain.
+	Delivery(UnrewardedRelayersState, FromBridgedChainMessagesDeliveryProof<TargetHash>),
+}
+
+/// Proof type of the bridge between given chains.
+type BridgeProofOf<SC, TC> = BridgeProof<HeaderOf<SC>, HashOf<SC>, HashOf<TC>>;
+
+/// Generate finality proof of the source chain header.
+#[derive(StructOpt)]
+pub struct ProveFinality {
+	/// A bridge instance to generate proof for.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Number of the source chain block that needs to be proved. The block must have persistent
+	/// GRANDPA justification. If not specified, the command waits for the next justification
+	/// that is generated by the source chain.
+	#[structopt(long)]
+	block: Option<u64>,
+	/// Path to the file where proof will be written.
+	#[structopt(long)]
+	output: PathBuf,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+}
+
+impl ProveFinality {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_full_bridge!(self.bridge, {
+			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+			let finality_source =
+				SubstrateFinalitySource::<Finality>::new(source_client.clone(), None);
+
+			let (header, justification) = match self.block {
+				Some(block) => {
+					let (header, justification) = finality_source
+						.header_and_finality_proof(BlockNumberOf::<Source>::unique_saturated_from(
+							block,
+						))
+						.await?;
+					let justification = justification.ok_or_else(|| {
+						anyhow::format_err!(
+							"{} block #{} has no GRANDPA justification",
+							Source::NAME,
+							block
+						)
+					})?;
+					(header.into_inner(), justification)
+				},
+				None => {
+					log::info!(target: "bridge", "Waiting for the next {} justification", Source::NAME);
+					let justification =
+						finality_source.finality_proofs().await?.next().await.ok_or_else(|| {
+							anyhow::format_err!("{} justifications stream has ended", Source::NAME)
+						})?;
+					let header =
+						source_client.header_by_hash(justification.commit.target_hash).await?;
+					(header, justification)
+				},
+			};
+
+			log::info!(
+				target: "bridge",
+				"Writing finality proof of {} header {:?} to {:?}",
+				Source::NAME,
+				HeaderId(*header.number(), header.hash()),
+				self.output,
+			);
+			write_proof_file(
+				&self.output,
+				&BridgeProofOf::<Source, Target>::Finality(header, justification),
+			)
+		})
+	}
+}
+
+/// Generate proof of messages, sent by the source chain.
+#[derive(StructOpt)]
+pub struct ProveMessages {
+	/// A bridge instance to generate proof for.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded id of lane with messages.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+	/// Nonce of the first message to prove.
+	#[structopt(long)]
+	nonces_start: MessageNonce,
+	/// Nonce of the last message to prove.
+	#[structopt(long)]
+	nonces_end: MessageNonce,
+	/// Number of the source chain block to generate proof at. The header of this block must be
+	/// known to the target chain before proof is submitted. Defaults to the best finalized block.
+	#[structopt(long)]
+	at_block: Option<u64>,
+	/// Include proof of the outbound lane state into the messages proof.
+	#[structopt(long)]
+	with_outbound_lane_state: bool,
+	/// Path to the file where proof will be written.
+	#[structopt(long)]
+	output: PathBuf,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+}
+
+impl ProveMessages {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		if self.nonces_start > self.nonces_end {
+			return Err(anyhow::format_err!(
+				"Invalid nonces range: {}..={}",
+				self.nonces_start,
+				self.nonces_end,
+			))
+		}
+
+		select_full_bridge!(self.bridge, {
+			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+			let lane = self.lane.into();
+			let nonces = self.nonces_start..=self.nonces_end;
+			let at_block = block_hash_or_best_finalized(&source_client, self.at_block).await?;
+
+			let messages = substrate_relay_helper::messages_source::generated_message_details::<
+				MessagesLane,
+			>(&source_client, lane, at_block, nonces.clone())
+			.await?;
+			let messages_count = self.nonces_end - self.nonces_start + 1;
+			if messages.len() as MessageNonce != messages_count {
+				return Err(anyhow::format_err!(
+					"Only {} of {} messages are stored at {} block {:?}. Already delivered?",
+					messages.len(),
+					messages_count,
+					Source::NAME,
+					at_block,
+				))
+			}
+			let dispatch_weight = messages
+				.values()
+				.fold(0, |total: Weight, details| total.saturating_add(details.dispatch_weight));
+
+			let proof = substrate_relay_helper::messages_source::prove_messages::<MessagesLane>(
+				&source_client,
+				lane,
+				at_block,
+				nonces.clone(),
+				MessageProofParameters {
+					outbound_state_proof_required: self.with_outbound_lane_state,
+					dispatch_weight,
+				},
+			)
+			.await?;
+
+			log::info!(
+				target: "bridge",
+				"Writing proof of {} messages {:?} (dispatch weight: {}) at block {:?} to {:?}",
+				Source::NAME,
+				nonces,
+				dispatch_weight,
+				at_block,
+				self.output,
+			);
+			write_proof_file(
+				&self.output,
+				&BridgeProofOf::<Source, Target>::Messages(proof.0, proof.1),
+			)
+		})
+	}
+}
+
+/// Generate proof of messages delivery to the target chain.
+#[derive(StructOpt)]
+pub struct ProveDelivery {
+	/// A bridge instance to generate proof for.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded id of lane with messages.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+	/// Number of the target chain block to generate proof at. The header of this block must be
+	/// known to the source chain before proof is submitted. Defaults to the best finalized block.
+	#[structopt(long)]
+	at_block: Option<u64>,
+	/// Path to the file where proof will be written.
+	#[structopt(long)]
+	output: PathBuf,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+}
+
+impl ProveDelivery {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_full_bridge!(self.bridge, {
+			let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
+			let at_block = block_hash_or_best_finalized(&target_client, self.at_block).await?;
+
+			let proof = substrate_relay_helper::messages_target::prove_messages_receiving::<
+				MessagesLane,
+			>(&target_client, self.lane.into(), at_block)
+			.await?;
+
+			log::info!(
+				target: "bridge",
+				"Writing proof of messages delivery to {} ({:?}) at block {:?} to {:?}",
+				Target::NAME,
+				proof.0,
+				at_block,
+				self.output,
+			);
+			write_proof_file(
+				&self.output,
+				&BridgeProofOf::<Source, Target>::Delivery(proof.0, proof.1),
+			)
+		})
+	}
+}
+
+/// Sign and submit proof, generated by one of `prove-*` commands.
+///
+/// Finality and messages proofs are submitted to the target chain. Delivery proofs are submitted
+/// to the source chain.
+#[derive(StructOpt)]
+pub struct SubmitProof {
+	/// A bridge instance the proof has been generated for.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Path to the file with proof.
+	#[structopt(long)]
+	input: PathBuf,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	source_sign: SourceSigningParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+}
+
+impl SubmitProof {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_full_bridge!(self.bridge, {
+			let proof: BridgeProofOf<Source, Target> = read_proof_file(&self.input)?;
+			match proof {
+				BridgeProof::Finality(header, justification) => {
+					let target_client =
+						self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
+					let finality_target = SubstrateFinalityTarget::<Finality>::new(
+						target_client,
+						TransactionParams {
+							signer: self.target_sign.to_signer::<Target>()?,
+							mortality: self.target_sign.transactions_mortality()?,
+						},
+					);
+
+					log::info!(
+						target: "bridge",
+						"Submitting finality proof of {} header {:?} to {}",
+						Source::NAME,
+						HeaderId(*header.number(), header.hash()),
+						Target::NAME,
+					);
+					finality_target.submit_finality_proof(header.into(), justification).await?;
+				},
+				BridgeProof::Messages(dispatch_weight, proof) => {
+					let source_client =
+						self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+					let target_client =
+						self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
+					let relayer_id_at_source: AccountIdOf<Source> =
+						self.source_sign.to_signer::<Source>()?.public().into();
+					let standalone_metrics =
+						substrate_relay_helper::messages_metrics::standalone_metrics::<MessagesLane>(
+							source_client,
+							target_client.clone(),
+							None,
+							None,
+						)?;
+					let messages_target = SubstrateMessagesTarget::<MessagesLane>::new(
+						target_client,
+						proof.lane,
+						relayer_id_at_source,
+						TransactionParams {
+							signer: self.target_sign.to_signer::<Target>()?,
+							mortality: self.target_sign.transactions_mortality()?,
+						},
+						standalone_metrics,
+						None,
+					);
+
+					let nonces = proof.nonces_start..=proof.nonces_end;
+					log::info!(
+						target: "bridge",
+						"Submitting proof of {} messages {:?} to {}",
+						Source::NAME,
+						nonces,
+						Target::NAME,
+					);
+					messages_target
+						.submit_messages_proof(
+							HeaderId(Default::default(), proof.bridged_header_hash),
+							nonces,
+							(dispatch_weight, proof),
+						)
+						.await?;
+				},
+				BridgeProof::Delivery(relayers_state, proof) => {
+					let source_client =
+						self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+					let messages_source = SubstrateMessagesSource::<MessagesLane>::new(
+						source_client,
+						proof.lane,
+						TransactionParams {
+							signer: self.source_sign.to_signer::<Source>()?,
+							mortality: self.source_sign.transactions_mortality()?,
+						},
+						None,
+					);
+
+					log::info!(
+						target: "bridge",
+						"Submitting proof of messages delivery to {} ({:?}) to {}",
+						Target::NAME,
+						relayers_state,
+						Source::NAME,
+					);
+					messages_source
+						.submit_messages_receiving_proof(
+							HeaderId(Default::default(), proof.bridged_header_hash),
+							(relayers_state, proof),
+						)
+						.await?;
+				},
+			}
+
+			Ok(())
+		})
+	}
+}
+
+/// Return hash of the block with given number or hash of the best finalized block.
+async fn block_hash_or_best_finalized<C: Chain>(
+	client: &Client<C>,
+	block: Option<u64>,
+) -> anyhow::Result<HashOf<C>> {
+	Ok(match block {
+		Some(block) =>
+			client
+				.block_hash_by_number(BlockNumberOf::<C>::unique_saturated_from(block))
+				.await?,
+		None => client.best_finalized_header_hash().await?,
+	})
+}
+
+/// Encode proof as hex string.
+fn encode_proof(proof: &impl Encode) -> String {
+	format!("0x{}", hex::encode(proof.encode()))
+}
+
+/// Decode proof from hex string.
+fn decode_proof<T: Decode>(encoded_proof: &str) -> anyhow::Result<T> {
+	let encoded_proof = hex::decode(encoded_proof.trim().trim_start_matches("0x"))?;
+	T::decode(&mut &encoded_proof[..])
+		.map_err(|e| anyhow::format_err!("Failed to decode proof: {:?}", e))
+}
+
+/// Write proof to the file.
+fn write_proof_file(path: &Path, proof: &impl Encode) -> anyhow::Result<()> {
+	std::fs::write(path, encode_proof(proof))
+		.map_err(|e| anyhow::format_err!("Failed to write proof to {:?}: {}", path, e))
+}
+
+/// Read proof from the file.
+fn read_proof_file<T: Decode>(path: &Path) -> anyhow::Result<T> {
+	let encoded_proof = std::fs::read_to_string(path)
+		.map_err(|e| anyhow::format_err!("Failed to read proof from {:?}: {}", path, e))?;
+	decode_proof(&encoded_proof)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use relay_millau_client::Millau;
+	use relay_rialto_client::Rialto;
+
+	type MillauToRialtoProof = BridgeProofOf<Millau, Rialto>;
+
+	#[test]
+	fn messages_proof_is_encoded_and_decoded() {
+		let proof = MillauToRialtoProof::Messages(
+			1_000,
+			FromBridgedChainMessagesProof {
+				bridged_header_hash: [1u8; 32].into(),
+				storage_proof: vec![vec![42; 64]],
+				lane: [0, 0, 0, 1],
+				nonces_start: 1,
+				nonces_end: 10,
+			},
+		);
+
+		let encoded_proof = encode_proof(&proof);
+		assert!(encoded_proof.starts_with("0x"));
+		assert_eq!(decode_proof::<MillauToRialtoProof>(&encoded_proof).unwrap(), proof);
+		assert_eq!(
+			decode_proof::<MillauToRialtoProof>(&format!("{}\n", encoded_proof)).unwrap(),
+			proof
+		);
+	}
+
+	#[test]
+	fn delivery_proof_is_encoded_and_decoded() {
+		let proof = MillauToRialtoProof::Delivery(
+			UnrewardedRelayersState {
+				unrewarded_relayer_entries: 1,
+				messages_in_oldest_entry: 1,
+				total_messages: 1,
+				last_delivered_nonce: 1,
+			},
+			FromBridgedChainMessagesDeliveryProof {
+				bridged_header_hash: [2u8; 32].into(),
+				storage_proof: vec![vec![42; 64]],
+				lane: [0, 0, 0, 1],
+			},
+		);
+
+		assert_eq!(decode_proof::<MillauToRialtoProof>(&encode_proof(&proof)).unwrap(), proof);
+	}
+
+	#[test]
+	fn invalid_proof_is_rejected() {
+		assert!(decode_proof::<MillauToRialtoProof>("0xzz").is_err());
+		assert!(decode_proof::<MillauToRialtoProof>("0x2a").is_err());
+	}
+}
@@ -35,7 +35,7 @@ use relay_substrate_client::{
 use rialto_runtime::SudoCall;
 use sp_core::{
 	storage::{well_known_keys::CODE, StorageKey},
-	Bytes, Pair,
+	Bytes,
 };
 use structopt::StructOpt;
 use strum::{EnumString, EnumVariantNames, VariantNames};
@@ -103,7 +103,7 @@ impl RegisterParachain {
 				.relay_connection
 				.to_client::<Relaychain>(RELAY_CHAIN_RUNTIME_VERSION)
 				.await?;
-			let relay_sign = self.relay_sign.to_keypair::<Relaychain>()?;
+			let relay_sign = self.relay_sign.to_signer::<Relaychain>()?;
 			let para_client =
 				self.para_connection.to_client::<Parachain>(PARA_CHAIN_RUNTIME_VERSION).await?;
 
@@ -132,7 +132,7 @@ impl RegisterParachain {
 					.submit_and_watch_signed_extrinsic(
 						relay_sudo_account.clone(),
 						move |_, transaction_nonce| {
-							Bytes(
+							Ok(Bytes(
 								Relaychain::sign_transaction(SignParam {
 									spec_version,
 									transaction_version,
@@ -143,9 +143,9 @@ impl RegisterParachain {
 										reserve_parachain_id_call,
 										transaction_nonce,
 									),
-								})
+								})?
 								.encode(),
-							)
+							))
 						},
 					)
 					.await?,
@@ -181,7 +181,7 @@ impl RegisterParachain {
 					.submit_and_watch_signed_extrinsic(
 						relay_sudo_account.clone(),
 						move |_, transaction_nonce| {
-							Bytes(
+							Ok(Bytes(
 								Relaychain::sign_transaction(SignParam {
 									spec_version,
 									transaction_version,
@@ -192,9 +192,9 @@ impl RegisterParachain {
 										register_parathread_call,
 										transaction_nonce,
 									),
-								})
+								})?
 								.encode(),
-							)
+							))
 						},
 					)
 					.await?,
@@ -243,7 +243,7 @@ impl RegisterParachain {
 			let force_lease_signer = relay_sign.clone();
 			relay_client
 				.submit_signed_extrinsic(relay_sudo_account.clone(), move |_, transaction_nonce| {
-					Bytes(
+					Ok(Bytes(
 						Relaychain::sign_transaction(SignParam {
 							spec_version,
 							transaction_version,
@@ -251,9 +251,9 @@ impl RegisterParachain {
 							signer: force_lease_signer,
 							era: relay_substrate_client::TransactionEra::immortal(),
 							unsigned: UnsignedTransaction::new(force_lease_call, transaction_nonce),
-						})
+						})?
 						.encode(),
-					)
+					))
 				})
 				.await?;
 			log::info!(target: "bridge", "Registered parachain leases: {:?}. Waiting for onboarding", para_id);
@@ -358,6 +358,10 @@ mod tests {
 					relaychain_signer_password: None,
 					relaychain_signer_file: None,
 					relaychain_signer_password_file: None,
+					relaychain_signer_env: None,
+					relaychain_signer_keystore: None,
+					relaychain_signer_public: None,
+					relaychain_signer_url: None,
 					relaychain_transactions_mortality: None,
 				},
 				para_connection: ParachainConnectionParams {
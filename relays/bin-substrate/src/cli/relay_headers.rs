@@ -21,7 +21,8 @@ use relay_utils::metrics::{GlobalMetrics, StandaloneMetric};
 use substrate_relay_helper::finality_pipeline::SubstrateFinalitySyncPipeline;
 
 use crate::cli::{
-	PrometheusParams, SourceConnectionParams, TargetConnectionParams, TargetSigningParams,
+	GuardParams, PrometheusParams, SourceConnectionParams, TargetConnectionParams,
+	TargetSigningParams,
 };
 
 /// Start headers relayer process.
@@ -42,6 +43,8 @@ pub struct RelayHeaders {
 	target_sign: TargetSigningParams,
 	#[structopt(flatten)]
 	prometheus_params: PrometheusParams,
+	#[structopt(flatten)]
+	guard_params: GuardParams,
 }
 
 #[derive(Debug, EnumString, EnumVariantNames)]
@@ -148,10 +151,16 @@ impl RelayHeaders {
 			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
 			let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
 			let target_transactions_mortality = self.target_sign.target_transactions_mortality;
-			let target_sign = self.target_sign.to_keypair::<Target>()?;
+			let target_sign = self.target_sign.to_signer::<Target>()?;
 
 			let metrics_params: relay_utils::metrics::MetricsParams = self.prometheus_params.into();
 			GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
+			let guard_policy = self.guard_params.into_policy(&metrics_params.registry)?;
+			if guard_policy.may_pause_lanes() {
+				return Err(anyhow::format_err!(
+					"The `pause` guard action is only supported by relays that are serving message lanes",
+				))
+			}
 
 			let target_transactions_params = substrate_relay_helper::TransactionParams {
 				signer: target_sign,
@@ -161,8 +170,13 @@ impl RelayHeaders {
 				&target_client,
 				&target_transactions_params,
 				self.target.can_start_version_guard(),
+				&guard_policy,
 			)
 			.await?;
+			substrate_relay_helper::finality_guards::start_bridge_pallet_guards::<Finality>(
+				&target_client,
+				&guard_policy,
+			);
 
 			substrate_relay_helper::finality_pipeline::run::<Finality>(
 				source_client,
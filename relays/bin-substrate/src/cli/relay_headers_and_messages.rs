@@ -23,16 +23,18 @@
 //! 3) add bridge support to the `select_bridge! { ... }` macro.
 
 use futures::{FutureExt, TryFutureExt};
+use std::sync::Arc;
 use structopt::StructOpt;
 use strum::VariantNames;
 
+use bp_messages::LaneId;
 use codec::Encode;
-use messages_relay::relay_strategy::MixStrategy;
+use messages_relay::relay_strategy::{CoordinatedStrategy, MixStrategy};
 use relay_substrate_client::{
-	AccountIdOf, CallOf, Chain, ChainRuntimeVersion, Client, SignParam, TransactionSignScheme,
-	UnsignedTransaction,
+	AccountIdOf, AccountKeyPairOf, CallOf, Chain, ChainRuntimeVersion, Client, SignParam, Signer,
+	TransactionSignScheme, UnsignedTransaction,
 };
-use relay_utils::metrics::MetricsParams;
+use relay_utils::{admin::AdminParams, metrics::MetricsParams};
 use sp_core::{Bytes, Pair};
 use substrate_relay_helper::{
 	finality_pipeline::SubstrateFinalitySyncPipeline, messages_lane::MessagesRelayParams,
@@ -40,7 +42,11 @@ use substrate_relay_helper::{
 };
 
 use crate::{
-	cli::{relay_messages::RelayerMode, CliChain, HexLaneId, PrometheusParams, RuntimeVersionType},
+	cli::{
+		relay_messages::{HeadersRelayCosts, RelayMode, RelayerMode},
+		AdminServerParams, CliChain, CoordinationParams, GuardParams, HexLaneId, PrometheusParams,
+		RuntimeVersionType,
+	},
 	declare_chain_options,
 };
 
@@ -68,6 +74,19 @@ pub struct HeadersAndMessagesSharedParams {
 	lane: Vec<HexLaneId>,
 	#[structopt(long, possible_values = RelayerMode::VARIANTS, case_insensitive = true, default_value = "rational")]
 	relayer_mode: RelayerMode,
+	/// Costs of relaying non-mandatory left header to the right chain and right header to the
+	/// left chain, in the left chain tokens. Only used in the `profit-aware` relayer mode by the
+	/// left-to-right messages relay.
+	#[structopt(long, default_value = "0,0")]
+	left_to_right_headers_relay_costs: HeadersRelayCosts,
+	/// Costs of relaying non-mandatory right header to the left chain and left header to the
+	/// right chain, in the right chain tokens. Only used in the `profit-aware` relayer mode by the
+	/// right-to-left messages relay.
+	#[structopt(long, default_value = "0,0")]
+	right_to_left_headers_relay_costs: HeadersRelayCosts,
+	/// Races that are run by the relayer: deliver messages, deliver confirmations or both.
+	#[structopt(long, possible_values = RelayMode::VARIANTS, case_insensitive = true, default_value = "both")]
+	relay_mode: RelayMode,
 	/// Create relayers fund accounts on both chains, if it does not exists yet.
 	#[structopt(long)]
 	create_relayers_fund_accounts: bool,
@@ -77,6 +96,20 @@ pub struct HeadersAndMessagesSharedParams {
 	only_mandatory_headers: bool,
 	#[structopt(flatten)]
 	prometheus_params: PrometheusParams,
+	#[structopt(flatten)]
+	admin_params: AdminServerParams,
+	#[structopt(flatten)]
+	guard_params: GuardParams,
+	// relayers are identified by their accounts at the left chain in both directions
+	#[structopt(flatten)]
+	coordination_params: CoordinationParams,
+	/// Path to the TOML configuration file of the relay. Options from the configuration file
+	/// are overridden by options that are explicitly specified in the command line.
+	///
+	/// The file is read (and expanded into command line options) before options are parsed.
+	#[allow(dead_code)]
+	#[structopt(long)]
+	config: Option<std::path::PathBuf>,
 }
 
 // The reason behind this macro is that 'normal' relays are using source and target chains
@@ -97,11 +130,15 @@ macro_rules! declare_bridge_options {
 				#[structopt(flatten)]
 				left_messages_pallet_owner: [<$chain1 MessagesPalletOwnerSigningParams>],
 				#[structopt(flatten)]
+				left_price: [<$chain1 PriceOracleParams>],
+				#[structopt(flatten)]
 				right: [<$chain2 ConnectionParams>],
 				#[structopt(flatten)]
 				right_sign: [<$chain2 SigningParams>],
 				#[structopt(flatten)]
 				right_messages_pallet_owner: [<$chain2 MessagesPalletOwnerSigningParams>],
+				#[structopt(flatten)]
+				right_price: [<$chain2 PriceOracleParams>],
 			}
 
 			impl From<RelayHeadersAndMessages> for [<$chain1 $chain2 HeadersAndMessages>] {
@@ -151,7 +188,7 @@ macro_rules! select_bridge {
 
 				async fn left_create_account(
 					_left_client: Client<Left>,
-					_left_sign: <Left as TransactionSignScheme>::AccountKeyPair,
+					_left_sign: Signer<AccountKeyPairOf<Left>>,
 					_account_id: AccountIdOf<Left>,
 				) -> anyhow::Result<()> {
 					Err(anyhow::format_err!("Account creation is not supported by this bridge"))
@@ -159,7 +196,7 @@ macro_rules! select_bridge {
 
 				async fn right_create_account(
 					_right_client: Client<Right>,
-					_right_sign: <Right as TransactionSignScheme>::AccountKeyPair,
+					_right_sign: Signer<AccountKeyPairOf<Right>>,
 					_account_id: AccountIdOf<Right>,
 				) -> anyhow::Result<()> {
 					Err(anyhow::format_err!("Account creation is not supported by this bridge"))
@@ -209,7 +246,7 @@ macro_rules! select_bridge {
 
 				async fn left_create_account(
 					left_client: Client<Left>,
-					left_sign: <Left as TransactionSignScheme>::AccountKeyPair,
+					left_sign: Signer<AccountKeyPairOf<Left>>,
 					account_id: AccountIdOf<Left>,
 				) -> anyhow::Result<()> {
 					submit_signed_extrinsic(
@@ -227,7 +264,7 @@ macro_rules! select_bridge {
 
 				async fn right_create_account(
 					right_client: Client<Right>,
-					right_sign: <Right as TransactionSignScheme>::AccountKeyPair,
+					right_sign: Signer<AccountKeyPairOf<Right>>,
 					account_id: AccountIdOf<Right>,
 				) -> anyhow::Result<()> {
 					submit_signed_extrinsic(
@@ -277,7 +314,7 @@ macro_rules! select_bridge {
 
 				async fn left_create_account(
 					left_client: Client<Left>,
-					left_sign: <Left as TransactionSignScheme>::AccountKeyPair,
+					left_sign: Signer<AccountKeyPairOf<Left>>,
 					account_id: AccountIdOf<Left>,
 				) -> anyhow::Result<()> {
 					submit_signed_extrinsic(
@@ -295,7 +332,7 @@ macro_rules! select_bridge {
 
 				async fn right_create_account(
 					right_client: Client<Right>,
-					right_sign: <Right as TransactionSignScheme>::AccountKeyPair,
+					right_sign: Signer<AccountKeyPairOf<Right>>,
 					account_id: AccountIdOf<Right>,
 				) -> anyhow::Result<()> {
 					submit_signed_extrinsic(
@@ -337,26 +374,70 @@ impl RelayHeadersAndMessages {
 
 			let left_client = params.left.to_client::<Left>(LEFT_RUNTIME_VERSION).await?;
 			let left_transactions_mortality = params.left_sign.transactions_mortality()?;
-			let left_sign = params.left_sign.to_keypair::<Left>()?;
+			let left_sign = params.left_sign.to_signer::<Left>()?;
 			let left_messages_pallet_owner =
 				params.left_messages_pallet_owner.to_keypair::<Left>()?;
 			let right_client = params.right.to_client::<Right>(RIGHT_RUNTIME_VERSION).await?;
 			let right_transactions_mortality = params.right_sign.transactions_mortality()?;
-			let right_sign = params.right_sign.to_keypair::<Right>()?;
+			let right_sign = params.right_sign.to_signer::<Right>()?;
 			let right_messages_pallet_owner =
 				params.right_messages_pallet_owner.to_keypair::<Right>()?;
 
-			let lanes = params.shared.lane;
+			let lanes = params.shared.lane.into_iter().map(Into::into).collect::<Vec<LaneId>>();
 			let relayer_mode = params.shared.relayer_mode.into();
-			let relay_strategy = MixStrategy::new(relayer_mode);
+			let relayer_id_at_left: AccountIdOf<Left> = left_sign.public().into();
+			let coordination = params
+				.shared
+				.coordination_params
+				.into_coordination(&relayer_id_at_left.encode())?;
+			let left_to_right_relay_strategy = CoordinatedStrategy::new(
+				MixStrategy::new(relayer_mode).with_profit_aware_strategy(
+					params.shared.left_to_right_headers_relay_costs.into(),
+				),
+				coordination.clone(),
+			);
+			let right_to_left_relay_strategy = CoordinatedStrategy::new(
+				MixStrategy::new(relayer_mode).with_profit_aware_strategy(
+					params.shared.right_to_left_headers_relay_costs.into(),
+				),
+				coordination,
+			);
+			let relay_mode = params.shared.relay_mode.into();
 
 			// create metrics registry and register standalone metrics
 			let metrics_params: MetricsParams = params.shared.prometheus_params.into();
 			let metrics_params = relay_utils::relay_metrics(metrics_params).into_params();
+			let admin_params: AdminParams = params.shared.admin_params.into();
+			let guard_policy = params.shared.guard_params.into_policy(&metrics_params.registry)?;
+			// lanes may only be paused by guards if lane loops are controlled
+			let admin_registry = if guard_policy.may_pause_lanes() {
+				Some(admin_params.registry.clone())
+			} else {
+				admin_params.enabled_registry()
+			};
+			// finality guards are only pausing lanes that are delivering messages in the same
+			// direction as the headers
+			let left_to_right_guard_policy =
+				substrate_relay_helper::messages_guards::with_paused_lanes::<LeftToRightMessageLane>(
+					guard_policy.clone(),
+					admin_registry.as_ref(),
+					&lanes,
+				);
+			let right_to_left_guard_policy =
+				substrate_relay_helper::messages_guards::with_paused_lanes::<RightToLeftMessageLane>(
+					guard_policy.clone(),
+					admin_registry.as_ref(),
+					&lanes,
+				);
 			let left_to_right_metrics =
 				substrate_relay_helper::messages_metrics::standalone_metrics::<
 					LeftToRightMessageLane,
-				>(left_client.clone(), right_client.clone())?;
+				>(
+					left_client.clone(),
+					right_client.clone(),
+					params.left_price.to_price_oracle()?,
+					params.right_price.to_price_oracle()?,
+				)?;
 			let right_to_left_metrics = left_to_right_metrics.clone().reverse();
 
 			// start conversion rate update loops for left/right chains
@@ -370,21 +451,23 @@ impl RelayHeadersAndMessages {
 					)
 				};
 				substrate_relay_helper::conversion_rate_update::run_conversion_rate_update_loop(
-					left_to_right_metrics
-						.target_to_source_conversion_rate
-						.as_ref()
-						.ok_or_else(format_err)?
-						.shared_value_ref(),
+					Arc::new(
+						left_to_right_metrics
+							.target_to_source_conversion_rate
+							.as_ref()
+							.ok_or_else(format_err)?
+							.oracle(),
+					),
 					left_to_right_metrics
 						.target_to_base_conversion_rate
 						.as_ref()
 						.ok_or_else(format_err)?
-						.shared_value_ref(),
+						.oracle(),
 					left_to_right_metrics
 						.source_to_base_conversion_rate
 						.as_ref()
 						.ok_or_else(format_err)?
-						.shared_value_ref(),
+						.oracle(),
 					CONVERSION_RATE_ALLOWED_DIFFERENCE_RATIO,
 					move |new_rate| {
 						log::info!(
@@ -413,21 +496,23 @@ impl RelayHeadersAndMessages {
 					)
 				};
 				substrate_relay_helper::conversion_rate_update::run_conversion_rate_update_loop(
-					right_to_left_metrics
-						.target_to_source_conversion_rate
-						.as_ref()
-						.ok_or_else(format_err)?
-						.shared_value_ref(),
+					Arc::new(
+						right_to_left_metrics
+							.target_to_source_conversion_rate
+							.as_ref()
+							.ok_or_else(format_err)?
+							.oracle(),
+					),
 					left_to_right_metrics
 						.source_to_base_conversion_rate
 						.as_ref()
 						.ok_or_else(format_err)?
-						.shared_value_ref(),
+						.oracle(),
 					left_to_right_metrics
 						.target_to_base_conversion_rate
 						.as_ref()
 						.ok_or_else(format_err)?
-						.shared_value_ref(),
+						.oracle(),
 					CONVERSION_RATE_ALLOWED_DIFFERENCE_RATIO,
 					move |new_rate| {
 						log::info!(
@@ -517,31 +602,54 @@ impl RelayHeadersAndMessages {
 				&right_client,
 				&left_to_right_transaction_params,
 				params.right.can_start_version_guard(),
+				&left_to_right_guard_policy,
 			)
 			.await?;
 			RightToLeftFinality::start_relay_guards(
 				&left_client,
 				&right_to_left_transaction_params,
 				params.left.can_start_version_guard(),
+				&right_to_left_guard_policy,
 			)
 			.await?;
+			substrate_relay_helper::finality_guards::start_bridge_pallet_guards::<
+				LeftToRightFinality,
+			>(&right_client, &left_to_right_guard_policy);
+			substrate_relay_helper::finality_guards::start_bridge_pallet_guards::<
+				RightToLeftFinality,
+			>(&left_client, &right_to_left_guard_policy);
+			substrate_relay_helper::messages_guards::start::<LeftToRightMessageLane>(
+				&left_client,
+				&right_client,
+				&lanes,
+				admin_registry.as_ref(),
+				&guard_policy,
+			);
+			substrate_relay_helper::messages_guards::start::<RightToLeftMessageLane>(
+				&right_client,
+				&left_client,
+				&lanes,
+				admin_registry.as_ref(),
+				&guard_policy,
+			);
 			let left_to_right_on_demand_headers = OnDemandHeadersRelay::new::<LeftToRightFinality>(
 				left_client.clone(),
 				right_client.clone(),
 				left_to_right_transaction_params,
 				params.shared.only_mandatory_headers,
+				admin_registry.clone(),
 			);
 			let right_to_left_on_demand_headers = OnDemandHeadersRelay::new::<RightToLeftFinality>(
 				right_client.clone(),
 				left_client.clone(),
 				right_to_left_transaction_params,
 				params.shared.only_mandatory_headers,
+				admin_registry.clone(),
 			);
 
 			// Need 2x capacity since we consider both directions for each lane
 			let mut message_relays = Vec::with_capacity(lanes.len() * 2);
 			for lane in lanes {
-				let lane = lane.into();
 				let left_to_right_messages = substrate_relay_helper::messages_lane::run::<
 					LeftToRightMessageLane,
 				>(MessagesRelayParams {
@@ -560,7 +668,9 @@ impl RelayHeadersAndMessages {
 					lane_id: lane,
 					metrics_params: metrics_params.clone().disable(),
 					standalone_metrics: Some(left_to_right_metrics.clone()),
-					relay_strategy: relay_strategy.clone(),
+					relay_mode,
+					relay_strategy: left_to_right_relay_strategy.clone(),
+					admin_registry: admin_registry.clone(),
 				})
 				.map_err(|e| anyhow::format_err!("{}", e))
 				.boxed();
@@ -582,7 +692,9 @@ impl RelayHeadersAndMessages {
 					lane_id: lane,
 					metrics_params: metrics_params.clone().disable(),
 					standalone_metrics: Some(right_to_left_metrics.clone()),
-					relay_strategy: relay_strategy.clone(),
+					relay_mode,
+					relay_strategy: right_to_left_relay_strategy.clone(),
+					admin_registry: admin_registry.clone(),
 				})
 				.map_err(|e| anyhow::format_err!("{}", e))
 				.boxed();
@@ -595,6 +707,7 @@ impl RelayHeadersAndMessages {
 				.expose()
 				.await
 				.map_err(|e| anyhow::format_err!("{}", e))?;
+			admin_params.expose().await.map_err(|e| anyhow::format_err!("{}", e))?;
 
 			futures::future::select_all(message_relays).await.0
 		})
@@ -604,7 +717,7 @@ impl RelayHeadersAndMessages {
 /// Sign and submit transaction with given call to the chain.
 async fn submit_signed_extrinsic<C: Chain + TransactionSignScheme<Chain = C>>(
 	client: Client<C>,
-	sign: C::AccountKeyPair,
+	sign: Signer<C::AccountKeyPair>,
 	call: CallOf<C>,
 ) -> anyhow::Result<()>
 where
@@ -615,7 +728,7 @@ where
 	let (spec_version, transaction_version) = client.simple_runtime_version().await?;
 	client
 		.submit_signed_extrinsic(sign.public().into(), move |_, transaction_nonce| {
-			Bytes(
+			Ok(Bytes(
 				C::sign_transaction(SignParam {
 					spec_version,
 					transaction_version,
@@ -623,9 +736,9 @@ where
 					signer: sign,
 					era: relay_substrate_client::TransactionEra::immortal(),
 					unsigned: UnsignedTransaction::new(call, transaction_nonce),
-				})
+				})?
 				.encode(),
-			)
+			))
 		})
 		.await
 		.map(drop)
@@ -14,16 +14,19 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
+use codec::Encode;
 use structopt::StructOpt;
 use strum::{EnumString, EnumVariantNames, VariantNames};
 
-use messages_relay::relay_strategy::MixStrategy;
+use messages_relay::relay_strategy::{CoordinatedStrategy, MixStrategy, ProfitAwareStrategy};
+use relay_utils::admin::AdminParams;
 use substrate_relay_helper::{messages_lane::MessagesRelayParams, TransactionParams};
 
 use crate::{
 	cli::{
-		bridge::FullBridge, HexLaneId, PrometheusParams, SourceConnectionParams,
-		SourceSigningParams, TargetConnectionParams, TargetSigningParams,
+		bridge::FullBridge, AdminServerParams, CoordinationParams, HexLaneId, PrometheusParams,
+		SourceConnectionParams, SourcePriceOracleParams, SourceSigningParams,
+		TargetConnectionParams, TargetPriceOracleParams, TargetSigningParams,
 	},
 	select_full_bridge,
 };
@@ -37,6 +40,9 @@ pub enum RelayerMode {
 	/// The relayer will deliver all messages and confirmations as long as he's not losing any
 	/// funds.
 	Rational,
+	/// The relayer will deliver all messages and confirmations as long as it is not losing any
+	/// funds, including funds that are spent on relaying required headers.
+	ProfitAware,
 }
 
 impl From<RelayerMode> for messages_relay::message_lane_loop::RelayerMode {
@@ -44,6 +50,70 @@ impl From<RelayerMode> for messages_relay::message_lane_loop::RelayerMode {
 		match mode {
 			RelayerMode::Altruistic => Self::Altruistic,
 			RelayerMode::Rational => Self::Rational,
+			RelayerMode::ProfitAware => Self::ProfitAware,
+		}
+	}
+}
+
+/// Costs of headers relay that are accounted by the `profit-aware` relayer, in the messages
+/// source chain tokens.
+///
+/// Parsed from the `<source-header-relay-cost>,<target-header-relay-cost>` string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadersRelayCosts {
+	/// Cost of relaying non-mandatory source header to the target chain.
+	pub source_header_relay_cost: u128,
+	/// Cost of relaying target header to the source chain.
+	pub target_header_relay_cost: u128,
+}
+
+impl std::str::FromStr for HeadersRelayCosts {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parse_cost = |cost: &str| {
+			cost.trim()
+				.parse::<u128>()
+				.map_err(|e| format!("Failed to parse header relay cost '{}': {}", cost, e))
+		};
+		let mut costs = s.split(',');
+		match (costs.next(), costs.next(), costs.next()) {
+			(Some(source), Some(target), None) => Ok(HeadersRelayCosts {
+				source_header_relay_cost: parse_cost(source)?,
+				target_header_relay_cost: parse_cost(target)?,
+			}),
+			_ => Err(format!(
+				"Expected '<source-header-relay-cost>,<target-header-relay-cost>', got '{}'",
+				s,
+			)),
+		}
+	}
+}
+
+impl From<HeadersRelayCosts> for ProfitAwareStrategy {
+	fn from(costs: HeadersRelayCosts) -> Self {
+		ProfitAwareStrategy::new(costs.source_header_relay_cost, costs.target_header_relay_cost)
+	}
+}
+
+/// Races that are run by the messages relayer.
+#[derive(Debug, EnumString, EnumVariantNames, Clone, Copy, PartialEq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum RelayMode {
+	/// Only deliver messages to the target chain.
+	Delivery,
+	/// Only deliver messages delivery confirmations to the source chain.
+	Confirmations,
+	/// Deliver both messages and delivery confirmations.
+	Both,
+}
+
+impl From<RelayMode> for messages_relay::message_lane_loop::RelayMode {
+	fn from(mode: RelayMode) -> Self {
+		match mode {
+			RelayMode::Delivery => Self::Delivery,
+			RelayMode::Confirmations => Self::Confirmations,
+			RelayMode::Both => Self::Both,
 		}
 	}
 }
@@ -59,6 +129,13 @@ pub struct RelayMessages {
 	lane: HexLaneId,
 	#[structopt(long, possible_values = RelayerMode::VARIANTS, case_insensitive = true, default_value = "rational")]
 	relayer_mode: RelayerMode,
+	/// Costs of relaying non-mandatory source header to the target chain and target header to
+	/// the source chain, in the source chain tokens. Only used in the `profit-aware` relayer mode.
+	#[structopt(long, default_value = "0,0")]
+	headers_relay_costs: HeadersRelayCosts,
+	/// Races that are run by the relayer: deliver messages, deliver confirmations or both.
+	#[structopt(long, possible_values = RelayMode::VARIANTS, case_insensitive = true, default_value = "both")]
+	relay_mode: RelayMode,
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	#[structopt(flatten)]
@@ -68,7 +145,15 @@ pub struct RelayMessages {
 	#[structopt(flatten)]
 	target_sign: TargetSigningParams,
 	#[structopt(flatten)]
+	source_price: SourcePriceOracleParams,
+	#[structopt(flatten)]
+	target_price: TargetPriceOracleParams,
+	#[structopt(flatten)]
 	prometheus_params: PrometheusParams,
+	#[structopt(flatten)]
+	admin_params: AdminServerParams,
+	#[structopt(flatten)]
+	coordination_params: CoordinationParams,
 }
 
 impl RelayMessages {
@@ -76,13 +161,31 @@ impl RelayMessages {
 	pub async fn run(self) -> anyhow::Result<()> {
 		select_full_bridge!(self.bridge, {
 			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
-			let source_sign = self.source_sign.to_keypair::<Source>()?;
+			let source_sign = self.source_sign.to_signer::<Source>()?;
 			let source_transactions_mortality = self.source_sign.transactions_mortality()?;
 			let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
-			let target_sign = self.target_sign.to_keypair::<Target>()?;
+			let target_sign = self.target_sign.to_signer::<Target>()?;
 			let target_transactions_mortality = self.target_sign.transactions_mortality()?;
 			let relayer_mode = self.relayer_mode.into();
-			let relay_strategy = MixStrategy::new(relayer_mode);
+			let relayer_id_at_source: relay_substrate_client::AccountIdOf<Source> =
+				source_sign.public().into();
+			let coordination =
+				self.coordination_params.into_coordination(&relayer_id_at_source.encode())?;
+			let relay_strategy = CoordinatedStrategy::new(
+				MixStrategy::new(relayer_mode)
+					.with_profit_aware_strategy(self.headers_relay_costs.into()),
+				coordination,
+			);
+			let admin_params: AdminParams = self.admin_params.into();
+			let admin_registry = admin_params.enabled_registry();
+			admin_params.expose().await.map_err(|e| anyhow::format_err!("{}", e))?;
+			let standalone_metrics =
+				substrate_relay_helper::messages_metrics::standalone_metrics::<MessagesLane>(
+					source_client.clone(),
+					target_client.clone(),
+					self.source_price.to_price_oracle()?,
+					self.target_price.to_price_oracle()?,
+				)?;
 
 			substrate_relay_helper::messages_lane::run::<MessagesLane>(MessagesRelayParams {
 				source_client,
@@ -99,8 +202,10 @@ impl RelayMessages {
 				target_to_source_headers_relay: None,
 				lane_id: self.lane.into(),
 				metrics_params: self.prometheus_params.into(),
-				standalone_metrics: None,
+				standalone_metrics: Some(standalone_metrics),
+				relay_mode: self.relay_mode.into(),
 				relay_strategy,
+				admin_registry,
 			})
 			.await
 			.map_err(|e| anyhow::format_err!("{}", e))
@@ -146,4 +251,123 @@ mod tests {
 			RelayerMode::Altruistic,
 		);
 	}
+
+	#[test]
+	fn should_use_both_relay_mode_by_default() {
+		assert_eq!(
+			RelayMessages::from_iter(vec![
+				"relay-messages",
+				"rialto-to-millau",
+				"--source-port=0",
+				"--source-signer=//Alice",
+				"--target-port=0",
+				"--target-signer=//Alice",
+				"--lane=00000000",
+			])
+			.relay_mode,
+			RelayMode::Both,
+		);
+	}
+
+	#[test]
+	fn should_accept_confirmations_relay_mode() {
+		assert_eq!(
+			RelayMessages::from_iter(vec![
+				"relay-messages",
+				"rialto-to-millau",
+				"--source-port=0",
+				"--source-signer=//Alice",
+				"--target-port=0",
+				"--target-signer=//Alice",
+				"--lane=00000000",
+				"--relay-mode=confirmations",
+			])
+			.relay_mode,
+			RelayMode::Confirmations,
+		);
+	}
+
+	#[test]
+	fn should_accept_coordination_params() {
+		let coordination = RelayMessages::from_iter(vec![
+			"relay-messages",
+			"rialto-to-millau",
+			"--source-port=0",
+			"--source-signer=//Alice",
+			"--target-port=0",
+			"--target-signer=//Alice",
+			"--lane=00000000",
+			"--coordination-relayer=01",
+			"--coordination-relayer=02",
+			"--coordination-slot-length=8",
+		])
+		.coordination_params;
+
+		assert!(coordination.into_coordination(&[2]).unwrap().unwrap().is_own_slot(8));
+	}
+
+	#[test]
+	fn should_reject_coordination_if_relayer_is_not_in_the_set() {
+		let coordination = RelayMessages::from_iter(vec![
+			"relay-messages",
+			"rialto-to-millau",
+			"--source-port=0",
+			"--source-signer=//Alice",
+			"--target-port=0",
+			"--target-signer=//Alice",
+			"--lane=00000000",
+			"--coordination-relayer=01",
+		])
+		.coordination_params;
+
+		assert!(coordination.into_coordination(&[2]).is_err());
+	}
+
+	#[test]
+	fn should_accept_profit_aware_relayer_mode() {
+		let relay_messages = RelayMessages::from_iter(vec![
+			"relay-messages",
+			"rialto-to-millau",
+			"--source-port=0",
+			"--source-signer=//Alice",
+			"--target-port=0",
+			"--target-signer=//Alice",
+			"--lane=00000000",
+			"--relayer-mode=profit-aware",
+			"--headers-relay-costs=100,200",
+		]);
+
+		assert_eq!(relay_messages.relayer_mode, RelayerMode::ProfitAware);
+		assert_eq!(
+			relay_messages.headers_relay_costs,
+			HeadersRelayCosts { source_header_relay_cost: 100, target_header_relay_cost: 200 },
+		);
+	}
+
+	#[test]
+	fn should_reject_invalid_headers_relay_costs() {
+		assert!("100".parse::<HeadersRelayCosts>().is_err());
+		assert!("100,200,300".parse::<HeadersRelayCosts>().is_err());
+		assert!("100,x".parse::<HeadersRelayCosts>().is_err());
+	}
+
+	#[test]
+	fn should_accept_price_oracle_params() {
+		let relay_messages = RelayMessages::from_iter(vec![
+			"relay-messages",
+			"rialto-to-millau",
+			"--source-port=0",
+			"--source-signer=//Alice",
+			"--target-port=0",
+			"--target-signer=//Alice",
+			"--lane=00000000",
+			"--source-price-file=rialto.json",
+			"--target-price-url=http://127.0.0.1/price",
+			"--target-price-json-path=$.millau.usd",
+		]);
+
+		assert!(relay_messages.source_price.to_price_oracle().unwrap().is_some());
+		assert!(relay_messages.target_price.to_price_oracle().unwrap().is_some());
+		assert_eq!(relay_messages.target_price.target_price_json_path, "$.millau.usd");
+	}
 }
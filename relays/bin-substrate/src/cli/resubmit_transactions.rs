@@ -19,7 +19,7 @@ use crate::cli::{Balance, TargetConnectionParams, TargetSigningParams};
 use codec::{Decode, Encode};
 use num_traits::{One, Zero};
 use relay_substrate_client::{
-	BlockWithJustification, Chain, Client, Error as SubstrateError, HeaderOf, SignParam,
+	BlockWithJustification, Chain, Client, Error as SubstrateError, HeaderOf, SignParam, Signer,
 	TransactionSignScheme,
 };
 use relay_utils::FailedClient;
@@ -122,7 +122,7 @@ impl ResubmitTransactions {
 		select_bridge!(self.chain, {
 			let relay_loop_name = format!("ResubmitTransactions{}", Target::NAME);
 			let client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
-			let key_pair = self.target_sign.to_keypair::<Target>()?;
+			let key_pair = self.target_sign.to_signer::<Target>()?;
 
 			relay_utils::relay_loop((), client)
 				.run(relay_loop_name, move |_, client, _| {
@@ -219,7 +219,7 @@ impl<C: Chain> Context<C> {
 /// Run resubmit transactions loop.
 async fn run_until_connection_lost<C: Chain, S: TransactionSignScheme<Chain = C>>(
 	client: Client<C>,
-	key_pair: S::AccountKeyPair,
+	key_pair: Signer<S::AccountKeyPair>,
 	mut context: Context<C>,
 ) -> Result<(), FailedClient> {
 	loop {
@@ -244,7 +244,7 @@ async fn run_until_connection_lost<C: Chain, S: TransactionSignScheme<Chain = C>
 /// Run single loop iteration.
 async fn run_loop_iteration<C: Chain, S: TransactionSignScheme<Chain = C>>(
 	client: Client<C>,
-	key_pair: S::AccountKeyPair,
+	key_pair: Signer<S::AccountKeyPair>,
 	mut context: Context<C>,
 ) -> Result<Context<C>, SubstrateError> {
 	// correct best header is required for all other actions
@@ -319,7 +319,7 @@ async fn run_loop_iteration<C: Chain, S: TransactionSignScheme<Chain = C>>(
 /// Search transaction pool for transaction, signed by given key pair.
 async fn lookup_signer_transaction<C: Chain, S: TransactionSignScheme<Chain = C>>(
 	client: &Client<C>,
-	key_pair: &S::AccountKeyPair,
+	key_pair: &Signer<S::AccountKeyPair>,
 ) -> Result<Option<S::SignedTransaction>, SubstrateError> {
 	let pending_transactions = client.pending_extrinsics().await?;
 	for pending_transaction in pending_transactions {
@@ -404,7 +404,7 @@ fn select_transaction_from_queue<C: Chain>(
 /// Try to find appropriate tip for transaction so that its priority is larger than given.
 async fn update_transaction_tip<C: Chain, S: TransactionSignScheme<Chain = C>>(
 	client: &Client<C>,
-	key_pair: &S::AccountKeyPair,
+	key_pair: &Signer<S::AccountKeyPair>,
 	at_block: C::Hash,
 	tx: S::SignedTransaction,
 	tip_step: C::Balance,
@@ -445,7 +445,7 @@ async fn update_transaction_tip<C: Chain, S: TransactionSignScheme<Chain = C>>(
 					signer: key_pair.clone(),
 					era: relay_substrate_client::TransactionEra::immortal(),
 					unsigned: unsigned_tx.clone(),
-				}),
+				})?,
 			)
 			.await??
 			.priority;
@@ -468,7 +468,7 @@ async fn update_transaction_tip<C: Chain, S: TransactionSignScheme<Chain = C>>(
 			signer: key_pair.clone(),
 			era: relay_substrate_client::TransactionEra::immortal(),
 			unsigned: unsigned_tx,
-		}),
+		})?,
 	))
 }
 
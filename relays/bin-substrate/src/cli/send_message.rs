@@ -22,11 +22,11 @@ use crate::cli::{
 	SourceSigningParams, TargetConnectionParams, TargetSigningParams,
 };
 use bp_message_dispatch::{CallOrigin, MessagePayload};
-use bp_runtime::{BalanceOf, Chain as _};
-use codec::Encode;
+use bp_runtime::{messages::MessageDispatchDryRunResult, BalanceOf, Chain as _};
+use codec::{Decode, Encode};
 use frame_support::weights::Weight;
 use relay_substrate_client::{Chain, SignParam, TransactionSignScheme, UnsignedTransaction};
-use sp_core::{Bytes, Pair};
+use sp_core::Bytes;
 use sp_runtime::{traits::IdentifyAccount, AccountId32, MultiSignature, MultiSigner};
 use std::fmt::Debug;
 use structopt::StructOpt;
@@ -88,6 +88,16 @@ pub struct SendMessage {
 	/// `SourceAccount`.
 	#[structopt(long, possible_values = &Origins::variants(), default_value = "Source")]
 	origin: Origins,
+	/// Nonce of the message, dispatched with `Target` origin. Every nonce may only be used once
+	/// by the target signer, so the message is protected from being dispatched more than once.
+	/// If not passed, current UNIX time (in milliseconds) is used. Nonces are only supported by
+	/// Millau and Rialto chains - messages to other chains are sent without nonce.
+	#[structopt(long)]
+	target_account_nonce: Option<u64>,
+	/// Do not send the message. Instead, dry run its dispatch at the target chain and print
+	/// the dispatch result, actual dispatch weight and events that would be deposited.
+	#[structopt(long)]
+	dry_run: bool,
 
 	// Normally we don't need to connect to the target chain to send message. But for testing
 	// we may want to use **actual** `spec_version` of the target chain when composing a message.
@@ -108,11 +118,12 @@ impl SendMessage {
 				dispatch_fee_payment,
 				dispatch_weight,
 				origin,
+				target_account_nonce,
 				bridge,
 				..
 			} = self;
 
-			let source_sign = source_sign.to_keypair::<Source>()?;
+			let source_sign = source_sign.to_signer::<Source>()?;
 
 			encode_call::preprocess_call::<Source, Target>(message, bridge.bridge_instance_index());
 			let target_call = Target::encode_call(message)?;
@@ -122,9 +133,27 @@ impl SendMessage {
 				.await?;
 
 			let payload = {
+				let mut default_dispatch_weight = Target::get_dispatch_info(&target_call)?.weight;
+				let target_account_nonce = match (&*origin, bridge.target_account_nonce_weight()) {
+					(Origins::Target, Some(nonce_weight)) => {
+						default_dispatch_weight =
+							default_dispatch_weight.saturating_add(nonce_weight);
+						Some(crate::cli::target_account_nonce(*target_account_nonce))
+					},
+					(Origins::Target, None) if target_account_nonce.is_some() => {
+						anyhow::bail!(
+							"Target chain of {:?} bridge does not support target account nonces",
+							bridge,
+						)
+					},
+					(Origins::Source, _) if target_account_nonce.is_some() => {
+						anyhow::bail!("Target account nonce may only be used with `Target` origin")
+					},
+					_ => None,
+				};
 				let target_call_weight = prepare_call_dispatch_weight(
 					dispatch_weight,
-					ExplicitOrMaximal::Explicit(Target::get_dispatch_info(&target_call)?.weight),
+					ExplicitOrMaximal::Explicit(default_dispatch_weight),
 					compute_maximal_message_dispatch_weight(Target::max_extrinsic_weight()),
 				);
 				let source_sender_public: MultiSigner = source_sign.public().into();
@@ -136,19 +165,38 @@ impl SendMessage {
 					match origin {
 						Origins::Source => CallOrigin::SourceAccount(source_account_id),
 						Origins::Target => {
-							let target_sign = target_sign.to_keypair::<Target>()?;
-							let digest = account_ownership_digest(
-								&target_call,
-								source_account_id.clone(),
-								target_spec_version,
-							);
+							let target_sign = target_sign.to_signer::<Target>()?;
 							let target_origin_public = target_sign.public();
-							let digest_signature = target_sign.sign(&digest);
-							CallOrigin::TargetAccount(
-								source_account_id,
-								target_origin_public.into(),
-								digest_signature.into(),
-							)
+							match target_account_nonce {
+								Some(nonce) => {
+									let digest = account_ownership_digest_with_nonce(
+										&target_call,
+										source_account_id.clone(),
+										nonce,
+										target_spec_version,
+									);
+									let digest_signature = target_sign.sign(&digest)?;
+									CallOrigin::TargetAccountWithNonce(
+										source_account_id,
+										target_origin_public.into(),
+										digest_signature.into(),
+										nonce,
+									)
+								},
+								None => {
+									let digest = account_ownership_digest(
+										&target_call,
+										source_account_id.clone(),
+										target_spec_version,
+									);
+									let digest_signature = target_sign.sign(&digest)?;
+									CallOrigin::TargetAccount(
+										source_account_id,
+										target_origin_public.into(),
+										digest_signature.into(),
+									)
+								},
+							}
 						},
 					},
 					&target_call,
@@ -164,8 +212,29 @@ impl SendMessage {
 		crate::select_full_bridge!(self.bridge, {
 			let payload = self.encode_payload().await?;
 
+			if self.dry_run {
+				let dry_run_method = DRY_RUN_MESSAGE_METHOD.ok_or_else(|| {
+					anyhow::format_err!(
+						"Dry run of messages is not supported by {} runtime",
+						Target::NAME,
+					)
+				})?;
+				let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
+				let lane = self.lane.clone().into();
+				let dry_run_result: MessageDispatchDryRunResult<TargetDispatchEvent> =
+					dry_run_message(&target_client, dry_run_method, lane, &payload).await?;
+
+				log::info!(
+					target: "bridge",
+					"Message dispatch dry run at {}: {:#?}",
+					Target::NAME,
+					dry_run_result,
+				);
+				return Ok(())
+			}
+
 			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
-			let source_sign = self.source_sign.to_keypair::<Source>()?;
+			let source_sign = self.source_sign.to_signer::<Source>()?;
 
 			let lane = self.lane.clone().into();
 			let fee = match self.fee {
@@ -200,7 +269,7 @@ impl SendMessage {
 						signer: source_sign.clone(),
 						era: relay_substrate_client::TransactionEra::immortal(),
 						unsigned: UnsignedTransaction::new(send_message_call.clone(), 0),
-					})
+					})?
 					.encode(),
 				))
 				.await?;
@@ -213,7 +282,7 @@ impl SendMessage {
 						signer: source_sign.clone(),
 						era: relay_substrate_client::TransactionEra::immortal(),
 						unsigned: UnsignedTransaction::new(send_message_call, transaction_nonce),
-					})
+					})?
 					.encode();
 
 					log::info!(
@@ -241,7 +310,7 @@ impl SendMessage {
 						HexBytes::encode(&signed_source_call)
 					);
 
-					Bytes(signed_source_call)
+					Ok(Bytes(signed_source_call))
 				})
 				.await?;
 		});
@@ -250,6 +319,21 @@ impl SendMessage {
 	}
 }
 
+/// Dry run message dispatch at the target chain.
+pub(crate) async fn dry_run_message<Event: Decode, C: Chain, P: Encode>(
+	client: &relay_substrate_client::Client<C>,
+	dry_run_method: &str,
+	lane: bp_messages::LaneId,
+	payload: &P,
+) -> anyhow::Result<MessageDispatchDryRunResult<Event>> {
+	let encoded_response = client
+		.state_call(dry_run_method.into(), (lane, payload.encode()).encode().into(), None)
+		.await?;
+	Decode::decode(&mut &encoded_response.0[..])
+		.map_err(relay_substrate_client::Error::ResponseParseFailed)
+		.map_err(Into::into)
+}
+
 fn prepare_call_dispatch_weight(
 	user_specified_dispatch_weight: &Option<ExplicitOrMaximal<Weight>>,
 	weight_from_pre_dispatch_call: ExplicitOrMaximal<Weight>,
@@ -299,6 +383,7 @@ pub(crate) fn compute_maximal_message_dispatch_weight(maximal_extrinsic_weight:
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use frame_support::traits::Get;
 	use hex_literal::hex;
 
 	#[async_std::test]
@@ -356,21 +441,74 @@ mod tests {
 		// when
 		let payload = send_message.encode_payload().await.unwrap();
 
+		// then
+		// Since signatures are randomized and nonce is selected by the CLI, we extract them from
+		// here and only check the rest.
+		let (signature, nonce) = match payload.origin {
+			CallOrigin::TargetAccountWithNonce(_, _, ref sig, nonce) => (sig.clone(), nonce),
+			_ => panic!("Unexpected `CallOrigin`: {:?}", payload),
+		};
+		assert_eq!(
+			payload,
+			MessagePayload {
+				spec_version: relay_millau_client::Millau::RUNTIME_VERSION.spec_version,
+				weight: pallet_bridge_dispatch::target_account_nonce_weight(
+					rialto_runtime::DbWeight::get()
+				),
+				origin: CallOrigin::TargetAccountWithNonce(
+					sp_keyring::AccountKeyring::Alice.to_account_id(),
+					sp_keyring::AccountKeyring::Bob.into(),
+					signature,
+					nonce,
+				),
+				dispatch_fee_payment: bp_runtime::messages::DispatchFeePayment::AtSourceChain,
+				call: hex!("0001081234").to_vec(),
+			}
+		);
+	}
+
+	#[async_std::test]
+	async fn send_remark_millau_to_rialto_with_target_account_nonce() {
+		// given
+		let mut send_message = SendMessage::from_iter(vec![
+			"send-message",
+			"millau-to-rialto",
+			"--source-port",
+			"1234",
+			"--source-signer",
+			"//Alice",
+			"--origin",
+			"Target",
+			"--target-signer",
+			"//Bob",
+			"--target-account-nonce",
+			"42",
+			"remark",
+			"--remark-payload",
+			"1234",
+		]);
+
+		// when
+		let payload = send_message.encode_payload().await.unwrap();
+
 		// then
 		// Since signatures are randomized we extract it from here and only check the rest.
 		let signature = match payload.origin {
-			CallOrigin::TargetAccount(_, _, ref sig) => sig.clone(),
+			CallOrigin::TargetAccountWithNonce(_, _, ref sig, _) => sig.clone(),
 			_ => panic!("Unexpected `CallOrigin`: {:?}", payload),
 		};
 		assert_eq!(
 			payload,
 			MessagePayload {
 				spec_version: relay_millau_client::Millau::RUNTIME_VERSION.spec_version,
-				weight: 0,
-				origin: CallOrigin::TargetAccount(
+				weight: pallet_bridge_dispatch::target_account_nonce_weight(
+					rialto_runtime::DbWeight::get()
+				),
+				origin: CallOrigin::TargetAccountWithNonce(
 					sp_keyring::AccountKeyring::Alice.to_account_id(),
 					sp_keyring::AccountKeyring::Bob.into(),
 					signature,
+					42,
 				),
 				dispatch_fee_payment: bp_runtime::messages::DispatchFeePayment::AtSourceChain,
 				call: hex!("0001081234").to_vec(),
@@ -20,29 +20,38 @@
 // to show token swap progress
 #![allow(dead_code)]
 
-use codec::Encode;
+use codec::{Decode, Encode};
 use num_traits::One;
 use rand::random;
 use structopt::StructOpt;
 use strum::{EnumString, EnumVariantNames, VariantNames};
 
-use frame_support::dispatch::GetDispatchInfo;
+use frame_support::{dispatch::GetDispatchInfo, traits::Get};
 use relay_substrate_client::{
 	AccountIdOf, AccountPublicOf, BalanceOf, BlockNumberOf, CallOf, Chain, ChainWithBalances,
-	Client, Error as SubstrateError, HashOf, SignParam, SignatureOf, Subscription,
+	Client, Error as SubstrateError, HashOf, SignParam, SignatureOf, Signer, Subscription,
 	TransactionSignScheme, TransactionStatusOf, UnsignedTransaction,
 };
-use sp_core::{blake2_256, storage::StorageKey, Bytes, Pair, U256};
+use sp_core::{storage::StorageKey, Bytes, Pair, U256};
 use sp_runtime::traits::{Convert, Header as HeaderT};
 
 use crate::cli::{
-	Balance, CliChain, SourceConnectionParams, SourceSigningParams, TargetConnectionParams,
-	TargetSigningParams,
+	AccountId, Balance, CliChain, SourceConnectionParams, SourceSigningParams,
+	TargetConnectionParams, TargetSigningParams,
 };
 
 /// Swap tokens.
 #[derive(StructOpt, Debug, PartialEq)]
-pub struct SwapTokens {
+pub enum SwapTokens {
+	/// Start new tokens swap and wait until it is claimed or canceled.
+	Start(StartSwap),
+	/// Print pending tokens swaps.
+	Status(SwapsStatus),
+}
+
+/// Start new tokens swap.
+#[derive(StructOpt, Debug, PartialEq)]
+pub struct StartSwap {
 	/// A bridge instance to use in token swap.
 	#[structopt(possible_values = SwapTokensBridge::VARIANTS, case_insensitive = true)]
 	bridge: SwapTokensBridge,
@@ -59,12 +68,43 @@ pub struct SwapTokens {
 
 	#[structopt(subcommand)]
 	swap_type: TokenSwapType,
+	/// Source chain asset that source signer wants to swap. If not passed, source chain native
+	/// tokens are swapped.
+	#[structopt(long)]
+	source_asset_id: Option<u32>,
 	/// Source chain balance that source signer wants to swap.
 	#[structopt(long)]
 	source_balance: Balance,
+	/// Target chain asset that target signer wants to swap. If not passed, target chain native
+	/// tokens are swapped.
+	#[structopt(long)]
+	target_asset_id: Option<u32>,
 	/// Target chain balance that target signer wants to swap.
 	#[structopt(long)]
 	target_balance: Balance,
+	/// Nonce of the tokens transfer message, signed by the target signer. Every nonce may only
+	/// be used once by the target signer. If not passed, current UNIX time (in milliseconds) is
+	/// used.
+	#[structopt(long)]
+	target_account_nonce: Option<u64>,
+}
+
+/// Print pending tokens swaps.
+#[derive(StructOpt, Debug, PartialEq)]
+pub struct SwapsStatus {
+	/// A bridge instance to use in token swap.
+	#[structopt(possible_values = SwapTokensBridge::VARIANTS, case_insensitive = true)]
+	bridge: SwapTokensBridge,
+
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+
+	/// Only print swaps that have been started by given source chain account.
+	#[structopt(long)]
+	source_account: Option<AccountId>,
+	/// Only print swaps with given state.
+	#[structopt(long, possible_values = TokenSwapState::VARIANTS, case_insensitive = true)]
+	state: Option<TokenSwapState>,
 }
 
 /// Token swap type.
@@ -84,6 +124,28 @@ pub enum TokenSwapType {
 	},
 }
 
+/// Token swap state.
+#[derive(Debug, EnumString, EnumVariantNames, PartialEq, Clone, Copy)]
+#[strum(serialize_all = "kebab_case")]
+pub enum TokenSwapState {
+	/// The transfer message has been sent, but its dispatch result is not yet known.
+	Started,
+	/// The transfer has succeeded at the target chain and the swap may be claimed.
+	Confirmed,
+	/// The transfer has failed at the target chain and the swap may be canceled.
+	Failed,
+}
+
+impl From<TokenSwapState> for bp_token_swap::TokenSwapState {
+	fn from(state: TokenSwapState) -> Self {
+		match state {
+			TokenSwapState::Started => bp_token_swap::TokenSwapState::Started,
+			TokenSwapState::Confirmed => bp_token_swap::TokenSwapState::Confirmed,
+			TokenSwapState::Failed => bp_token_swap::TokenSwapState::Failed,
+		}
+	}
+}
+
 /// Swap tokens bridge.
 #[derive(Debug, EnumString, EnumVariantNames, PartialEq)]
 #[strum(serialize_all = "kebab_case")]
@@ -107,16 +169,32 @@ macro_rules! select_bridge {
 					Some(rialto_runtime::VERSION);
 
 				type FromSwapToThisAccountIdConverter = bp_rialto::AccountIdConverter;
+				type TargetDbWeight = rialto_runtime::DbWeight;
+
+				type SourceAssetId = bp_millau::AssetId;
+				type TargetAssetId = bp_rialto::AssetId;
+
+				const TOKEN_SWAP_PALLET_NAME: &str = bp_millau::WITH_RIALTO_TOKEN_SWAP_PALLET_NAME;
+				const SWAPS_BY_SOURCE_ACCOUNT_METHOD: &str =
+					bp_millau::WITH_RIALTO_TOKEN_SWAPS_BY_SOURCE_ACCOUNT_METHOD;
+				const SWAPS_BY_STATE_METHOD: &str =
+					bp_millau::WITH_RIALTO_TOKEN_SWAPS_BY_STATE_METHOD;
+				const ESTIMATE_SOURCE_TO_TARGET_MESSAGE_FEE_METHOD: &str =
+					bp_rialto::TO_RIALTO_ESTIMATE_MESSAGE_FEE_METHOD;
+				const ESTIMATE_TARGET_TO_SOURCE_MESSAGE_FEE_METHOD: &str =
+					bp_millau::TO_MILLAU_ESTIMATE_MESSAGE_FEE_METHOD;
+
+				fn derive_source_account_from_target_account(
+					id: bp_runtime::SourceAccount<bp_rialto::AccountId>,
+				) -> bp_millau::AccountId {
+					bp_millau::derive_account_from_rialto_id(id)
+				}
 
-				use bp_millau::{
-					derive_account_from_rialto_id as derive_source_account_from_target_account,
-					TO_MILLAU_ESTIMATE_MESSAGE_FEE_METHOD as ESTIMATE_TARGET_TO_SOURCE_MESSAGE_FEE_METHOD,
-					WITH_RIALTO_TOKEN_SWAP_PALLET_NAME as TOKEN_SWAP_PALLET_NAME,
-				};
-				use bp_rialto::{
-					derive_account_from_millau_id as derive_target_account_from_source_account,
-					TO_RIALTO_ESTIMATE_MESSAGE_FEE_METHOD as ESTIMATE_SOURCE_TO_TARGET_MESSAGE_FEE_METHOD,
-				};
+				fn derive_target_account_from_source_account(
+					id: bp_runtime::SourceAccount<bp_millau::AccountId>,
+				) -> bp_rialto::AccountId {
+					bp_rialto::derive_account_from_millau_id(id)
+				}
 
 				const SOURCE_CHAIN_ID: bp_runtime::ChainId = bp_runtime::MILLAU_CHAIN_ID;
 				const TARGET_CHAIN_ID: bp_runtime::ChainId = bp_runtime::RIALTO_CHAIN_ID;
@@ -131,20 +209,34 @@ macro_rules! select_bridge {
 }
 
 impl SwapTokens {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self {
+			Self::Start(arg) => arg.run().await,
+			Self::Status(arg) => arg.run().await,
+		}
+	}
+}
+
+impl StartSwap {
 	/// Run the command.
 	pub async fn run(self) -> anyhow::Result<()> {
 		select_bridge!(self.bridge, {
 			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
-			let source_sign = self.source_sign.to_keypair::<Target>()?;
+			let source_sign = self.source_sign.to_signer::<Target>()?;
 			let target_client = self.target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
-			let target_sign = self.target_sign.to_keypair::<Target>()?;
+			let target_sign = self.target_sign.to_signer::<Target>()?;
 
 			// names of variables in this function are matching names used by the
 			// `pallet-bridge-token-swap`
 
 			// prepare token swap intention
 			let token_swap = self
-				.prepare_token_swap::<Source, Target>(&source_client, &source_sign, &target_sign)
+				.prepare_token_swap::<Source, SourceAssetId, Target, TargetAssetId>(
+					&source_client,
+					&source_sign,
+					&target_sign,
+				)
 				.await?;
 
 			// group all accounts that will be used later
@@ -161,9 +253,7 @@ impl SwapTokens {
 				),
 				source_account_at_this_chain: token_swap.source_account_at_this_chain.clone(),
 				target_account_at_bridged_chain: token_swap.target_account_at_bridged_chain.clone(),
-				swap_account: FromSwapToThisAccountIdConverter::convert(
-					token_swap.using_encoded(blake2_256).into(),
-				),
+				swap_account: FromSwapToThisAccountIdConverter::convert(token_swap.hash()),
 			};
 
 			// account balances are used to demonstrate what's happening :)
@@ -179,26 +269,41 @@ impl SwapTokens {
 			// Step 1: swap is created
 			//
 
-			// prepare `Currency::transfer` call that will happen at the target chain
-			let bridged_currency_transfer: CallOf<Target> = pallet_balances::Call::transfer {
-				dest: accounts.source_account_at_bridged_chain.clone().into(),
-				value: token_swap.target_balance_at_bridged_chain,
-			}
-			.into();
+			// prepare `Currency::transfer` (or `pallet_assets::Call::transfer`) call that will
+			// happen at the target chain
+			let bridged_currency_transfer: CallOf<Target> =
+				match token_swap.target_asset_at_bridged_chain {
+					Some(target_asset_at_bridged_chain) => pallet_assets::Call::transfer {
+						id: target_asset_at_bridged_chain,
+						target: accounts.source_account_at_bridged_chain.clone().into(),
+						amount: token_swap.target_balance_at_bridged_chain,
+					}
+					.into(),
+					None => pallet_balances::Call::transfer {
+						dest: accounts.source_account_at_bridged_chain.clone().into(),
+						value: token_swap.target_balance_at_bridged_chain,
+					}
+					.into(),
+				};
 			let bridged_currency_transfer_weight =
-				bridged_currency_transfer.get_dispatch_info().weight;
+				bridged_currency_transfer.get_dispatch_info().weight.saturating_add(
+					pallet_bridge_dispatch::target_account_nonce_weight(TargetDbWeight::get()),
+				);
 
 			// sign message
 			let bridged_chain_spec_version = TARGET_SPEC_VERSION;
-			let signature_payload = pallet_bridge_dispatch::account_ownership_digest(
+			let bridged_currency_transfer_nonce =
+				crate::cli::target_account_nonce(self.target_account_nonce);
+			let signature_payload = pallet_bridge_dispatch::account_ownership_digest_with_nonce(
 				&bridged_currency_transfer,
 				&accounts.swap_account,
+				bridged_currency_transfer_nonce,
 				&bridged_chain_spec_version,
 				SOURCE_CHAIN_ID,
 				TARGET_CHAIN_ID,
 			);
 			let bridged_currency_transfer_signature: SignatureOf<Target> =
-				target_sign.sign(&signature_payload).into();
+				target_sign.sign(&signature_payload)?.into();
 
 			// prepare `create_swap` call
 			let target_public_at_bridged_chain: AccountPublicOf<Target> =
@@ -211,10 +316,11 @@ impl SwapTokens {
 					bp_message_dispatch::MessagePayload {
 						spec_version: TARGET_SPEC_VERSION,
 						weight: bridged_currency_transfer_weight,
-						origin: bp_message_dispatch::CallOrigin::TargetAccount(
+						origin: bp_message_dispatch::CallOrigin::TargetAccountWithNonce(
 							accounts.swap_account.clone(),
 							target_public_at_bridged_chain.clone(),
 							bridged_currency_transfer_signature.clone(),
+							bridged_currency_transfer_nonce,
 						),
 						dispatch_fee_payment:
 							bp_runtime::messages::DispatchFeePayment::AtTargetChain,
@@ -230,6 +336,7 @@ impl SwapTokens {
 					bridged_chain_spec_version,
 					bridged_currency_transfer: bridged_currency_transfer.encode(),
 					bridged_currency_transfer_weight,
+					bridged_currency_transfer_nonce,
 					bridged_currency_transfer_signature,
 				}),
 			}
@@ -245,7 +352,7 @@ impl SwapTokens {
 					.submit_and_watch_signed_extrinsic(
 						accounts.source_account_at_this_chain.clone(),
 						move |_, transaction_nonce| {
-							Bytes(
+							Ok(Bytes(
 								Source::sign_transaction(SignParam {
 									spec_version,
 									transaction_version,
@@ -256,9 +363,9 @@ impl SwapTokens {
 										create_swap_call,
 										transaction_nonce,
 									),
-								})
+								})?
 								.encode(),
-							)
+							))
 						},
 					)
 					.await?,
@@ -386,7 +493,7 @@ impl SwapTokens {
 						.submit_and_watch_signed_extrinsic(
 							accounts.target_account_at_bridged_chain.clone(),
 							move |_, transaction_nonce| {
-								Bytes(
+								Ok(Bytes(
 									Target::sign_transaction(SignParam {
 										spec_version,
 										transaction_version,
@@ -397,9 +504,9 @@ impl SwapTokens {
 											send_message_call,
 											transaction_nonce,
 										),
-									})
+									})?
 									.encode(),
-								)
+								))
 							},
 						)
 						.await?,
@@ -430,7 +537,7 @@ impl SwapTokens {
 						.submit_and_watch_signed_extrinsic(
 							accounts.source_account_at_this_chain.clone(),
 							move |_, transaction_nonce| {
-								Bytes(
+								Ok(Bytes(
 									Source::sign_transaction(SignParam {
 										spec_version,
 										transaction_version,
@@ -441,9 +548,9 @@ impl SwapTokens {
 											cancel_swap_call,
 											transaction_nonce,
 										),
-									})
+									})?
 									.encode(),
-								)
+								))
 							},
 						)
 						.await?,
@@ -461,16 +568,18 @@ impl SwapTokens {
 	}
 
 	/// Prepare token swap intention.
-	async fn prepare_token_swap<Source: CliChain, Target: CliChain>(
+	async fn prepare_token_swap<Source: CliChain, SourceAssetId, Target: CliChain, TargetAssetId>(
 		&self,
 		source_client: &Client<Source>,
-		source_sign: &Source::KeyPair,
-		target_sign: &Target::KeyPair,
+		source_sign: &Signer<Source::KeyPair>,
+		target_sign: &Signer<Target::KeyPair>,
 	) -> anyhow::Result<
 		bp_token_swap::TokenSwap<
 			BlockNumberOf<Source>,
+			SourceAssetId,
 			BalanceOf<Source>,
 			AccountIdOf<Source>,
+			TargetAssetId,
 			BalanceOf<Target>,
 			AccountIdOf<Target>,
 		>,
@@ -480,20 +589,28 @@ impl SwapTokens {
 		AccountIdOf<Target>: From<<Target::KeyPair as Pair>::Public>,
 		BalanceOf<Source>: From<u64>,
 		BalanceOf<Target>: From<u64>,
+		SourceAssetId: From<u32>,
+		TargetAssetId: From<u32>,
 	{
 		// accounts that are directly controlled by participants
 		let source_account_at_this_chain: AccountIdOf<Source> = source_sign.public().into();
 		let target_account_at_bridged_chain: AccountIdOf<Target> = target_sign.public().into();
 
-		// balances that we're going to swap
+		// assets and balances that we're going to swap
+		let source_asset_at_this_chain: Option<SourceAssetId> =
+			self.source_asset_id.map(Into::into);
+		let target_asset_at_bridged_chain: Option<TargetAssetId> =
+			self.target_asset_id.map(Into::into);
 		let source_balance_at_this_chain: BalanceOf<Source> = self.source_balance.cast().into();
 		let target_balance_at_bridged_chain: BalanceOf<Target> = self.target_balance.cast().into();
 
 		// prepare token swap intention
 		Ok(bp_token_swap::TokenSwap {
 			swap_type: self.prepare_token_swap_type(source_client).await?,
+			source_asset_at_this_chain,
 			source_balance_at_this_chain,
 			source_account_at_this_chain: source_account_at_this_chain.clone(),
+			target_asset_at_bridged_chain,
 			target_balance_at_bridged_chain,
 			target_account_at_bridged_chain: target_account_at_bridged_chain.clone(),
 		})
@@ -521,6 +638,113 @@ impl SwapTokens {
 	}
 }
 
+impl SwapsStatus {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_bridge!(self.bridge, {
+			let source_client = self.source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+
+			let state = self.state;
+			let swaps: Vec<TokenSwapDetailsOf<Source, SourceAssetId, Target, TargetAssetId>> =
+				match self.source_account {
+					Some(mut source_account) => {
+						source_account.enforce_chain::<Source>();
+						let source_account: AccountIdOf<Source> = source_account.raw_id();
+						read_token_swaps::<Source, SourceAssetId, Target, TargetAssetId>(
+							&source_client,
+							SWAPS_BY_SOURCE_ACCOUNT_METHOD,
+							source_account.encode(),
+						)
+						.await?
+						.into_iter()
+						.filter(|details| match state {
+							Some(state) =>
+								details.state == bp_token_swap::TokenSwapState::from(state),
+							None => true,
+						})
+						.collect()
+					},
+					None => {
+						let states = match state {
+							Some(state) => vec![state],
+							None => vec![
+								TokenSwapState::Started,
+								TokenSwapState::Confirmed,
+								TokenSwapState::Failed,
+							],
+						};
+
+						let mut swaps = Vec::new();
+						for state in states {
+							let state: bp_token_swap::TokenSwapState = state.into();
+							swaps.extend(
+								read_token_swaps::<Source, SourceAssetId, Target, TargetAssetId>(
+									&source_client,
+									SWAPS_BY_STATE_METHOD,
+									state.encode(),
+								)
+								.await?,
+							);
+						}
+						swaps
+					},
+				};
+
+			if swaps.is_empty() {
+				println!("There are no pending token swaps at {}", Source::NAME);
+			}
+			for details in swaps {
+				println!(
+					"Swap {:?}: {:?}, swapping {:?} (asset: {:?}) of {} account {} to {:?} \
+					(asset: {:?}) of {} account {}. Transfer message: {:?}",
+					details.hash,
+					details.state,
+					details.swap.source_balance_at_this_chain,
+					details.swap.source_asset_at_this_chain,
+					Source::NAME,
+					details.swap.source_account_at_this_chain,
+					details.swap.target_balance_at_bridged_chain,
+					details.swap.target_asset_at_bridged_chain,
+					Target::NAME,
+					details.swap.target_account_at_bridged_chain,
+					details.transfer_message_nonce,
+				);
+			}
+
+			Ok(())
+		})
+	}
+}
+
+/// Pending token swap details, returned by the source chain runtime.
+type TokenSwapDetailsOf<Source, SourceAssetId, Target, TargetAssetId> =
+	bp_token_swap::TokenSwapDetails<
+		BlockNumberOf<Source>,
+		SourceAssetId,
+		BalanceOf<Source>,
+		AccountIdOf<Source>,
+		TargetAssetId,
+		BalanceOf<Target>,
+		AccountIdOf<Target>,
+	>;
+
+/// Read pending token swaps using given runtime method.
+async fn read_token_swaps<
+	Source: Chain,
+	SourceAssetId: Decode,
+	Target: Chain,
+	TargetAssetId: Decode,
+>(
+	client: &Client<Source>,
+	method: &str,
+	arguments: Vec<u8>,
+) -> anyhow::Result<Vec<TokenSwapDetailsOf<Source, SourceAssetId, Target, TargetAssetId>>> {
+	let encoded_response = client.state_call(method.into(), Bytes(arguments), None).await?;
+	Decode::decode(&mut &encoded_response.0[..])
+		.map_err(relay_substrate_client::Error::ResponseParseFailed)
+		.map_err(Into::into)
+}
+
 /// Accounts that are participating in the swap.
 #[derive(Debug)]
 struct TokenSwapAccounts<ThisAccountId, BridgedAccountId> {
@@ -697,6 +921,7 @@ mod tests {
 	fn swap_tokens_millau_to_rialto_no_lock() {
 		let swap_tokens = SwapTokens::from_iter(vec![
 			"swap-tokens",
+			"start",
 			"millau-to-rialto",
 			"--source-host",
 			"127.0.0.1",
@@ -719,7 +944,7 @@ mod tests {
 
 		assert_eq!(
 			swap_tokens,
-			SwapTokens {
+			SwapTokens::Start(StartSwap {
 				bridge: SwapTokensBridge::MillauToRialto,
 				source: SourceConnectionParams {
 					source_host: "127.0.0.1".into(),
@@ -736,6 +961,10 @@ mod tests {
 					source_signer_password: None,
 					source_signer_file: None,
 					source_signer_password_file: None,
+					source_signer_env: None,
+					source_signer_keystore: None,
+					source_signer_public: None,
+					source_signer_url: None,
 					source_transactions_mortality: None,
 				},
 				target: TargetConnectionParams {
@@ -753,12 +982,19 @@ mod tests {
 					target_signer_password: None,
 					target_signer_file: None,
 					target_signer_password_file: None,
+					target_signer_env: None,
+					target_signer_keystore: None,
+					target_signer_public: None,
+					target_signer_url: None,
 					target_transactions_mortality: None,
 				},
 				swap_type: TokenSwapType::NoLock,
+				source_asset_id: None,
 				source_balance: Balance(8000000000),
+				target_asset_id: None,
 				target_balance: Balance(9000000000),
-			}
+				target_account_nonce: None,
+			})
 		);
 	}
 
@@ -766,6 +1002,7 @@ mod tests {
 	fn swap_tokens_millau_to_rialto_lock_until() {
 		let swap_tokens = SwapTokens::from_iter(vec![
 			"swap-tokens",
+			"start",
 			"millau-to-rialto",
 			"--source-host",
 			"127.0.0.1",
@@ -790,7 +1027,7 @@ mod tests {
 
 		assert_eq!(
 			swap_tokens,
-			SwapTokens {
+			SwapTokens::Start(StartSwap {
 				bridge: SwapTokensBridge::MillauToRialto,
 				source: SourceConnectionParams {
 					source_host: "127.0.0.1".into(),
@@ -807,6 +1044,10 @@ mod tests {
 					source_signer_password: None,
 					source_signer_file: None,
 					source_signer_password_file: None,
+					source_signer_env: None,
+					source_signer_keystore: None,
+					source_signer_public: None,
+					source_signer_url: None,
 					source_transactions_mortality: None,
 				},
 				target: TargetConnectionParams {
@@ -824,15 +1065,141 @@ mod tests {
 					target_signer_password: None,
 					target_signer_file: None,
 					target_signer_password_file: None,
+					target_signer_env: None,
+					target_signer_keystore: None,
+					target_signer_public: None,
+					target_signer_url: None,
 					target_transactions_mortality: None,
 				},
 				swap_type: TokenSwapType::LockUntilBlock {
 					blocks_before_expire: 1,
 					swap_nonce: None,
 				},
+				source_asset_id: None,
 				source_balance: Balance(8000000000),
+				target_asset_id: None,
 				target_balance: Balance(9000000000),
-			}
+				target_account_nonce: None,
+			})
+		);
+	}
+
+	#[test]
+	fn swap_tokens_millau_to_rialto_assets() {
+		let swap_tokens = SwapTokens::from_iter(vec![
+			"swap-tokens",
+			"start",
+			"millau-to-rialto",
+			"--source-host",
+			"127.0.0.1",
+			"--source-port",
+			"9000",
+			"--source-signer",
+			"//Alice",
+			"--source-asset-id",
+			"1",
+			"--source-balance",
+			"8000000000",
+			"--target-host",
+			"127.0.0.1",
+			"--target-port",
+			"9001",
+			"--target-signer",
+			"//Bob",
+			"--target-asset-id",
+			"2",
+			"--target-balance",
+			"9000000000",
+			"no-lock",
+		]);
+
+		assert_eq!(
+			swap_tokens,
+			SwapTokens::Start(StartSwap {
+				bridge: SwapTokensBridge::MillauToRialto,
+				source: SourceConnectionParams {
+					source_host: "127.0.0.1".into(),
+					source_port: 9000,
+					source_secure: false,
+					source_runtime_version: SourceRuntimeVersionParams {
+						source_version_mode: RuntimeVersionType::Bundle,
+						source_spec_version: None,
+						source_transaction_version: None,
+					}
+				},
+				source_sign: SourceSigningParams {
+					source_signer: Some("//Alice".into()),
+					source_signer_password: None,
+					source_signer_file: None,
+					source_signer_password_file: None,
+					source_signer_env: None,
+					source_signer_keystore: None,
+					source_signer_public: None,
+					source_signer_url: None,
+					source_transactions_mortality: None,
+				},
+				target: TargetConnectionParams {
+					target_host: "127.0.0.1".into(),
+					target_port: 9001,
+					target_secure: false,
+					target_runtime_version: TargetRuntimeVersionParams {
+						target_version_mode: RuntimeVersionType::Bundle,
+						target_spec_version: None,
+						target_transaction_version: None,
+					}
+				},
+				target_sign: TargetSigningParams {
+					target_signer: Some("//Bob".into()),
+					target_signer_password: None,
+					target_signer_file: None,
+					target_signer_password_file: None,
+					target_signer_env: None,
+					target_signer_keystore: None,
+					target_signer_public: None,
+					target_signer_url: None,
+					target_transactions_mortality: None,
+				},
+				swap_type: TokenSwapType::NoLock,
+				source_asset_id: Some(1),
+				source_balance: Balance(8000000000),
+				target_asset_id: Some(2),
+				target_balance: Balance(9000000000),
+				target_account_nonce: None,
+			})
+		);
+	}
+
+	#[test]
+	fn swap_tokens_status() {
+		let swap_tokens = SwapTokens::from_iter(vec![
+			"swap-tokens",
+			"status",
+			"millau-to-rialto",
+			"--source-host",
+			"127.0.0.1",
+			"--source-port",
+			"9000",
+			"--state",
+			"confirmed",
+		]);
+
+		assert_eq!(
+			swap_tokens,
+			SwapTokens::Status(SwapsStatus {
+				bridge: SwapTokensBridge::MillauToRialto,
+				source: SourceConnectionParams {
+					source_host: "127.0.0.1".into(),
+					source_port: 9000,
+					source_secure: false,
+					source_runtime_version: SourceRuntimeVersionParams {
+						source_version_mode: RuntimeVersionType::Bundle,
+						source_spec_version: None,
+						source_transaction_version: None,
+					}
+				},
+				source_account: None,
+				state: Some(TokenSwapState::Confirmed),
+			})
 		);
 	}
 }
@@ -0,0 +1,608 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Message tracing.
+//!
+//! Follows the message through all stages of its lifecycle: from being accepted by the source
+//! chain, to delivery confirmation. All stages are detected by looking at the bridge pallets
+//! storage. The block where given stage has happened is found using binary search over chain
+//! blocks, so nodes that we're connected to must keep state of these blocks (i.e. for old
+//! messages you'll need archive nodes).
+//!
+//! Once the delivery block is found, the command reads events of this block and reports events
+//! of the bridge dispatch pallet, related to the message. This is only supported for target
+//! chains whose runtime events may be decoded by the relay (see `MessageDispatchEvent`).
+
+use crate::{
+	cli::{bridge::FullBridge, HexLaneId, SourceConnectionParams, TargetConnectionParams},
+	select_full_bridge,
+};
+
+use bp_header_chain::storage_keys::best_finalized_hash_key;
+use bp_messages::{
+	storage_keys::{inbound_lane_data_key, outbound_lane_data_key},
+	InboundLaneData, LaneId, MessageNonce, OutboundLaneData,
+};
+use codec::Decode;
+use frame_system::EventRecord;
+use relay_substrate_client::{
+	AccountIdOf, BlockNumberOf, Chain, ChainWithGrandpa, ChainWithMessages, Client, HashOf,
+};
+use sp_core::storage::StorageKey;
+use sp_runtime::{
+	traits::{Header as HeaderT, UniqueSaturatedFrom, UniqueSaturatedInto},
+	DispatchResult,
+};
+use std::{fmt, future::Future};
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Trace message, sent over the bridge.
+#[derive(StructOpt)]
+pub struct TraceMessage {
+	/// A bridge instance the message has been sent over.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded id of lane the message has been sent to.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+	/// Nonce of the message.
+	#[structopt(long)]
+	nonce: MessageNonce,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+}
+
+impl TraceMessage {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		let Self { bridge, lane, nonce, source, target } = self;
+
+		select_full_bridge!(bridge, {
+			let source_client = source.to_client::<Source>(SOURCE_RUNTIME_VERSION).await?;
+			let target_client = target.to_client::<Target>(TARGET_RUNTIME_VERSION).await?;
+
+			let trace = trace_message::<Source, Target, TargetDispatchEvent>(
+				&source_client,
+				&target_client,
+				lane.into(),
+				nonce,
+			)
+			.await?;
+			println!("{}", trace);
+			Ok(())
+		})
+	}
+}
+
+/// Target chain runtime event, that may be emitted by the bridge dispatch pallet.
+pub trait MessageDispatchEvent: Decode + Send + 'static {
+	/// Returns false if the relay can't decode runtime events of the target chain.
+	const IS_DECODABLE: bool = true;
+
+	/// If this is the dispatch pallet event of the message with given id, returns its
+	/// description.
+	fn message_dispatch_event(&self, message_id: &(LaneId, MessageNonce)) -> Option<String>;
+}
+
+impl MessageDispatchEvent for () {
+	const IS_DECODABLE: bool = false;
+
+	fn message_dispatch_event(&self, _message_id: &(LaneId, MessageNonce)) -> Option<String> {
+		None
+	}
+}
+
+/// Returns description of the bridge dispatch pallet event, if this event is related to the
+/// message with given id.
+pub fn describe_dispatch_event<T, I>(
+	event: &pallet_bridge_dispatch::Event<T, I>,
+	message_id: &(LaneId, MessageNonce),
+) -> Option<String>
+where
+	T: pallet_bridge_dispatch::Config<I, BridgeMessageId = (LaneId, MessageNonce)>,
+	I: 'static,
+{
+	use pallet_bridge_dispatch::Event;
+
+	let (id, description) = match *event {
+		Event::MessageRejected(_, ref id) => (id, "MessageRejected".into()),
+		Event::MessageVersionSpecMismatch(_, ref id, expected, passed) => (
+			id,
+			format!(
+				"MessageVersionSpecMismatch (expected spec version: {}, passed: {})",
+				expected, passed,
+			),
+		),
+		Event::MessageWeightMismatch(_, ref id, expected, passed) => (
+			id,
+			format!("MessageWeightMismatch (expected weight: {}, passed: {})", expected, passed),
+		),
+		Event::MessageSignatureMismatch(_, ref id) => (id, "MessageSignatureMismatch".into()),
+		Event::MessageNonceAlreadyUsed(_, ref id, nonce) =>
+			(id, format!("MessageNonceAlreadyUsed (target account nonce: {})", nonce)),
+		Event::MessageCallDecodeFailed(_, ref id) => (id, "MessageCallDecodeFailed".into()),
+		Event::MessageCallRejected(_, ref id) => (id, "MessageCallRejected".into()),
+		Event::MessageDispatchPaymentFailed(_, ref id, _, weight) =>
+			(id, format!("MessageDispatchPaymentFailed (dispatch weight: {})", weight)),
+		Event::MessageDispatched(_, ref id, ref result) =>
+			(id, format!("MessageDispatched (result: {})", format_dispatch_result(result))),
+		Event::_Dummy(_) => return None,
+	};
+
+	if id == message_id {
+		Some(description)
+	} else {
+		None
+	}
+}
+
+/// Format dispatch result of the message.
+fn format_dispatch_result(result: &DispatchResult) -> String {
+	match *result {
+		Ok(()) => "ok".into(),
+		Err(ref error) => format!("{:?}", error),
+	}
+}
+
+/// Block where some stage of message lifecycle has happened.
+#[derive(Debug, Clone, PartialEq)]
+struct BlockInfo {
+	/// Block number.
+	number: u64,
+	/// Formatted block hash.
+	hash: String,
+}
+
+impl fmt::Display for BlockInfo {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		write!(fmt, "#{} ({})", self.number, self.hash)
+	}
+}
+
+/// Collected information about the message.
+#[derive(Debug)]
+struct MessageTrace {
+	/// Name of the source chain.
+	source_chain: &'static str,
+	/// Name of the target chain.
+	target_chain: &'static str,
+	/// Lane the message has been sent to.
+	lane: LaneId,
+	/// Message nonce.
+	nonce: MessageNonce,
+	/// Source block where the message has been accepted by the messages pallet
+	/// (`MessageAccepted` event).
+	accepted: Option<BlockInfo>,
+	/// Number of the best source header, known to the target chain.
+	bridged_best_finalized: Option<u64>,
+	/// Target block where the message has been delivered and dispatched.
+	delivered: Option<BlockInfo>,
+	/// Relayer that has delivered the message.
+	relayer: Option<String>,
+	/// Dispatch result of the message.
+	dispatch_result: Option<bool>,
+	/// Events of the bridge dispatch pallet, related to the message. `None` if target chain
+	/// events can't be decoded.
+	dispatch_events: Option<Vec<String>>,
+	/// Source block where the message delivery has been confirmed and relayer rewards have been
+	/// paid.
+	confirmed: Option<BlockInfo>,
+}
+
+impl MessageTrace {
+	/// Returns true if the source header with the message is finalized at the target chain.
+	fn is_finalized_at_target(&self) -> bool {
+		match (self.accepted.as_ref(), self.bridged_best_finalized) {
+			(Some(accepted), Some(bridged_best_finalized)) =>
+				accepted.number <= bridged_best_finalized,
+			_ => false,
+		}
+	}
+}
+
+impl fmt::Display for MessageTrace {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(
+			fmt,
+			"Message {} at lane {} ({} -> {}):",
+			self.nonce,
+			hex::encode(self.lane),
+			self.source_chain,
+			self.target_chain,
+		)?;
+
+		let accepted = match self.accepted {
+			Some(ref accepted) => accepted,
+			None =>
+				return write!(fmt, "  accepted:   no, message has not been sent to the lane yet"),
+		};
+		writeln!(fmt, "  accepted:   {} block {}", self.source_chain, accepted)?;
+
+		let bridged_best_finalized = self
+			.bridged_best_finalized
+			.map(|number| format!("#{}", number))
+			.unwrap_or_else(|| "unknown".into());
+		writeln!(
+			fmt,
+			"  finalized:  {} (best {} header, known to {}: {})",
+			if self.is_finalized_at_target() { "yes" } else { "no" },
+			self.source_chain,
+			self.target_chain,
+			bridged_best_finalized,
+		)?;
+
+		let delivered = match self.delivered {
+			Some(ref delivered) => delivered,
+			None => return write!(fmt, "  delivered:  no"),
+		};
+		let relayer = self.relayer.as_deref().unwrap_or("unknown relayer");
+		writeln!(fmt, "  delivered:  {} block {} by {}", self.target_chain, delivered, relayer)?;
+		writeln!(
+			fmt,
+			"  dispatched: {} block {}, message id: ({}, {}), dispatch result: {}",
+			self.target_chain,
+			delivered,
+			hex::encode(self.lane),
+			self.nonce,
+			match self.dispatch_result {
+				Some(true) => "ok",
+				Some(false) => "failed",
+				None => "unknown",
+			},
+		)?;
+		match self.dispatch_events {
+			Some(ref dispatch_events) if dispatch_events.is_empty() =>
+				writeln!(fmt, "  events:     no dispatch events found")?,
+			Some(ref dispatch_events) =>
+				for dispatch_event in dispatch_events {
+					writeln!(fmt, "  event:      {}", dispatch_event)?;
+				},
+			None => writeln!(
+				fmt,
+				"  events:     unknown, {} events can't be decoded",
+				self.target_chain
+			)?,
+		}
+
+		match self.confirmed {
+			Some(ref confirmed) => write!(
+				fmt,
+				"  confirmed:  {} block {}, rewards paid to {}",
+				self.source_chain, confirmed, relayer,
+			),
+			None => write!(fmt, "  confirmed:  no, rewards are not paid yet"),
+		}
+	}
+}
+
+/// Collect information about the message.
+async fn trace_message<SC, TC, TE>(
+	source_client: &Client<SC>,
+	target_client: &Client<TC>,
+	lane: LaneId,
+	nonce: MessageNonce,
+) -> anyhow::Result<MessageTrace>
+where
+	SC: ChainWithGrandpa + ChainWithMessages,
+	TC: ChainWithMessages,
+	TE: MessageDispatchEvent,
+{
+	let mut trace = MessageTrace {
+		source_chain: SC::NAME,
+		target_chain: TC::NAME,
+		lane,
+		nonce,
+		accepted: None,
+		bridged_best_finalized: None,
+		delivered: None,
+		relayer: None,
+		dispatch_result: None,
+		dispatch_events: None,
+		confirmed: None,
+	};
+
+	let outbound_lane_data_key = outbound_lane_data_key(TC::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane);
+	trace.accepted = find_first_block(
+		source_client,
+		outbound_lane_data_key.clone(),
+		|data: &OutboundLaneData| data.latest_generated_nonce >= nonce,
+	)
+	.await?;
+	if trace.accepted.is_none() {
+		return Ok(trace)
+	}
+
+	let bridged_best_finalized_hash = target_client
+		.storage_value::<HashOf<SC>>(
+			best_finalized_hash_key(SC::WITH_CHAIN_GRANDPA_PALLET_NAME),
+			None,
+		)
+		.await?;
+	if let Some(bridged_best_finalized_hash) = bridged_best_finalized_hash {
+		let bridged_best_finalized_header =
+			source_client.header_by_hash(bridged_best_finalized_hash).await?;
+		trace.bridged_best_finalized =
+			Some((*bridged_best_finalized_header.number()).unique_saturated_into());
+	}
+
+	let inbound_lane_data_key = inbound_lane_data_key(SC::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane);
+	trace.delivered = find_first_block(
+		target_client,
+		inbound_lane_data_key.clone(),
+		|data: &InboundLaneData<AccountIdOf<SC>>| data.last_delivered_nonce() >= nonce,
+	)
+	.await?;
+	let delivered_at = match trace.delivered {
+		Some(ref delivered) => delivered.number,
+		None => return Ok(trace),
+	};
+
+	let delivered_at_hash = target_client
+		.block_hash_by_number(BlockNumberOf::<TC>::unique_saturated_from(delivered_at))
+		.await?;
+	let inbound_lane_data = target_client
+		.storage_value::<InboundLaneData<AccountIdOf<SC>>>(
+			inbound_lane_data_key,
+			Some(delivered_at_hash),
+		)
+		.await?
+		.unwrap_or_default();
+	if let Some((relayer, dispatch_result)) = find_delivering_relayer(&inbound_lane_data, nonce) {
+		trace.relayer = Some(relayer.to_string());
+		trace.dispatch_result = Some(dispatch_result);
+	}
+	if TE::IS_DECODABLE {
+		let events = target_client
+			.storage_value::<Vec<EventRecord<TE, HashOf<TC>>>>(
+				StorageKey(frame_support::storage::storage_prefix(b"System", b"Events").to_vec()),
+				Some(delivered_at_hash),
+			)
+			.await?
+			.unwrap_or_default();
+		trace.dispatch_events = Some(find_dispatch_events(&events, lane, nonce));
+	}
+
+	trace.confirmed =
+		find_first_block(source_client, outbound_lane_data_key, |data: &OutboundLaneData| {
+			data.latest_received_nonce >= nonce
+		})
+		.await?;
+
+	Ok(trace)
+}
+
+/// Find the relayer that has delivered the message and the message dispatch result.
+fn find_delivering_relayer<RelayerId>(
+	inbound_lane_data: &InboundLaneData<RelayerId>,
+	nonce: MessageNonce,
+) -> Option<(&RelayerId, bool)> {
+	inbound_lane_data
+		.relayers
+		.iter()
+		.find(|entry| entry.messages.contains_message(nonce))
+		.map(|entry| (&entry.relayer, entry.messages.message_dispatch_result(nonce)))
+}
+
+/// Find events of the bridge dispatch pallet, related to the message.
+fn find_dispatch_events<E: MessageDispatchEvent, H>(
+	events: &[EventRecord<E, H>],
+	lane: LaneId,
+	nonce: MessageNonce,
+) -> Vec<String> {
+	let message_id = (lane, nonce);
+	events
+		.iter()
+		.filter_map(|record| record.event.message_dispatch_event(&message_id))
+		.collect()
+}
+
+/// Find the first block where the value, stored under given key, matches the predicate.
+///
+/// The predicate must be monotonic: once it is true for some block, it must be true for all
+/// descendants of this block.
+async fn find_first_block<C: Chain, T: Decode + Send + 'static>(
+	client: &Client<C>,
+	storage_key: StorageKey,
+	predicate: impl Fn(&T) -> bool,
+) -> anyhow::Result<Option<BlockInfo>> {
+	let best_block: u64 = (*client.best_header().await?.number()).unique_saturated_into();
+	let storage_key = &storage_key;
+	let predicate = &predicate;
+	let block_number = binary_search_first(best_block, move |number| async move {
+		let block_hash = client
+			.block_hash_by_number(BlockNumberOf::<C>::unique_saturated_from(number))
+			.await?;
+		Ok::<_, anyhow::Error>(
+			client
+				.storage_value::<T>(storage_key.clone(), Some(block_hash))
+				.await?
+				.map(|value| predicate(&value))
+				.unwrap_or(false),
+		)
+	})
+	.await?;
+
+	match block_number {
+		Some(number) => {
+			let hash = client
+				.block_hash_by_number(BlockNumberOf::<C>::unique_saturated_from(number))
+				.await?;
+			Ok(Some(BlockInfo { number, hash: format!("{:?}", hash) }))
+		},
+		None => Ok(None),
+	}
+}
+
+/// Find the first number in `0..=end` range, that matches the monotonic predicate.
+async fn binary_search_first<F, Fut>(end: u64, predicate: F) -> anyhow::Result<Option<u64>>
+where
+	F: Fn(u64) -> Fut,
+	Fut: Future<Output = anyhow::Result<bool>>,
+{
+	if !predicate(end).await? {
+		return Ok(None)
+	}
+
+	let (mut low, mut high) = (0, end);
+	while low < high {
+		let middle = low + (high - low) / 2;
+		if predicate(middle).await? {
+			high = middle;
+		} else {
+			low = middle + 1;
+		}
+	}
+
+	Ok(Some(high))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_messages::{DeliveredMessages, UnrewardedRelayer};
+
+	fn block(number: u64) -> BlockInfo {
+		BlockInfo { number, hash: format!("0x{:02x}", number) }
+	}
+
+	fn trace() -> MessageTrace {
+		MessageTrace {
+			source_chain: "Millau",
+			target_chain: "Rialto",
+			lane: [0, 0, 0, 0],
+			nonce: 42,
+			accepted: Some(block(10)),
+			bridged_best_finalized: Some(12),
+			delivered: Some(block(20)),
+			relayer: Some("Relayer".into()),
+			dispatch_result: Some(true),
+			dispatch_events: Some(vec!["MessageDispatched (result: ok)".into()]),
+			confirmed: Some(block(30)),
+		}
+	}
+
+	#[test]
+	fn binary_search_first_works() {
+		for first in 0..=10 {
+			assert_eq!(
+				async_std::task::block_on(binary_search_first(10, |number| async move {
+					Ok(number >= first)
+				}))
+				.unwrap(),
+				Some(first),
+			);
+		}
+		assert_eq!(
+			async_std::task::block_on(binary_search_first(10, |_| async move { Ok(false) }))
+				.unwrap(),
+			None,
+		);
+	}
+
+	#[test]
+	fn find_delivering_relayer_works() {
+		let mut messages = DeliveredMessages::new(1, true);
+		messages.note_dispatched_message(false);
+		let inbound_lane_data = InboundLaneData {
+			relayers: vec![
+				UnrewardedRelayer { relayer: 1, messages },
+				UnrewardedRelayer { relayer: 2, messages: DeliveredMessages::new(3, true) },
+			]
+			.into_iter()
+			.collect(),
+			last_confirmed_nonce: 0,
+		};
+
+		assert_eq!(find_delivering_relayer(&inbound_lane_data, 1), Some((&1, true)));
+		assert_eq!(find_delivering_relayer(&inbound_lane_data, 2), Some((&1, false)));
+		assert_eq!(find_delivering_relayer(&inbound_lane_data, 3), Some((&2, true)));
+		assert_eq!(find_delivering_relayer(&inbound_lane_data, 4), None);
+	}
+
+	#[test]
+	fn find_dispatch_events_works() {
+		type DispatchEvent = pallet_bridge_dispatch::Event<rialto_runtime::Runtime>;
+
+		let record = |event| EventRecord {
+			phase: frame_system::Phase::ApplyExtrinsic(1),
+			event,
+			topics: Vec::<sp_core::H256>::new(),
+		};
+		let events = vec![
+			record(rialto_runtime::Event::BridgeDispatch(DispatchEvent::MessageDispatched(
+				bp_runtime::MILLAU_CHAIN_ID,
+				([0, 0, 0, 0], 41),
+				Ok(()),
+			))),
+			record(rialto_runtime::Event::BridgeDispatch(DispatchEvent::MessageCallRejected(
+				bp_runtime::MILLAU_CHAIN_ID,
+				([0, 0, 0, 0], 42),
+			))),
+			record(rialto_runtime::Event::BridgeDispatch(DispatchEvent::MessageDispatched(
+				bp_runtime::MILLAU_CHAIN_ID,
+				([0, 0, 0, 1], 42),
+				Ok(()),
+			))),
+		];
+
+		assert_eq!(
+			find_dispatch_events(&events, [0, 0, 0, 0], 42),
+			vec!["MessageCallRejected".to_string()],
+		);
+		assert_eq!(
+			find_dispatch_events(&events, [0, 0, 0, 0], 41),
+			vec!["MessageDispatched (result: ok)".to_string()],
+		);
+		assert!(find_dispatch_events(&events, [0, 0, 0, 0], 43).is_empty());
+	}
+
+	#[test]
+	fn confirmed_message_trace_is_displayed() {
+		assert_eq!(
+			trace().to_string(),
+			"Message 42 at lane 00000000 (Millau -> Rialto):\n\
+			\x20 accepted:   Millau block #10 (0x0a)\n\
+			\x20 finalized:  yes (best Millau header, known to Rialto: #12)\n\
+			\x20 delivered:  Rialto block #20 (0x14) by Relayer\n\
+			\x20 dispatched: Rialto block #20 (0x14), message id: (00000000, 42), dispatch result: ok\n\
+			\x20 event:      MessageDispatched (result: ok)\n\
+			\x20 confirmed:  Millau block #30 (0x1e), rewards paid to Relayer",
+		);
+	}
+
+	#[test]
+	fn undelivered_message_trace_is_displayed() {
+		let trace = MessageTrace {
+			bridged_best_finalized: Some(8),
+			delivered: None,
+			relayer: None,
+			dispatch_result: None,
+			dispatch_events: None,
+			confirmed: None,
+			..trace()
+		};
+		assert_eq!(
+			trace.to_string(),
+			"Message 42 at lane 00000000 (Millau -> Rialto):\n\
+			\x20 accepted:   Millau block #10 (0x0a)\n\
+			\x20 finalized:  no (best Millau header, known to Rialto: #8)\n\
+			\x20 delivered:  no",
+		);
+	}
+}
@@ -51,6 +51,31 @@ where
 	)
 }
 
+/// Polkadot account ownership digest from Kusama, protected from replay.
+///
+/// The byte vector returned by this function should be signed with a Polkadot account private key.
+/// Every `nonce` may only be used once by the Polkadot account.
+pub fn kusama_to_polkadot_account_ownership_digest_with_nonce<Call, AccountId, SpecVersion>(
+	polkadot_call: &Call,
+	kusama_account_id: AccountId,
+	nonce: bp_message_dispatch::TargetAccountNonce,
+	polkadot_spec_version: SpecVersion,
+) -> Vec<u8>
+where
+	Call: codec::Encode,
+	AccountId: codec::Encode,
+	SpecVersion: codec::Encode,
+{
+	pallet_bridge_dispatch::account_ownership_digest_with_nonce(
+		polkadot_call,
+		kusama_account_id,
+		nonce,
+		polkadot_spec_version,
+		bp_runtime::KUSAMA_CHAIN_ID,
+		bp_runtime::POLKADOT_CHAIN_ID,
+	)
+}
+
 /// Kusama Runtime `Call` enum.
 ///
 /// The enum represents a subset of possible `Call`s we can send to Kusama chain.
@@ -20,10 +20,11 @@ use bp_messages::MessageNonce;
 use codec::{Compact, Decode, Encode};
 use frame_support::weights::Weight;
 use relay_substrate_client::{
-	BalanceOf, Chain, ChainBase, ChainWithBalances, ChainWithGrandpa, ChainWithMessages, IndexOf,
-	SignParam, TransactionSignScheme, UnsignedTransaction,
+	BalanceOf, Chain, ChainBase, ChainWithBalances, ChainWithGrandpa, ChainWithMessages,
+	Error as SubstrateError, IndexOf, SignParam, Signer, TransactionSignScheme,
+	UnsignedTransaction,
 };
-use sp_core::{storage::StorageKey, Pair};
+use sp_core::storage::StorageKey;
 use sp_runtime::{generic::SignedPayload, traits::IdentifyAccount};
 use std::time::Duration;
 
@@ -103,7 +104,7 @@ impl TransactionSignScheme for Millau {
 	type AccountKeyPair = sp_core::sr25519::Pair;
 	type SignedTransaction = millau_runtime::UncheckedExtrinsic;
 
-	fn sign_transaction(param: SignParam<Self>) -> Self::SignedTransaction {
+	fn sign_transaction(param: SignParam<Self>) -> Result<Self::SignedTransaction, SubstrateError> {
 		let raw_payload = SignedPayload::from_raw(
 			param.unsigned.call.clone(),
 			(
@@ -114,6 +115,7 @@ impl TransactionSignScheme for Millau {
 				frame_system::CheckNonce::<millau_runtime::Runtime>::from(param.unsigned.nonce),
 				frame_system::CheckWeight::<millau_runtime::Runtime>::new(),
 				pallet_transaction_payment::ChargeTransactionPayment::<millau_runtime::Runtime>::from(param.unsigned.tip),
+				millau_runtime::BridgeRejectObsoleteMessages,
 			),
 			(
 				param.spec_version,
@@ -123,25 +125,26 @@ impl TransactionSignScheme for Millau {
 				(),
 				(),
 				(),
+				(),
 			),
 		);
-		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
+		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload))?;
 		let signer: sp_runtime::MultiSigner = param.signer.public().into();
 		let (call, extra, _) = raw_payload.deconstruct();
 
-		millau_runtime::UncheckedExtrinsic::new_signed(
+		Ok(millau_runtime::UncheckedExtrinsic::new_signed(
 			call,
 			signer.into_account(),
 			signature.into(),
 			extra,
-		)
+		))
 	}
 
 	fn is_signed(tx: &Self::SignedTransaction) -> bool {
 		tx.signature.is_some()
 	}
 
-	fn is_signed_by(signer: &Self::AccountKeyPair, tx: &Self::SignedTransaction) -> bool {
+	fn is_signed_by(signer: &Signer<Self::AccountKeyPair>, tx: &Self::SignedTransaction) -> bool {
 		tx.signature
 			.as_ref()
 			.map(|(address, _, _)| {
@@ -167,3 +170,37 @@ pub type SigningParams = sp_core::sr25519::Pair;
 
 /// Millau header type used in headers sync.
 pub type SyncHeader = relay_substrate_client::SyncHeader<millau_runtime::Header>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use relay_substrate_client::{bridge_call_indices, encoded_call_indices, BridgeCall};
+
+	#[test]
+	fn bridge_call_indices_are_read_from_runtime_metadata() {
+		const REMARK: BridgeCall = BridgeCall { pallet: "System", call: "remark" };
+		const SET_OPERATIONAL: BridgeCall =
+			BridgeCall { pallet: "BridgeRialtoGrandpa", call: "set_operational" };
+		const MISSING_CALL: BridgeCall =
+			BridgeCall { pallet: "BridgeRialtoGrandpa", call: "missing_call" };
+
+		let raw_metadata = millau_runtime::Runtime::metadata().encode();
+		let indices =
+			bridge_call_indices(&raw_metadata, vec![REMARK, SET_OPERATIONAL, MISSING_CALL])
+				.unwrap()
+				.expect("Millau runtime is using V14 metadata");
+
+		let remark_call: millau_runtime::Call =
+			frame_system::Call::<millau_runtime::Runtime>::remark { remark: vec![] }.into();
+		let set_operational_call: millau_runtime::Call = millau_runtime::BridgeGrandpaCall::<
+			millau_runtime::Runtime,
+			millau_runtime::RialtoGrandpaInstance,
+		>::set_operational {
+			operational: true,
+		}
+		.into();
+		assert_eq!(indices[&REMARK], Some(encoded_call_indices(&remark_call)));
+		assert_eq!(indices[&SET_OPERATIONAL], Some(encoded_call_indices(&set_operational_call)));
+		assert_eq!(indices[&MISSING_CALL], None);
+	}
+}
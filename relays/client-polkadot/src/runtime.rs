@@ -51,6 +51,31 @@ where
 	)
 }
 
+/// Kusama account ownership digest from Polkadot, protected from replay.
+///
+/// The byte vector returned by this function should be signed with a Kusama account private key.
+/// Every `nonce` may only be used once by the Kusama account.
+pub fn polkadot_to_kusama_account_ownership_digest_with_nonce<Call, AccountId, SpecVersion>(
+	kusama_call: &Call,
+	polkadot_account_id: AccountId,
+	nonce: bp_message_dispatch::TargetAccountNonce,
+	kusama_spec_version: SpecVersion,
+) -> Vec<u8>
+where
+	Call: codec::Encode,
+	AccountId: codec::Encode,
+	SpecVersion: codec::Encode,
+{
+	pallet_bridge_dispatch::account_ownership_digest_with_nonce(
+		kusama_call,
+		polkadot_account_id,
+		nonce,
+		kusama_spec_version,
+		bp_runtime::POLKADOT_CHAIN_ID,
+		bp_runtime::KUSAMA_CHAIN_ID,
+	)
+}
+
 /// Polkadot Runtime `Call` enum.
 ///
 /// The enum represents a subset of possible `Call`s we can send to Polkadot chain.
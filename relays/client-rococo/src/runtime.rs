@@ -50,6 +50,31 @@ where
 	)
 }
 
+/// Wococo account ownership digest from Rococo, protected from replay.
+///
+/// The byte vector returned by this function should be signed with a Wococo account private key.
+/// Every `nonce` may only be used once by the Wococo account.
+pub fn rococo_to_wococo_account_ownership_digest_with_nonce<Call, AccountId, SpecVersion>(
+	wococo_call: &Call,
+	rococo_account_id: AccountId,
+	nonce: bp_message_dispatch::TargetAccountNonce,
+	wococo_spec_version: SpecVersion,
+) -> Vec<u8>
+where
+	Call: codec::Encode,
+	AccountId: codec::Encode,
+	SpecVersion: codec::Encode,
+{
+	pallet_bridge_dispatch::account_ownership_digest_with_nonce(
+		wococo_call,
+		rococo_account_id,
+		nonce,
+		wococo_spec_version,
+		bp_runtime::ROCOCO_CHAIN_ID,
+		bp_runtime::WOCOCO_CHAIN_ID,
+	)
+}
+
 /// Rococo Runtime `Call` enum.
 ///
 /// The enum represents a subset of possible `Call`s we can send to Rococo chain.
@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::{Error as SubstrateError, Signer};
+
 use bp_messages::MessageNonce;
 use bp_runtime::{Chain as ChainBase, HashOf, TransactionEraOf};
 use codec::{Codec, Encode};
@@ -174,7 +176,9 @@ pub trait TransactionSignScheme {
 	type SignedTransaction: Clone + Debug + Codec + Send + 'static;
 
 	/// Create transaction for given runtime call, signed by given account.
-	fn sign_transaction(param: SignParam<Self>) -> Self::SignedTransaction
+	///
+	/// Fails if the signer has failed to sign the transaction.
+	fn sign_transaction(param: SignParam<Self>) -> Result<Self::SignedTransaction, SubstrateError>
 	where
 		Self: Sized;
 
@@ -182,7 +186,7 @@ pub trait TransactionSignScheme {
 	fn is_signed(tx: &Self::SignedTransaction) -> bool;
 
 	/// Returns true if transaction is signed by given signer.
-	fn is_signed_by(signer: &Self::AccountKeyPair, tx: &Self::SignedTransaction) -> bool;
+	fn is_signed_by(signer: &Signer<Self::AccountKeyPair>, tx: &Self::SignedTransaction) -> bool;
 
 	/// Parse signed transaction into its unsigned part.
 	///
@@ -199,7 +203,7 @@ pub struct SignParam<T: TransactionSignScheme> {
 	/// Hash of the genesis block.
 	pub genesis_hash: <T::Chain as ChainBase>::Hash,
 	/// Signer account
-	pub signer: T::AccountKeyPair,
+	pub signer: Signer<T::AccountKeyPair>,
 	/// Transaction era used by the chain.
 	pub era: TransactionEraOf<T::Chain>,
 	/// Transaction before it is signed.
@@ -19,6 +19,7 @@
 use crate::{
 	chain::{Chain, ChainWithBalances, TransactionStatusOf},
 	rpc::Substrate,
+	runtime_upgrade::{bridge_call_indices, BridgeCall, CallIndices, RuntimeUpgradeState},
 	ConnectionParams, Error, HashOf, HeaderIdOf, Result,
 };
 
@@ -51,6 +52,7 @@ use sp_version::RuntimeVersion;
 use std::{convert::TryFrom, future::Future};
 
 const SUB_API_GRANDPA_AUTHORITIES: &str = "GrandpaApi_grandpa_authorities";
+const SUB_API_GRANDPA_CURRENT_SET_ID: &str = "GrandpaApi_current_set_id";
 const SUB_API_TXPOOL_VALIDATE_TRANSACTION: &str = "TaggedTransactionQueue_validate_transaction";
 const MAX_SUBSCRIPTION_CAPACITY: usize = 4096;
 
@@ -88,8 +90,10 @@ pub struct Client<C: Chain> {
 	/// transactions will be rejected from the pool. This lock is here to prevent situations like
 	/// that.
 	submit_signed_extrinsic_lock: Arc<Mutex<()>>,
-	/// Saved chain runtime version
-	chain_runtime_version: ChainRuntimeVersion,
+	/// Saved chain runtime version. It is updated when runtime upgrade is detected.
+	chain_runtime_version: Arc<Mutex<ChainRuntimeVersion>>,
+	/// Runtime upgrades tracking state.
+	runtime_upgrade: Arc<Mutex<RuntimeUpgradeState>>,
 }
 
 #[async_trait]
@@ -113,6 +117,7 @@ impl<C: Chain> Clone for Client<C> {
 			genesis_hash: self.genesis_hash,
 			submit_signed_extrinsic_lock: self.submit_signed_extrinsic_lock.clone(),
 			chain_runtime_version: self.chain_runtime_version.clone(),
+			runtime_upgrade: self.runtime_upgrade.clone(),
 		}
 	}
 }
@@ -165,7 +170,8 @@ impl<C: Chain> Client<C> {
 			client,
 			genesis_hash,
 			submit_signed_extrinsic_lock: Arc::new(Mutex::new(())),
-			chain_runtime_version,
+			chain_runtime_version: Arc::new(Mutex::new(chain_runtime_version)),
+			runtime_upgrade: Arc::new(Mutex::new(RuntimeUpgradeState::default())),
 		})
 	}
 
@@ -196,7 +202,8 @@ impl<C: Chain> Client<C> {
 impl<C: Chain> Client<C> {
 	/// Return simple runtime version, only include `spec_version` and `transaction_version`.
 	pub async fn simple_runtime_version(&self) -> Result<(u32, u32)> {
-		let (spec_version, transaction_version) = match self.chain_runtime_version {
+		let chain_runtime_version = self.chain_runtime_version.lock().await.clone();
+		let (spec_version, transaction_version) = match chain_runtime_version {
 			ChainRuntimeVersion::Auto => {
 				let runtime_version = self.runtime_version().await?;
 				(runtime_version.spec_version, runtime_version.transaction_version)
@@ -369,11 +376,13 @@ impl<C: Chain> Client<C> {
 	/// `submit_signed_extrinsic()` call. This guarantees that no nonces collision may happen
 	/// if all client instances are clones of the same initial `Client`.
 	///
-	/// Note: The given transaction needs to be SCALE encoded beforehand.
+	/// Note: The given transaction needs to be SCALE encoded beforehand. Signing may block
+	/// (e.g. when remote signer is used), so `prepare_extrinsic` is called from the thread,
+	/// dedicated to blocking tasks.
 	pub async fn submit_signed_extrinsic(
 		&self,
 		extrinsic_signer: C::AccountId,
-		prepare_extrinsic: impl FnOnce(HeaderIdOf<C>, C::Index) -> Bytes + Send + 'static,
+		prepare_extrinsic: impl FnOnce(HeaderIdOf<C>, C::Index) -> Result<Bytes> + Send + 'static,
 	) -> Result<C::Hash> {
 		let _guard = self.submit_signed_extrinsic_lock.lock().await;
 		let transaction_nonce = self.next_account_index(extrinsic_signer).await?;
@@ -390,7 +399,10 @@ impl<C: Chain> Client<C> {
 		};
 
 		self.jsonrpsee_execute(move |client| async move {
-			let extrinsic = prepare_extrinsic(best_header_id, transaction_nonce);
+			let extrinsic = tokio::task::spawn_blocking(move || {
+				prepare_extrinsic(best_header_id, transaction_nonce)
+			})
+			.await??;
 			let tx_hash = Substrate::<C>::author_submit_extrinsic(&*client, extrinsic).await?;
 			log::trace!(target: "bridge", "Sent transaction to {} node: {:?}", C::NAME, tx_hash);
 			Ok(tx_hash)
@@ -403,7 +415,7 @@ impl<C: Chain> Client<C> {
 	pub async fn submit_and_watch_signed_extrinsic(
 		&self,
 		extrinsic_signer: C::AccountId,
-		prepare_extrinsic: impl FnOnce(HeaderIdOf<C>, C::Index) -> Bytes + Send + 'static,
+		prepare_extrinsic: impl FnOnce(HeaderIdOf<C>, C::Index) -> Result<Bytes> + Send + 'static,
 	) -> Result<Subscription<TransactionStatusOf<C>>> {
 		let _guard = self.submit_signed_extrinsic_lock.lock().await;
 		let transaction_nonce = self.next_account_index(extrinsic_signer).await?;
@@ -411,7 +423,10 @@ impl<C: Chain> Client<C> {
 		let best_header_id = HeaderId(*best_header.number(), best_header.hash());
 		let subscription = self
 			.jsonrpsee_execute(move |client| async move {
-				let extrinsic = prepare_extrinsic(best_header_id, transaction_nonce);
+				let extrinsic = tokio::task::spawn_blocking(move || {
+					prepare_extrinsic(best_header_id, transaction_nonce)
+				})
+				.await??;
 				let tx_hash = C::Hasher::hash(&extrinsic.0);
 				let subscription = client
 					.subscribe(
@@ -511,6 +526,21 @@ impl<C: Chain> Client<C> {
 		.await
 	}
 
+	/// Get the id of the current GRANDPA authority set at given block.
+	pub async fn grandpa_current_set_id(&self, block: Option<C::Hash>) -> Result<u64> {
+		self.jsonrpsee_execute(move |client| async move {
+			let call = SUB_API_GRANDPA_CURRENT_SET_ID.to_string();
+			let data = Bytes(Vec::new());
+
+			let encoded_response = Substrate::<C>::state_call(&*client, call, data, block).await?;
+			let set_id =
+				u64::decode(&mut &encoded_response.0[..]).map_err(Error::ResponseParseFailed)?;
+
+			Ok(set_id)
+		})
+		.await
+	}
+
 	/// Execute runtime call at given block.
 	pub async fn state_call(
 		&self,
@@ -541,6 +571,14 @@ impl<C: Chain> Client<C> {
 		.await
 	}
 
+	/// Return SCALE-encoded runtime metadata at the best block.
+	pub async fn raw_runtime_metadata(&self) -> Result<Bytes> {
+		self.jsonrpsee_execute(move |client| async move {
+			Ok(Substrate::<C>::state_get_metadata(&*client, None).await?)
+		})
+		.await
+	}
+
 	/// Return `tokenDecimals` property from the set of chain properties.
 	pub async fn token_decimals(&self) -> Result<Option<u64>> {
 		self.jsonrpsee_execute(move |client| async move {
@@ -573,6 +611,161 @@ impl<C: Chain> Client<C> {
 		Ok(Subscription(Mutex::new(receiver)))
 	}
 
+	/// Return runtime version stream.
+	pub async fn subscribe_runtime_version(&self) -> Result<Subscription<RuntimeVersion>> {
+		let subscription = self
+			.jsonrpsee_execute(move |client| async move {
+				Ok(client
+					.subscribe(
+						"state_subscribeRuntimeVersion",
+						JsonRpcParams::NoParams,
+						"state_unsubscribeRuntimeVersion",
+					)
+					.await?)
+			})
+			.await?;
+		let (sender, receiver) = futures::channel::mpsc::channel(MAX_SUBSCRIPTION_CAPACITY);
+		self.tokio.spawn(Subscription::background_worker(
+			C::NAME.into(),
+			"runtime version".into(),
+			subscription,
+			sender,
+		));
+		Ok(Subscription(Mutex::new(receiver)))
+	}
+
+	/// Returns error if any of given bridge calls is incompatible with the actual runtime.
+	///
+	/// Every call comes with indices that the relay is using to encode it. When new call (or
+	/// the call with new indices) is seen, these indices are compared with indices of the call
+	/// in the actual runtime metadata and the client starts following runtime upgrades. After
+	/// every upgrade, the runtime version that is used to sign transactions is refreshed and
+	/// indices of all tracked calls are compared with their indices in the upgraded runtime.
+	pub async fn ensure_bridge_calls_compatible(
+		&self,
+		calls: &[(BridgeCall, CallIndices)],
+	) -> Result<()> {
+		let mut state = self.runtime_upgrade.lock().await;
+		let mut has_new_calls = false;
+		for (call, indices) in calls {
+			has_new_calls |= state.track_call(*call, *indices);
+		}
+		if has_new_calls {
+			let runtime_version = self.runtime_version().await?;
+			self.check_runtime(&mut state, runtime_version).await?;
+		}
+		if !state.is_subscribed {
+			state.is_subscribed = true;
+			async_std::task::spawn(self.clone().follow_runtime_upgrades());
+		}
+
+		state.ensure_compatible(&calls.iter().map(|(call, _)| *call).collect::<Vec<_>>())
+	}
+
+	/// Follow runtime upgrades, refreshing runtime version and checking bridge calls.
+	async fn follow_runtime_upgrades(mut self) {
+		loop {
+			let result = async {
+				let subscription = self.subscribe_runtime_version().await?;
+				while let Some(runtime_version) = subscription.next().await? {
+					self.on_runtime_version(runtime_version).await?;
+				}
+				Ok::<_, Error>(())
+			}
+			.await;
+
+			log::warn!(
+				target: "bridge",
+				"{} runtime version subscription has ended ({:?}). Restarting in {}s",
+				C::NAME,
+				result.err(),
+				RECONNECT_DELAY.as_secs(),
+			);
+			async_std::task::sleep(RECONNECT_DELAY).await;
+			if let Err(error) = relay_utils::relay_loop::Client::reconnect(&mut self).await {
+				log::warn!(
+					target: "bridge",
+					"Failed to reconnect to {} node: {:?}",
+					C::NAME,
+					error,
+				);
+			}
+		}
+	}
+
+	/// Process new runtime version.
+	async fn on_runtime_version(&self, runtime_version: RuntimeVersion) -> Result<()> {
+		let mut state = self.runtime_upgrade.lock().await;
+		if state.spec_version == Some(runtime_version.spec_version) {
+			return Ok(())
+		}
+
+		log::info!(
+			target: "bridge",
+			"{} runtime has been upgraded to spec_version: {}, transaction_version: {}",
+			C::NAME,
+			runtime_version.spec_version,
+			runtime_version.transaction_version,
+		);
+		self.check_runtime(&mut state, runtime_version).await
+	}
+
+	/// Check tracked calls against the actual runtime and refresh the runtime version that is
+	/// used to sign transactions.
+	async fn check_runtime(
+		&self,
+		state: &mut RuntimeUpgradeState,
+		runtime_version: RuntimeVersion,
+	) -> Result<()> {
+		let raw_metadata = self.raw_runtime_metadata().await?;
+		let actual_indices =
+			bridge_call_indices(&raw_metadata.0, state.tracked_calls.keys().copied())?;
+		if actual_indices.is_none() {
+			log::warn!(
+				target: "bridge",
+				"Unsupported {} runtime metadata version. Unable to check bridge calls",
+				C::NAME,
+			);
+		}
+		state.on_runtime_upgrade(runtime_version.spec_version, actual_indices.as_ref());
+
+		let mut chain_runtime_version = self.chain_runtime_version.lock().await;
+		if let ChainRuntimeVersion::Custom(spec_version, transaction_version) =
+			*chain_runtime_version
+		{
+			if (spec_version, transaction_version) !=
+				(runtime_version.spec_version, runtime_version.transaction_version)
+			{
+				log::info!(
+					target: "bridge",
+					"Signing {} transactions with spec_version: {}, transaction_version: {} \
+					instead of spec_version: {}, transaction_version: {}",
+					C::NAME,
+					runtime_version.spec_version,
+					runtime_version.transaction_version,
+					spec_version,
+					transaction_version,
+				);
+				*chain_runtime_version = ChainRuntimeVersion::Custom(
+					runtime_version.spec_version,
+					runtime_version.transaction_version,
+				);
+			}
+		}
+
+		for reason in state.incompatible_calls.values() {
+			log::error!(
+				target: "bridge",
+				"{} runtime is incompatible with the relay: {}. Affected relay loops will keep \
+				failing (and retrying) until the relay is upgraded",
+				C::NAME,
+				reason,
+			);
+		}
+
+		Ok(())
+	}
+
 	/// Execute jsonrpsee future in tokio context.
 	async fn jsonrpsee_execute<MF, F, T>(&self, make_jsonrpsee_future: MF) -> Result<T>
 	where
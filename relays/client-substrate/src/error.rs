@@ -60,6 +60,15 @@ pub enum Error {
 	/// The Substrate transaction is invalid.
 	#[error("Substrate transaction is invalid: {0:?}")]
 	TransactionInvalid(#[from] TransactionValidityError),
+	/// Remote signer has failed to sign transaction.
+	#[error("Remote signer has failed: {0}")]
+	RemoteSigner(String),
+	/// Runtime is incompatible with the relay (e.g. after runtime upgrade).
+	#[error("Runtime is incompatible with the relay: {0}")]
+	IncompatibleRuntime(String),
+	/// Guard has failed to send alert to the webhook.
+	#[error("Failed to call guard webhook {0}: {1}")]
+	GuardWebhook(String, String),
 	/// Custom logic error.
 	#[error("{0}")]
 	Custom(String),
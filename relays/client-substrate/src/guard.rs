@@ -15,19 +15,337 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Pallet provides a set of guard functions that are running in background threads
-//! and are aborting process if some condition fails.
+//! and are checking some conditions of the chain or of the bridge.
+//!
+//! What happens when the condition fails is decided by the [`GuardPolicy`]. Every condition
+//! may be configured to log error, update metric, call webhook, pause affected message lanes
+//! or to abort the process. By default, balance guard is aborting the process and all other
+//! guards are only logging errors. Spec version guard is not aborting by default, because clients
+//! are following runtime upgrades and are refreshing runtime version themselves.
 
 use crate::{error::Error, Chain, ChainWithBalances, Client};
 
 use async_trait::async_trait;
+use bp_messages::{InboundLaneData, LaneId, MessageNonce, OperatingMode};
+use codec::Decode;
 use num_traits::CheckedSub;
+use relay_utils::{
+	admin::LaneControl,
+	metrics::{metric_name, register, GaugeVec, Metric, Opts, PrometheusError, Registry, U64},
+};
+use sp_core::storage::{StorageData, StorageKey};
 use sp_version::RuntimeVersion;
 use std::{
-	collections::VecDeque,
+	collections::{BTreeMap, VecDeque},
 	fmt::Display,
+	str::FromStr,
 	time::{Duration, Instant},
 };
 
+/// Percent of the inbound lane limits after which the lane is considered to be near its limit.
+const NEAR_LIMIT_PERCENT: MessageNonce = 90;
+
+/// Condition that is checked by some guard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GuardCondition {
+	/// Runtime spec version has changed.
+	SpecVersionChanged,
+	/// Balance of the relayer account has decreased too much.
+	BalanceDecreased,
+	/// Best finalized header of the bridged chain has not been updated for too long.
+	FinalityStalled,
+	/// Bridge pallet is halted.
+	PalletHalted,
+	/// Number of unrewarded relayer entries or unconfirmed messages at the inbound lane is
+	/// near the limit.
+	UnrewardedRelayersNearLimit,
+}
+
+impl GuardCondition {
+	/// All guard conditions.
+	pub const ALL: [GuardCondition; 5] = [
+		GuardCondition::SpecVersionChanged,
+		GuardCondition::BalanceDecreased,
+		GuardCondition::FinalityStalled,
+		GuardCondition::PalletHalted,
+		GuardCondition::UnrewardedRelayersNearLimit,
+	];
+
+	/// Return condition name.
+	pub fn name(&self) -> &'static str {
+		match *self {
+			GuardCondition::SpecVersionChanged => "spec-version-changed",
+			GuardCondition::BalanceDecreased => "balance-decreased",
+			GuardCondition::FinalityStalled => "finality-stalled",
+			GuardCondition::PalletHalted => "pallet-halted",
+			GuardCondition::UnrewardedRelayersNearLimit => "unrewarded-relayers-near-limit",
+		}
+	}
+}
+
+impl FromStr for GuardCondition {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		GuardCondition::ALL
+			.iter()
+			.find(|condition| condition.name() == s)
+			.copied()
+			.ok_or_else(|| format!("Unknown guard condition: {}", s))
+	}
+}
+
+/// Action that is performed when guard condition fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardAction {
+	/// Log error.
+	Log,
+	/// Set guard condition metric to `1` while condition is failing and to `0` otherwise.
+	Metric,
+	/// Send JSON alert to given URL when condition starts failing and when it is recovered.
+	Webhook(String),
+	/// Pause affected message lanes while condition is failing.
+	Pause,
+	/// Abort current process.
+	Abort,
+}
+
+impl FromStr for GuardAction {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"log" => Ok(GuardAction::Log),
+			"metric" => Ok(GuardAction::Metric),
+			"pause" => Ok(GuardAction::Pause),
+			"abort" => Ok(GuardAction::Abort),
+			_ => match s.strip_prefix("webhook:") {
+				Some(url) if !url.is_empty() => Ok(GuardAction::Webhook(url.into())),
+				_ => Err(format!(
+					"Unknown guard action: {}. Expected one of: log, metric, webhook:<url>, \
+					pause, abort",
+					s
+				)),
+			},
+		}
+	}
+}
+
+/// Actions that are performed when given guard condition fails.
+///
+/// The string representation is `<condition>=<action>[,<action>...]`, e.g.
+/// `balance-decreased=log,webhook:http://127.0.0.1:8080/alert,pause`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardConditionPolicy {
+	/// Guard condition.
+	pub condition: GuardCondition,
+	/// Actions that are performed when condition fails.
+	pub actions: Vec<GuardAction>,
+}
+
+impl FromStr for GuardConditionPolicy {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (condition, actions) = s.split_once('=').ok_or_else(|| {
+			format!("Invalid guard policy: {}. Expected <condition>=<action>[,<action>...]", s)
+		})?;
+		Ok(GuardConditionPolicy {
+			condition: condition.parse()?,
+			actions: actions.split(',').map(GuardAction::from_str).collect::<Result<_, _>>()?,
+		})
+	}
+}
+
+/// Guards policy: what happens when guard condition fails.
+#[derive(Debug, Clone)]
+pub struct GuardPolicy {
+	/// Actions that are performed when guard condition fails.
+	actions: BTreeMap<GuardCondition, Vec<GuardAction>>,
+	/// Metrics that are updated by the `GuardAction::Metric` action.
+	metrics: Option<GuardMetrics>,
+	/// Lanes that are paused by the `GuardAction::Pause` action.
+	paused_lanes: Vec<LaneControl>,
+	/// Maximal time that the best finalized header of the bridged chain may stay the same.
+	finality_stall_timeout: Duration,
+}
+
+impl Default for GuardPolicy {
+	fn default() -> Self {
+		GuardPolicy {
+			actions: GuardCondition::ALL
+				.iter()
+				.map(|condition| {
+					let actions = match *condition {
+						GuardCondition::BalanceDecreased =>
+							vec![GuardAction::Log, GuardAction::Abort],
+						_ => vec![GuardAction::Log],
+					};
+					(*condition, actions)
+				})
+				.collect(),
+			metrics: None,
+			paused_lanes: Vec::new(),
+			finality_stall_timeout: Duration::from_secs(60 * 60),
+		}
+	}
+}
+
+impl GuardPolicy {
+	/// Override actions that are performed when given condition fails.
+	pub fn with_condition_policy(mut self, policy: GuardConditionPolicy) -> Self {
+		self.actions.insert(policy.condition, policy.actions);
+		self
+	}
+
+	/// Use given metrics for `GuardAction::Metric` action.
+	pub fn with_metrics(mut self, metrics: GuardMetrics) -> Self {
+		self.metrics = Some(metrics);
+		self
+	}
+
+	/// Pause given lane when `GuardAction::Pause` action is performed.
+	pub fn with_paused_lane(mut self, lane: LaneControl) -> Self {
+		self.paused_lanes.push(lane);
+		self
+	}
+
+	/// Set maximal time that the best finalized header of the bridged chain may stay the same.
+	pub fn with_finality_stall_timeout(mut self, timeout: Duration) -> Self {
+		self.finality_stall_timeout = timeout;
+		self
+	}
+
+	/// Return actions that are performed when given condition fails.
+	pub fn actions(&self, condition: GuardCondition) -> &[GuardAction] {
+		self.actions.get(&condition).map(|actions| &actions[..]).unwrap_or(&[])
+	}
+
+	/// Returns true if any condition may pause message lanes.
+	pub fn may_pause_lanes(&self) -> bool {
+		self.actions.values().flatten().any(|action| *action == GuardAction::Pause)
+	}
+
+	/// Returns true if any condition updates metrics.
+	pub fn uses_metrics(&self) -> bool {
+		self.actions.values().flatten().any(|action| *action == GuardAction::Metric)
+	}
+}
+
+/// Guards metrics.
+#[derive(Clone)]
+pub struct GuardMetrics {
+	/// Guard conditions state: `1` if condition is failing and `0` otherwise.
+	failed_conditions: GaugeVec<U64>,
+}
+
+impl GuardMetrics {
+	/// Create guards metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(GuardMetrics {
+			failed_conditions: GaugeVec::new(
+				Opts::new(
+					metric_name(prefix, "guard_failed_conditions"),
+					"Guard conditions that are currently failing",
+				),
+				&["chain", "condition"],
+			)?,
+		})
+	}
+
+	/// Update state of guard condition at given chain.
+	pub fn update(&self, chain: &str, condition: GuardCondition, is_failing: bool) {
+		self.failed_conditions
+			.with_label_values(&[chain, condition.name()])
+			.set(if is_failing { 1 } else { 0 });
+	}
+
+	/// Returns true if guard condition at given chain is failing.
+	pub fn is_failing(&self, chain: &str, condition: GuardCondition) -> bool {
+		self.failed_conditions.with_label_values(&[chain, condition.name()]).get() != 0
+	}
+}
+
+impl std::fmt::Debug for GuardMetrics {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		fmt.debug_struct("GuardMetrics").finish()
+	}
+}
+
+impl Metric for GuardMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.failed_conditions.clone(), registry)?;
+		Ok(())
+	}
+}
+
+/// Alert that is sent to webhooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardAlert {
+	/// Name of the chain where condition is checked.
+	pub chain: &'static str,
+	/// Guard condition.
+	pub condition: GuardCondition,
+	/// True if condition has started failing and false if it has been recovered.
+	pub is_failing: bool,
+	/// Human-readable description of the alert.
+	pub message: String,
+}
+
+impl GuardAlert {
+	/// Return JSON representation of the alert.
+	pub fn to_json(&self) -> serde_json::Value {
+		serde_json::json!({
+			"chain": self.chain,
+			"condition": self.condition.name(),
+			"failing": self.is_failing,
+			"message": self.message,
+		})
+	}
+}
+
+/// Storage value that tells whether the bridge pallet is halted.
+#[derive(Debug, Clone, Copy)]
+pub enum PalletHaltFlag {
+	/// `IsHalted` value of the bridge GRANDPA pallet with given name.
+	Grandpa(&'static str),
+	/// `PalletOperatingMode` value of the bridge messages pallet with given name.
+	Messages(&'static str),
+}
+
+impl PalletHaltFlag {
+	/// Return name of the pallet.
+	pub fn pallet_name(&self) -> &'static str {
+		match *self {
+			PalletHaltFlag::Grandpa(pallet_name) | PalletHaltFlag::Messages(pallet_name) =>
+				pallet_name,
+		}
+	}
+
+	/// Return storage key of the flag.
+	pub fn storage_key(&self) -> StorageKey {
+		match *self {
+			PalletHaltFlag::Grandpa(pallet_name) =>
+				bp_header_chain::storage_keys::is_halted_key(pallet_name),
+			PalletHaltFlag::Messages(pallet_name) =>
+				bp_messages::storage_keys::operating_mode_key(pallet_name),
+		}
+	}
+
+	/// Returns true if encoded flag value says that the pallet is halted.
+	pub fn is_halted(&self, value: Option<&[u8]>) -> Result<bool, codec::Error> {
+		let mut value = match value {
+			Some(value) => value,
+			None => return Ok(false),
+		};
+		match *self {
+			PalletHaltFlag::Grandpa(_) => bool::decode(&mut value),
+			PalletHaltFlag::Messages(_) =>
+				OperatingMode::decode(&mut value).map(|mode| mode == OperatingMode::Halted),
+		}
+	}
+}
+
 /// Guards environment.
 #[async_trait]
 pub trait Environment<C: ChainWithBalances>: Send + Sync + 'static {
@@ -36,11 +354,21 @@ pub trait Environment<C: ChainWithBalances>: Send + Sync + 'static {
 
 	/// Return current runtime version.
 	async fn runtime_version(&mut self) -> Result<RuntimeVersion, Self::Error>;
+	/// Return spec version that the relay is using to sign transactions. It changes when the
+	/// relay adapts to the runtime upgrade. Returns `None` if it is not tracked.
+	async fn signing_spec_version(&mut self) -> Result<Option<u32>, Self::Error> {
+		Ok(None)
+	}
 	/// Return free native balance of the account on the chain.
 	async fn free_native_balance(
 		&mut self,
 		account: C::AccountId,
 	) -> Result<C::Balance, Self::Error>;
+	/// Return raw value of the runtime storage item at the best block.
+	async fn raw_storage_value(
+		&mut self,
+		storage_key: StorageKey,
+	) -> Result<Option<StorageData>, Self::Error>;
 
 	/// Return current time.
 	fn now(&self) -> Instant {
@@ -52,15 +380,204 @@ pub trait Environment<C: ChainWithBalances>: Send + Sync + 'static {
 		async_std::task::sleep(duration).await
 	}
 
+	/// Update guard condition metric.
+	fn update_metric(
+		&mut self,
+		metrics: &GuardMetrics,
+		condition: GuardCondition,
+		is_failing: bool,
+	) {
+		metrics.update(C::NAME, condition, is_failing);
+	}
+
+	/// Send alert to given webhook URL.
+	async fn call_webhook(&mut self, url: &str, alert: &GuardAlert) -> Result<(), Error> {
+		let request = isahc::Request::post(url)
+			.header("Content-Type", "application/json")
+			.body(alert.to_json().to_string())
+			.map_err(|e| Error::GuardWebhook(url.into(), e.to_string()))?;
+		let response = isahc::send_async(request)
+			.await
+			.map_err(|e| Error::GuardWebhook(url.into(), e.to_string()))?;
+		if !response.status().is_success() {
+			return Err(Error::GuardWebhook(
+				url.into(),
+				format!("unexpected response status: {}", response.status()),
+			))
+		}
+		Ok(())
+	}
+
+	/// Pause given message lanes for given reason. Called when guard condition fails.
+	async fn pause(&mut self, lanes: &[LaneControl], reason: &str) {
+		lanes.iter().for_each(|lane| lane.pause_by(reason));
+	}
+
+	/// Cancel pause of given message lanes for given reason. Called when previously failed guard
+	/// condition is recovered. Lanes that are paused for other reasons stay paused.
+	async fn resume(&mut self, lanes: &[LaneControl], reason: &str) {
+		lanes.iter().for_each(|lane| lane.resume_by(reason));
+	}
+
 	/// Abort current process. Called when guard condition check fails.
 	async fn abort(&mut self) {
 		std::process::abort();
 	}
 }
 
+/// State of the guard condition.
+struct GuardState<C> {
+	condition: GuardCondition,
+	/// Reason of lanes pause. Unique for every guard instance.
+	pause_reason: String,
+	policy: GuardPolicy,
+	is_failing: bool,
+	has_paused_lanes: bool,
+	_phantom: std::marker::PhantomData<C>,
+}
+
+impl<C: ChainWithBalances> GuardState<C> {
+	/// Create new condition state. The `subject` identifies what is checked by the guard
+	/// (e.g. pallet or account).
+	fn new(condition: GuardCondition, subject: String, policy: GuardPolicy) -> Self {
+		GuardState {
+			condition,
+			pause_reason: format!("{} guard of {}", condition.name(), subject),
+			policy,
+			is_failing: false,
+			has_paused_lanes: false,
+			_phantom: Default::default(),
+		}
+	}
+
+	/// Perform policy actions after condition check has failed.
+	async fn failed(&mut self, env: &mut impl Environment<C>, message: String) {
+		let is_new_failure = !std::mem::replace(&mut self.is_failing, true);
+		let alert =
+			GuardAlert { chain: C::NAME, condition: self.condition, is_failing: true, message };
+
+		let mut abort = false;
+		for action in self.policy.actions(self.condition) {
+			match *action {
+				GuardAction::Log =>
+					if is_new_failure {
+						log::error!(
+							target: "bridge-guard",
+							"{} guard has failed: {}",
+							self.condition.name(),
+							alert.message,
+						)
+					},
+				GuardAction::Metric =>
+					if let Some(ref metrics) = self.policy.metrics {
+						env.update_metric(metrics, self.condition, true);
+					},
+				GuardAction::Webhook(ref url) =>
+					if is_new_failure {
+						call_webhook(env, url, &alert).await;
+					},
+				GuardAction::Pause =>
+					if !self.has_paused_lanes {
+						log::warn!(
+							target: "bridge-guard",
+							"Pausing {} message lane(s) because of failed {} guard",
+							self.policy.paused_lanes.len(),
+							self.condition.name(),
+						);
+						env.pause(&self.policy.paused_lanes, &self.pause_reason).await;
+						self.has_paused_lanes = true;
+					},
+				GuardAction::Abort => abort = true,
+			}
+		}
+
+		if abort {
+			log::error!(
+				target: "bridge-guard",
+				"{} guard has failed. Aborting relay",
+				self.condition.name(),
+			);
+			env.abort().await;
+		}
+	}
+
+	/// Perform policy actions after condition check has passed.
+	async fn passed(&mut self, env: &mut impl Environment<C>, message: impl FnOnce() -> String) {
+		let is_recovered = std::mem::replace(&mut self.is_failing, false);
+		let alert = if is_recovered {
+			Some(GuardAlert {
+				chain: C::NAME,
+				condition: self.condition,
+				is_failing: false,
+				message: message(),
+			})
+		} else {
+			None
+		};
+
+		for action in self.policy.actions(self.condition) {
+			match (action, &alert) {
+				(GuardAction::Log, Some(alert)) => log::info!(
+					target: "bridge-guard",
+					"{} guard has been recovered: {}",
+					self.condition.name(),
+					alert.message,
+				),
+				(GuardAction::Metric, _) =>
+					if let Some(ref metrics) = self.policy.metrics {
+						env.update_metric(metrics, self.condition, false);
+					},
+				(GuardAction::Webhook(url), Some(alert)) => call_webhook(env, url, alert).await,
+				(GuardAction::Pause, _) if self.has_paused_lanes => {
+					log::info!(
+						target: "bridge-guard",
+						"Resuming {} message lane(s) paused by {} guard",
+						self.policy.paused_lanes.len(),
+						self.condition.name(),
+					);
+					env.resume(&self.policy.paused_lanes, &self.pause_reason).await;
+					self.has_paused_lanes = false;
+				},
+				_ => (),
+			}
+		}
+	}
+}
+
+/// Send alert to the webhook, logging error if call has failed.
+async fn call_webhook<C: ChainWithBalances>(
+	env: &mut impl Environment<C>,
+	url: &str,
+	alert: &GuardAlert,
+) {
+	if let Err(error) = env.call_webhook(url, alert).await {
+		log::warn!(
+			target: "bridge-guard",
+			"Failed to send {} guard alert: {}",
+			alert.condition.name(),
+			error,
+		);
+	}
+}
+
 /// Abort when runtime spec version is different from specified.
 pub fn abort_on_spec_version_change<C: ChainWithBalances>(
+	env: impl Environment<C>,
+	expected_spec_version: u32,
+) {
+	let policy = GuardPolicy::default().with_condition_policy(GuardConditionPolicy {
+		condition: GuardCondition::SpecVersionChanged,
+		actions: vec![GuardAction::Log, GuardAction::Abort],
+	});
+	guard_spec_version(env, policy, expected_spec_version)
+}
+
+/// Check that runtime spec version is the same as the version that the relay is using to sign
+/// transactions (see `Environment::signing_spec_version`). If the environment doesn't track
+/// this version, the runtime spec version must be the same as specified.
+pub fn guard_spec_version<C: ChainWithBalances>(
 	mut env: impl Environment<C>,
+	policy: GuardPolicy,
 	expected_spec_version: u32,
 ) {
 	async_std::task::spawn(async move {
@@ -71,21 +588,46 @@ pub fn abort_on_spec_version_change<C: ChainWithBalances>(
 			expected_spec_version,
 		);
 
+		let mut state =
+			GuardState::<C>::new(GuardCondition::SpecVersionChanged, C::NAME.into(), policy);
 		loop {
-			let actual_spec_version = env.runtime_version().await;
-			match actual_spec_version {
-				Ok(version) if version.spec_version == expected_spec_version => (),
-				Ok(version) => {
-					log::error!(
+			// if the relay has adapted to the runtime upgrade, we expect the new version
+			let expected_spec_version = match env.signing_spec_version().await {
+				Ok(signing_spec_version) => signing_spec_version.unwrap_or(expected_spec_version),
+				Err(error) => {
+					log::warn!(
 						target: "bridge-guard",
-						"{} runtime spec version has changed from {} to {}. Aborting relay",
+						"Failed to read {} signing spec version: {}",
 						C::NAME,
-						expected_spec_version,
-						version.spec_version,
+						error,
 					);
-
-					env.abort().await;
+					expected_spec_version
 				},
+			};
+			let actual_spec_version = env.runtime_version().await;
+			match actual_spec_version {
+				Ok(version) if version.spec_version == expected_spec_version =>
+					state
+						.passed(&mut env, || {
+							format!(
+								"{} runtime spec version is back to {}",
+								C::NAME,
+								expected_spec_version,
+							)
+						})
+						.await,
+				Ok(version) =>
+					state
+						.failed(
+							&mut env,
+							format!(
+								"{} runtime spec version has changed from {} to {}",
+								C::NAME,
+								expected_spec_version,
+								version.spec_version,
+							),
+						)
+						.await,
 				Err(error) => log::warn!(
 					target: "bridge-guard",
 					"Failed to read {} runtime version: {}. Relay may need to be stopped manually",
@@ -103,7 +645,19 @@ pub fn abort_on_spec_version_change<C: ChainWithBalances>(
 /// Other components may increase (or decrease) balance of account and it WILL affect logic of the
 /// guard.
 pub fn abort_when_account_balance_decreased<C: ChainWithBalances>(
+	env: impl Environment<C>,
+	account_id: C::AccountId,
+	maximal_decrease: C::Balance,
+) {
+	guard_account_balance(env, GuardPolicy::default(), account_id, maximal_decrease)
+}
+
+/// Check that, during 24 hours, free balance of given account is not decreased by more than
+/// given value. Other components may increase (or decrease) balance of account and it WILL
+/// affect logic of the guard.
+pub fn guard_account_balance<C: ChainWithBalances>(
 	mut env: impl Environment<C>,
+	policy: GuardPolicy,
 	account_id: C::AccountId,
 	maximal_decrease: C::Balance,
 ) {
@@ -118,6 +672,11 @@ pub fn abort_when_account_balance_decreased<C: ChainWithBalances>(
 			maximal_decrease,
 		);
 
+		let mut state = GuardState::<C>::new(
+			GuardCondition::BalanceDecreased,
+			format!("{}/{:?}", C::NAME, account_id),
+			policy,
+		);
 		let mut balances = VecDeque::new();
 
 		loop {
@@ -143,17 +702,26 @@ pub fn abort_when_account_balance_decreased<C: ChainWithBalances>(
 						balances.front().expect("pushed to queue couple of lines above; qed");
 					let balances_difference = oldest_balance.checked_sub(&current_balance);
 					if balances_difference > Some(maximal_decrease) {
-						log::error!(
-							target: "bridge-guard",
-							"Balance of {} account {:?} has decreased from {:?} to {:?} in {} minutes. Aborting relay",
+						let message = format!(
+							"Balance of {} account {:?} has decreased from {:?} to {:?} in {} minutes",
 							C::NAME,
 							account_id,
 							oldest_balance,
 							current_balance,
 							current_time.duration_since(*oldest_time).as_secs() / 60,
 						);
-
-						env.abort().await;
+						state.failed(&mut env, message).await;
+					} else {
+						state
+							.passed(&mut env, || {
+								format!(
+									"Balance of {} account {:?} is {:?}",
+									C::NAME,
+									account_id,
+									current_balance,
+								)
+							})
+							.await;
 					}
 				},
 				Err(error) => {
@@ -172,6 +740,222 @@ pub fn abort_when_account_balance_decreased<C: ChainWithBalances>(
 	});
 }
 
+/// Check that the value of given storage item (that is expected to hold best finalized header
+/// of the bridged chain) is updated at least once per `GuardPolicy::finality_stall_timeout`.
+pub fn guard_finality_stall<C: ChainWithBalances>(
+	mut env: impl Environment<C>,
+	policy: GuardPolicy,
+	bridged_chain_name: &'static str,
+	best_finalized_key: StorageKey,
+) {
+	async_std::task::spawn(async move {
+		let stall_timeout = policy.finality_stall_timeout;
+		log::info!(
+			target: "bridge-guard",
+			"Starting finality stall guard for {} headers at {}. Stall timeout: {}s",
+			bridged_chain_name,
+			C::NAME,
+			stall_timeout.as_secs(),
+		);
+
+		let mut state = GuardState::<C>::new(
+			GuardCondition::FinalityStalled,
+			format!("{} headers at {}", bridged_chain_name, C::NAME),
+			policy,
+		);
+		let mut last_update: Option<(Instant, Option<StorageData>)> = None;
+
+		loop {
+			let current_time = env.now();
+			match env.raw_storage_value(best_finalized_key.clone()).await {
+				Ok(best_finalized) => {
+					let updated_at = match last_update {
+						Some((updated_at, ref value)) if *value == best_finalized => updated_at,
+						_ => {
+							last_update = Some((current_time, best_finalized));
+							current_time
+						},
+					};
+
+					let stalled_for = current_time.duration_since(updated_at);
+					if stalled_for > stall_timeout {
+						let message = format!(
+							"Best finalized {} header at {} has not been updated for {} minutes",
+							bridged_chain_name,
+							C::NAME,
+							stalled_for.as_secs() / 60,
+						);
+						state.failed(&mut env, message).await;
+					} else {
+						state
+							.passed(&mut env, || {
+								format!(
+									"Best finalized {} header at {} has been updated",
+									bridged_chain_name,
+									C::NAME,
+								)
+							})
+							.await;
+					}
+				},
+				Err(error) => log::warn!(
+					target: "bridge-guard",
+					"Failed to read best finalized {} header at {}: {}",
+					bridged_chain_name,
+					C::NAME,
+					error,
+				),
+			}
+
+			env.sleep(conditions_check_delay::<C>()).await;
+		}
+	});
+}
+
+/// Check that the bridge pallet is not halted.
+pub fn guard_pallet_halted<C: ChainWithBalances>(
+	mut env: impl Environment<C>,
+	policy: GuardPolicy,
+	halt_flag: PalletHaltFlag,
+) {
+	async_std::task::spawn(async move {
+		log::info!(
+			target: "bridge-guard",
+			"Starting halted pallet guard for {}/{}",
+			C::NAME,
+			halt_flag.pallet_name(),
+		);
+
+		let mut state = GuardState::<C>::new(
+			GuardCondition::PalletHalted,
+			format!("{}/{}", C::NAME, halt_flag.pallet_name()),
+			policy,
+		);
+		loop {
+			let is_halted = env
+				.raw_storage_value(halt_flag.storage_key())
+				.await
+				.map_err(|e| e.to_string())
+				.and_then(|value| {
+					halt_flag.is_halted(value.as_ref().map(|v| &v.0[..])).map_err(|e| e.to_string())
+				});
+			match is_halted {
+				Ok(true) =>
+					state
+						.failed(
+							&mut env,
+							format!("Pallet {}/{} is halted", C::NAME, halt_flag.pallet_name()),
+						)
+						.await,
+				Ok(false) =>
+					state
+						.passed(&mut env, || {
+							format!("Pallet {}/{} is resumed", C::NAME, halt_flag.pallet_name())
+						})
+						.await,
+				Err(error) => log::warn!(
+					target: "bridge-guard",
+					"Failed to read operating mode of {}/{}: {}",
+					C::NAME,
+					halt_flag.pallet_name(),
+					error,
+				),
+			}
+
+			env.sleep(conditions_check_delay::<C>()).await;
+		}
+	});
+}
+
+/// Check that number of unrewarded relayer entries and number of unconfirmed messages at the
+/// inbound lane are not near the limits of the bridged chain.
+pub fn guard_unrewarded_relayers<C: ChainWithBalances>(
+	mut env: impl Environment<C>,
+	policy: GuardPolicy,
+	lane: LaneId,
+	inbound_lane_key: StorageKey,
+	max_unrewarded_relayer_entries: MessageNonce,
+	max_unconfirmed_messages: MessageNonce,
+) {
+	async_std::task::spawn(async move {
+		log::info!(
+			target: "bridge-guard",
+			"Starting unrewarded relayers guard for {} inbound lane {:?}. Limits: {} relayer \
+			entries, {} unconfirmed messages",
+			C::NAME,
+			lane,
+			max_unrewarded_relayer_entries,
+			max_unconfirmed_messages,
+		);
+
+		let mut state = GuardState::<C>::new(
+			GuardCondition::UnrewardedRelayersNearLimit,
+			format!("{} inbound lane {:?}", C::NAME, lane),
+			policy,
+		);
+		loop {
+			let inbound_lane_data = env
+				.raw_storage_value(inbound_lane_key.clone())
+				.await
+				.map_err(|e| e.to_string())
+				.and_then(|value| {
+					value
+						.map(|value| InboundLaneData::<C::AccountId>::decode(&mut &value.0[..]))
+						.transpose()
+						.map_err(|e| e.to_string())
+				});
+			match inbound_lane_data {
+				Ok(inbound_lane_data) => {
+					let inbound_lane_data = inbound_lane_data.unwrap_or_default();
+					let relayer_entries = inbound_lane_data.relayers.len() as MessageNonce;
+					let unconfirmed_messages = inbound_lane_data
+						.last_delivered_nonce()
+						.saturating_sub(inbound_lane_data.last_confirmed_nonce);
+					if is_near_limit(relayer_entries, max_unrewarded_relayer_entries) ||
+						is_near_limit(unconfirmed_messages, max_unconfirmed_messages)
+					{
+						let message = format!(
+							"{} inbound lane {:?} has {} of {} unrewarded relayer entries and \
+							{} of {} unconfirmed messages",
+							C::NAME,
+							lane,
+							relayer_entries,
+							max_unrewarded_relayer_entries,
+							unconfirmed_messages,
+							max_unconfirmed_messages,
+						);
+						state.failed(&mut env, message).await;
+					} else {
+						state
+							.passed(&mut env, || {
+								format!(
+									"{} inbound lane {:?} is no longer near its limits",
+									C::NAME,
+									lane,
+								)
+							})
+							.await;
+					}
+				},
+				Err(error) => log::warn!(
+					target: "bridge-guard",
+					"Failed to read {} inbound lane {:?} state: {}",
+					C::NAME,
+					lane,
+					error,
+				),
+			}
+
+			env.sleep(conditions_check_delay::<C>()).await;
+		}
+	});
+}
+
+/// Returns true if value has reached `NEAR_LIMIT_PERCENT` of the limit.
+fn is_near_limit(value: MessageNonce, limit: MessageNonce) -> bool {
+	value.saturating_mul(100) >= limit.saturating_mul(NEAR_LIMIT_PERCENT)
+}
+
 /// Delay between conditions check.
 fn conditions_check_delay<C: Chain>() -> Duration {
 	C::AVERAGE_BLOCK_INTERVAL * (10 + rand::random::<u32>() % 10)
@@ -185,17 +969,31 @@ impl<C: ChainWithBalances> Environment<C> for Client<C> {
 		Client::<C>::runtime_version(self).await
 	}
 
+	async fn signing_spec_version(&mut self) -> Result<Option<u32>, Self::Error> {
+		Client::<C>::simple_runtime_version(self)
+			.await
+			.map(|(spec_version, _)| Some(spec_version))
+	}
+
 	async fn free_native_balance(
 		&mut self,
 		account: C::AccountId,
 	) -> Result<C::Balance, Self::Error> {
 		Client::<C>::free_native_balance(self, account).await
 	}
+
+	async fn raw_storage_value(
+		&mut self,
+		storage_key: StorageKey,
+	) -> Result<Option<StorageData>, Self::Error> {
+		Client::<C>::raw_storage_value(self, storage_key, None).await
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use codec::Encode;
 	use frame_support::weights::{IdentityFee, Weight};
 	use futures::{
 		channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
@@ -252,6 +1050,27 @@ mod tests {
 		free_native_balance_rx: UnboundedReceiver<u32>,
 		slept_tx: UnboundedSender<()>,
 		aborted_tx: UnboundedSender<()>,
+		hooks: TestHooks,
+	}
+
+	struct TestHooks {
+		now: Instant,
+		signing_spec_version: Option<u32>,
+		storage_value_rx: UnboundedReceiver<Option<StorageData>>,
+		alerts_tx: UnboundedSender<GuardAlert>,
+		paused_tx: UnboundedSender<bool>,
+	}
+
+	impl Default for TestHooks {
+		fn default() -> Self {
+			TestHooks {
+				now: Instant::now(),
+				signing_spec_version: None,
+				storage_value_rx: unbounded().1,
+				alerts_tx: unbounded().0,
+				paused_tx: unbounded().0,
+			}
+		}
 	}
 
 	#[async_trait]
@@ -262,14 +1081,43 @@ mod tests {
 			Ok(self.runtime_version_rx.next().await.unwrap_or_default())
 		}
 
+		async fn signing_spec_version(&mut self) -> Result<Option<u32>, Self::Error> {
+			Ok(self.hooks.signing_spec_version)
+		}
+
 		async fn free_native_balance(&mut self, _account: u32) -> Result<u32, Self::Error> {
 			Ok(self.free_native_balance_rx.next().await.unwrap_or_default())
 		}
 
+		async fn raw_storage_value(
+			&mut self,
+			_storage_key: StorageKey,
+		) -> Result<Option<StorageData>, Self::Error> {
+			Ok(self.hooks.storage_value_rx.next().await.unwrap_or_default())
+		}
+
+		fn now(&self) -> Instant {
+			self.hooks.now
+		}
+
 		async fn sleep(&mut self, _duration: Duration) {
+			self.hooks.now += Duration::from_secs(60);
 			let _ = self.slept_tx.send(()).await;
 		}
 
+		async fn call_webhook(&mut self, _url: &str, alert: &GuardAlert) -> Result<(), Error> {
+			let _ = self.hooks.alerts_tx.send(alert.clone()).await;
+			Ok(())
+		}
+
+		async fn pause(&mut self, _lanes: &[LaneControl], _reason: &str) {
+			let _ = self.hooks.paused_tx.send(true).await;
+		}
+
+		async fn resume(&mut self, _lanes: &[LaneControl], _reason: &str) {
+			let _ = self.hooks.paused_tx.send(false).await;
+		}
+
 		async fn abort(&mut self) {
 			let _ = self.aborted_tx.send(()).await;
 			// simulate process abort :)
@@ -292,6 +1140,7 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					hooks: Default::default(),
 				},
 				0,
 			);
@@ -324,6 +1173,7 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					hooks: Default::default(),
 				},
 				42,
 			);
@@ -341,6 +1191,39 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn does_not_abort_when_relay_has_adapted_to_spec_version_change() {
+		async_std::task::block_on(async {
+			let (
+				(mut runtime_version_tx, runtime_version_rx),
+				(_free_native_balance_tx, free_native_balance_rx),
+				(slept_tx, mut slept_rx),
+				(aborted_tx, mut aborted_rx),
+			) = (unbounded(), unbounded(), unbounded(), unbounded());
+			abort_on_spec_version_change(
+				TestEnvironment {
+					runtime_version_rx,
+					free_native_balance_rx,
+					slept_tx,
+					aborted_tx,
+					hooks: TestHooks { signing_spec_version: Some(42), ..Default::default() },
+				},
+				0,
+			);
+
+			// client responds with the version that the relay is already using
+			runtime_version_tx
+				.send(RuntimeVersion { spec_version: 42, ..Default::default() })
+				.await
+				.unwrap();
+
+			// then the `sleep` function is called
+			slept_rx.next().await;
+			// and the `abort` function is not called
+			assert!(aborted_rx.next().now_or_never().is_none());
+		});
+	}
+
 	#[test]
 	fn aborts_when_balance_is_too_low() {
 		async_std::task::block_on(async {
@@ -356,6 +1239,7 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					hooks: Default::default(),
 				},
 				0,
 				100,
@@ -392,6 +1276,7 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					hooks: Default::default(),
 				},
 				0,
 				100,
@@ -412,4 +1297,148 @@ mod tests {
 			assert!(aborted_rx.next().now_or_never().is_none());
 		});
 	}
+
+	#[test]
+	fn guard_policy_is_parsed() {
+		assert_eq!(
+			"balance-decreased=log,webhook:http://127.0.0.1:8080/alert,pause".parse(),
+			Ok(GuardConditionPolicy {
+				condition: GuardCondition::BalanceDecreased,
+				actions: vec![
+					GuardAction::Log,
+					GuardAction::Webhook("http://127.0.0.1:8080/alert".into()),
+					GuardAction::Pause,
+				],
+			}),
+		);
+		assert!("balance-decreased".parse::<GuardConditionPolicy>().is_err());
+		assert!("unknown=log".parse::<GuardConditionPolicy>().is_err());
+		assert!("pallet-halted=log,unknown".parse::<GuardConditionPolicy>().is_err());
+		assert!("pallet-halted=webhook:".parse::<GuardConditionPolicy>().is_err());
+	}
+
+	#[test]
+	fn alerts_and_pauses_lanes_while_pallet_is_halted() {
+		async_std::task::block_on(async {
+			let (
+				(_runtime_version_tx, runtime_version_rx),
+				(_free_native_balance_tx, free_native_balance_rx),
+				(slept_tx, mut slept_rx),
+				(aborted_tx, mut aborted_rx),
+				(mut storage_value_tx, storage_value_rx),
+				(alerts_tx, mut alerts_rx),
+				(paused_tx, mut paused_rx),
+			) = (
+				unbounded(),
+				unbounded(),
+				unbounded(),
+				unbounded(),
+				unbounded(),
+				unbounded(),
+				unbounded(),
+			);
+			let metrics = GuardMetrics::new(None).unwrap();
+			let policy = GuardPolicy::default()
+				.with_condition_policy(
+					"pallet-halted=metric,webhook:http://alert,pause".parse().unwrap(),
+				)
+				.with_metrics(metrics.clone());
+			guard_pallet_halted(
+				TestEnvironment {
+					runtime_version_rx,
+					free_native_balance_rx,
+					slept_tx,
+					aborted_tx,
+					hooks: TestHooks {
+						storage_value_rx,
+						alerts_tx,
+						paused_tx,
+						..Default::default()
+					},
+				},
+				policy,
+				PalletHaltFlag::Grandpa("BridgeGrandpa"),
+			);
+
+			// pallet is halted
+			storage_value_tx.send(Some(StorageData(true.encode()))).await.unwrap();
+
+			// then alert is sent, lanes are paused and metric is updated
+			assert!(alerts_rx.next().await.unwrap().is_failing);
+			assert_eq!(paused_rx.next().await, Some(true));
+			slept_rx.next().await;
+			assert!(metrics.is_failing(TestChain::NAME, GuardCondition::PalletHalted));
+
+			// pallet is still halted
+			storage_value_tx.send(Some(StorageData(true.encode()))).await.unwrap();
+
+			// then no new alerts are sent
+			slept_rx.next().await;
+			assert!(alerts_rx.next().now_or_never().is_none());
+			assert!(paused_rx.next().now_or_never().is_none());
+
+			// pallet is resumed
+			storage_value_tx.send(Some(StorageData(false.encode()))).await.unwrap();
+
+			// then recovery alert is sent, lanes are resumed and metric is updated
+			assert!(!alerts_rx.next().await.unwrap().is_failing);
+			assert_eq!(paused_rx.next().await, Some(false));
+			slept_rx.next().await;
+			assert!(!metrics.is_failing(TestChain::NAME, GuardCondition::PalletHalted));
+
+			// and the process is never aborted
+			assert!(aborted_rx.next().now_or_never().is_none());
+		});
+	}
+
+	#[test]
+	fn alerts_when_finality_is_stalled() {
+		async_std::task::block_on(async {
+			let (
+				(_runtime_version_tx, runtime_version_rx),
+				(_free_native_balance_tx, free_native_balance_rx),
+				(slept_tx, mut slept_rx),
+				(aborted_tx, _aborted_rx),
+				(mut storage_value_tx, storage_value_rx),
+				(alerts_tx, mut alerts_rx),
+			) = (unbounded(), unbounded(), unbounded(), unbounded(), unbounded(), unbounded());
+			let policy = GuardPolicy::default()
+				.with_condition_policy("finality-stalled=webhook:http://alert".parse().unwrap())
+				.with_finality_stall_timeout(Duration::from_secs(90));
+			guard_finality_stall(
+				TestEnvironment {
+					runtime_version_rx,
+					free_native_balance_rx,
+					slept_tx,
+					aborted_tx,
+					hooks: TestHooks { storage_value_rx, alerts_tx, ..Default::default() },
+				},
+				policy,
+				"Bridged",
+				StorageKey(vec![42]),
+			);
+
+			// best finalized header is not updated for two minutes
+			for _ in 0..3 {
+				storage_value_tx.send(Some(StorageData(vec![1]))).await.unwrap();
+				slept_rx.next().await;
+			}
+
+			// then alert is sent
+			assert!(alerts_rx.next().await.unwrap().is_failing);
+
+			// and when best finalized header is updated, recovery alert is sent
+			storage_value_tx.send(Some(StorageData(vec![2]))).await.unwrap();
+			assert!(!alerts_rx.next().await.unwrap().is_failing);
+		});
+	}
+
+	#[test]
+	fn near_limit_is_detected() {
+		assert!(!is_near_limit(0, 10));
+		assert!(!is_near_limit(8, 10));
+		assert!(is_near_limit(9, 10));
+		assert!(is_near_limit(10, 10));
+		assert!(is_near_limit(11, 10));
+	}
 }
@@ -22,10 +22,13 @@ mod chain;
 mod client;
 mod error;
 mod rpc;
+mod runtime_upgrade;
+mod signer;
 mod sync_header;
 
 pub mod guard;
 pub mod metrics;
+pub mod price_oracle;
 
 use std::time::Duration;
 
@@ -37,6 +40,8 @@ pub use crate::{
 	},
 	client::{ChainRuntimeVersion, Client, OpaqueGrandpaAuthoritiesSet, Subscription},
 	error::{Error, Result},
+	runtime_upgrade::{bridge_call_indices, encoded_call_indices, BridgeCall, CallIndices},
+	signer::{Signer, SignerBackend},
 	sync_header::SyncHeader,
 };
 pub use bp_runtime::{
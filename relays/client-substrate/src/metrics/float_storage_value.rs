@@ -14,15 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{chain::Chain, client::Client, Error as SubstrateError};
+use crate::{
+	chain::Chain, client::Client, price_oracle::StorageValuePriceOracle, Error as SubstrateError,
+};
 
-use async_std::sync::{Arc, RwLock};
 use async_trait::async_trait;
 use codec::Decode;
 use num_traits::One;
 use relay_utils::metrics::{
-	metric_name, register, F64SharedRef, Gauge, Metric, PrometheusError, Registry,
-	StandaloneMetric, F64,
+	metric_name, register, Gauge, Metric, PrometheusError, Registry, StandaloneMetric, F64,
 };
 use sp_core::storage::{StorageData, StorageKey};
 use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber, FixedU128};
@@ -71,7 +71,6 @@ pub struct FloatStorageValueMetric<C: Chain, V: FloatStorageValue> {
 	client: Client<C>,
 	storage_key: StorageKey,
 	metric: Gauge<F64>,
-	shared_value_ref: F64SharedRef,
 	_phantom: PhantomData<V>,
 }
 
@@ -84,20 +83,22 @@ impl<C: Chain, V: FloatStorageValue> FloatStorageValueMetric<C, V> {
 		name: String,
 		help: String,
 	) -> Result<Self, PrometheusError> {
-		let shared_value_ref = Arc::new(RwLock::new(None));
 		Ok(FloatStorageValueMetric {
 			value_converter,
 			client,
 			storage_key,
 			metric: Gauge::new(metric_name(None, &name), help)?,
-			shared_value_ref,
 			_phantom: Default::default(),
 		})
 	}
 
-	/// Get shared reference to metric value.
-	pub fn shared_value_ref(&self) -> F64SharedRef {
-		self.shared_value_ref.clone()
+	/// Get price oracle that reads the same storage value.
+	pub fn oracle(&self) -> StorageValuePriceOracle<C, V> {
+		StorageValuePriceOracle::new(
+			self.value_converter.clone(),
+			self.client.clone(),
+			self.storage_key.clone(),
+		)
 	}
 }
 
@@ -114,20 +115,29 @@ impl<C: Chain, V: FloatStorageValue> StandaloneMetric for FloatStorageValueMetri
 	}
 
 	async fn update(&self) {
-		let value = self
-			.client
-			.raw_storage_value(self.storage_key.clone(), None)
-			.await
-			.and_then(|maybe_storage_value| {
-				self.value_converter.decode(maybe_storage_value).map(|maybe_fixed_point_value| {
-					maybe_fixed_point_value.map(|fixed_point_value| {
-						fixed_point_value.into_inner().unique_saturated_into() as f64 /
-							V::Value::DIV.unique_saturated_into() as f64
-					})
+		let value =
+			read_float_storage_value(&self.client, self.storage_key.clone(), &self.value_converter)
+				.await
+				.map_err(|e| e.to_string());
+		relay_utils::metrics::set_gauge_value(&self.metric, value);
+	}
+}
+
+/// Read fixed-point runtime storage value at the best block and convert it to float.
+pub(crate) async fn read_float_storage_value<C: Chain, V: FloatStorageValue>(
+	client: &Client<C>,
+	storage_key: StorageKey,
+	value_converter: &V,
+) -> Result<Option<f64>, SubstrateError> {
+	client
+		.raw_storage_value(storage_key, None)
+		.await
+		.and_then(|maybe_storage_value| {
+			value_converter.decode(maybe_storage_value).map(|maybe_fixed_point_value| {
+				maybe_fixed_point_value.map(|fixed_point_value| {
+					fixed_point_value.into_inner().unique_saturated_into() as f64 /
+						V::Value::DIV.unique_saturated_into() as f64
 				})
 			})
-			.map_err(|e| e.to_string());
-		relay_utils::metrics::set_gauge_value(&self.metric, value.clone());
-		*self.shared_value_ref.write().await = value.ok().and_then(|x| x);
-	}
+		})
 }
@@ -19,5 +19,5 @@
 pub use float_storage_value::{FixedU128OrOne, FloatStorageValue, FloatStorageValueMetric};
 pub use storage_proof_overhead::StorageProofOverheadMetric;
 
-mod float_storage_value;
+pub(crate) mod float_storage_value;
 mod storage_proof_overhead;
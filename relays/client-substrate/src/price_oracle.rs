@@ -0,0 +1,60 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Price oracles that are reading values from the Substrate runtime storage.
+
+use crate::{
+	chain::Chain,
+	client::Client,
+	metrics::{float_storage_value::read_float_storage_value, FloatStorageValue},
+};
+
+use async_trait::async_trait;
+use relay_utils::{error::Error as RelayUtilsError, price_oracle::PriceOracle};
+use sp_core::storage::StorageKey;
+use std::fmt::Debug;
+
+/// Price oracle that reads fixed-point runtime storage value (e.g. conversion rate parameter of
+/// the messages pallet) at the best block.
+#[derive(Clone, Debug)]
+pub struct StorageValuePriceOracle<C: Chain, V> {
+	value_converter: V,
+	client: Client<C>,
+	storage_key: StorageKey,
+}
+
+impl<C: Chain, V: FloatStorageValue> StorageValuePriceOracle<C, V> {
+	/// Create new oracle.
+	pub fn new(value_converter: V, client: Client<C>, storage_key: StorageKey) -> Self {
+		StorageValuePriceOracle { value_converter, client, storage_key }
+	}
+}
+
+#[async_trait]
+impl<C: Chain, V: FloatStorageValue + Debug> PriceOracle for StorageValuePriceOracle<C, V> {
+	async fn price(&self) -> relay_utils::error::Result<f64> {
+		read_float_storage_value(&self.client, self.storage_key.clone(), &self.value_converter)
+			.await
+			.map_err(|e| RelayUtilsError::ReadStoredTokenPrice(e.to_string()))?
+			.ok_or_else(|| {
+				RelayUtilsError::ReadStoredTokenPrice(format!(
+					"value {:?} is missing from the {} storage",
+					self.storage_key,
+					C::NAME,
+				))
+			})
+	}
+}
@@ -55,6 +55,8 @@ jsonrpsee_proc_macros::rpc_client_api! {
 		fn state_prove_storage(keys: Vec<StorageKey>, hash: Option<C::Hash>) -> ReadProof<C::Hash>;
 		#[rpc(method = "state_getRuntimeVersion", positional_params)]
 		fn state_runtime_version() -> RuntimeVersion;
+		#[rpc(method = "state_getMetadata", positional_params)]
+		fn state_get_metadata(at_block: Option<C::Hash>) -> Bytes;
 		#[rpc(method = "payment_queryFeeDetails", positional_params)]
 		fn payment_query_fee_details(extrinsic: Bytes, at_block: Option<C::Hash>) -> FeeDetails<NumberOrHex>;
 	}
@@ -0,0 +1,265 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime upgrades tracking.
+//!
+//! When the relay is signing transactions with the bundled (or custom) runtime version, every
+//! runtime upgrade makes these transactions invalid. The client follows runtime upgrades using
+//! the `state_subscribeRuntimeVersion` subscription, refreshes the runtime version that is used
+//! to sign transactions and checks that bridge calls, encoded by relay loops, have the same
+//! pallet and call indices in the actual runtime.
+
+use crate::{error::Error, Result};
+
+use codec::{Decode, Encode};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use scale_info::TypeDef;
+use std::{collections::BTreeMap, fmt};
+
+/// Bridge call that is submitted by relay loops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BridgeCall {
+	/// Name of the pallet (as it is specified in the `construct_runtime` macro call).
+	pub pallet: &'static str,
+	/// Name of the call.
+	pub call: &'static str,
+}
+
+impl fmt::Display for BridgeCall {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		write!(fmt, "{}::{}", self.pallet, self.call)
+	}
+}
+
+/// Indices of the pallet and of the call within the runtime `Call` enum. This is the prefix
+/// of every SCALE-encoded call.
+pub type CallIndices = (u8, u8);
+
+/// Returns indices of the encoded runtime call.
+pub fn encoded_call_indices(call: &impl Encode) -> CallIndices {
+	call.using_encoded(|encoded| {
+		(encoded.get(0).copied().unwrap_or_default(), encoded.get(1).copied().unwrap_or_default())
+	})
+}
+
+/// Runtime upgrades tracking state, shared by all clones of the client.
+#[derive(Debug, Default)]
+pub(crate) struct RuntimeUpgradeState {
+	/// Spec version of the runtime that has been checked by the tracker.
+	pub spec_version: Option<u32>,
+	/// Indices of tracked calls, encoded by the relay (i.e. using the bundled runtime).
+	pub tracked_calls: BTreeMap<BridgeCall, CallIndices>,
+	/// Tracked calls that are incompatible with the current runtime and the reason.
+	pub incompatible_calls: BTreeMap<BridgeCall, String>,
+	/// True if the runtime version subscription has been started.
+	pub is_subscribed: bool,
+}
+
+impl RuntimeUpgradeState {
+	/// Track given call. Returns true if the call has not been tracked before or if the relay
+	/// has started encoding it using other indices.
+	pub fn track_call(&mut self, call: BridgeCall, indices: CallIndices) -> bool {
+		self.tracked_calls.insert(call, indices) != Some(indices)
+	}
+
+	/// Update state after checking the runtime. If `actual_indices` are `None`, the runtime
+	/// metadata has not been recognized and all calls are assumed to be compatible.
+	pub fn on_runtime_upgrade(
+		&mut self,
+		spec_version: u32,
+		actual_indices: Option<&BTreeMap<BridgeCall, Option<CallIndices>>>,
+	) {
+		self.spec_version = Some(spec_version);
+		let actual_indices = match actual_indices {
+			Some(actual_indices) => actual_indices,
+			None => {
+				self.incompatible_calls.clear();
+				return
+			},
+		};
+		self.incompatible_calls = self
+			.tracked_calls
+			.iter()
+			.filter_map(|(call, expected)| {
+				let reason = match actual_indices.get(call).copied().flatten() {
+					Some(actual) if actual == *expected => return None,
+					Some(actual) => format!(
+						"indices of {} have changed from {:?} to {:?} in runtime {}",
+						call, expected, actual, spec_version,
+					),
+					None => format!("{} is missing from runtime {}", call, spec_version),
+				};
+				Some((*call, reason))
+			})
+			.collect();
+	}
+
+	/// Returns error if any of given calls is incompatible with the current runtime.
+	pub fn ensure_compatible(&self, calls: &[BridgeCall]) -> Result<()> {
+		match calls.iter().find_map(|call| self.incompatible_calls.get(call)) {
+			Some(reason) => Err(Error::IncompatibleRuntime(reason.clone())),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Read indices of given calls from the SCALE-encoded runtime metadata. Missing calls are
+/// mapped to `None`. Returns `None` if the metadata version is not supported (only V14 metadata
+/// carries call names in a form that we're able to read).
+pub fn bridge_call_indices(
+	raw_metadata: &[u8],
+	calls: impl IntoIterator<Item = BridgeCall>,
+) -> Result<Option<BTreeMap<BridgeCall, Option<CallIndices>>>> {
+	let metadata = match RuntimeMetadataPrefixed::decode(&mut &raw_metadata[..])?.1 {
+		RuntimeMetadata::V14(metadata) => metadata,
+		_ => return Ok(None),
+	};
+
+	Ok(Some(
+		calls
+			.into_iter()
+			.map(|call| {
+				let indices = metadata
+					.pallets
+					.iter()
+					.find(|pallet| pallet.name == call.pallet)
+					.and_then(|pallet| {
+						let calls_type = metadata.types.resolve(pallet.calls.as_ref()?.ty.id())?;
+						match calls_type.type_def() {
+							TypeDef::Variant(variants) => variants
+								.variants()
+								.iter()
+								.find(|variant| variant.name() == call.call)
+								.map(|variant| (pallet.index, variant.index())),
+							_ => None,
+						}
+					});
+				(call, indices)
+			})
+			.collect(),
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SUBMIT_FINALITY_PROOF: BridgeCall =
+		BridgeCall { pallet: "BridgeRialtoGrandpa", call: "submit_finality_proof" };
+	const RECEIVE_MESSAGES_PROOF: BridgeCall =
+		BridgeCall { pallet: "BridgeRialtoMessages", call: "receive_messages_proof" };
+
+	fn tracking_state() -> RuntimeUpgradeState {
+		RuntimeUpgradeState {
+			spec_version: Some(1),
+			tracked_calls: vec![
+				(SUBMIT_FINALITY_PROOF, (10, 0)),
+				(RECEIVE_MESSAGES_PROOF, (11, 5)),
+			]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn calls_are_compatible_if_indices_are_the_same() {
+		let mut state = tracking_state();
+		state.on_runtime_upgrade(
+			2,
+			Some(
+				&vec![
+					(SUBMIT_FINALITY_PROOF, Some((10, 0))),
+					(RECEIVE_MESSAGES_PROOF, Some((11, 5))),
+				]
+				.into_iter()
+				.collect(),
+			),
+		);
+		assert_eq!(state.spec_version, Some(2));
+		assert!(state
+			.ensure_compatible(&[SUBMIT_FINALITY_PROOF, RECEIVE_MESSAGES_PROOF])
+			.is_ok());
+	}
+
+	#[test]
+	fn only_changed_calls_are_incompatible() {
+		let mut state = tracking_state();
+		state.on_runtime_upgrade(
+			2,
+			Some(
+				&vec![
+					(SUBMIT_FINALITY_PROOF, Some((10, 0))),
+					(RECEIVE_MESSAGES_PROOF, Some((12, 5))),
+				]
+				.into_iter()
+				.collect(),
+			),
+		);
+		assert!(state.ensure_compatible(&[SUBMIT_FINALITY_PROOF]).is_ok());
+		assert!(matches!(
+			state.ensure_compatible(&[RECEIVE_MESSAGES_PROOF]),
+			Err(Error::IncompatibleRuntime(_)),
+		));
+
+		// missing call is incompatible too
+		state.on_runtime_upgrade(
+			3,
+			Some(&vec![(SUBMIT_FINALITY_PROOF, None)].into_iter().collect()),
+		);
+		assert!(state.ensure_compatible(&[SUBMIT_FINALITY_PROOF]).is_err());
+		assert!(state.ensure_compatible(&[RECEIVE_MESSAGES_PROOF]).is_err());
+
+		// and calls are compatible again once indices are restored
+		state.on_runtime_upgrade(
+			4,
+			Some(
+				&vec![
+					(SUBMIT_FINALITY_PROOF, Some((10, 0))),
+					(RECEIVE_MESSAGES_PROOF, Some((11, 5))),
+				]
+				.into_iter()
+				.collect(),
+			),
+		);
+		assert!(state
+			.ensure_compatible(&[SUBMIT_FINALITY_PROOF, RECEIVE_MESSAGES_PROOF])
+			.is_ok());
+	}
+
+	#[test]
+	fn calls_are_compatible_if_metadata_is_not_recognized() {
+		let mut state = tracking_state();
+		state.on_runtime_upgrade(2, Some(&Default::default()));
+		assert!(state.ensure_compatible(&[SUBMIT_FINALITY_PROOF]).is_err());
+
+		state.on_runtime_upgrade(3, None);
+		assert!(state.ensure_compatible(&[SUBMIT_FINALITY_PROOF]).is_ok());
+	}
+
+	#[test]
+	fn call_is_retracked_when_indices_change() {
+		let mut state = tracking_state();
+		assert!(!state.track_call(SUBMIT_FINALITY_PROOF, (10, 0)));
+		assert!(state.track_call(SUBMIT_FINALITY_PROOF, (12, 0)));
+		assert!(state.track_call(BridgeCall { pallet: "System", call: "remark" }, (0, 1)));
+	}
+
+	#[test]
+	fn encoded_call_indices_works() {
+		assert_eq!(encoded_call_indices(&(3u8, 7u8, 42u64)), (3, 7));
+	}
+}
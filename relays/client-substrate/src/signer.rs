@@ -0,0 +1,262 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transactions signer.
+//!
+//! The signer is either a local key pair, or a remote signing service. The remote signing
+//! service is expected to implement simple HTTP/JSON protocol. The relay sends `POST` request
+//! with `{"public": "0x<hex>", "payload": "0x<hex>"}` body, and the service responds with
+//! `{"signature": "0x<hex>"}`, where signature is the SCALE-encoded signature of the payload.
+//!
+//! Remote signing is a blocking operation, so transactions are signed (see
+//! `Client::submit_signed_extrinsic`) in a separate thread, dedicated to blocking tasks. The
+//! request fails if the service hasn't responded in `REMOTE_SIGNER_TIMEOUT`.
+
+use crate::error::{Error, Result};
+
+use codec::{Decode, Encode};
+use sp_core::Pair;
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+/// Maximal time we're waiting for the remote signing service response.
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Signing backend.
+pub trait SignerBackend<P: Pair>: Send + Sync {
+	/// Sign given message.
+	fn sign(&self, message: &[u8]) -> Result<P::Signature>;
+}
+
+impl<P: Pair> SignerBackend<P> for P {
+	fn sign(&self, message: &[u8]) -> Result<P::Signature> {
+		Ok(Pair::sign(self, message))
+	}
+}
+
+/// Transactions signer.
+pub struct Signer<P: Pair> {
+	public: P::Public,
+	backend: Arc<dyn SignerBackend<P>>,
+}
+
+impl<P: Pair> Signer<P> {
+	/// Create signer that is using given backend to sign messages on behalf of given account.
+	pub fn new(public: P::Public, backend: Arc<dyn SignerBackend<P>>) -> Self {
+		Signer { public, backend }
+	}
+
+	/// Create signer that is using remote signing service.
+	pub fn remote(public: P::Public, url: String) -> Self
+	where
+		P::Public: Encode,
+		P::Signature: Decode,
+	{
+		let backend = RemoteSigner { url, public: public.encode(), timeout: REMOTE_SIGNER_TIMEOUT };
+		Signer::new(public, Arc::new(backend))
+	}
+
+	/// Returns public key of the signer.
+	pub fn public(&self) -> P::Public {
+		self.public.clone()
+	}
+
+	/// Sign given message.
+	///
+	/// Local key pairs can't fail. Remote signer errors are returned to the caller and the
+	/// message is not retried here - failed transactions are resubmitted by relay loops.
+	pub fn sign(&self, message: &[u8]) -> Result<P::Signature> {
+		self.backend.sign(message).map_err(|error| {
+			log::error!(target: "bridge", "Failed to sign message: {:?}", error);
+			error
+		})
+	}
+}
+
+impl<P: Pair> From<P> for Signer<P> {
+	fn from(pair: P) -> Self {
+		Signer::new(pair.public(), Arc::new(pair))
+	}
+}
+
+impl<P: Pair> Clone for Signer<P> {
+	fn clone(&self) -> Self {
+		Signer { public: self.public.clone(), backend: self.backend.clone() }
+	}
+}
+
+impl<P: Pair> Debug for Signer<P> {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		fmt.debug_struct("Signer").finish()
+	}
+}
+
+/// Remote signing service.
+struct RemoteSigner {
+	url: String,
+	public: Vec<u8>,
+	timeout: Duration,
+}
+
+impl RemoteSigner {
+	/// Ask remote service to sign given message.
+	fn request_signature(&self, message: &[u8]) -> Result<Vec<u8>> {
+		use isahc::{config::Configurable, ReadResponseExt, Request};
+
+		let request_body = serde_json::json!({
+			"public": format!("0x{}", hex::encode(&self.public)),
+			"payload": format!("0x{}", hex::encode(message)),
+		})
+		.to_string();
+		let request = Request::post(&self.url)
+			.header("Content-Type", "application/json")
+			.timeout(self.timeout)
+			.body(request_body)
+			.map_err(|e| Error::RemoteSigner(e.to_string()))?;
+		let response = isahc::send(request)
+			.map_err(|e| Error::RemoteSigner(e.to_string()))?
+			.text()
+			.map_err(|e| Error::RemoteSigner(e.to_string()))?;
+
+		let response: serde_json::Value = serde_json::from_str(&response)
+			.map_err(|e| Error::RemoteSigner(format!("invalid response {:?}: {}", response, e)))?;
+		let signature =
+			response.get("signature").and_then(|signature| signature.as_str()).ok_or_else(
+				|| Error::RemoteSigner(format!("missing signature in response {:?}", response)),
+			)?;
+		hex::decode(signature.trim_start_matches("0x"))
+			.map_err(|e| Error::RemoteSigner(format!("invalid signature {:?}: {}", signature, e)))
+	}
+}
+
+impl<P: Pair> SignerBackend<P> for RemoteSigner
+where
+	P::Signature: Decode,
+{
+	fn sign(&self, message: &[u8]) -> Result<P::Signature> {
+		let signature = self.request_signature(message)?;
+		P::Signature::decode(&mut &signature[..]).map_err(|e| {
+			Error::RemoteSigner(format!("failed to decode signature {:?}: {}", signature, e))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::sr25519;
+	use std::{
+		io::{Read, Write},
+		net::TcpListener,
+	};
+
+	/// Start HTTP server that signs payload of the single request with given key pair.
+	fn start_stub_signer(pair: sr25519::Pair) -> String {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let address = listener.local_addr().unwrap();
+		std::thread::spawn(move || {
+			let (mut stream, _) = listener.accept().unwrap();
+			let mut request = Vec::new();
+			let mut buffer = [0u8; 1024];
+			let body = loop {
+				let read = stream.read(&mut buffer).unwrap();
+				request.extend_from_slice(&buffer[..read]);
+				let request = String::from_utf8_lossy(&request).into_owned();
+				if let Some((_, body)) = request.split_once("\r\n\r\n") {
+					if let Ok(body) = serde_json::from_str::<serde_json::Value>(body) {
+						break body
+					}
+				}
+			};
+
+			assert_eq!(
+				body["public"].as_str().unwrap(),
+				format!("0x{}", hex::encode(pair.public().encode())),
+			);
+			let payload =
+				hex::decode(body["payload"].as_str().unwrap().trim_start_matches("0x")).unwrap();
+			let response = serde_json::json!({
+				"signature": format!("0x{}", hex::encode(Pair::sign(&pair, &payload).encode())),
+			})
+			.to_string();
+			write!(
+				stream,
+				"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+				Connection: close\r\n\r\n{}",
+				response.len(),
+				response,
+			)
+			.unwrap();
+		});
+		format!("http://{}/sign", address)
+	}
+
+	#[test]
+	fn key_pair_signer_works() {
+		let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+		let signer: Signer<sr25519::Pair> = pair.clone().into();
+		assert_eq!(signer.public(), pair.public());
+		assert!(sr25519::Pair::verify(
+			&signer.sign(b"message").unwrap(),
+			b"message",
+			&pair.public()
+		));
+	}
+
+	#[test]
+	fn remote_signer_works() {
+		let pair = sr25519::Pair::from_string("//Bob", None).unwrap();
+		let url = start_stub_signer(pair.clone());
+		let signer = Signer::<sr25519::Pair>::remote(pair.public(), url);
+		assert_eq!(signer.public(), pair.public());
+		assert!(sr25519::Pair::verify(
+			&signer.sign(b"message").unwrap(),
+			b"message",
+			&pair.public()
+		));
+	}
+
+	#[test]
+	fn remote_signer_error_is_returned() {
+		let pair = sr25519::Pair::from_string("//Bob", None).unwrap();
+		// select some port that nobody listens to
+		let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+		let signer =
+			Signer::<sr25519::Pair>::remote(pair.public(), format!("http://{}/sign", address));
+		assert!(matches!(signer.sign(b"message"), Err(Error::RemoteSigner(_))));
+	}
+
+	#[test]
+	fn remote_signer_request_times_out() {
+		// accept connection, but never respond
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let address = listener.local_addr().unwrap();
+		std::thread::spawn(move || {
+			let _connection = listener.accept().unwrap();
+			std::thread::sleep(Duration::from_secs(60));
+		});
+
+		let pair = sr25519::Pair::from_string("//Bob", None).unwrap();
+		let signer = RemoteSigner {
+			url: format!("http://{}/sign", address),
+			public: pair.public().encode(),
+			timeout: Duration::from_millis(100),
+		};
+		assert!(matches!(
+			SignerBackend::<sr25519::Pair>::sign(&signer, b"message"),
+			Err(Error::RemoteSigner(_))
+		));
+	}
+}
@@ -50,6 +50,31 @@ where
 	)
 }
 
+/// Rococo account ownership digest from Wococo, protected from replay.
+///
+/// The byte vector returned by this function should be signed with a Rococo account private key.
+/// Every `nonce` may only be used once by the Rococo account.
+pub fn wococo_to_rococo_account_ownership_digest_with_nonce<Call, AccountId, SpecVersion>(
+	rococo_call: &Call,
+	wococo_account_id: AccountId,
+	nonce: bp_message_dispatch::TargetAccountNonce,
+	rococo_spec_version: SpecVersion,
+) -> Vec<u8>
+where
+	Call: codec::Encode,
+	AccountId: codec::Encode,
+	SpecVersion: codec::Encode,
+{
+	pallet_bridge_dispatch::account_ownership_digest_with_nonce(
+		rococo_call,
+		wococo_account_id,
+		nonce,
+		rococo_spec_version,
+		bp_runtime::WOCOCO_CHAIN_ID,
+		bp_runtime::ROCOCO_CHAIN_ID,
+	)
+}
+
 /// Wococo Runtime `Call` enum.
 ///
 /// The enum represents a subset of possible `Call`s we can send to Rococo chain.
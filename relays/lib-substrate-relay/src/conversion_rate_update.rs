@@ -16,7 +16,7 @@
 
 //! Tools for updating conversion rate that is stored in the runtime storage.
 
-use relay_utils::metrics::F64SharedRef;
+use relay_utils::price_oracle::{PriceOracle, SharedPriceOracle};
 use std::{future::Future, time::Duration};
 
 /// Duration between updater iterations.
@@ -34,13 +34,14 @@ enum TransactionStatus {
 /// Run infinite conversion rate updater loop.
 ///
 /// The loop is maintaining the Left -> Right conversion rate, used as `RightTokens = LeftTokens *
-/// Rate`.
+/// Rate`. The stored conversion rate is usually read from the runtime storage and token prices
+/// are read from some external price oracles.
 pub fn run_conversion_rate_update_loop<
 	SubmitConversionRateFuture: Future<Output = anyhow::Result<()>> + Send + 'static,
 >(
-	left_to_right_stored_conversion_rate: F64SharedRef,
-	left_to_base_conversion_rate: F64SharedRef,
-	right_to_base_conversion_rate: F64SharedRef,
+	left_to_right_stored_conversion_rate: SharedPriceOracle,
+	left_to_base_conversion_rate: SharedPriceOracle,
+	right_to_base_conversion_rate: SharedPriceOracle,
 	max_difference_ratio: f64,
 	submit_conversion_rate: impl Fn(f64) -> SubmitConversionRateFuture + Send + 'static,
 ) {
@@ -50,9 +51,9 @@ pub fn run_conversion_rate_update_loop<
 			async_std::task::sleep(SLEEP_DURATION).await;
 			let maybe_new_conversion_rate = maybe_select_new_conversion_rate(
 				&mut transaction_status,
-				&left_to_right_stored_conversion_rate,
-				&left_to_base_conversion_rate,
-				&right_to_base_conversion_rate,
+				&*left_to_right_stored_conversion_rate,
+				&*left_to_base_conversion_rate,
+				&*right_to_base_conversion_rate,
 				max_difference_ratio,
 			)
 			.await;
@@ -74,13 +75,13 @@ pub fn run_conversion_rate_update_loop<
 /// Select new conversion rate to submit to the node.
 async fn maybe_select_new_conversion_rate(
 	transaction_status: &mut TransactionStatus,
-	left_to_right_stored_conversion_rate: &F64SharedRef,
-	left_to_base_conversion_rate: &F64SharedRef,
-	right_to_base_conversion_rate: &F64SharedRef,
+	left_to_right_stored_conversion_rate: &dyn PriceOracle,
+	left_to_base_conversion_rate: &dyn PriceOracle,
+	right_to_base_conversion_rate: &dyn PriceOracle,
 	max_difference_ratio: f64,
 ) -> Option<(f64, f64)> {
 	let left_to_right_stored_conversion_rate =
-		(*left_to_right_stored_conversion_rate.read().await)?;
+		read_price("stored conversion rate", left_to_right_stored_conversion_rate).await?;
 	match *transaction_status {
 		TransactionStatus::Idle => (),
 		TransactionStatus::Submitted(previous_left_to_right_stored_conversion_rate) => {
@@ -103,8 +104,10 @@ async fn maybe_select_new_conversion_rate(
 		},
 	}
 
-	let left_to_base_conversion_rate = (*left_to_base_conversion_rate.read().await)?;
-	let right_to_base_conversion_rate = (*right_to_base_conversion_rate.read().await)?;
+	let left_to_base_conversion_rate =
+		read_price("left token price", left_to_base_conversion_rate).await?;
+	let right_to_base_conversion_rate =
+		read_price("right token price", right_to_base_conversion_rate).await?;
 	let actual_left_to_right_conversion_rate =
 		right_to_base_conversion_rate / left_to_base_conversion_rate;
 
@@ -118,10 +121,31 @@ async fn maybe_select_new_conversion_rate(
 	Some((left_to_right_stored_conversion_rate, actual_left_to_right_conversion_rate))
 }
 
+/// Read price from the oracle, logging error if it has failed.
+async fn read_price(price_name: &str, oracle: &dyn PriceOracle) -> Option<f64> {
+	match oracle.price().await {
+		Ok(price) => Some(price),
+		Err(error) => {
+			log::trace!(target: "bridge", "Failed to read {} from {:?}: {:?}", price_name, oracle, error);
+			None
+		},
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use async_std::sync::{Arc, RwLock};
+	use async_trait::async_trait;
+
+	#[derive(Debug)]
+	struct TestPriceOracle(Option<f64>);
+
+	#[async_trait]
+	impl PriceOracle for TestPriceOracle {
+		async fn price(&self) -> relay_utils::error::Result<f64> {
+			self.0.ok_or_else(|| relay_utils::Error::MissingResponseValue("test".into()))
+		}
+	}
 
 	fn test_maybe_select_new_conversion_rate(
 		mut transaction_status: TransactionStatus,
@@ -130,9 +154,9 @@ mod tests {
 		right_to_base_conversion_rate: Option<f64>,
 		max_difference_ratio: f64,
 	) -> (Option<(f64, f64)>, TransactionStatus) {
-		let stored_conversion_rate = Arc::new(RwLock::new(stored_conversion_rate));
-		let left_to_base_conversion_rate = Arc::new(RwLock::new(left_to_base_conversion_rate));
-		let right_to_base_conversion_rate = Arc::new(RwLock::new(right_to_base_conversion_rate));
+		let stored_conversion_rate = TestPriceOracle(stored_conversion_rate);
+		let left_to_base_conversion_rate = TestPriceOracle(left_to_base_conversion_rate);
+		let right_to_base_conversion_rate = TestPriceOracle(right_to_base_conversion_rate);
 		let result = async_std::task::block_on(maybe_select_new_conversion_rate(
 			&mut transaction_status,
 			&stored_conversion_rate,
@@ -49,6 +49,9 @@ pub enum Error<Hash: Debug + MaybeDisplay, HeaderNumber: Debug + MaybeDisplay> {
 	/// Failed to retrieve GRANDPA authorities at the given header from the source chain.
 	#[error("Failed to retrive {0} GRANDPA authorities set at header {1}: {2:?}")]
 	RetrieveAuthorities(&'static str, Hash, client::Error),
+	/// Failed to retrieve GRANDPA authorities set id at the given header from the source chain.
+	#[error("Failed to retrieve {0} GRANDPA authorities set id at header {1}: {2:?}")]
+	RetrieveAuthoritiesSetId(&'static str, Hash, client::Error),
 	/// Failed to decode GRANDPA authorities at the given header of the source chain.
 	#[error("Failed to decode {0} GRANDPA authorities set at header {1}: {2:?}")]
 	DecodeAuthorities(&'static str, Hash, codec::Error),
@@ -58,4 +61,31 @@ pub enum Error<Hash: Debug + MaybeDisplay, HeaderNumber: Debug + MaybeDisplay> {
 	/// Failed to retrieve best finalized source header hash from the target chain.
 	#[error("Failed to retrieve best finalized {0} header from the target chain: {1}")]
 	RetrieveBestFinalizedHeaderHash(&'static str, client::Error),
+	/// Failed to retrieve initial source header hash from the target chain.
+	#[error("Failed to retrieve initial {0} header from the target chain: {1}")]
+	RetrieveInitialHeaderHash(&'static str, client::Error),
+	/// Failed to retrieve current source GRANDPA authorities set from the target chain.
+	#[error("Failed to retrieve current {0} GRANDPA authorities set from the target chain: {1}")]
+	RetrieveBridgedAuthorities(&'static str, client::Error),
+	/// The headers bridge is not initialized.
+	#[error("{0}-headers bridge is not initialized")]
+	NotInitialized(&'static str),
+	/// The headers bridge has been initialized with header, other than the checkpoint header.
+	#[error(
+		"{0}-headers bridge has been initialized with header {2}, but checkpoint header is {1}"
+	)]
+	CheckpointMismatch(&'static str, Hash, Hash),
+	/// Header, known to the headers bridge, is not a canonical header at the source node.
+	#[error("{0} header {1}, known to the bridge, is not canonical at the source node")]
+	NonCanonicalHeader(&'static str, Hash),
+	/// GRANDPA authorities set, known to the headers bridge, differs from the set at the source
+	/// node.
+	#[error("{0} GRANDPA authorities set at header {1} differs from the set, known to the bridge")]
+	AuthoritiesMismatch(&'static str, Hash),
+	/// GRANDPA authorities set id, known to the headers bridge, differs from the set id at the
+	/// source node.
+	#[error(
+		"{0} GRANDPA authorities set id at header {1} is {2}, but the bridge knows set id {3}"
+	)]
+	AuthoritiesSetIdMismatch(&'static str, Hash, u64, u64),
 }
@@ -16,10 +16,13 @@
 
 //! Tools for starting guards of finality relays.
 
-use crate::TransactionParams;
+use crate::{finality_pipeline::SubstrateFinalitySyncPipeline, TransactionParams};
 
+use bp_header_chain::storage_keys::best_finalized_hash_key;
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, ChainWithBalances, TransactionSignScheme,
+	guard::{GuardPolicy, PalletHaltFlag},
+	AccountIdOf, AccountKeyPairOf, Chain, ChainWithBalances, ChainWithGrandpa, Client,
+	TransactionSignScheme,
 };
 use sp_core::Pair;
 
@@ -29,20 +32,49 @@ pub async fn start<C: ChainWithBalances, S: TransactionSignScheme<Chain = C>>(
 	transaction_params: &TransactionParams<S::AccountKeyPair>,
 	enable_version_guard: bool,
 	maximal_balance_decrease_per_day: C::Balance,
+	guard_policy: &GuardPolicy,
 ) -> relay_substrate_client::Result<()>
 where
 	AccountIdOf<C>: From<<AccountKeyPairOf<S> as Pair>::Public>,
 {
 	if enable_version_guard {
-		relay_substrate_client::guard::abort_on_spec_version_change(
+		// the client refreshes the version after adapting to runtime upgrade, so the guard only
+		// uses the startup version until then
+		relay_substrate_client::guard::guard_spec_version(
 			target_client.clone(),
+			guard_policy.clone(),
 			target_client.simple_runtime_version().await?.0,
 		);
 	}
-	relay_substrate_client::guard::abort_when_account_balance_decreased(
+	relay_substrate_client::guard::guard_account_balance(
 		target_client.clone(),
+		guard_policy.clone(),
 		transaction_params.signer.public().into(),
 		maximal_balance_decrease_per_day,
 	);
 	Ok(())
 }
+
+/// Start guards that are watching state of the bridge GRANDPA pallet at the target chain.
+///
+/// Unlike `start`, these guards are not chain-specific and may be started for any finality
+/// relay.
+pub fn start_bridge_pallet_guards<P: SubstrateFinalitySyncPipeline>(
+	target_client: &Client<P::TargetChain>,
+	guard_policy: &GuardPolicy,
+) where
+	P::TargetChain: ChainWithBalances,
+{
+	let pallet_name = P::SourceChain::WITH_CHAIN_GRANDPA_PALLET_NAME;
+	relay_substrate_client::guard::guard_finality_stall(
+		target_client.clone(),
+		guard_policy.clone(),
+		P::SourceChain::NAME,
+		best_finalized_hash_key(pallet_name),
+	);
+	relay_substrate_client::guard::guard_pallet_halted(
+		target_client.clone(),
+		guard_policy.clone(),
+		PalletHaltFlag::Grandpa(pallet_name),
+	);
+}
@@ -27,8 +27,8 @@ use bp_header_chain::justification::GrandpaJustification;
 use finality_relay::FinalitySyncPipeline;
 use pallet_bridge_grandpa::{Call as BridgeGrandpaCall, Config as BridgeGrandpaConfig};
 use relay_substrate_client::{
-	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain,
-	ChainWithGrandpa, Client, HashOf, HeaderOf, SyncHeader, TransactionSignScheme,
+	guard::GuardPolicy, transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BlockNumberOf,
+	CallOf, Chain, ChainWithGrandpa, Client, HashOf, HeaderOf, SyncHeader, TransactionSignScheme,
 };
 use relay_utils::metrics::MetricsParams;
 use sp_core::Pair;
@@ -58,6 +58,7 @@ pub trait SubstrateFinalitySyncPipeline: 'static + Clone + Debug + Send + Sync {
 		_target_client: &Client<Self::TargetChain>,
 		_transaction_params: &TransactionParams<AccountKeyPairOf<Self::TransactionSignScheme>>,
 		_enable_version_guard: bool,
+		_guard_policy: &GuardPolicy,
 	) -> relay_substrate_client::Result<()> {
 		Ok(())
 	}
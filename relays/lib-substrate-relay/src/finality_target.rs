@@ -30,12 +30,21 @@ use bp_header_chain::{justification::GrandpaJustification, storage_keys::is_halt
 use codec::Encode;
 use finality_relay::TargetClient;
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, BlockNumberOf, Chain, ChainWithGrandpa, Client, Error, HashOf,
-	HeaderOf, SignParam, SyncHeader, TransactionEra, TransactionSignScheme, UnsignedTransaction,
+	encoded_call_indices, AccountIdOf, AccountKeyPairOf, BlockNumberOf, BridgeCall, Chain,
+	ChainWithGrandpa, Client, Error, HashOf, HeaderOf, SignParam, SyncHeader, TransactionEra,
+	TransactionSignScheme, UnsignedTransaction,
 };
 use relay_utils::relay_loop::Client as RelayClient;
 use sp_core::{Bytes, Pair};
 
+/// Bridge call that is submitted to the target chain by the finality relay.
+pub fn submit_finality_proof_call<P: SubstrateFinalitySyncPipeline>() -> BridgeCall {
+	BridgeCall {
+		pallet: P::SourceChain::WITH_CHAIN_GRANDPA_PALLET_NAME,
+		call: "submit_finality_proof",
+	}
+}
+
 /// Substrate client as Substrate finality target.
 pub struct SubstrateFinalityTarget<P: SubstrateFinalitySyncPipeline> {
 	client: Client<P::TargetChain>,
@@ -118,12 +127,18 @@ where
 		let transaction_params = self.transaction_params.clone();
 		let call =
 			P::SubmitFinalityProofCallBuilder::build_submit_finality_proof_call(header, proof);
+		self.client
+			.ensure_bridge_calls_compatible(&[(
+				submit_finality_proof_call::<P>(),
+				encoded_call_indices(&call),
+			)])
+			.await?;
 		let (spec_version, transaction_version) = self.client.simple_runtime_version().await?;
 		self.client
 			.submit_signed_extrinsic(
 				self.transaction_params.signer.public().into(),
 				move |best_block_id, transaction_nonce| {
-					Bytes(
+					Ok(Bytes(
 						P::TransactionSignScheme::sign_transaction(SignParam {
 							spec_version,
 							transaction_version,
@@ -131,9 +146,9 @@ where
 							signer: transaction_params.signer.clone(),
 							era: TransactionEra::new(best_block_id, transaction_params.mortality),
 							unsigned: UnsignedTransaction::new(call, transaction_nonce),
-						})
+						})?
 						.encode(),
-					)
+					))
 				},
 			)
 			.await
@@ -20,18 +20,23 @@
 //! `pallet-bridge-grandpa` pallet. This transaction brings initial header
 //! and authorities set from source to target chain. The headers sync starts
 //! with this header.
+//!
+//! The initialization data is either prepared from the current state of the source node, or
+//! provided by the caller (e.g. read from the audited checkpoint file).
 
 use crate::error::Error;
 
 use bp_header_chain::{
 	find_grandpa_authorities_scheduled_change,
 	justification::{verify_justification, GrandpaJustification},
-	InitializationData,
+	AuthoritySet, InitializationData,
 };
 use codec::Decode;
 use finality_grandpa::voter_set::VoterSet;
 use num_traits::{One, Zero};
-use relay_substrate_client::{BlockNumberOf, Chain, ChainWithGrandpa, Client, HashOf};
+use relay_substrate_client::{
+	BlockNumberOf, Chain, ChainWithGrandpa, Client, Error as SubstrateError, HashOf,
+};
 use sp_core::Bytes;
 use sp_finality_grandpa::AuthorityList as GrandpaAuthoritiesSet;
 use sp_runtime::traits::Header as HeaderT;
@@ -41,7 +46,11 @@ pub async fn initialize<SourceChain: ChainWithGrandpa, TargetChain: Chain>(
 	source_client: Client<SourceChain>,
 	target_client: Client<TargetChain>,
 	target_transactions_signer: TargetChain::AccountId,
-	prepare_initialize_transaction: impl FnOnce(TargetChain::Index, InitializationData<SourceChain::Header>) -> Bytes
+	checkpoint: Option<InitializationData<SourceChain::Header>>,
+	prepare_initialize_transaction: impl FnOnce(
+			TargetChain::Index,
+			InitializationData<SourceChain::Header>,
+		) -> Result<Bytes, SubstrateError>
 		+ Send
 		+ 'static,
 ) {
@@ -49,6 +58,7 @@ pub async fn initialize<SourceChain: ChainWithGrandpa, TargetChain: Chain>(
 		source_client,
 		target_client,
 		target_transactions_signer,
+		checkpoint,
 		prepare_initialize_transaction,
 	)
 	.await;
@@ -77,7 +87,11 @@ async fn do_initialize<SourceChain: ChainWithGrandpa, TargetChain: Chain>(
 	source_client: Client<SourceChain>,
 	target_client: Client<TargetChain>,
 	target_transactions_signer: TargetChain::AccountId,
-	prepare_initialize_transaction: impl FnOnce(TargetChain::Index, InitializationData<SourceChain::Header>) -> Bytes
+	checkpoint: Option<InitializationData<SourceChain::Header>>,
+	prepare_initialize_transaction: impl FnOnce(
+			TargetChain::Index,
+			InitializationData<SourceChain::Header>,
+		) -> Result<Bytes, SubstrateError>
 		+ Send
 		+ 'static,
 ) -> Result<
@@ -95,14 +109,29 @@ async fn do_initialize<SourceChain: ChainWithGrandpa, TargetChain: Chain>(
 		return Ok(None)
 	}
 
-	let initialization_data = prepare_initialization_data(source_client).await?;
-	log::info!(
-		target: "bridge",
-		"Prepared initialization data for {}-headers bridge at {}: {:?}",
-		SourceChain::NAME,
-		TargetChain::NAME,
-		initialization_data,
-	);
+	let initialization_data = match checkpoint {
+		Some(checkpoint) => {
+			log::info!(
+				target: "bridge",
+				"Using checkpoint to initialize {}-headers bridge at {}: {:?}",
+				SourceChain::NAME,
+				TargetChain::NAME,
+				checkpoint,
+			);
+			checkpoint
+		},
+		None => {
+			let initialization_data = prepare_initialization_data(source_client).await?;
+			log::info!(
+				target: "bridge",
+				"Prepared initialization data for {}-headers bridge at {}: {:?}",
+				SourceChain::NAME,
+				TargetChain::NAME,
+				initialization_data,
+			);
+			initialization_data
+		},
+	};
 
 	let initialization_tx_hash = target_client
 		.submit_signed_extrinsic(target_transactions_signer, move |_, transaction_nonce| {
@@ -129,8 +158,92 @@ async fn is_initialized<SourceChain: ChainWithGrandpa, TargetChain: Chain>(
 		.is_some())
 }
 
+/// Verify that the headers bridge at the target chain has been initialized with the header and
+/// GRANDPA authorities set (both authorities and set id) that are known to the source node.
+///
+/// If `checkpoint` is provided, this also checks that the bridge has been initialized with the
+/// checkpoint header.
+pub async fn verify_initialization<SourceChain: ChainWithGrandpa, TargetChain: Chain>(
+	source_client: &Client<SourceChain>,
+	target_client: &Client<TargetChain>,
+	checkpoint: Option<&InitializationData<SourceChain::Header>>,
+) -> Result<(), Error<HashOf<SourceChain>, BlockNumberOf<SourceChain>>> {
+	let pallet_name = SourceChain::WITH_CHAIN_GRANDPA_PALLET_NAME;
+	let initial_hash = target_client
+		.storage_value::<HashOf<SourceChain>>(
+			bp_header_chain::storage_keys::initial_hash_key(pallet_name),
+			None,
+		)
+		.await
+		.map_err(|err| Error::RetrieveInitialHeaderHash(SourceChain::NAME, err))?
+		.ok_or(Error::NotInitialized(SourceChain::NAME))?;
+	if let Some(checkpoint) = checkpoint {
+		let checkpoint_hash = checkpoint.header.hash();
+		if checkpoint_hash != initial_hash {
+			return Err(Error::CheckpointMismatch(SourceChain::NAME, checkpoint_hash, initial_hash))
+		}
+	}
+	ensure_canonical_header(source_client, initial_hash).await?;
+
+	let best_finalized_hash = target_client
+		.storage_value::<HashOf<SourceChain>>(
+			bp_header_chain::storage_keys::best_finalized_hash_key(pallet_name),
+			None,
+		)
+		.await
+		.map_err(|err| Error::RetrieveBestFinalizedHeaderHash(SourceChain::NAME, err))?
+		.ok_or(Error::NotInitialized(SourceChain::NAME))?;
+	ensure_canonical_header(source_client, best_finalized_hash).await?;
+
+	let bridged_authorities_set = target_client
+		.storage_value::<AuthoritySet>(
+			bp_header_chain::storage_keys::current_authority_set_key(pallet_name),
+			None,
+		)
+		.await
+		.map_err(|err| Error::RetrieveBridgedAuthorities(SourceChain::NAME, err))?
+		.ok_or(Error::NotInitialized(SourceChain::NAME))?;
+	let authorities_set = source_authorities_set(source_client, best_finalized_hash).await?;
+	if bridged_authorities_set.authorities != authorities_set {
+		return Err(Error::AuthoritiesMismatch(SourceChain::NAME, best_finalized_hash))
+	}
+	let authorities_set_id = source_client
+		.grandpa_current_set_id(Some(best_finalized_hash))
+		.await
+		.map_err(|err| {
+			Error::RetrieveAuthoritiesSetId(SourceChain::NAME, best_finalized_hash, err)
+		})?;
+	if bridged_authorities_set.set_id != authorities_set_id {
+		return Err(Error::AuthoritiesSetIdMismatch(
+			SourceChain::NAME,
+			best_finalized_hash,
+			authorities_set_id,
+			bridged_authorities_set.set_id,
+		))
+	}
+
+	Ok(())
+}
+
+/// Ensure that the header with given hash is the canonical header at the source node.
+async fn ensure_canonical_header<SourceChain: Chain>(
+	source_client: &Client<SourceChain>,
+	header_hash: SourceChain::Hash,
+) -> Result<(), Error<SourceChain::Hash, <SourceChain::Header as HeaderT>::Number>> {
+	let header = source_header(source_client, header_hash).await?;
+	let canonical_hash = source_client
+		.block_hash_by_number(*header.number())
+		.await
+		.map_err(|err| Error::RetrieveHeader(SourceChain::NAME, header_hash, err))?;
+	if canonical_hash != header_hash {
+		return Err(Error::NonCanonicalHeader(SourceChain::NAME, header_hash))
+	}
+
+	Ok(())
+}
+
 /// Prepare initialization data for the GRANDPA verifier pallet.
-async fn prepare_initialization_data<SourceChain: Chain>(
+pub async fn prepare_initialization_data<SourceChain: Chain>(
 	source_client: Client<SourceChain>,
 ) -> Result<
 	InitializationData<SourceChain::Header>,
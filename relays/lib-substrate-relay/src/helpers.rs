@@ -16,13 +16,27 @@
 
 //! Substrate relay helpers
 
-use relay_utils::metrics::{FloatJsonValueMetric, PrometheusError};
+use relay_utils::{
+	metrics::{PrometheusError, TokenPriceMetric},
+	price_oracle::{HttpJsonPriceOracle, SharedPriceOracle},
+};
+use std::sync::Arc;
 
-/// Creates standalone token price metric.
-pub fn token_price_metric(token_id: &str) -> Result<FloatJsonValueMetric, PrometheusError> {
-	FloatJsonValueMetric::new(
+/// Creates price oracle that reads token price from the CoinGecko API.
+pub fn token_price_oracle(token_id: &str) -> SharedPriceOracle {
+	Arc::new(HttpJsonPriceOracle::new(
 		format!("https://api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies=btc", token_id),
 		format!("$.{}.btc", token_id),
+	))
+}
+
+/// Creates standalone token price metric.
+pub fn token_price_metric(
+	token_id: &str,
+	oracle: SharedPriceOracle,
+) -> Result<TokenPriceMetric, PrometheusError> {
+	TokenPriceMetric::new(
+		oracle,
 		format!("{}_to_base_conversion_rate", token_id.replace("-", "_")),
 		format!("Rate used to convert from {} to some BASE tokens", token_id.to_uppercase()),
 	)
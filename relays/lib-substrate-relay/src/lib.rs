@@ -18,6 +18,8 @@
 
 #![warn(missing_docs)]
 
+use relay_substrate_client::Signer;
+use sp_core::Pair;
 use std::time::Duration;
 
 pub mod conversion_rate_update;
@@ -28,6 +30,7 @@ pub mod finality_source;
 pub mod finality_target;
 pub mod headers_initialize;
 pub mod helpers;
+pub mod messages_guards;
 pub mod messages_lane;
 pub mod messages_metrics;
 pub mod messages_source;
@@ -45,9 +48,23 @@ pub const STALL_TIMEOUT: Duration = Duration::from_secs(60 * 60);
 
 /// Transaction creation parameters.
 #[derive(Clone, Debug)]
-pub struct TransactionParams<TS> {
+pub struct TransactionParams<TS: Pair> {
 	/// Transactions author.
-	pub signer: TS,
+	pub signer: Signer<TS>,
 	/// Transactions mortality.
 	pub mortality: Option<u32>,
 }
+
+impl<TS: Pair> TransactionParams<TS> {
+	/// Returns parameters of 'dummy' transactions that are only used to estimate transaction
+	/// fees and are never submitted.
+	///
+	/// Dummy transactions are signed by the local key pair, so the transactions signer (that
+	/// may be a remote signing service) isn't involved.
+	pub fn dummy(&self) -> Self {
+		TransactionParams {
+			signer: TS::from_seed(&Default::default()).into(),
+			mortality: self.mortality,
+		}
+	}
+}
@@ -0,0 +1,77 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tools for starting guards of message lanes.
+
+use crate::messages_lane::{lane_control, SubstrateMessageLane};
+
+use bp_messages::{storage_keys::inbound_lane_data_key, LaneId};
+use relay_substrate_client::{
+	guard::{GuardPolicy, PalletHaltFlag},
+	ChainWithBalances, ChainWithMessages, Client,
+};
+use relay_utils::admin::AdminRegistry;
+
+/// Start guards of message lanes.
+///
+/// The source chain messages pallet is checked for being halted and every inbound lane at the
+/// target chain is checked for being near its unrewarded relayers limits. The halted pallet
+/// guard may pause all given lanes of `P` and the unrewarded relayers guard only pauses the
+/// lane it is watching. Lanes may only be paused if `admin_registry` is provided.
+pub fn start<P: SubstrateMessageLane>(
+	source_client: &Client<P::SourceChain>,
+	target_client: &Client<P::TargetChain>,
+	lanes: &[LaneId],
+	admin_registry: Option<&AdminRegistry>,
+	guard_policy: &GuardPolicy,
+) where
+	P::SourceChain: ChainWithBalances,
+	P::TargetChain: ChainWithBalances,
+{
+	relay_substrate_client::guard::guard_pallet_halted(
+		source_client.clone(),
+		with_paused_lanes::<P>(guard_policy.clone(), admin_registry, lanes),
+		PalletHaltFlag::Messages(P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME),
+	);
+	for lane in lanes {
+		relay_substrate_client::guard::guard_unrewarded_relayers(
+			target_client.clone(),
+			with_paused_lanes::<P>(guard_policy.clone(), admin_registry, &[*lane]),
+			*lane,
+			inbound_lane_data_key(P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME, lane),
+			P::SourceChain::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
+			P::SourceChain::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+		);
+	}
+}
+
+/// Returns guard policy that pauses given lanes of `P` when the `GuardAction::Pause` action is
+/// performed.
+///
+/// [`GuardAction::Pause`]: relay_substrate_client::guard::GuardAction::Pause
+pub fn with_paused_lanes<P: SubstrateMessageLane>(
+	guard_policy: GuardPolicy,
+	admin_registry: Option<&AdminRegistry>,
+	lanes: &[LaneId],
+) -> GuardPolicy {
+	match admin_registry {
+		Some(admin_registry) => lanes
+			.iter()
+			.map(|lane| lane_control::<P>(admin_registry, *lane))
+			.fold(guard_policy, GuardPolicy::with_paused_lane),
+		None => guard_policy,
+	}
+}
@@ -31,13 +31,18 @@ use bridge_runtime_common::messages::{
 };
 use codec::Encode;
 use frame_support::weights::{GetDispatchInfo, Weight};
-use messages_relay::{message_lane::MessageLane, relay_strategy::RelayStrategy};
+use messages_relay::{
+	message_lane::MessageLane, message_lane_loop::RelayMode, relay_strategy::RelayStrategy,
+};
 use pallet_bridge_messages::{Call as BridgeMessagesCall, Config as BridgeMessagesConfig};
 use relay_substrate_client::{
 	transaction_stall_timeout, AccountKeyPairOf, BalanceOf, BlockNumberOf, CallOf, Chain,
 	ChainWithMessages, Client, HashOf, TransactionSignScheme,
 };
-use relay_utils::metrics::MetricsParams;
+use relay_utils::{
+	admin::{AdminRegistry, LaneControl},
+	metrics::MetricsParams,
+};
 use sp_core::Pair;
 use std::{convert::TryFrom, fmt::Debug, marker::PhantomData};
 
@@ -116,10 +121,27 @@ pub struct MessagesRelayParams<P: SubstrateMessageLane> {
 	pub lane_id: LaneId,
 	/// Metrics parameters.
 	pub metrics_params: MetricsParams,
-	/// Pre-registered standalone metrics.
+	/// Pre-registered standalone metrics. If `None`, metrics are created without explicit token
+	/// price oracles, so token prices are only tracked (using CoinGecko API) for chains that
+	/// have `TOKEN_ID`.
 	pub standalone_metrics: Option<StandaloneMessagesMetrics<P::SourceChain, P::TargetChain>>,
+	/// Races that are run by the relay.
+	pub relay_mode: RelayMode,
 	/// Relay strategy.
 	pub relay_strategy: P::RelayStrategy,
+	/// Registry of components, controlled by the admin server. If `None`, the relay can't be
+	/// controlled by the admin.
+	pub admin_registry: Option<AdminRegistry>,
+}
+
+/// Returns control handle of the given message lane relay.
+pub fn lane_control<P: SubstrateMessageLane>(
+	registry: &AdminRegistry,
+	lane: LaneId,
+) -> LaneControl {
+	registry.lane_control(
+		messages_relay::message_lane_loop::metrics_prefix::<MessageLaneAdapter<P>>(&lane),
+	)
 }
 
 /// Run Substrate-to-Substrate messages sync loop.
@@ -163,6 +185,8 @@ where
 		crate::messages_metrics::standalone_metrics::<P>(
 			source_client.clone(),
 			target_client.clone(),
+			None,
+			None,
 		)
 	})?;
 
@@ -173,6 +197,7 @@ where
 			Max messages in single transaction: {}\n\t\
 			Max messages size in single transaction: {}\n\t\
 			Max messages weight in single transaction: {}\n\t\
+			Relay mode: {:?}\n\t\
 			Tx mortality: {:?} (~{}m)/{:?} (~{}m)\n\t\
 			Stall timeout: {:?}",
 		P::SourceChain::NAME,
@@ -182,6 +207,7 @@ where
 		max_messages_in_single_batch,
 		max_messages_size_in_single_batch,
 		max_messages_weight_in_single_batch,
+		params.relay_mode,
 		params.source_transaction_params.mortality,
 		transaction_stall_timeout(
 			params.source_transaction_params.mortality,
@@ -204,6 +230,10 @@ where
 			target_tick: P::TargetChain::AVERAGE_BLOCK_INTERVAL,
 			reconnect_delay: relay_utils::relay_loop::RECONNECT_DELAY,
 			stall_timeout,
+			relay_mode: params.relay_mode,
+			lane_control: params
+				.admin_registry
+				.map(|registry| lane_control::<P>(&registry, params.lane_id)),
 			delivery_params: messages_relay::message_lane_loop::MessageDeliveryParams {
 				max_unrewarded_relayer_entries_at_target:
 					P::SourceChain::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
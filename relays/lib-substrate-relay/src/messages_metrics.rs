@@ -27,12 +27,16 @@ use relay_substrate_client::{
 	},
 	AccountIdOf, BalanceOf, Chain, ChainWithBalances, Client, Error as SubstrateError, IndexOf,
 };
-use relay_utils::metrics::{
-	FloatJsonValueMetric, GlobalMetrics, MetricsParams, PrometheusError, StandaloneMetric,
+use relay_utils::{
+	metrics::{GlobalMetrics, MetricsParams, PrometheusError, StandaloneMetric, TokenPriceMetric},
+	price_oracle::{CachedPriceOracle, SharedPriceOracle},
 };
 use sp_core::storage::StorageData;
 use sp_runtime::{FixedPointNumber, FixedU128};
-use std::{convert::TryFrom, fmt::Debug, marker::PhantomData};
+use std::{convert::TryFrom, fmt::Debug, marker::PhantomData, sync::Arc, time::Duration};
+
+/// How long token prices, returned by external price oracles, are cached.
+const TOKEN_PRICE_CACHE_DURATION: Duration = Duration::from_secs(60);
 
 /// Shared references to the standalone metrics of the message lane relay loop.
 #[derive(Debug, Clone)]
@@ -44,9 +48,9 @@ pub struct StandaloneMessagesMetrics<SC: Chain, TC: Chain> {
 	/// Target chain proof overhead metric.
 	pub target_storage_proof_overhead: StorageProofOverheadMetric<TC>,
 	/// Source tokens to base conversion rate metric.
-	pub source_to_base_conversion_rate: Option<FloatJsonValueMetric>,
+	pub source_to_base_conversion_rate: Option<TokenPriceMetric>,
 	/// Target tokens to base conversion rate metric.
-	pub target_to_base_conversion_rate: Option<FloatJsonValueMetric>,
+	pub target_to_base_conversion_rate: Option<TokenPriceMetric>,
 	/// Source tokens to target tokens conversion rate metric. This rate is stored by the target
 	/// chain.
 	pub source_to_target_conversion_rate: Option<FloatStorageValueMetric<TC, FixedU128OrOne>>,
@@ -92,11 +96,22 @@ impl<SC: Chain, TC: Chain> StandaloneMessagesMetrics<SC, TC> {
 	/// Return conversion rate from target to source tokens.
 	pub async fn target_to_source_conversion_rate(&self) -> Option<f64> {
 		Self::compute_target_to_source_conversion_rate(
-			*self.target_to_base_conversion_rate.as_ref()?.shared_value_ref().read().await,
-			*self.source_to_base_conversion_rate.as_ref()?.shared_value_ref().read().await,
+			Self::read_token_price(self.target_to_base_conversion_rate.as_ref()?).await,
+			Self::read_token_price(self.source_to_base_conversion_rate.as_ref()?).await,
 		)
 	}
 
+	/// Read token price from the oracle that is used by given metric.
+	async fn read_token_price(metric: &TokenPriceMetric) -> Option<f64> {
+		match metric.oracle().price().await {
+			Ok(price) => Some(price),
+			Err(error) => {
+				log::trace!(target: "bridge", "Failed to read token price: {:?}", error);
+				None
+			},
+		}
+	}
+
 	/// Return conversion rate from target to source tokens, given conversion rates from
 	/// target/source tokens to some base token.
 	fn compute_target_to_source_conversion_rate(
@@ -113,9 +128,14 @@ impl<SC: Chain, TC: Chain> StandaloneMessagesMetrics<SC, TC> {
 /// and by loops that are serving reverse lane (`P` with swapped `TargetChain` and `SourceChain`).
 /// We assume that either conversion rate parameters have values in the storage, or they are
 /// initialized with 1:1.
+///
+/// If token price oracle is not provided, then the price of chain token is read from the
+/// CoinGecko API (if chain has `TOKEN_ID`).
 pub fn standalone_metrics<P: SubstrateMessageLane>(
 	source_client: Client<P::SourceChain>,
 	target_client: Client<P::TargetChain>,
+	source_token_price_oracle: Option<SharedPriceOracle>,
+	target_token_price_oracle: Option<SharedPriceOracle>,
 ) -> anyhow::Result<StandaloneMessagesMetrics<P::SourceChain, P::TargetChain>> {
 	Ok(StandaloneMessagesMetrics {
 		global: GlobalMetrics::new()?,
@@ -129,16 +149,12 @@ pub fn standalone_metrics<P: SubstrateMessageLane>(
 			format!("{}_storage_proof_overhead", P::TargetChain::NAME.to_lowercase()),
 			format!("{} storage proof overhead", P::TargetChain::NAME),
 		)?,
-		source_to_base_conversion_rate: P::SourceChain::TOKEN_ID
-			.map(|source_chain_token_id| {
-				crate::helpers::token_price_metric(source_chain_token_id).map(Some)
-			})
-			.unwrap_or(Ok(None))?,
-		target_to_base_conversion_rate: P::TargetChain::TOKEN_ID
-			.map(|target_chain_token_id| {
-				crate::helpers::token_price_metric(target_chain_token_id).map(Some)
-			})
-			.unwrap_or(Ok(None))?,
+		source_to_base_conversion_rate: token_price_metric::<P::SourceChain>(
+			source_token_price_oracle,
+		)?,
+		target_to_base_conversion_rate: token_price_metric::<P::TargetChain>(
+			target_token_price_oracle,
+		)?,
 		source_to_target_conversion_rate: P::SOURCE_TO_TARGET_CONVERSION_RATE_PARAMETER_NAME
 			.map(bp_runtime::storage_parameter_key)
 			.map(|key| {
@@ -188,6 +204,22 @@ pub fn standalone_metrics<P: SubstrateMessageLane>(
 	})
 }
 
+/// Create token price metric that is using given price oracle or the default oracle of the chain.
+fn token_price_metric<C: Chain>(
+	oracle: Option<SharedPriceOracle>,
+) -> Result<Option<TokenPriceMetric>, PrometheusError> {
+	let token_id = C::TOKEN_ID.map(Into::into).unwrap_or_else(|| C::NAME.to_lowercase());
+	let oracle = match oracle.or_else(|| C::TOKEN_ID.map(crate::helpers::token_price_oracle)) {
+		Some(oracle) => oracle,
+		None => return Ok(None),
+	};
+	crate::helpers::token_price_metric(
+		&token_id,
+		Arc::new(CachedPriceOracle::new(oracle, TOKEN_PRICE_CACHE_DURATION)),
+	)
+	.map(Some)
+}
+
 /// Add relay accounts balance metrics.
 pub async fn add_relay_balances_metrics<C: ChainWithBalances>(
 	client: Client<C>,
@@ -36,6 +36,7 @@ use bridge_runtime_common::messages::{
 	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
 };
 use codec::{Decode, Encode};
+use finality_relay::SourceHeader as _;
 use frame_support::weights::Weight;
 use messages_relay::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
@@ -46,9 +47,9 @@ use messages_relay::{
 };
 use num_traits::{Bounded, Zero};
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, BalanceOf, Chain, ChainWithMessages, Client,
-	Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam, TransactionEra,
-	TransactionSignScheme, UnsignedTransaction,
+	encoded_call_indices, AccountIdOf, AccountKeyPairOf, BalanceOf, BridgeCall, Chain,
+	ChainWithMessages, Client, Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam,
+	SyncHeader, TransactionEra, TransactionSignScheme, UnsignedTransaction,
 };
 use relay_utils::{relay_loop::Client as RelayClient, HeaderId};
 use sp_core::{Bytes, Pair};
@@ -60,6 +61,14 @@ use std::ops::RangeInclusive;
 /// the proof itself.
 pub type SubstrateMessagesProof<C> = (Weight, FromBridgedChainMessagesProof<HashOf<C>>);
 
+/// Bridge call that is submitted to the source chain by the messages relay.
+pub fn receive_messages_delivery_proof_call<P: SubstrateMessageLane>() -> BridgeCall {
+	BridgeCall {
+		pallet: P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+		call: "receive_messages_delivery_proof",
+	}
+}
+
 /// Substrate client as Substrate messages source.
 pub struct SubstrateMessagesSource<P: SubstrateMessageLane> {
 	client: Client<P::SourceChain>,
@@ -182,20 +191,7 @@ where
 		MessageDetailsMap<<MessageLaneAdapter<P> as MessageLane>::SourceChainBalance>,
 		SubstrateError,
 	> {
-		let encoded_response = self
-			.client
-			.state_call(
-				P::TargetChain::TO_CHAIN_MESSAGE_DETAILS_METHOD.into(),
-				Bytes((self.lane_id, nonces.start(), nonces.end()).encode()),
-				Some(id.1),
-			)
-			.await?;
-
-		make_message_details_map::<P::SourceChain>(
-			Decode::decode(&mut &encoded_response.0[..])
-				.map_err(SubstrateError::ResponseParseFailed)?,
-			nonces,
-		)
+		generated_message_details::<P>(&self.client, self.lane_id, id.1, nonces).await
 	}
 
 	async fn prove_messages(
@@ -211,34 +207,10 @@ where
 		),
 		SubstrateError,
 	> {
-		let mut storage_keys =
-			Vec::with_capacity(nonces.end().saturating_sub(*nonces.start()) as usize + 1);
-		let mut message_nonce = *nonces.start();
-		while message_nonce <= *nonces.end() {
-			let message_key = bp_messages::storage_keys::message_key(
-				P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
-				&self.lane_id,
-				message_nonce,
-			);
-			storage_keys.push(message_key);
-			message_nonce += 1;
-		}
-		if proof_parameters.outbound_state_proof_required {
-			storage_keys.push(bp_messages::storage_keys::outbound_lane_data_key(
-				P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
-				&self.lane_id,
-			));
-		}
-
-		let proof = self.client.prove_storage(storage_keys, id.1).await?.iter_nodes().collect();
-		let proof = FromBridgedChainMessagesProof {
-			bridged_header_hash: id.1,
-			storage_proof: proof,
-			lane: self.lane_id,
-			nonces_start: *nonces.start(),
-			nonces_end: *nonces.end(),
-		};
-		Ok((id, nonces, (proof_parameters.dispatch_weight, proof)))
+		let proof =
+			prove_messages::<P>(&self.client, self.lane_id, id.1, nonces.clone(), proof_parameters)
+				.await?;
+		Ok((id, nonces, proof))
 	}
 
 	async fn submit_messages_receiving_proof(
@@ -248,6 +220,19 @@ where
 	) -> Result<(), SubstrateError> {
 		let genesis_hash = *self.client.genesis_hash();
 		let transaction_params = self.transaction_params.clone();
+		// indices of the call don't depend on the proof, so we may use the dummy proof here
+		let call_indices = encoded_call_indices(
+			&P::ReceiveMessagesDeliveryProofCallBuilder::build_receive_messages_delivery_proof_call(
+				prepare_dummy_messages_delivery_proof::<P::SourceChain, P::TargetChain>(),
+				false,
+			),
+		);
+		self.client
+			.ensure_bridge_calls_compatible(&[(
+				receive_messages_delivery_proof_call::<P>(),
+				call_indices,
+			)])
+			.await?;
 		let (spec_version, transaction_version) = self.client.simple_runtime_version().await?;
 		self.client
 			.submit_signed_extrinsic(
@@ -282,21 +267,33 @@ where
 			Ok(v) => v,
 			Err(_) => return BalanceOf::<P::SourceChain>::max_value(),
 		};
+		let dummy_tx = match make_messages_delivery_proof_transaction::<P>(
+			runtime_version.spec_version,
+			runtime_version.transaction_version,
+			self.client.genesis_hash(),
+			&self.transaction_params.dummy(),
+			HeaderId(Default::default(), Default::default()),
+			Zero::zero(),
+			prepare_dummy_messages_delivery_proof::<P::SourceChain, P::TargetChain>(),
+			false,
+		) {
+			Ok(dummy_tx) => dummy_tx,
+			Err(_) => return BalanceOf::<P::SourceChain>::max_value(),
+		};
 		self.client
-			.estimate_extrinsic_fee(make_messages_delivery_proof_transaction::<P>(
-				runtime_version.spec_version,
-				runtime_version.transaction_version,
-				self.client.genesis_hash(),
-				&self.transaction_params,
-				HeaderId(Default::default(), Default::default()),
-				Zero::zero(),
-				prepare_dummy_messages_delivery_proof::<P::SourceChain, P::TargetChain>(),
-				false,
-			))
+			.estimate_extrinsic_fee(dummy_tx)
 			.await
 			.map(|fee| fee.inclusion_fee())
 			.unwrap_or_else(|_| BalanceOf::<P::SourceChain>::max_value())
 	}
+
+	async fn is_mandatory_header(
+		&self,
+		id: SourceHeaderIdOf<MessageLaneAdapter<P>>,
+	) -> Result<bool, SubstrateError> {
+		let header = self.client.header_by_hash(id.1).await?;
+		Ok(SyncHeader::from(header).is_mandatory())
+	}
 }
 
 /// Ensure that the messages pallet at source chain is active.
@@ -318,6 +315,66 @@ where
 	}
 }
 
+/// Read details of messages that are generated at the source chain.
+pub async fn generated_message_details<P: SubstrateMessageLane>(
+	client: &Client<P::SourceChain>,
+	lane_id: LaneId,
+	at_block: HashOf<P::SourceChain>,
+	nonces: RangeInclusive<MessageNonce>,
+) -> Result<MessageDetailsMap<BalanceOf<P::SourceChain>>, SubstrateError> {
+	let encoded_response = client
+		.state_call(
+			P::TargetChain::TO_CHAIN_MESSAGE_DETAILS_METHOD.into(),
+			Bytes((lane_id, nonces.start(), nonces.end()).encode()),
+			Some(at_block),
+		)
+		.await?;
+
+	make_message_details_map::<P::SourceChain>(
+		Decode::decode(&mut &encoded_response.0[..])
+			.map_err(SubstrateError::ResponseParseFailed)?,
+		nonces,
+	)
+}
+
+/// Prove messages with given nonces, stored at the source chain at given block.
+pub async fn prove_messages<P: SubstrateMessageLane>(
+	client: &Client<P::SourceChain>,
+	lane_id: LaneId,
+	at_block: HashOf<P::SourceChain>,
+	nonces: RangeInclusive<MessageNonce>,
+	proof_parameters: MessageProofParameters,
+) -> Result<SubstrateMessagesProof<P::SourceChain>, SubstrateError> {
+	let mut storage_keys =
+		Vec::with_capacity(nonces.end().saturating_sub(*nonces.start()) as usize + 1);
+	let mut message_nonce = *nonces.start();
+	while message_nonce <= *nonces.end() {
+		let message_key = bp_messages::storage_keys::message_key(
+			P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+			&lane_id,
+			message_nonce,
+		);
+		storage_keys.push(message_key);
+		message_nonce += 1;
+	}
+	if proof_parameters.outbound_state_proof_required {
+		storage_keys.push(bp_messages::storage_keys::outbound_lane_data_key(
+			P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+			&lane_id,
+		));
+	}
+
+	let proof = client.prove_storage(storage_keys, at_block).await?.iter_nodes().collect();
+	let proof = FromBridgedChainMessagesProof {
+		bridged_header_hash: at_block,
+		storage_proof: proof,
+		lane: lane_id,
+		nonces_start: *nonces.start(),
+		nonces_end: *nonces.end(),
+	};
+	Ok((proof_parameters.dispatch_weight, proof))
+}
+
 /// Make messages delivery proof transaction from given proof.
 fn make_messages_delivery_proof_transaction<P: SubstrateMessageLane>(
 	spec_version: u32,
@@ -328,7 +385,7 @@ fn make_messages_delivery_proof_transaction<P: SubstrateMessageLane>(
 	transaction_nonce: IndexOf<P::SourceChain>,
 	proof: SubstrateMessagesDeliveryProof<P::TargetChain>,
 	trace_call: bool,
-) -> Bytes
+) -> Result<Bytes, SubstrateError>
 where
 	P::SourceTransactionSignScheme: TransactionSignScheme<Chain = P::SourceChain>,
 {
@@ -336,7 +393,7 @@ where
 		P::ReceiveMessagesDeliveryProofCallBuilder::build_receive_messages_delivery_proof_call(
 			proof, trace_call,
 		);
-	Bytes(
+	Ok(Bytes(
 		P::SourceTransactionSignScheme::sign_transaction(SignParam {
 			spec_version,
 			transaction_version,
@@ -344,9 +401,9 @@ where
 			signer: source_transaction_params.signer.clone(),
 			era: TransactionEra::new(source_best_block_id, source_transaction_params.mortality),
 			unsigned: UnsignedTransaction::new(call, transaction_nonce),
-		})
+		})?
 		.encode(),
-	)
+	))
 }
 
 /// Prepare 'dummy' messages delivery proof that will compose the delivery confirmation transaction.
@@ -367,6 +424,7 @@ fn prepare_dummy_messages_delivery_proof<SC: Chain, TC: Chain>(
 			unrewarded_relayer_entries: 1,
 			messages_in_oldest_entry: 1,
 			total_messages: 1,
+			last_delivered_nonce: 1,
 		},
 		FromBridgedChainMessagesDeliveryProof {
 			bridged_header_hash: Default::default(),
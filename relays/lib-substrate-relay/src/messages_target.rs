@@ -42,9 +42,9 @@ use messages_relay::{
 };
 use num_traits::{Bounded, Zero};
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, BalanceOf, Chain, ChainWithMessages, Client,
-	Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam, TransactionEra,
-	TransactionSignScheme, UnsignedTransaction, WeightToFeeOf,
+	encoded_call_indices, AccountIdOf, AccountKeyPairOf, BalanceOf, BridgeCall, Chain,
+	ChainWithMessages, Client, Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam,
+	TransactionEra, TransactionSignScheme, UnsignedTransaction, WeightToFeeOf,
 };
 use relay_utils::{relay_loop::Client as RelayClient, HeaderId};
 use sp_core::{Bytes, Pair};
@@ -55,6 +55,14 @@ use std::{convert::TryFrom, ops::RangeInclusive};
 pub type SubstrateMessagesDeliveryProof<C> =
 	(UnrewardedRelayersState, FromBridgedChainMessagesDeliveryProof<HashOf<C>>);
 
+/// Bridge call that is submitted to the target chain by the messages relay.
+pub fn receive_messages_proof_call<P: SubstrateMessageLane>() -> BridgeCall {
+	BridgeCall {
+		pallet: P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+		call: "receive_messages_proof",
+	}
+}
+
 /// Substrate client as Substrate messages target.
 pub struct SubstrateMessagesTarget<P: SubstrateMessageLane> {
 	client: Client<P::TargetChain>,
@@ -183,17 +191,8 @@ where
 		id: TargetHeaderIdOf<MessageLaneAdapter<P>>,
 	) -> Result<(TargetHeaderIdOf<MessageLaneAdapter<P>>, UnrewardedRelayersState), SubstrateError>
 	{
-		let encoded_response = self
-			.client
-			.state_call(
-				P::SourceChain::FROM_CHAIN_UNREWARDED_RELAYERS_STATE.into(),
-				Bytes(self.lane_id.encode()),
-				Some(id.1),
-			)
-			.await?;
-		let unrewarded_relayers_state: UnrewardedRelayersState =
-			Decode::decode(&mut &encoded_response.0[..])
-				.map_err(SubstrateError::ResponseParseFailed)?;
+		let unrewarded_relayers_state =
+			unrewarded_relayers_state::<P>(&self.client, self.lane_id, id.1).await?;
 		Ok((id, unrewarded_relayers_state))
 	}
 
@@ -207,23 +206,8 @@ where
 		),
 		SubstrateError,
 	> {
-		let (id, relayers_state) = self.unrewarded_relayers_state(id).await?;
-		let inbound_data_key = bp_messages::storage_keys::inbound_lane_data_key(
-			P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
-			&self.lane_id,
-		);
-		let proof = self
-			.client
-			.prove_storage(vec![inbound_data_key], id.1)
-			.await?
-			.iter_nodes()
-			.collect();
-		let proof = FromBridgedChainMessagesDeliveryProof {
-			bridged_header_hash: id.1,
-			storage_proof: proof,
-			lane: self.lane_id,
-		};
-		Ok((id, (relayers_state, proof)))
+		let proof = prove_messages_receiving::<P>(&self.client, self.lane_id, id.1).await?;
+		Ok((id, proof))
 	}
 
 	async fn submit_messages_proof(
@@ -236,6 +220,19 @@ where
 		let transaction_params = self.transaction_params.clone();
 		let relayer_id_at_source = self.relayer_id_at_source.clone();
 		let nonces_clone = nonces.clone();
+		// indices of the call don't depend on the proof, so we may use the dummy proof here
+		let call_indices = encoded_call_indices(
+			&P::ReceiveMessagesProofCallBuilder::build_receive_messages_proof_call(
+				relayer_id_at_source.clone(),
+				prepare_dummy_messages_proof::<P::SourceChain>(1..=1, 0, 0),
+				1,
+				0,
+				false,
+			),
+		);
+		self.client
+			.ensure_bridge_calls_compatible(&[(receive_messages_proof_call::<P>(), call_indices)])
+			.await?;
 		let (spec_version, transaction_version) = self.client.simple_runtime_version().await?;
 		self.client
 			.submit_signed_extrinsic(
@@ -287,7 +284,7 @@ where
 			spec_version,
 			transaction_version,
 			self.client.genesis_hash(),
-			&self.transaction_params,
+			&self.transaction_params.dummy(),
 			HeaderId(Default::default(), Default::default()),
 			Zero::zero(),
 			self.relayer_id_at_source.clone(),
@@ -298,7 +295,7 @@ where
 				total_size,
 			),
 			false,
-		);
+		)?;
 		let delivery_tx_fee = self.client.estimate_extrinsic_fee(delivery_tx).await?;
 		let inclusion_fee_in_target_tokens = delivery_tx_fee.inclusion_fee();
 
@@ -329,7 +326,7 @@ where
 					spec_version,
 					transaction_version,
 					self.client.genesis_hash(),
-					&self.transaction_params,
+					&self.transaction_params.dummy(),
 					HeaderId(Default::default(), Default::default()),
 					Zero::zero(),
 					self.relayer_id_at_source.clone(),
@@ -340,7 +337,7 @@ where
 						total_size,
 					),
 					false,
-				))
+				)?)
 				.await?;
 
 			compute_prepaid_messages_refund::<P::TargetChain>(
@@ -389,6 +386,46 @@ where
 	}
 }
 
+/// Read state of unrewarded relayers at the target chain inbound lane.
+pub async fn unrewarded_relayers_state<P: SubstrateMessageLane>(
+	client: &Client<P::TargetChain>,
+	lane_id: LaneId,
+	at_block: HashOf<P::TargetChain>,
+) -> Result<UnrewardedRelayersState, SubstrateError> {
+	let encoded_response = client
+		.state_call(
+			P::SourceChain::FROM_CHAIN_UNREWARDED_RELAYERS_STATE.into(),
+			Bytes(lane_id.encode()),
+			Some(at_block),
+		)
+		.await?;
+	Decode::decode(&mut &encoded_response.0[..]).map_err(SubstrateError::ResponseParseFailed)
+}
+
+/// Prove state of the target chain inbound lane at given block.
+pub async fn prove_messages_receiving<P: SubstrateMessageLane>(
+	client: &Client<P::TargetChain>,
+	lane_id: LaneId,
+	at_block: HashOf<P::TargetChain>,
+) -> Result<SubstrateMessagesDeliveryProof<P::TargetChain>, SubstrateError> {
+	let relayers_state = unrewarded_relayers_state::<P>(client, lane_id, at_block).await?;
+	let inbound_data_key = bp_messages::storage_keys::inbound_lane_data_key(
+		P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+		&lane_id,
+	);
+	let proof = client
+		.prove_storage(vec![inbound_data_key], at_block)
+		.await?
+		.iter_nodes()
+		.collect();
+	let proof = FromBridgedChainMessagesDeliveryProof {
+		bridged_header_hash: at_block,
+		storage_proof: proof,
+		lane: lane_id,
+	};
+	Ok((relayers_state, proof))
+}
+
 /// Make messages delivery transaction from given proof.
 #[allow(clippy::too_many_arguments)]
 fn make_messages_delivery_transaction<P: SubstrateMessageLane>(
@@ -402,7 +439,7 @@ fn make_messages_delivery_transaction<P: SubstrateMessageLane>(
 	nonces: RangeInclusive<MessageNonce>,
 	proof: SubstrateMessagesProof<P::SourceChain>,
 	trace_call: bool,
-) -> Bytes
+) -> Result<Bytes, SubstrateError>
 where
 	P::TargetTransactionSignScheme: TransactionSignScheme<Chain = P::TargetChain>,
 {
@@ -415,7 +452,7 @@ where
 		dispatch_weight,
 		trace_call,
 	);
-	Bytes(
+	Ok(Bytes(
 		P::TargetTransactionSignScheme::sign_transaction(SignParam {
 			spec_version,
 			transaction_version,
@@ -423,9 +460,9 @@ where
 			signer: target_transaction_params.signer.clone(),
 			era: TransactionEra::new(target_best_block_id, target_transaction_params.mortality),
 			unsigned: UnsignedTransaction::new(call, transaction_nonce),
-		})
+		})?
 		.encode(),
-	)
+	))
 }
 
 /// Prepare 'dummy' messages proof that will compose the delivery transaction.
@@ -19,6 +19,7 @@
 use async_std::sync::{Arc, Mutex};
 use futures::{select, FutureExt};
 use num_traits::{One, Zero};
+use std::convert::TryFrom;
 
 use finality_relay::{FinalitySyncParams, SourceHeader, TargetClient as FinalityTargetClient};
 use relay_substrate_client::{
@@ -26,7 +27,10 @@ use relay_substrate_client::{
 	TransactionSignScheme,
 };
 use relay_utils::{
-	metrics::MetricsParams, relay_loop::Client as RelayClient, FailedClient, MaybeConnectionError,
+	admin::{AdminRegistry, HeadersRelayControl},
+	metrics::MetricsParams,
+	relay_loop::Client as RelayClient,
+	FailedClient, MaybeConnectionError,
 };
 
 use crate::{
@@ -56,6 +60,7 @@ impl<SourceChain: Chain> OnDemandHeadersRelay<SourceChain> {
 		target_client: Client<P::TargetChain>,
 		target_transaction_params: TransactionParams<AccountKeyPairOf<P::TransactionSignScheme>>,
 		only_mandatory_headers: bool,
+		admin_registry: Option<AdminRegistry>,
 	) -> Self
 	where
 		AccountIdOf<P::TargetChain>:
@@ -63,8 +68,11 @@ impl<SourceChain: Chain> OnDemandHeadersRelay<SourceChain> {
 		P::TransactionSignScheme: TransactionSignScheme<Chain = P::TargetChain>,
 	{
 		let required_header_number = Arc::new(Mutex::new(Zero::zero()));
+		let relay_task_name = on_demand_headers_relay_name::<P::SourceChain, P::TargetChain>();
+		let headers_relay_control =
+			admin_registry.map(|registry| registry.headers_relay_control(relay_task_name.clone()));
 		let this = OnDemandHeadersRelay {
-			relay_task_name: on_demand_headers_relay_name::<P::SourceChain, P::TargetChain>(),
+			relay_task_name,
 			required_header_number: required_header_number.clone(),
 		};
 		async_std::task::spawn(async move {
@@ -74,6 +82,7 @@ impl<SourceChain: Chain> OnDemandHeadersRelay<SourceChain> {
 				target_transaction_params,
 				only_mandatory_headers,
 				required_header_number,
+				headers_relay_control,
 			)
 			.await;
 		});
@@ -105,6 +114,7 @@ async fn background_task<P: SubstrateFinalitySyncPipeline>(
 	target_transaction_params: TransactionParams<AccountKeyPairOf<P::TransactionSignScheme>>,
 	only_mandatory_headers: bool,
 	required_header_number: RequiredHeaderNumberRef<P::SourceChain>,
+	headers_relay_control: Option<HeadersRelayControl>,
 ) where
 	AccountIdOf<P::TargetChain>:
 		From<<AccountKeyPairOf<P::TransactionSignScheme> as sp_core::Pair>::Public>,
@@ -161,6 +171,46 @@ async fn background_task<P: SubstrateFinalitySyncPipeline>(
 			continue
 		}
 
+		// sync headers that have been requested by admin
+		if let Some(ref headers_relay_control) = headers_relay_control {
+			if let Some(forced_header_number) = headers_relay_control.take_forced_header_number() {
+				match BlockNumberOf::<P::SourceChain>::try_from(forced_header_number) {
+					Ok(forced_header_number) => {
+						let mut required_header_number = required_header_number.lock().await;
+						if forced_header_number > *required_header_number {
+							log::info!(
+								target: "bridge",
+								"Admin has asked {} relay to sync {} headers up to the {}",
+								relay_task_name,
+								P::SourceChain::NAME,
+								forced_header_number,
+							);
+							*required_header_number = forced_header_number;
+						}
+					},
+					Err(_) => log::warn!(
+						target: "bridge",
+						"Admin has asked {} relay to sync {} headers up to the invalid header {}",
+						relay_task_name,
+						P::SourceChain::NAME,
+						forced_header_number,
+					),
+				}
+			}
+
+			headers_relay_control.update_status(serde_json::json!({
+				"required_header_number": required_header_number.lock().await.to_string(),
+				"best_finalized_source_header_at_source": best_finalized_source_header_at_source
+					.as_ref()
+					.ok()
+					.map(ToString::to_string),
+				"best_finalized_source_header_at_target": best_finalized_source_header_at_target
+					.as_ref()
+					.ok()
+					.map(ToString::to_string),
+			}));
+		}
+
 		// submit mandatory header if some headers are missing
 		let best_finalized_source_header_at_source_fmt =
 			format!("{:?}", best_finalized_source_header_at_source);
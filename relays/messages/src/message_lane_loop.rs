@@ -32,7 +32,11 @@ use futures::{channel::mpsc::unbounded, future::FutureExt, stream::StreamExt};
 use bp_messages::{LaneId, MessageNonce, UnrewardedRelayersState, Weight};
 use bp_runtime::messages::DispatchFeePayment;
 use relay_utils::{
-	interval, metrics::MetricsParams, process_future_result, relay_loop::Client as RelayClient,
+	admin::{LaneControl, StrategyParams},
+	interval,
+	metrics::MetricsParams,
+	process_future_result,
+	relay_loop::Client as RelayClient,
 	retry_backoff, FailedClient,
 };
 
@@ -57,6 +61,11 @@ pub struct Params<Strategy: RelayStrategy> {
 	pub reconnect_delay: Duration,
 	/// The loop will auto-restart if there has been no updates during this period.
 	pub stall_timeout: Duration,
+	/// Races that are run by this loop.
+	pub relay_mode: RelayMode,
+	/// Admin control handle of this loop. If `None`, the loop can't be paused and its
+	/// parameters can't be changed at runtime.
+	pub lane_control: Option<LaneControl>,
 	/// Message delivery race parameters.
 	pub delivery_params: MessageDeliveryParams<Strategy>,
 }
@@ -69,6 +78,35 @@ pub enum RelayerMode {
 	/// The relayer will deliver all messages and confirmations as long as he's not losing any
 	/// funds.
 	Rational,
+	/// The relayer will deliver all messages and confirmations as long as it is not losing any
+	/// funds, including funds that are spent on relaying headers, required to deliver messages
+	/// and confirmations.
+	ProfitAware,
+}
+
+/// Races that are run by the message lane loop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelayMode {
+	/// Only messages are delivered to the target chain. Delivery confirmations are expected
+	/// to be submitted by other relayers.
+	Delivery,
+	/// Only delivery confirmations are delivered to the source chain. Messages are expected
+	/// to be delivered by other relayers.
+	Confirmations,
+	/// Both messages and delivery confirmations are delivered.
+	Both,
+}
+
+impl RelayMode {
+	/// Returns true if messages delivery race is run in this mode.
+	pub fn is_delivery_enabled(&self) -> bool {
+		matches!(*self, RelayMode::Delivery | RelayMode::Both)
+	}
+
+	/// Returns true if messages receiving (delivery confirmations) race is run in this mode.
+	pub fn is_confirmations_enabled(&self) -> bool {
+		matches!(*self, RelayMode::Confirmations | RelayMode::Both)
+	}
 }
 
 /// Message delivery race parameters.
@@ -167,6 +205,10 @@ pub trait SourceClient<P: MessageLane>: RelayClient {
 
 	/// Estimate cost of single message confirmation transaction in source chain tokens.
 	async fn estimate_confirmation_transaction(&self) -> P::SourceChainBalance;
+
+	/// Returns true if given source header is mandatory. Mandatory headers are relayed to the
+	/// target chain even if there are no messages that require them.
+	async fn is_mandatory_header(&self, id: SourceHeaderIdOf<P>) -> Result<bool, Self::Error>;
 }
 
 /// Target client trait.
@@ -272,7 +314,7 @@ pub async fn run<P: MessageLane, Strategy: RelayStrategy>(
 		.expose()
 		.await?
 		.run(metrics_prefix::<P>(&params.lane), move |source_client, target_client, metrics| {
-			run_until_connection_lost(
+			run_until_connection_lost_or_interrupted(
 				params.clone(),
 				source_client,
 				target_client,
@@ -283,6 +325,92 @@ pub async fn run<P: MessageLane, Strategy: RelayStrategy>(
 		.await
 }
 
+/// Run one-way message delivery loop until connection with target or source node is lost, or exit
+/// signal is received.
+///
+/// If the loop is paused by admin, races are stopped until the loop is resumed. If admin has
+/// changed delivery parameters, races are restarted with new parameters.
+async fn run_until_connection_lost_or_interrupted<
+	P: MessageLane,
+	Strategy: RelayStrategy,
+	SC: SourceClient<P>,
+	TC: TargetClient<P>,
+>(
+	mut params: Params<Strategy>,
+	source_client: SC,
+	target_client: TC,
+	metrics_msg: Option<MessageLaneLoopMetrics>,
+	exit_signal: impl Future<Output = ()> + Clone,
+) -> Result<(), FailedClient> {
+	let lane_control = match params.lane_control.clone() {
+		Some(lane_control) => lane_control,
+		None =>
+			return run_until_connection_lost(
+				params,
+				source_client,
+				target_client,
+				metrics_msg,
+				exit_signal,
+			)
+			.await,
+	};
+
+	loop {
+		if lane_control.is_paused() {
+			log::info!(
+				target: "bridge",
+				"{} -> {} message lane {} relay is paused",
+				P::SOURCE_NAME,
+				P::TARGET_NAME,
+				hex::encode(params.lane),
+			);
+			futures::select! {
+				_ = lane_control.wait_resumed().fuse() => (),
+				_ = exit_signal.clone().fuse() => return Ok(()),
+			}
+			log::info!(
+				target: "bridge",
+				"{} -> {} message lane {} relay is resumed",
+				P::SOURCE_NAME,
+				P::TARGET_NAME,
+				hex::encode(params.lane),
+			);
+		}
+
+		apply_strategy_params(&mut params.delivery_params, lane_control.strategy_params());
+		futures::select! {
+			result = run_until_connection_lost(
+				params.clone(),
+				source_client.clone(),
+				target_client.clone(),
+				metrics_msg.clone(),
+				exit_signal.clone(),
+			).fuse() => return result,
+			_ = lane_control.wait_interrupted().fuse() => (),
+		}
+	}
+}
+
+/// Override delivery parameters with parameters that have been set by admin.
+fn apply_strategy_params<Strategy: RelayStrategy>(
+	delivery_params: &mut MessageDeliveryParams<Strategy>,
+	strategy_params: StrategyParams,
+) {
+	if let Some(max_messages_in_single_batch) = strategy_params.max_messages_in_single_batch {
+		delivery_params.max_messages_in_single_batch = max_messages_in_single_batch;
+	}
+	if let Some(max_messages_weight_in_single_batch) =
+		strategy_params.max_messages_weight_in_single_batch
+	{
+		delivery_params.max_messages_weight_in_single_batch = max_messages_weight_in_single_batch;
+	}
+	if let Some(max_messages_size_in_single_batch) =
+		strategy_params.max_messages_size_in_single_batch
+	{
+		delivery_params.max_messages_size_in_single_batch = max_messages_size_in_single_batch;
+	}
+}
+
 /// Run one-way message delivery loop until connection with target or source node is lost, or exit
 /// signal is received.
 async fn run_until_connection_lost<
@@ -297,6 +425,12 @@ async fn run_until_connection_lost<
 	metrics_msg: Option<MessageLaneLoopMetrics>,
 	exit_signal: impl Future<Output = ()>,
 ) -> Result<(), FailedClient> {
+	let lane_control = params.lane_control.clone();
+	let component_status =
+		|name: &str| lane_control.as_ref().map(|control| control.component_status(name));
+	let source_status = component_status("source_client");
+	let target_status = component_status("target_client");
+
 	let mut source_retry_backoff = retry_backoff();
 	let mut source_client_is_online = false;
 	let mut source_state_required = true;
@@ -311,34 +445,47 @@ async fn run_until_connection_lost<
 	let target_go_offline_future = futures::future::Fuse::terminated();
 	let target_tick_stream = interval(params.target_tick).fuse();
 
-	let (
-		(delivery_source_state_sender, delivery_source_state_receiver),
-		(delivery_target_state_sender, delivery_target_state_receiver),
-	) = (unbounded(), unbounded());
-	let delivery_race_loop = run_message_delivery_race(
-		source_client.clone(),
-		delivery_source_state_receiver,
-		target_client.clone(),
-		delivery_target_state_receiver,
-		params.stall_timeout,
-		metrics_msg.clone(),
-		params.delivery_params,
-	)
-	.fuse();
-
-	let (
-		(receiving_source_state_sender, receiving_source_state_receiver),
-		(receiving_target_state_sender, receiving_target_state_receiver),
-	) = (unbounded(), unbounded());
-	let receiving_race_loop = run_message_receiving_race(
-		source_client.clone(),
-		receiving_source_state_receiver,
-		target_client.clone(),
-		receiving_target_state_receiver,
-		params.stall_timeout,
-		metrics_msg.clone(),
-	)
-	.fuse();
+	// state channels are only created for enabled races - otherwise nobody reads from them
+	let (delivery_source_state_sender, delivery_target_state_sender, delivery_race_loop) =
+		if params.relay_mode.is_delivery_enabled() {
+			let (source_state_sender, source_state_receiver) = unbounded();
+			let (target_state_sender, target_state_receiver) = unbounded();
+			let race_loop = run_message_delivery_race(
+				source_client.clone(),
+				source_state_receiver,
+				target_client.clone(),
+				target_state_receiver,
+				params.stall_timeout,
+				metrics_msg.clone(),
+				params.delivery_params,
+				component_status("delivery_race"),
+			)
+			.left_future();
+			(Some(source_state_sender), Some(target_state_sender), race_loop)
+		} else {
+			(None, None, futures::future::pending().right_future())
+		};
+	let delivery_race_loop = delivery_race_loop.fuse();
+
+	let (receiving_source_state_sender, receiving_target_state_sender, receiving_race_loop) =
+		if params.relay_mode.is_confirmations_enabled() {
+			let (source_state_sender, source_state_receiver) = unbounded();
+			let (target_state_sender, target_state_receiver) = unbounded();
+			let race_loop = run_message_receiving_race(
+				source_client.clone(),
+				source_state_receiver,
+				target_client.clone(),
+				target_state_receiver,
+				params.stall_timeout,
+				metrics_msg.clone(),
+				component_status("receiving_race"),
+			)
+			.left_future();
+			(Some(source_state_sender), Some(target_state_sender), race_loop)
+		} else {
+			(None, None, futures::future::pending().right_future())
+		};
+	let receiving_race_loop = receiving_race_loop.fuse();
 
 	let exit_signal = exit_signal.fuse();
 
@@ -369,9 +516,16 @@ async fn run_until_connection_lost<
 							P::SOURCE_NAME,
 							new_source_state,
 						);
-						let _ = delivery_source_state_sender.unbounded_send(new_source_state.clone());
-						let _ = receiving_source_state_sender.unbounded_send(new_source_state.clone());
+						for sender in delivery_source_state_sender
+							.iter()
+							.chain(receiving_source_state_sender.iter())
+						{
+							let _ = sender.unbounded_send(new_source_state.clone());
+						}
 
+						if let Some(source_status) = source_status.as_ref() {
+							source_status.update(client_state_status(&new_source_state));
+						}
 						if let Some(metrics_msg) = metrics_msg.as_ref() {
 							metrics_msg.update_source_state::<P>(new_source_state);
 						}
@@ -400,9 +554,16 @@ async fn run_until_connection_lost<
 							P::TARGET_NAME,
 							new_target_state,
 						);
-						let _ = delivery_target_state_sender.unbounded_send(new_target_state.clone());
-						let _ = receiving_target_state_sender.unbounded_send(new_target_state.clone());
+						for sender in delivery_target_state_sender
+							.iter()
+							.chain(receiving_target_state_sender.iter())
+						{
+							let _ = sender.unbounded_send(new_target_state.clone());
+						}
 
+						if let Some(target_status) = target_status.as_ref() {
+							target_status.update(client_state_status(&new_target_state));
+						}
 						if let Some(metrics_msg) = metrics_msg.as_ref() {
 							metrics_msg.update_target_state::<P>(new_target_state);
 						}
@@ -451,6 +612,17 @@ async fn run_until_connection_lost<
 	}
 }
 
+/// Returns client state as it is exposed by the admin server.
+fn client_state_status<SelfHeaderId: Debug, PeerHeaderId: Debug>(
+	state: &ClientState<SelfHeaderId, PeerHeaderId>,
+) -> serde_json::Value {
+	serde_json::json!({
+		"best_self": format!("{:?}", state.best_self),
+		"best_finalized_self": format!("{:?}", state.best_finalized_self),
+		"best_finalized_peer_at_best_self": format!("{:?}", state.best_finalized_peer_at_best_self),
+	})
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
 	use std::sync::Arc;
@@ -529,6 +701,7 @@ pub(crate) mod tests {
 		target_to_source_header_requirements: Vec<TestTargetHeaderId>,
 		source_to_target_header_required: Option<TestSourceHeaderId>,
 		source_to_target_header_requirements: Vec<TestSourceHeaderId>,
+		source_mandatory_headers: Vec<TestSourceHeaderId>,
 	}
 
 	#[derive(Clone)]
@@ -546,6 +719,13 @@ pub(crate) mod tests {
 		}
 	}
 
+	impl TestSourceClient {
+		/// Mark given source header as mandatory.
+		pub fn mark_header_as_mandatory(&self, id: TestSourceHeaderId) {
+			self.data.lock().source_mandatory_headers.push(id);
+		}
+	}
+
 	#[async_trait]
 	impl RelayClient for TestSourceClient {
 		type Error = TestError;
@@ -662,6 +842,13 @@ pub(crate) mod tests {
 		async fn estimate_confirmation_transaction(&self) -> TestSourceChainBalance {
 			CONFIRMATION_TRANSACTION_COST
 		}
+
+		async fn is_mandatory_header(
+			&self,
+			id: SourceHeaderIdOf<TestMessageLane>,
+		) -> Result<bool, TestError> {
+			Ok(self.data.lock().source_mandatory_headers.contains(&id))
+		}
 	}
 
 	#[derive(Clone)]
@@ -726,6 +913,7 @@ pub(crate) mod tests {
 					unrewarded_relayer_entries: 0,
 					messages_in_oldest_entry: 0,
 					total_messages: 0,
+					last_delivered_nonce: 0,
 				},
 			))
 		}
@@ -793,6 +981,18 @@ pub(crate) mod tests {
 	}
 
 	fn run_loop_test(
+		relay_mode: RelayMode,
+		data: TestClientData,
+		source_tick: Arc<dyn Fn(&mut TestClientData) + Send + Sync>,
+		target_tick: Arc<dyn Fn(&mut TestClientData) + Send + Sync>,
+		exit_signal: impl Future<Output = ()> + 'static + Send,
+	) -> TestClientData {
+		run_controlled_loop_test(relay_mode, None, data, source_tick, target_tick, exit_signal)
+	}
+
+	fn run_controlled_loop_test(
+		relay_mode: RelayMode,
+		lane_control: Option<LaneControl>,
 		data: TestClientData,
 		source_tick: Arc<dyn Fn(&mut TestClientData) + Send + Sync>,
 		target_tick: Arc<dyn Fn(&mut TestClientData) + Send + Sync>,
@@ -810,6 +1010,8 @@ pub(crate) mod tests {
 					target_tick: Duration::from_millis(100),
 					reconnect_delay: Duration::from_millis(0),
 					stall_timeout: Duration::from_millis(60 * 1000),
+					relay_mode,
+					lane_control,
 					delivery_params: MessageDeliveryParams {
 						max_unrewarded_relayer_entries_at_target: 4,
 						max_unconfirmed_nonces_at_target: 4,
@@ -837,6 +1039,7 @@ pub(crate) mod tests {
 		// able to deliver messages.
 		let (exit_sender, exit_receiver) = unbounded();
 		let result = run_loop_test(
+			RelayMode::Both,
 			TestClientData {
 				is_source_fails: true,
 				source_state: ClientState {
@@ -883,6 +1086,7 @@ pub(crate) mod tests {
 	fn message_lane_loop_works() {
 		let (exit_sender, exit_receiver) = unbounded();
 		let result = run_loop_test(
+			RelayMode::Both,
 			TestClientData {
 				source_state: ClientState {
 					best_self: HeaderId(10, 10),
@@ -958,4 +1162,158 @@ pub(crate) mod tests {
 		assert!(!result.target_to_source_header_requirements.is_empty());
 		assert!(!result.source_to_target_header_requirements.is_empty());
 	}
+
+	#[test]
+	fn message_lane_loop_only_delivers_messages_in_delivery_mode() {
+		let (exit_sender, exit_receiver) = unbounded();
+		let result = run_loop_test(
+			RelayMode::Delivery,
+			TestClientData {
+				source_state: ClientState {
+					best_self: HeaderId(10, 10),
+					best_finalized_self: HeaderId(10, 10),
+					best_finalized_peer_at_best_self: HeaderId(0, 0),
+				},
+				source_latest_generated_nonce: 4,
+				target_state: ClientState {
+					best_self: HeaderId(0, 0),
+					best_finalized_self: HeaderId(0, 0),
+					best_finalized_peer_at_best_self: HeaderId(10, 10),
+				},
+				target_latest_received_nonce: 0,
+				..Default::default()
+			},
+			Arc::new(|data: &mut TestClientData| {
+				data.source_state.best_finalized_peer_at_best_self = data.target_state.best_self;
+			}),
+			Arc::new(move |data: &mut TestClientData| {
+				data.target_state.best_self =
+					HeaderId(data.target_state.best_self.0 + 1, data.target_state.best_self.1 + 1);
+				data.target_state.best_finalized_self = data.target_state.best_self;
+				// give the receiving race (if it is running) a chance to submit confirmations
+				if data.target_latest_received_nonce == 4 && data.target_state.best_self.0 >= 20 {
+					exit_sender.unbounded_send(()).unwrap();
+				}
+			}),
+			exit_receiver.into_future().map(|(_, _)| ()),
+		);
+
+		assert_eq!(result.submitted_messages_proofs[0].0, 1..=4);
+		assert!(result.submitted_messages_receiving_proofs.is_empty());
+	}
+
+	#[test]
+	fn message_lane_loop_only_delivers_confirmations_in_confirmations_mode() {
+		let (exit_sender, exit_receiver) = unbounded();
+		let result = run_loop_test(
+			RelayMode::Confirmations,
+			TestClientData {
+				source_state: ClientState {
+					best_self: HeaderId(10, 10),
+					best_finalized_self: HeaderId(10, 10),
+					best_finalized_peer_at_best_self: HeaderId(0, 0),
+				},
+				source_latest_generated_nonce: 10,
+				target_state: ClientState {
+					best_self: HeaderId(10, 10),
+					best_finalized_self: HeaderId(10, 10),
+					best_finalized_peer_at_best_self: HeaderId(10, 10),
+				},
+				target_latest_received_nonce: 4,
+				..Default::default()
+			},
+			Arc::new(move |data: &mut TestClientData| {
+				data.source_state.best_self =
+					HeaderId(data.source_state.best_self.0 + 1, data.source_state.best_self.1 + 1);
+				data.source_state.best_finalized_self = data.source_state.best_self;
+				data.source_state.best_finalized_peer_at_best_self = data.target_state.best_self;
+				if data.source_latest_confirmed_received_nonce == 4 {
+					exit_sender.unbounded_send(()).unwrap();
+				}
+			}),
+			Arc::new(|data: &mut TestClientData| {
+				data.target_state.best_self =
+					HeaderId(data.target_state.best_self.0 + 1, data.target_state.best_self.1 + 1);
+				data.target_state.best_finalized_self = data.target_state.best_self;
+				data.target_state.best_finalized_peer_at_best_self = data.source_state.best_self;
+			}),
+			exit_receiver.into_future().map(|(_, _)| ()),
+		);
+
+		assert!(result.submitted_messages_proofs.is_empty());
+		assert!(!result.submitted_messages_receiving_proofs.is_empty());
+	}
+
+	#[test]
+	fn message_lane_loop_does_not_run_while_lane_is_paused() {
+		let lane_control = LaneControl::default();
+		lane_control.pause();
+		let resumed_lane_control = lane_control.clone();
+		std::thread::spawn(move || {
+			std::thread::sleep(Duration::from_secs(2));
+			resumed_lane_control.resume();
+		});
+
+		let ticked_while_paused = Arc::new(std::sync::atomic::AtomicBool::new(false));
+		let tick_lane_control = lane_control.clone();
+		let tick_ticked_while_paused = ticked_while_paused.clone();
+		let (exit_sender, exit_receiver) = unbounded();
+		let result = run_controlled_loop_test(
+			RelayMode::Delivery,
+			Some(lane_control.clone()),
+			TestClientData {
+				source_state: ClientState {
+					best_self: HeaderId(10, 10),
+					best_finalized_self: HeaderId(10, 10),
+					best_finalized_peer_at_best_self: HeaderId(0, 0),
+				},
+				source_latest_generated_nonce: 4,
+				target_state: ClientState {
+					best_self: HeaderId(0, 0),
+					best_finalized_self: HeaderId(0, 0),
+					best_finalized_peer_at_best_self: HeaderId(10, 10),
+				},
+				target_latest_received_nonce: 0,
+				..Default::default()
+			},
+			Arc::new(move |data: &mut TestClientData| {
+				if tick_lane_control.is_paused() {
+					tick_ticked_while_paused.store(true, std::sync::atomic::Ordering::SeqCst);
+				}
+				data.source_state.best_finalized_peer_at_best_self = data.target_state.best_self;
+			}),
+			Arc::new(move |data: &mut TestClientData| {
+				data.target_state.best_self =
+					HeaderId(data.target_state.best_self.0 + 1, data.target_state.best_self.1 + 1);
+				data.target_state.best_finalized_self = data.target_state.best_self;
+				if data.target_latest_received_nonce == 4 {
+					exit_sender.unbounded_send(()).unwrap();
+				}
+			}),
+			exit_receiver.into_future().map(|(_, _)| ()),
+		);
+
+		assert!(!ticked_while_paused.load(std::sync::atomic::Ordering::SeqCst));
+		assert_eq!(result.submitted_messages_proofs[0].0, 1..=4);
+		assert!(lane_control.status()["components"]["delivery_race"].is_object());
+	}
+
+	#[test]
+	fn strategy_params_are_applied() {
+		let mut delivery_params = MessageDeliveryParams {
+			max_unrewarded_relayer_entries_at_target: 4,
+			max_unconfirmed_nonces_at_target: 4,
+			max_messages_in_single_batch: 4,
+			max_messages_weight_in_single_batch: 4,
+			max_messages_size_in_single_batch: 4,
+			relay_strategy: AltruisticStrategy,
+		};
+		apply_strategy_params(
+			&mut delivery_params,
+			StrategyParams { max_messages_in_single_batch: Some(1), ..Default::default() },
+		);
+		assert_eq!(delivery_params.max_messages_in_single_batch, 1);
+		assert_eq!(delivery_params.max_messages_weight_in_single_batch, 4);
+		assert_eq!(delivery_params.max_messages_size_in_single_batch, 4);
+	}
 }
@@ -19,7 +19,7 @@ use async_trait::async_trait;
 use futures::stream::FusedStream;
 
 use bp_messages::{MessageNonce, UnrewardedRelayersState, Weight};
-use relay_utils::FailedClient;
+use relay_utils::{admin::ComponentStatus, FailedClient};
 
 use crate::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
@@ -46,6 +46,7 @@ pub async fn run<P: MessageLane, Strategy: RelayStrategy>(
 	stall_timeout: Duration,
 	metrics_msg: Option<MessageLaneLoopMetrics>,
 	params: MessageDeliveryParams<Strategy>,
+	race_status: Option<ComponentStatus>,
 ) -> Result<(), FailedClient> {
 	crate::message_race_loop::run(
 		MessageDeliveryRaceSource {
@@ -75,6 +76,7 @@ pub async fn run<P: MessageLane, Strategy: RelayStrategy>(
 			target_nonces: None,
 			strategy: BasicStrategy::new(),
 		},
+		race_status,
 	)
 	.await
 }
@@ -505,6 +507,11 @@ where
 		let max_messages_size_in_single_batch = self.max_messages_size_in_single_batch;
 		let lane_source_client = self.lane_source_client.clone();
 		let lane_target_client = self.lane_target_client.clone();
+		let best_target_header_number: u64 = race_state
+			.best_target_header_id
+			.as_ref()
+			.map(|id| id.0.into())
+			.unwrap_or_default();
 
 		let maximal_source_queue_index =
 			self.strategy.maximal_available_source_queue_index(race_state)?;
@@ -517,6 +524,8 @@ where
 			max_messages_size_in_single_batch,
 			lane_source_client: lane_source_client.clone(),
 			lane_target_client: lane_target_client.clone(),
+			best_target_header_number,
+			best_finalized_source_header_id_at_best_target,
 			nonces_queue: source_queue.clone(),
 			nonces_queue_range: 0..maximal_source_queue_index + 1,
 		};
@@ -570,7 +579,7 @@ mod tests {
 			},
 			MessageDetails, RelayerMode,
 		},
-		relay_strategy::MixStrategy,
+		relay_strategy::{MixStrategy, ProfitAwareStrategy},
 	};
 
 	use super::*;
@@ -639,6 +648,7 @@ mod tests {
 						unrewarded_relayer_entries: 0,
 						messages_in_oldest_entry: 0,
 						total_messages: 0,
+						last_delivered_nonce: 0,
 					},
 				},
 			}),
@@ -662,6 +672,26 @@ mod tests {
 		(race_state, race_strategy)
 	}
 
+	fn with_relay_strategy<Strategy: RelayStrategy>(
+		strategy: TestStrategy,
+		relay_strategy: Strategy,
+	) -> MessageDeliveryStrategy<TestMessageLane, Strategy, TestSourceClient, TestTargetClient> {
+		MessageDeliveryStrategy {
+			lane_source_client: strategy.lane_source_client,
+			lane_target_client: strategy.lane_target_client,
+			max_unrewarded_relayer_entries_at_target: strategy
+				.max_unrewarded_relayer_entries_at_target,
+			max_unconfirmed_nonces_at_target: strategy.max_unconfirmed_nonces_at_target,
+			max_messages_in_single_batch: strategy.max_messages_in_single_batch,
+			max_messages_weight_in_single_batch: strategy.max_messages_weight_in_single_batch,
+			max_messages_size_in_single_batch: strategy.max_messages_size_in_single_batch,
+			relay_strategy,
+			latest_confirmed_nonces_at_source: strategy.latest_confirmed_nonces_at_source,
+			target_nonces: strategy.target_nonces,
+			strategy: strategy.strategy,
+		}
+	}
+
 	fn proof_parameters(state_required: bool, weight: Weight) -> MessageProofParameters {
 		MessageProofParameters {
 			outbound_state_proof_required: state_required,
@@ -954,6 +984,7 @@ mod tests {
 						unrewarded_relayer_entries: 2,
 						messages_in_oldest_entry: 2,
 						total_messages: 2,
+						last_delivered_nonce: 21,
 					},
 				},
 			},
@@ -1048,6 +1079,78 @@ mod tests {
 		);
 	}
 
+	#[async_std::test]
+	async fn profit_aware_relayer_is_not_delivering_messages_if_reward_does_not_cover_headers_relay(
+	) {
+		let (state, strategy) = prepare_strategy();
+		let mut strategy = with_relay_strategy(strategy, ProfitAwareStrategy::new(2, 2));
+
+		// so now we have:
+		// - 20..=23 with reward = delivery + confirmation cost
+		// - additional headers relay cost (2 + 2) is never covered by the reward
+		// => strategy shall select nothing
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+
+		// when headers relay is free, all messages are selected
+		strategy.relay_strategy = ProfitAwareStrategy::new(0, 0);
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=23), proof_parameters(false, 4)))
+		);
+	}
+
+	#[async_std::test]
+	async fn profit_aware_relayer_is_delivering_messages_if_reward_covers_headers_relay() {
+		let (mut state, mut strategy) = prepare_strategy();
+		let nonces =
+			source_nonces(24..=24, 19, DEFAULT_REWARD + 4, DispatchFeePayment::AtSourceChain);
+		strategy.strategy.source_nonces_updated(header_id(2), nonces);
+		state.best_finalized_source_header_id_at_best_target = Some(header_id(2));
+		strategy.max_unrewarded_relayer_entries_at_target = 100;
+		strategy.max_unconfirmed_nonces_at_target = 100;
+		strategy.max_messages_in_single_batch = 100;
+		strategy.max_messages_weight_in_single_batch = 100;
+		strategy.max_messages_size_in_single_batch = 100;
+		let mut strategy = with_relay_strategy(strategy, ProfitAwareStrategy::new(2, 2));
+
+		// so now we have:
+		// - 20..=23 with reward = delivery + confirmation cost
+		// - 24..=24 with reward that also covers headers relay cost (2 + 2)
+		// => strategy shall select all 20..=24
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=24), proof_parameters(false, 5)))
+		);
+	}
+
+	#[async_std::test]
+	async fn profit_aware_relayer_ignores_cost_of_mandatory_source_headers() {
+		let (state, strategy) = prepare_strategy();
+		strategy.lane_source_client.mark_header_as_mandatory(header_id(1));
+		let mut strategy = with_relay_strategy(strategy, ProfitAwareStrategy::new(100, 0));
+
+		// messages 20..=23 have been generated at source block#1, which is mandatory => it is
+		// relayed anyway and we don't need to pay for its relay
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=23), proof_parameters(false, 4)))
+		);
+	}
+
+	#[async_std::test]
+	async fn profit_aware_relayer_ignores_cost_of_older_source_headers() {
+		let (mut state, strategy) = prepare_strategy();
+		let mut strategy = with_relay_strategy(strategy, ProfitAwareStrategy::new(100, 0));
+		state.best_finalized_source_header_id_at_best_target = Some(header_id(2));
+
+		// messages 20..=23 have been generated at source block#1, which is not the best finalized
+		// source block at target => its relay has already been paid
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=23), proof_parameters(false, 4)))
+		);
+	}
+
 	#[async_std::test]
 	async fn relayer_uses_flattened_view_of_the_source_queue_to_select_nonces() {
 		// Real scenario that has happened on test deployments:
@@ -28,7 +28,10 @@ use futures::{
 	future::FutureExt,
 	stream::{FusedStream, StreamExt},
 };
-use relay_utils::{process_future_result, retry_backoff, FailedClient, MaybeConnectionError};
+use relay_utils::{
+	admin::ComponentStatus, process_future_result, retry_backoff, FailedClient,
+	MaybeConnectionError,
+};
 use std::{
 	fmt::Debug,
 	ops::RangeInclusive,
@@ -231,6 +234,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 		ProofParameters = SC::ProofParameters,
 		TargetNoncesData = TC::TargetNoncesData,
 	>,
+	race_status: Option<ComponentStatus>,
 ) -> Result<(), FailedClient> {
 	let mut progress_context = Instant::now();
 	let mut race_state = RaceState::default();
@@ -428,6 +432,9 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 		}
 
 		progress_context = print_race_progress::<P, _>(progress_context, &strategy);
+		if let Some(ref race_status) = race_status {
+			update_race_status(race_status, &race_state, &strategy);
+		}
 
 		if stall_countdown.elapsed() > stall_timeout {
 			log::warn!(
@@ -567,6 +574,33 @@ where
 	now_time
 }
 
+/// Update race status that is exposed by the admin server.
+fn update_race_status<SourceHeaderId, TargetHeaderId, Proof, Strategy>(
+	race_status: &ComponentStatus,
+	race_state: &RaceState<SourceHeaderId, TargetHeaderId, Proof>,
+	strategy: &Strategy,
+) where
+	SourceHeaderId: Debug,
+	TargetHeaderId: Debug,
+	Strategy: RaceStrategy<SourceHeaderId, TargetHeaderId, Proof>,
+{
+	race_status.update(serde_json::json!({
+		"best_nonce_at_source": strategy.best_at_source(),
+		"best_nonce_at_target": strategy.best_at_target(),
+		"best_finalized_source_header_id_at_source":
+			format!("{:?}", race_state.best_finalized_source_header_id_at_source),
+		"best_finalized_source_header_id_at_best_target":
+			format!("{:?}", race_state.best_finalized_source_header_id_at_best_target),
+		"best_target_header_id": format!("{:?}", race_state.best_target_header_id),
+		"best_finalized_target_header_id": format!("{:?}", race_state.best_finalized_target_header_id),
+		"nonces_to_submit": race_state
+			.nonces_to_submit
+			.as_ref()
+			.map(|(at_block, nonces, _)| format!("{:?} at {:?}", nonces, at_block)),
+		"nonces_submitted": race_state.nonces_submitted.as_ref().map(|nonces| format!("{:?}", nonces)),
+	}));
+}
+
 async fn select_nonces_to_deliver<SourceHeaderId, TargetHeaderId, Proof, Strategy>(
 	race_state: RaceState<SourceHeaderId, TargetHeaderId, Proof>,
 	strategy: &mut Strategy,
@@ -30,7 +30,7 @@ use crate::{
 use async_trait::async_trait;
 use bp_messages::MessageNonce;
 use futures::stream::FusedStream;
-use relay_utils::FailedClient;
+use relay_utils::{admin::ComponentStatus, FailedClient};
 use std::{marker::PhantomData, ops::RangeInclusive, time::Duration};
 
 /// Message receiving confirmations delivery strategy.
@@ -51,6 +51,7 @@ pub async fn run<P: MessageLane>(
 	target_state_updates: impl FusedStream<Item = TargetClientState<P>>,
 	stall_timeout: Duration,
 	metrics_msg: Option<MessageLaneLoopMetrics>,
+	race_status: Option<ComponentStatus>,
 ) -> Result<(), FailedClient> {
 	crate::message_race_loop::run(
 		ReceivingConfirmationsRaceSource {
@@ -67,6 +68,7 @@ pub async fn run<P: MessageLane>(
 		source_state_updates,
 		stall_timeout,
 		ReceivingConfirmationsBasicStrategy::<P>::new(),
+		race_status,
 	)
 	.await
 }
@@ -0,0 +1,191 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Coordination between multiple relayers, serving the same lane.
+//!
+//! If several rational relayers are delivering messages of the same lane, they're submitting
+//! identical `receive_messages_proof` transactions. Only one of them gets the reward, while all
+//! others are paying the base delivery transaction cost. Coordination schemes allow relayers to
+//! agree on who is delivering messages at every moment.
+
+use async_trait::async_trait;
+
+use crate::{
+	message_lane::MessageLane,
+	message_lane_loop::{
+		SourceClient as MessageLaneSourceClient, TargetClient as MessageLaneTargetClient,
+	},
+	relay_strategy::RelayReference,
+};
+
+/// Relayers coordination scheme.
+#[async_trait]
+pub trait RelayCoordination: 'static + Clone + Send + Sync {
+	/// Returns true if this relayer may deliver messages, described by the reference, right now.
+	///
+	/// If false is returned, the message is not delivered right now. The delivery race asks again
+	/// when it is selecting nonces for the next delivery transaction.
+	async fn may_deliver<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&mut self,
+		reference: &RelayReference<P, SourceClient, TargetClient>,
+	) -> bool;
+}
+
+/// No coordination at all - relayer is always allowed to deliver messages.
+#[derive(Clone, Debug)]
+pub struct NoCoordination;
+
+#[async_trait]
+impl RelayCoordination for NoCoordination {
+	async fn may_deliver<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&mut self,
+		_reference: &RelayReference<P, SourceClient, TargetClient>,
+	) -> bool {
+		true
+	}
+}
+
+/// Optional coordination - if it is not configured, relayer is always allowed to deliver messages.
+#[async_trait]
+impl<Coordination: RelayCoordination> RelayCoordination for Option<Coordination> {
+	async fn may_deliver<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&mut self,
+		reference: &RelayReference<P, SourceClient, TargetClient>,
+	) -> bool {
+		match *self {
+			Some(ref mut coordination) => coordination.may_deliver(reference).await,
+			None => true,
+		}
+	}
+}
+
+/// Deterministic slot assignment.
+///
+/// Target chain blocks are split into slots of `slot_length` blocks. Every slot is assigned to
+/// a single relayer from the known relayers set, using round-robin over the sorted relayer
+/// account ids. Relayer is only delivering messages during its own slots.
+///
+/// All relayers must be started with the same relayers set and slot length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotCoordination {
+	/// Index of this relayer in the sorted relayers set.
+	relayer_index: u64,
+	/// Number of relayers in the set.
+	relayers_count: u64,
+	/// Number of target chain blocks in the single slot.
+	slot_length: u64,
+}
+
+impl SlotCoordination {
+	/// Create slot coordination instance.
+	///
+	/// Relayer ids are the encoded relayer accounts at the source chain (the accounts that are
+	/// receiving rewards). Returns `None` if the relayer is not in the relayers set or the slot
+	/// length is zero.
+	pub fn new(relayer_id: &[u8], mut relayers: Vec<Vec<u8>>, slot_length: u64) -> Option<Self> {
+		if slot_length == 0 {
+			return None
+		}
+
+		relayers.sort();
+		relayers.dedup();
+		let relayer_index = relayers.iter().position(|id| id.as_slice() == relayer_id)?;
+		Some(SlotCoordination {
+			relayer_index: relayer_index as u64,
+			relayers_count: relayers.len() as u64,
+			slot_length,
+		})
+	}
+
+	/// Returns true if the slot, containing given target block, is assigned to this relayer.
+	pub fn is_own_slot(&self, target_block_number: u64) -> bool {
+		(target_block_number / self.slot_length) % self.relayers_count == self.relayer_index
+	}
+}
+
+#[async_trait]
+impl RelayCoordination for SlotCoordination {
+	async fn may_deliver<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&mut self,
+		reference: &RelayReference<P, SourceClient, TargetClient>,
+	) -> bool {
+		let is_own_slot = self.is_own_slot(reference.best_target_header_number);
+		if !is_own_slot {
+			log::trace!(
+				target: "bridge",
+				"Target block {} belongs to the slot of other relayer. Not delivering nonce {}",
+				reference.best_target_header_number,
+				reference.nonce,
+			);
+		}
+		is_own_slot
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn slot_coordination_rejects_unknown_relayer() {
+		assert_eq!(SlotCoordination::new(&[3], vec![vec![1], vec![2]], 4), None);
+	}
+
+	#[test]
+	fn slot_coordination_rejects_zero_slot_length() {
+		assert_eq!(SlotCoordination::new(&[1], vec![vec![1], vec![2]], 0), None);
+	}
+
+	#[test]
+	fn slot_coordination_assigns_every_slot_to_single_relayer() {
+		let relayers = vec![vec![3], vec![1], vec![2], vec![1]];
+		let coordinations = [1u8, 2, 3]
+			.iter()
+			.map(|id| SlotCoordination::new(&[*id], relayers.clone(), 4).unwrap())
+			.collect::<Vec<_>>();
+
+		for block_number in 0..100 {
+			let owners = coordinations
+				.iter()
+				.filter(|coordination| coordination.is_own_slot(block_number))
+				.count();
+			assert_eq!(owners, 1);
+		}
+
+		// relayers are sorted by id => relayer `1` owns the first slot, `2` the second, ...
+		assert!(coordinations[0].is_own_slot(0));
+		assert!(coordinations[0].is_own_slot(3));
+		assert!(coordinations[1].is_own_slot(4));
+		assert!(coordinations[2].is_own_slot(8));
+		assert!(coordinations[0].is_own_slot(12));
+	}
+}
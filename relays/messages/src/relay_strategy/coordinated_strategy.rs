@@ -0,0 +1,65 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Strategy that only delivers messages when relayers coordination scheme allows it.
+
+use async_trait::async_trait;
+
+use crate::{
+	message_lane::MessageLane,
+	message_lane_loop::{
+		SourceClient as MessageLaneSourceClient, TargetClient as MessageLaneTargetClient,
+	},
+	relay_coordination::RelayCoordination,
+	relay_strategy::{RelayReference, RelayStrategy},
+};
+
+/// The relayer delivers messages, selected by the wrapped strategy, only when
+/// coordination scheme allows it to.
+#[derive(Clone)]
+pub struct CoordinatedStrategy<Strategy: RelayStrategy, Coordination: RelayCoordination> {
+	strategy: Strategy,
+	coordination: Coordination,
+}
+
+impl<Strategy: RelayStrategy, Coordination: RelayCoordination>
+	CoordinatedStrategy<Strategy, Coordination>
+{
+	/// Create coordinated strategy instance.
+	pub fn new(strategy: Strategy, coordination: Coordination) -> Self {
+		Self { strategy, coordination }
+	}
+}
+
+#[async_trait]
+impl<Strategy: RelayStrategy, Coordination: RelayCoordination> RelayStrategy
+	for CoordinatedStrategy<Strategy, Coordination>
+{
+	async fn decide<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&mut self,
+		reference: &mut RelayReference<P, SourceClient, TargetClient>,
+	) -> bool {
+		if !self.coordination.may_deliver(reference).await {
+			return false
+		}
+
+		self.strategy.decide(reference).await
+	}
+}
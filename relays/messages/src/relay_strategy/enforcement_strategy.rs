@@ -60,11 +60,18 @@ impl<Strategy: RelayStrategy> EnforcementStrategy<Strategy> {
 
 		let hard_selected_begin_nonce =
 			reference.nonces_queue[reference.nonces_queue_range.start].1.begin();
+		let hard_selected_begin_header_id =
+			reference.nonces_queue[reference.nonces_queue_range.start].0.clone();
 
 		// relay reference
 		let mut relay_reference = RelayReference {
 			lane_source_client: reference.lane_source_client.clone(),
 			lane_target_client: reference.lane_target_client.clone(),
+			best_target_header_number: reference.best_target_header_number,
+			best_finalized_source_header_id_at_best_target: reference
+				.best_finalized_source_header_id_at_best_target
+				.clone(),
+			required_source_header_id: hard_selected_begin_header_id,
 
 			selected_reward: P::SourceChainBalance::zero(),
 			selected_cost: P::SourceChainBalance::zero(),
@@ -91,10 +98,13 @@ impl<Strategy: RelayStrategy> EnforcementStrategy<Strategy> {
 		let all_ready_nonces = reference
 			.nonces_queue
 			.range(reference.nonces_queue_range.clone())
-			.flat_map(|(_, ready_nonces)| ready_nonces.iter())
+			.flat_map(|(at_block, ready_nonces)| {
+				ready_nonces.iter().map(move |(nonce, details)| (at_block, nonce, details))
+			})
 			.enumerate();
-		for (index, (nonce, details)) in all_ready_nonces {
+		for (index, (at_block, nonce, details)) in all_ready_nonces {
 			relay_reference.index = index;
+			relay_reference.required_source_header_id = at_block.clone();
 			relay_reference.nonce = *nonce;
 			relay_reference.details = *details;
 
@@ -24,19 +24,31 @@ use crate::{
 		RelayerMode, SourceClient as MessageLaneSourceClient,
 		TargetClient as MessageLaneTargetClient,
 	},
-	relay_strategy::{AltruisticStrategy, RationalStrategy, RelayReference, RelayStrategy},
+	relay_strategy::{
+		AltruisticStrategy, ProfitAwareStrategy, RationalStrategy, RelayReference, RelayStrategy,
+	},
 };
 
 /// `RelayerMode` adapter.
 #[derive(Clone)]
 pub struct MixStrategy {
 	relayer_mode: RelayerMode,
+	profit_aware_strategy: ProfitAwareStrategy,
 }
 
 impl MixStrategy {
 	/// Create mix strategy instance
 	pub fn new(relayer_mode: RelayerMode) -> Self {
-		Self { relayer_mode }
+		Self { relayer_mode, profit_aware_strategy: ProfitAwareStrategy::new(0, 0) }
+	}
+
+	/// Use given strategy in the `RelayerMode::ProfitAware` mode.
+	pub fn with_profit_aware_strategy(
+		mut self,
+		profit_aware_strategy: ProfitAwareStrategy,
+	) -> Self {
+		self.profit_aware_strategy = profit_aware_strategy;
+		self
 	}
 }
 
@@ -53,6 +65,7 @@ impl RelayStrategy for MixStrategy {
 		match self.relayer_mode {
 			RelayerMode::Altruistic => AltruisticStrategy.decide(reference).await,
 			RelayerMode::Rational => RationalStrategy.decide(reference).await,
+			RelayerMode::ProfitAware => self.profit_aware_strategy.decide(reference).await,
 		}
 	}
 }
@@ -23,7 +23,7 @@ use async_trait::async_trait;
 use bp_messages::{MessageNonce, Weight};
 
 use crate::{
-	message_lane::MessageLane,
+	message_lane::{MessageLane, SourceHeaderIdOf},
 	message_lane_loop::{
 		MessageDetails, MessageDetailsMap, SourceClient as MessageLaneSourceClient,
 		TargetClient as MessageLaneTargetClient,
@@ -32,11 +32,16 @@ use crate::{
 };
 
 pub(crate) use self::enforcement_strategy::*;
-pub use self::{altruistic_strategy::*, mix_strategy::*, rational_strategy::*};
+pub use self::{
+	altruistic_strategy::*, coordinated_strategy::*, mix_strategy::*, profit_aware_strategy::*,
+	rational_strategy::*,
+};
 
 mod altruistic_strategy;
+mod coordinated_strategy;
 mod enforcement_strategy;
 mod mix_strategy;
+mod profit_aware_strategy;
 mod rational_strategy;
 
 /// Relayer strategy trait
@@ -67,6 +72,13 @@ pub struct RelayReference<
 	pub lane_source_client: SourceClient,
 	/// The client that is connected to the message lane target node.
 	pub lane_target_client: TargetClient,
+	/// Number of the best target header, known to the delivery race.
+	pub best_target_header_number: u64,
+	/// Best finalized source header id, known to the best target header.
+	pub best_finalized_source_header_id_at_best_target: SourceHeaderIdOf<P>,
+	/// Source header at which current nonce has been generated. The header must be known
+	/// to the target chain to deliver the message.
+	pub required_source_header_id: SourceHeaderIdOf<P>,
 	/// Current block reward summary
 	pub selected_reward: P::SourceChainBalance,
 	/// Current block cost summary
@@ -112,6 +124,10 @@ pub struct RelayMessagesBatchReference<
 	pub lane_source_client: SourceClient,
 	/// The client that is connected to the message lane target node.
 	pub lane_target_client: TargetClient,
+	/// Number of the best target header, known to the delivery race.
+	pub best_target_header_number: u64,
+	/// Best finalized source header id, known to the best target header.
+	pub best_finalized_source_header_id_at_best_target: SourceHeaderIdOf<P>,
 	/// Source queue.
 	pub nonces_queue: SourceRangesQueue<
 		P::SourceHeaderHash,
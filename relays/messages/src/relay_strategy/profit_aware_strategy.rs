@@ -0,0 +1,155 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rational relayer strategy, which also accounts cost of headers relay.
+
+use async_trait::async_trait;
+use num_traits::{SaturatingAdd, Zero};
+use sp_arithmetic::traits::UniqueSaturatedFrom;
+
+use crate::{
+	message_lane::MessageLane,
+	message_lane_loop::{
+		SourceClient as MessageLaneSourceClient, TargetClient as MessageLaneTargetClient,
+	},
+	relay_strategy::{RationalStrategy, RelayReference, RelayStrategy},
+};
+
+/// The relayer will deliver messages as long as the total reward covers cost of the delivery
+/// transaction, cost of delivery confirmation transactions AND cost of finality transactions
+/// that are required to make the delivery and the confirmation transactions verifiable.
+///
+/// To be delivered, messages need the source header, at which they have been generated, to be
+/// finalized at the target chain. The cost of this header relay is only accounted once per batch
+/// and only if the batch requires the best finalized source header, known to the target chain
+/// (i.e. most likely the header has been relayed on-demand for these messages). Older headers
+/// are assumed to be paid by previous batches. Mandatory headers are relayed even if there are no
+/// messages at all, so the cost of their relay is never accounted.
+///
+/// Delivery confirmation needs the target header with the delivery transaction to be finalized at
+/// the source chain, so the cost of the reverse header relay is accounted once per batch as well.
+#[derive(Clone, Debug)]
+pub struct ProfitAwareStrategy {
+	/// Cost of finality transaction at the target chain, in the source chain tokens.
+	source_header_relay_cost: u128,
+	/// Cost of finality transaction at the source chain, in the source chain tokens.
+	target_header_relay_cost: u128,
+}
+
+impl ProfitAwareStrategy {
+	/// Create profit aware strategy instance.
+	///
+	/// Both costs are expected to be given in the source chain tokens. If single finality
+	/// transaction is shared by several lanes, the caller may pass the amortized cost here.
+	pub fn new(source_header_relay_cost: u128, target_header_relay_cost: u128) -> Self {
+		ProfitAwareStrategy { source_header_relay_cost, target_header_relay_cost }
+	}
+
+	/// Returns cost of headers relay that is required to deliver and confirm messages, selected
+	/// by the reference.
+	async fn headers_relay_cost<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&self,
+		reference: &RelayReference<P, SourceClient, TargetClient>,
+	) -> P::SourceChainBalance {
+		let is_source_header_relay_required = reference.required_source_header_id.0 >=
+			reference.best_finalized_source_header_id_at_best_target.0;
+		let is_source_header_relay_paid =
+			is_source_header_relay_required && !self.is_mandatory_source_header(reference).await;
+		let source_header_relay_cost = if is_source_header_relay_paid {
+			P::SourceChainBalance::unique_saturated_from(self.source_header_relay_cost)
+		} else {
+			Zero::zero()
+		};
+		let target_header_relay_cost =
+			P::SourceChainBalance::unique_saturated_from(self.target_header_relay_cost);
+
+		source_header_relay_cost.saturating_add(&target_header_relay_cost)
+	}
+
+	/// Returns true if the source header, required to deliver messages, is mandatory.
+	///
+	/// If we can't read the header, it is assumed to be non-mandatory.
+	async fn is_mandatory_source_header<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&self,
+		reference: &RelayReference<P, SourceClient, TargetClient>,
+	) -> bool {
+		let required_source_header_id = reference.required_source_header_id.clone();
+		match reference
+			.lane_source_client
+			.is_mandatory_header(required_source_header_id)
+			.await
+		{
+			Ok(is_mandatory) => is_mandatory,
+			Err(err) => {
+				log::trace!(
+					target: "bridge",
+					"Failed to check if source header {:?} is mandatory: {:?}",
+					reference.required_source_header_id,
+					err,
+				);
+				false
+			},
+		}
+	}
+}
+
+#[async_trait]
+impl RelayStrategy for ProfitAwareStrategy {
+	async fn decide<
+		P: MessageLane,
+		SourceClient: MessageLaneSourceClient<P>,
+		TargetClient: MessageLaneTargetClient<P>,
+	>(
+		&mut self,
+		reference: &mut RelayReference<P, SourceClient, TargetClient>,
+	) -> bool {
+		// rational strategy updates total reward and total cost of delivery + confirmation
+		// transactions. It may also update selected reward and cost, so let's remember it
+		let prev_selected_reward = reference.selected_reward;
+		let prev_selected_cost = reference.selected_cost;
+		RationalStrategy.decide(reference).await;
+		reference.selected_reward = prev_selected_reward;
+		reference.selected_cost = prev_selected_cost;
+
+		let headers_relay_cost = self.headers_relay_cost(reference).await;
+		let total_cost = reference.total_cost.saturating_add(&headers_relay_cost);
+		if reference.total_reward >= total_cost {
+			reference.selected_reward = reference.total_reward;
+			reference.selected_cost = total_cost;
+			return true
+		}
+
+		log::trace!(
+			target: "bridge",
+			"Message with nonce {} is not selected: total reward {:?} is less than total cost {:?}, \
+			including headers relay cost {:?}",
+			reference.nonce,
+			reference.total_reward,
+			total_cost,
+			headers_relay_cost,
+		);
+
+		false
+	}
+}
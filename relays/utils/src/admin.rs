@@ -0,0 +1,774 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relay admin server.
+//!
+//! The admin server is an optional HTTP endpoint that exposes live status of relay loops and
+//! accepts control commands. `GET` requests are answered with the relay status and `POST`
+//! requests are treated as JSON-RPC 2.0 calls. Supported methods are:
+//!
+//! - `relay_status()` - returns status of all registered components;
+//! - `relay_pauseLane(lane)` - pause message lane relay. Both messages delivery and delivery
+//!   confirmations are stopped while the lane is paused;
+//! - `relay_resumeLane(lane)` - resume message lane relay, paused by the `relay_pauseLane` call.
+//!   The lane stays paused if it has also been paused by relay guards;
+//! - `relay_forceHeaderSync(relay, number)` - ask on-demand headers relay to sync headers up to
+//!   given number;
+//! - `relay_updateStrategyParams(lane, params)` - update delivery strategy parameters of the lane.
+//!   The lane relay is restarted to apply new parameters.
+//!
+//! If access token is configured, every request must carry the `Authorization: Bearer <token>`
+//! header. Without the token, the server may only be exposed at the loopback interface.
+
+use crate::error::Error;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	net::SocketAddr,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	time::Duration,
+};
+
+/// Interval at which relay loops are checking whether they have been paused or resumed.
+pub const CONTROL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reason of the lane pause, requested using admin server.
+pub const OPERATOR_PAUSE_REASON: &str = "operator";
+
+/// Maximal size of the admin request (including headers).
+const MAX_REQUEST_SIZE: usize = 64 * 1024;
+
+/// JSON-RPC error code: invalid JSON was received by the server.
+const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code: the JSON sent is not a valid request object.
+const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code: the method does not exist.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code: invalid method parameters.
+const INVALID_PARAMS: i64 = -32602;
+
+/// Unparsed address that needs to be used to expose admin server.
+#[derive(Debug, Clone)]
+pub struct AdminAddress {
+	/// Serve HTTP requests at given host.
+	pub host: String,
+	/// Serve HTTP requests at given port.
+	pub port: u16,
+}
+
+/// Admin server params.
+#[derive(Debug, Clone)]
+pub struct AdminParams {
+	/// Interface and TCP port to be used when exposing admin server.
+	pub address: Option<AdminAddress>,
+	/// Access token that must be provided by clients. If it is `None`, the server may only be
+	/// exposed at the loopback interface.
+	pub token: Option<String>,
+	/// Registry of controlled components. May be shared by several relay loops.
+	pub registry: AdminRegistry,
+}
+
+impl Default for AdminAddress {
+	fn default() -> Self {
+		AdminAddress { host: "127.0.0.1".into(), port: 9617 }
+	}
+}
+
+impl AdminParams {
+	/// Creates admin params so that admin server is not started.
+	pub fn disabled() -> Self {
+		AdminParams { address: None, token: None, registry: AdminRegistry::default() }
+	}
+
+	/// Require clients to provide given access token.
+	pub fn with_token(mut self, token: Option<String>) -> Self {
+		self.token = token;
+		self
+	}
+
+	/// Returns registry of controlled components if admin server is enabled.
+	pub fn enabled_registry(&self) -> Option<AdminRegistry> {
+		self.address.as_ref().map(|_| self.registry.clone())
+	}
+
+	/// Start admin server at address passed at creation.
+	///
+	/// If passed `address` is `None`, server is not started.
+	pub async fn expose(self) -> Result<(), Error> {
+		let address = match self.address {
+			Some(address) => address,
+			None => return Ok(()),
+		};
+		let socket_addr = SocketAddr::new(
+			address
+				.host
+				.parse()
+				.map_err(|err| Error::ExposingAdminInvalidHost(address.host.clone(), err))?,
+			address.port,
+		);
+		if self.token.is_none() && !socket_addr.ip().is_loopback() {
+			return Err(Error::ExposingAdminWithoutToken(address.host))
+		}
+		let listener = async_std::net::TcpListener::bind(socket_addr)
+			.await
+			.map_err(|err| Error::StartAdminServer(socket_addr.to_string(), err))?;
+
+		log::info!(target: "bridge", "Admin server is listening at {}", socket_addr);
+
+		let registry = self.registry;
+		let token = self.token.map(Arc::new);
+		async_std::task::spawn(async move {
+			loop {
+				match listener.accept().await {
+					Ok((stream, _)) => {
+						let registry = registry.clone();
+						let token = token.clone();
+						async_std::task::spawn(async move {
+							let token = token.as_ref().map(|token| token.as_str());
+							if let Err(err) = serve_connection(&registry, token, stream).await {
+								log::trace!(
+									target: "bridge",
+									"Failed to serve admin request: {:?}",
+									err,
+								);
+							}
+						});
+					},
+					Err(err) => {
+						log::warn!(
+							target: "bridge",
+							"Admin server has failed to accept connection: {:?}",
+							err,
+						);
+					},
+				}
+			}
+		});
+
+		Ok(())
+	}
+}
+
+impl From<Option<AdminAddress>> for AdminParams {
+	fn from(address: Option<AdminAddress>) -> Self {
+		AdminParams { address, token: None, registry: AdminRegistry::default() }
+	}
+}
+
+/// Registry of components that are controlled by the admin server.
+#[derive(Debug, Clone, Default)]
+pub struct AdminRegistry {
+	lanes: Arc<Mutex<BTreeMap<String, LaneControl>>>,
+	header_relays: Arc<Mutex<BTreeMap<String, HeadersRelayControl>>>,
+}
+
+impl AdminRegistry {
+	/// Returns control handle of the message lane relay with given name. The handle is
+	/// registered if it has not been registered yet.
+	pub fn lane_control(&self, name: String) -> LaneControl {
+		self.lanes
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.entry(name)
+			.or_default()
+			.clone()
+	}
+
+	/// Returns control handle of the headers relay with given name. The handle is registered
+	/// if it has not been registered yet.
+	pub fn headers_relay_control(&self, name: String) -> HeadersRelayControl {
+		self.header_relays
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.entry(name)
+			.or_default()
+			.clone()
+	}
+
+	/// Returns status of all registered components.
+	pub fn status(&self) -> Value {
+		let lanes = self
+			.lanes
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.iter()
+			.map(|(name, control)| (name.clone(), control.status()))
+			.collect::<serde_json::Map<_, _>>();
+		let header_relays = self
+			.header_relays
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.iter()
+			.map(|(name, control)| (name.clone(), control.status()))
+			.collect::<serde_json::Map<_, _>>();
+		json!({ "lanes": lanes, "header_relays": header_relays })
+	}
+
+	fn find_lane(&self, name: &str) -> Result<LaneControl, String> {
+		self.lanes
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.get(name)
+			.cloned()
+			.ok_or_else(|| format!("Unknown lane: {}", name))
+	}
+
+	fn find_headers_relay(&self, name: &str) -> Result<HeadersRelayControl, String> {
+		self.header_relays
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.get(name)
+			.cloned()
+			.ok_or_else(|| format!("Unknown headers relay: {}", name))
+	}
+
+	/// Process JSON-RPC request and return response.
+	fn handle_rpc_request(&self, request: &str) -> Value {
+		let request: Value = match serde_json::from_str(request) {
+			Ok(request) => request,
+			Err(err) => return rpc_error(Value::Null, PARSE_ERROR, err.to_string()),
+		};
+		let id = request.get("id").cloned().unwrap_or(Value::Null);
+		let method = match request.get("method").and_then(Value::as_str) {
+			Some(method) => method,
+			None => return rpc_error(id, INVALID_REQUEST, "Missing method".into()),
+		};
+		let params = request.get("params").cloned().unwrap_or_else(|| json!([]));
+
+		let result = match method {
+			"relay_status" => Ok(self.status()),
+			"relay_pauseLane" => parse_params::<(String,)>(params).and_then(|(lane,)| {
+				self.find_lane(&lane).map_err(invalid_params).map(|control| {
+					control.pause();
+					Value::Bool(true)
+				})
+			}),
+			"relay_resumeLane" => parse_params::<(String,)>(params).and_then(|(lane,)| {
+				self.find_lane(&lane).map_err(invalid_params).map(|control| {
+					control.resume();
+					Value::Bool(true)
+				})
+			}),
+			"relay_forceHeaderSync" =>
+				parse_params::<(String, u64)>(params).and_then(|(relay, number)| {
+					self.find_headers_relay(&relay).map_err(invalid_params).map(|control| {
+						control.force_sync(number);
+						Value::Bool(true)
+					})
+				}),
+			"relay_updateStrategyParams" => parse_params::<(String, StrategyParams)>(params)
+				.and_then(|(lane, params)| {
+					self.find_lane(&lane).map_err(invalid_params).map(|control| {
+						control.update_strategy_params(params);
+						Value::Bool(true)
+					})
+				}),
+			_ => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+		};
+
+		match result {
+			Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+			Err((code, message)) => rpc_error(id, code, message),
+		}
+	}
+}
+
+/// Shared status of the relay component (e.g. message race) that is exposed by the admin server.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentStatus(Arc<Mutex<Value>>);
+
+impl ComponentStatus {
+	/// Update component status.
+	pub fn update(&self, status: Value) {
+		*self.0.lock().expect("poisoned only if other thread has panicked; qed") = status;
+	}
+
+	/// Returns current component status.
+	pub fn get(&self) -> Value {
+		self.0.lock().expect("poisoned only if other thread has panicked; qed").clone()
+	}
+}
+
+/// Delivery strategy parameters that may be updated while relay is running.
+///
+/// `None` means that the parameter value, specified at relay start, is used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StrategyParams {
+	/// Maximal number of relayed messages in single delivery transaction.
+	#[serde(default)]
+	pub max_messages_in_single_batch: Option<u64>,
+	/// Maximal cumulative dispatch weight of relayed messages in single delivery transaction.
+	#[serde(default)]
+	pub max_messages_weight_in_single_batch: Option<u64>,
+	/// Maximal cumulative size of relayed messages in single delivery transaction.
+	#[serde(default)]
+	pub max_messages_size_in_single_batch: Option<u32>,
+}
+
+/// Control handle of the message lane relay.
+#[derive(Debug, Clone, Default)]
+pub struct LaneControl {
+	pause_reasons: Arc<Mutex<BTreeSet<String>>>,
+	restart_requested: Arc<AtomicBool>,
+	strategy_params: Arc<Mutex<StrategyParams>>,
+	components: Arc<Mutex<BTreeMap<String, ComponentStatus>>>,
+}
+
+impl LaneControl {
+	/// Pause lane relay on operator request. Both messages delivery and delivery confirmations
+	/// are stopped while the lane is paused.
+	pub fn pause(&self) {
+		self.pause_by(OPERATOR_PAUSE_REASON);
+	}
+
+	/// Cancel operator pause of the lane relay.
+	pub fn resume(&self) {
+		self.resume_by(OPERATOR_PAUSE_REASON);
+	}
+
+	/// Pause lane relay for given reason. The lane stays paused until every reason is cancelled.
+	pub fn pause_by(&self, reason: &str) {
+		self.pause_reasons
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.insert(reason.into());
+	}
+
+	/// Cancel pause of the lane relay for given reason.
+	pub fn resume_by(&self, reason: &str) {
+		self.pause_reasons
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.remove(reason);
+	}
+
+	/// Returns reasons why the lane relay is paused.
+	pub fn pause_reasons(&self) -> Vec<String> {
+		self.pause_reasons
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.iter()
+			.cloned()
+			.collect()
+	}
+
+	/// Returns true if lane relay is paused.
+	pub fn is_paused(&self) -> bool {
+		!self
+			.pause_reasons
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.is_empty()
+	}
+
+	/// Update delivery strategy parameters and ask lane relay to restart.
+	pub fn update_strategy_params(&self, params: StrategyParams) {
+		{
+			let mut strategy_params = self
+				.strategy_params
+				.lock()
+				.expect("poisoned only if other thread has panicked; qed");
+			strategy_params.max_messages_in_single_batch = params
+				.max_messages_in_single_batch
+				.or(strategy_params.max_messages_in_single_batch);
+			strategy_params.max_messages_weight_in_single_batch = params
+				.max_messages_weight_in_single_batch
+				.or(strategy_params.max_messages_weight_in_single_batch);
+			strategy_params.max_messages_size_in_single_batch = params
+				.max_messages_size_in_single_batch
+				.or(strategy_params.max_messages_size_in_single_batch);
+		}
+		self.restart_requested.store(true, Ordering::SeqCst);
+	}
+
+	/// Returns current delivery strategy parameters.
+	pub fn strategy_params(&self) -> StrategyParams {
+		*self
+			.strategy_params
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+	}
+
+	/// Returns status handle of the lane relay component (e.g. delivery race) with given name.
+	pub fn component_status(&self, name: &str) -> ComponentStatus {
+		self.components
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.entry(name.into())
+			.or_default()
+			.clone()
+	}
+
+	/// Wait until lane relay is resumed. Returns immediately if lane is not paused.
+	pub async fn wait_resumed(&self) {
+		while self.is_paused() {
+			async_std::task::sleep(CONTROL_POLL_INTERVAL).await;
+		}
+	}
+
+	/// Wait until lane relay is paused or restart is requested.
+	pub async fn wait_interrupted(&self) {
+		loop {
+			if self.is_paused() || self.restart_requested.swap(false, Ordering::SeqCst) {
+				return
+			}
+			async_std::task::sleep(CONTROL_POLL_INTERVAL).await;
+		}
+	}
+
+	/// Returns lane status.
+	pub fn status(&self) -> Value {
+		let components = self
+			.components
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.iter()
+			.map(|(name, status)| (name.clone(), status.get()))
+			.collect::<serde_json::Map<_, _>>();
+		json!({
+			"paused": self.is_paused(),
+			"pause_reasons": self.pause_reasons(),
+			"strategy_params": self.strategy_params(),
+			"components": components,
+		})
+	}
+}
+
+/// Control handle of the (on-demand) headers relay.
+#[derive(Debug, Clone, Default)]
+pub struct HeadersRelayControl {
+	forced_header_number: Arc<Mutex<Option<u64>>>,
+	status: ComponentStatus,
+}
+
+impl HeadersRelayControl {
+	/// Ask headers relay to sync headers up to given number.
+	pub fn force_sync(&self, number: u64) {
+		let mut forced_header_number = self
+			.forced_header_number
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed");
+		*forced_header_number = Some(forced_header_number.unwrap_or(0).max(number));
+	}
+
+	/// Take header number that has been requested by the admin.
+	pub fn take_forced_header_number(&self) -> Option<u64> {
+		self.forced_header_number
+			.lock()
+			.expect("poisoned only if other thread has panicked; qed")
+			.take()
+	}
+
+	/// Update headers relay status.
+	pub fn update_status(&self, status: Value) {
+		self.status.update(status);
+	}
+
+	/// Returns headers relay status.
+	pub fn status(&self) -> Value {
+		self.status.get()
+	}
+}
+
+/// Parse JSON-RPC method params.
+fn parse_params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, (i64, String)> {
+	serde_json::from_value(params).map_err(|err| invalid_params(err.to_string()))
+}
+
+/// Make invalid params error.
+fn invalid_params(message: String) -> (i64, String) {
+	(INVALID_PARAMS, message)
+}
+
+/// Make JSON-RPC error response.
+fn rpc_error(id: Value, code: i64, message: String) -> Value {
+	json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Returns true if HTTP request headers are carrying given access token.
+fn is_authorized(headers: &str, token: &str) -> bool {
+	let expected = format!("Bearer {}", token);
+	headers
+		.lines()
+		.filter_map(|line| line.split_once(':'))
+		.filter(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+		.any(|(_, value)| {
+			let value = value.trim().as_bytes();
+			// compare all bytes to avoid leaking token prefix through response time
+			value.len() == expected.len() &&
+				value.iter().zip(expected.as_bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+		})
+}
+
+/// Read single HTTP request from the connection and send response.
+async fn serve_connection(
+	registry: &AdminRegistry,
+	token: Option<&str>,
+	mut stream: async_std::net::TcpStream,
+) -> std::io::Result<()> {
+	use async_std::io::{ReadExt, WriteExt};
+
+	let mut request = Vec::new();
+	let mut buffer = [0u8; 4096];
+	let headers_end = loop {
+		if let Some(position) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+			break position + 4
+		}
+		if request.len() > MAX_REQUEST_SIZE {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "request is too large"))
+		}
+		let read = stream.read(&mut buffer).await?;
+		if read == 0 {
+			return Ok(())
+		}
+		request.extend_from_slice(&buffer[..read]);
+	};
+
+	let headers = String::from_utf8_lossy(&request[..headers_end]).into_owned();
+	let http_method = headers.split_whitespace().next().unwrap_or_default().to_owned();
+	let content_length = headers
+		.lines()
+		.filter_map(|line| line.split_once(':'))
+		.find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+		.and_then(|(_, value)| value.trim().parse::<usize>().ok())
+		.unwrap_or(0);
+	if headers_end + content_length > MAX_REQUEST_SIZE {
+		return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "request is too large"))
+	}
+	while request.len() < headers_end + content_length {
+		let read = stream.read(&mut buffer).await?;
+		if read == 0 {
+			break
+		}
+		request.extend_from_slice(&buffer[..read]);
+	}
+
+	let authorized = token.map(|token| is_authorized(&headers, token)).unwrap_or(true);
+	let (status_line, response) = match http_method.as_str() {
+		_ if !authorized => ("401 Unauthorized", json!({ "error": "Invalid access token" })),
+		"GET" => ("200 OK", registry.status()),
+		"POST" => {
+			let body_end = std::cmp::min(request.len(), headers_end + content_length);
+			let body = String::from_utf8_lossy(&request[headers_end..body_end]);
+			("200 OK", registry.handle_rpc_request(&body))
+		},
+		_ => ("405 Method Not Allowed", json!({ "error": "Only GET and POST are supported" })),
+	};
+	let response = response.to_string();
+	stream
+		.write_all(
+			format!(
+				"HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+				Connection: close\r\n\r\n{}",
+				status_line,
+				response.len(),
+				response,
+			)
+			.as_bytes(),
+		)
+		.await?;
+	stream.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rpc(registry: &AdminRegistry, method: &str, params: Value) -> Value {
+		registry.handle_rpc_request(
+			&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string(),
+		)
+	}
+
+	#[test]
+	fn lane_is_paused_and_resumed() {
+		let registry = AdminRegistry::default();
+		let lane = registry.lane_control("lane".into());
+		assert!(!lane.is_paused());
+
+		assert_eq!(rpc(&registry, "relay_pauseLane", json!(["lane"]))["result"], json!(true));
+		assert!(lane.is_paused());
+		assert_eq!(registry.status()["lanes"]["lane"]["paused"], json!(true));
+
+		assert_eq!(rpc(&registry, "relay_resumeLane", json!(["lane"]))["result"], json!(true));
+		assert!(!lane.is_paused());
+	}
+
+	#[test]
+	fn lane_is_paused_until_all_reasons_are_cancelled() {
+		let registry = AdminRegistry::default();
+		let lane = registry.lane_control("lane".into());
+
+		lane.pause_by("guard");
+		rpc(&registry, "relay_pauseLane", json!(["lane"]));
+		assert_eq!(
+			registry.status()["lanes"]["lane"]["pause_reasons"],
+			json!(["guard", OPERATOR_PAUSE_REASON]),
+		);
+
+		lane.resume_by("guard");
+		assert!(lane.is_paused());
+
+		lane.pause_by("guard");
+		rpc(&registry, "relay_resumeLane", json!(["lane"]));
+		assert!(lane.is_paused());
+
+		lane.resume_by("guard");
+		assert!(!lane.is_paused());
+	}
+
+	#[test]
+	fn unknown_lane_is_rejected() {
+		let registry = AdminRegistry::default();
+		assert_eq!(
+			rpc(&registry, "relay_pauseLane", json!(["lane"]))["error"]["code"],
+			json!(INVALID_PARAMS),
+		);
+	}
+
+	#[test]
+	fn header_sync_is_forced() {
+		let registry = AdminRegistry::default();
+		let relay = registry.headers_relay_control("relay".into());
+		assert_eq!(relay.take_forced_header_number(), None);
+
+		rpc(&registry, "relay_forceHeaderSync", json!(["relay", 100]));
+		rpc(&registry, "relay_forceHeaderSync", json!(["relay", 50]));
+		assert_eq!(relay.take_forced_header_number(), Some(100));
+		assert_eq!(relay.take_forced_header_number(), None);
+	}
+
+	#[test]
+	fn strategy_params_are_updated() {
+		let registry = AdminRegistry::default();
+		let lane = registry.lane_control("lane".into());
+
+		rpc(
+			&registry,
+			"relay_updateStrategyParams",
+			json!(["lane", { "max_messages_in_single_batch": 4 }]),
+		);
+		rpc(
+			&registry,
+			"relay_updateStrategyParams",
+			json!(["lane", { "max_messages_size_in_single_batch": 1024 }]),
+		);
+		assert_eq!(
+			lane.strategy_params(),
+			StrategyParams {
+				max_messages_in_single_batch: Some(4),
+				max_messages_weight_in_single_batch: None,
+				max_messages_size_in_single_batch: Some(1024),
+			},
+		);
+		assert_eq!(
+			rpc(&registry, "relay_updateStrategyParams", json!(["lane", { "unknown": 1 }]))
+				["error"]["code"],
+			json!(INVALID_PARAMS),
+		);
+	}
+
+	#[test]
+	fn restart_request_interrupts_lane() {
+		let registry = AdminRegistry::default();
+		let lane = registry.lane_control("lane".into());
+		lane.update_strategy_params(StrategyParams::default());
+		async_std::task::block_on(lane.wait_interrupted());
+		assert!(!lane.restart_requested.load(Ordering::SeqCst));
+	}
+
+	#[test]
+	fn invalid_requests_are_rejected() {
+		let registry = AdminRegistry::default();
+		assert_eq!(registry.handle_rpc_request("{")["error"]["code"], json!(PARSE_ERROR));
+		assert_eq!(
+			rpc(&registry, "relay_unknown", json!([]))["error"]["code"],
+			json!(METHOD_NOT_FOUND)
+		);
+	}
+
+	#[test]
+	fn status_is_served_over_http() {
+		use std::io::{Read, Write};
+
+		let registry = AdminRegistry::default();
+		registry
+			.lane_control("lane".into())
+			.component_status("delivery_race")
+			.update(json!({ "nonces_submitted": "1..=2" }));
+		// select some free port for the admin server
+		let address = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+		let params = AdminParams {
+			address: Some(AdminAddress { host: "127.0.0.1".into(), port: address.port() }),
+			token: None,
+			registry: registry.clone(),
+		};
+		async_std::task::block_on(params.expose()).unwrap();
+
+		let mut stream = std::net::TcpStream::connect(address).unwrap();
+		write!(stream, "GET /status HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+		let mut response = String::new();
+		stream.read_to_string(&mut response).unwrap();
+		let body = response.split("\r\n\r\n").nth(1).unwrap();
+		assert_eq!(serde_json::from_str::<Value>(body).unwrap(), registry.status());
+		assert_eq!(
+			registry.status()["lanes"]["lane"]["components"]["delivery_race"]["nonces_submitted"],
+			json!("1..=2"),
+		);
+	}
+
+	#[test]
+	fn non_loopback_server_requires_token() {
+		let params = AdminParams::from(Some(AdminAddress { host: "0.0.0.0".into(), port: 0 }));
+		assert!(matches!(
+			async_std::task::block_on(params.expose()),
+			Err(Error::ExposingAdminWithoutToken(_)),
+		));
+	}
+
+	#[test]
+	fn requests_without_token_are_rejected() {
+		use std::io::{Read, Write};
+
+		let request = |address, authorization: &str| {
+			let mut stream = std::net::TcpStream::connect(address).unwrap();
+			write!(stream, "GET /status HTTP/1.1\r\nHost: localhost\r\n{}\r\n", authorization)
+				.unwrap();
+			let mut response = String::new();
+			stream.read_to_string(&mut response).unwrap();
+			response
+		};
+
+		// select some free port for the admin server
+		let address = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+		let params = AdminParams::from(Some(AdminAddress {
+			host: "127.0.0.1".into(),
+			port: address.port(),
+		}))
+		.with_token(Some("secret".into()));
+		async_std::task::block_on(params.expose()).unwrap();
+
+		assert!(request(address, "").starts_with("HTTP/1.1 401"));
+		assert!(request(address, "Authorization: Bearer wrong\r\n").starts_with("HTTP/1.1 401"));
+		assert!(request(address, "Authorization: Bearer secret\r\n").starts_with("HTTP/1.1 200"));
+	}
+}
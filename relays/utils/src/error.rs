@@ -11,7 +11,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::net::AddrParseError;
+use std::{net::AddrParseError, path::PathBuf};
 use thiserror::Error;
 
 /// Result type used by relay utilities.
@@ -23,9 +23,15 @@ pub enum Error {
 	/// Failed to request a float value from HTTP service.
 	#[error("Failed to fetch token price from remote server: {0}")]
 	FetchTokenPrice(#[source] anyhow::Error),
-	/// Failed to parse the response from HTTP service.
-	#[error("Failed to parse HTTP service response: {0:?}. Response: {1:?}")]
-	ParseHttp(serde_json::Error, String),
+	/// Failed to read token price from the file.
+	#[error("Failed to read token price from file {0:?}: {1}")]
+	ReadTokenPriceFile(PathBuf, #[source] std::io::Error),
+	/// Failed to read token price (or conversion rate) from the chain storage.
+	#[error("Failed to read token price from the chain storage: {0}")]
+	ReadStoredTokenPrice(String),
+	/// Failed to parse the JSON price source contents (HTTP service response or file contents).
+	#[error("Failed to parse JSON price source contents: {0:?}. Contents: {1:?}")]
+	ParseJson(serde_json::Error, String),
 	/// Failed to select response value from the Json response.
 	#[error("Failed to select value from response: {0:?}. Response: {1:?}")]
 	SelectResponseValue(jsonpath_lib::JsonPathError, String),
@@ -40,6 +46,17 @@ pub enum Error {
 	/// Invalid host address was used for exposing Prometheus metrics.
 	#[error("Invalid host {0} is used to expose Prometheus metrics: {1}")]
 	ExposingMetricsInvalidHost(String, AddrParseError),
+	/// Invalid host address was used for exposing admin server.
+	#[error("Invalid host {0} is used to expose admin server: {1}")]
+	ExposingAdminInvalidHost(String, AddrParseError),
+	/// Admin server may not be exposed at non-loopback interface without access token.
+	#[error(
+		"Admin server may only be exposed at non-loopback host {0} if access token is specified"
+	)]
+	ExposingAdminWithoutToken(String),
+	/// Failed to start admin server.
+	#[error("Failed to start admin server at {0}: {1}")]
+	StartAdminServer(String, #[source] std::io::Error),
 	/// Prometheus error.
 	#[error("{0}")]
 	Prometheus(#[from] substrate_prometheus_endpoint::prometheus::Error),
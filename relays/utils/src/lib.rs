@@ -32,9 +32,11 @@ pub const MAX_BACKOFF_INTERVAL: Duration = Duration::from_secs(60);
 /// reconnection again.
 pub const CONNECTION_ERROR_DELAY: Duration = Duration::from_secs(10);
 
+pub mod admin;
 pub mod error;
 pub mod initialize;
 pub mod metrics;
+pub mod price_oracle;
 pub mod relay_loop;
 
 /// Block number traits shared by all chains that relay is able to serve.
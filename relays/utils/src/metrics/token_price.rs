@@ -0,0 +1,68 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	metrics::{
+		metric_name, register, Gauge, Metric, PrometheusError, Registry, StandaloneMetric, F64,
+	},
+	price_oracle::SharedPriceOracle,
+};
+
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Value update interval.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Metric that exposes price, returned by the price oracle, as float gauge.
+#[derive(Debug, Clone)]
+pub struct TokenPriceMetric {
+	oracle: SharedPriceOracle,
+	metric: Gauge<F64>,
+}
+
+impl TokenPriceMetric {
+	/// Create new metric instance with given name and help.
+	pub fn new(
+		oracle: SharedPriceOracle,
+		name: String,
+		help: String,
+	) -> Result<Self, PrometheusError> {
+		Ok(TokenPriceMetric { oracle, metric: Gauge::new(metric_name(None, &name), help)? })
+	}
+
+	/// Get price oracle that is used by this metric.
+	pub fn oracle(&self) -> SharedPriceOracle {
+		self.oracle.clone()
+	}
+}
+
+impl Metric for TokenPriceMetric {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.metric.clone(), registry).map(drop)
+	}
+}
+
+#[async_trait]
+impl StandaloneMetric for TokenPriceMetric {
+	fn update_interval(&self) -> Duration {
+		UPDATE_INTERVAL
+	}
+
+	async fn update(&self) {
+		crate::metrics::set_gauge_value(&self.metric, self.oracle.price().await.map(Some));
+	}
+}
@@ -0,0 +1,270 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Token price oracles.
+//!
+//! Price oracle is a source of some float value (token price or conversion rate) that is used by
+//! rational relayers to compute cost of their transactions and by conversion rate updaters to
+//! decide whether the rate that is stored in the runtime storage needs to be updated.
+
+use crate::error::{self, Error};
+
+use async_std::sync::{Arc, RwLock};
+use async_trait::async_trait;
+use std::{
+	fmt::Debug,
+	path::PathBuf,
+	time::{Duration, Instant},
+};
+
+/// Shared reference to the price oracle.
+pub type SharedPriceOracle = Arc<dyn PriceOracle>;
+
+/// Source of the token price (or any other positive float value, e.g. conversion rate).
+#[async_trait]
+pub trait PriceOracle: 'static + Debug + Send + Sync {
+	/// Read current price.
+	///
+	/// The returned value is assumed to be normal (`f64::is_normal` should return `true`) and
+	/// strictly positive.
+	async fn price(&self) -> error::Result<f64>;
+}
+
+/// Price oracle that reads price from the JSON file.
+///
+/// The file is re-read on every request, so the price may be updated without restarting
+/// the relay.
+#[derive(Debug, Clone)]
+pub struct FilePriceOracle {
+	path: PathBuf,
+	json_path: String,
+}
+
+impl FilePriceOracle {
+	/// Create new oracle that reads value, selected by given JSON path, from given file.
+	pub fn new(path: PathBuf, json_path: String) -> Self {
+		FilePriceOracle { path, json_path }
+	}
+}
+
+#[async_trait]
+impl PriceOracle for FilePriceOracle {
+	async fn price(&self) -> error::Result<f64> {
+		let raw_contents = async_std::fs::read_to_string(&self.path)
+			.await
+			.map_err(|err| Error::ReadTokenPriceFile(self.path.clone(), err))?;
+		parse_json_price(&self.json_path, &raw_contents)
+	}
+}
+
+/// Price oracle that reads price from HTTP service that responds with JSON.
+#[derive(Debug, Clone)]
+pub struct HttpJsonPriceOracle {
+	url: String,
+	json_path: String,
+}
+
+impl HttpJsonPriceOracle {
+	/// Create new oracle that reads value, selected by given JSON path, from given URL.
+	pub fn new(url: String, json_path: String) -> Self {
+		HttpJsonPriceOracle { url, json_path }
+	}
+
+	/// Request value from HTTP service.
+	async fn request_value(&self) -> anyhow::Result<String> {
+		use isahc::{AsyncReadResponseExt, HttpClient, Request};
+
+		let request = Request::get(&self.url).header("Accept", "application/json").body(())?;
+		let raw_response = HttpClient::new()?.send_async(request).await?.text().await?;
+		Ok(raw_response)
+	}
+}
+
+#[async_trait]
+impl PriceOracle for HttpJsonPriceOracle {
+	async fn price(&self) -> error::Result<f64> {
+		let raw_response = self.request_value().await.map_err(Error::FetchTokenPrice)?;
+		parse_json_price(&self.json_path, &raw_response)
+	}
+}
+
+/// Price oracle that caches price, returned by the wrapped oracle, for given duration.
+///
+/// Errors are never cached.
+#[derive(Debug, Clone)]
+pub struct CachedPriceOracle {
+	oracle: SharedPriceOracle,
+	cache_duration: Duration,
+	cached_price: Arc<RwLock<Option<(Instant, f64)>>>,
+}
+
+impl CachedPriceOracle {
+	/// Create new caching oracle.
+	pub fn new(oracle: SharedPriceOracle, cache_duration: Duration) -> Self {
+		CachedPriceOracle { oracle, cache_duration, cached_price: Arc::new(RwLock::new(None)) }
+	}
+}
+
+#[async_trait]
+impl PriceOracle for CachedPriceOracle {
+	async fn price(&self) -> error::Result<f64> {
+		if let Some((read_at, price)) = *self.cached_price.read().await {
+			if read_at.elapsed() < self.cache_duration {
+				return Ok(price)
+			}
+		}
+
+		let price = self.oracle.price().await?;
+		*self.cached_price.write().await = Some((Instant::now(), price));
+		Ok(price)
+	}
+}
+
+/// Parse price from the JSON string (HTTP service response or file contents).
+fn parse_json_price(json_path: &str, response: &str) -> error::Result<f64> {
+	let json =
+		serde_json::from_str(response).map_err(|err| Error::ParseJson(err, response.to_owned()))?;
+
+	let mut selector = jsonpath_lib::selector(&json);
+	let maybe_selected_value =
+		selector(json_path).map_err(|err| Error::SelectResponseValue(err, response.to_owned()))?;
+	let selected_value = maybe_selected_value
+		.first()
+		.and_then(|v| v.as_f64())
+		.ok_or_else(|| Error::MissingResponseValue(response.to_owned()))?;
+	if !selected_value.is_normal() || selected_value < 0.0 {
+		return Err(Error::ParseFloat(selected_value))
+	}
+
+	Ok(selected_value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{
+		io::{Read, Write},
+		net::TcpListener,
+		sync::atomic::{AtomicUsize, Ordering},
+	};
+
+	#[derive(Debug, Default)]
+	struct CountingPriceOracle(AtomicUsize);
+
+	#[async_trait]
+	impl PriceOracle for CountingPriceOracle {
+		async fn price(&self) -> error::Result<f64> {
+			Ok((self.0.fetch_add(1, Ordering::SeqCst) + 1) as f64)
+		}
+	}
+
+	/// Start HTTP server that responds with given body to the single request.
+	fn start_stub_server(body: &'static str) -> String {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let address = listener.local_addr().unwrap();
+		std::thread::spawn(move || {
+			let (mut stream, _) = listener.accept().unwrap();
+			let mut request = Vec::new();
+			let mut buffer = [0u8; 1024];
+			while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+				let read = stream.read(&mut buffer).unwrap();
+				if read == 0 {
+					break
+				}
+				request.extend_from_slice(&buffer[..read]);
+			}
+			write!(
+				stream,
+				"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+				Connection: close\r\n\r\n{}",
+				body.len(),
+				body,
+			)
+			.unwrap();
+		});
+		format!("http://{}/price", address)
+	}
+
+	#[test]
+	fn parse_json_price_works() {
+		assert_eq!(
+			parse_json_price("$.kusama.usd", r#"{"kusama":{"usd":433.05}}"#).map_err(drop),
+			Ok(433.05),
+		);
+	}
+
+	#[test]
+	fn parse_json_price_rejects_negative_numbers() {
+		assert!(parse_json_price("$.kusama.usd", r#"{"kusama":{"usd":-433.05}}"#).is_err());
+	}
+
+	#[test]
+	fn parse_json_price_rejects_zero_numbers() {
+		assert!(parse_json_price("$.kusama.usd", r#"{"kusama":{"usd":0.0}}"#).is_err());
+	}
+
+	#[test]
+	fn parse_json_price_rejects_invalid_json() {
+		assert!(matches!(
+			parse_json_price("$.kusama.usd", r#"{"kusama":"#),
+			Err(Error::ParseJson(_, _)),
+		));
+	}
+
+	#[test]
+	fn parse_json_price_rejects_nan() {
+		assert!(parse_json_price("$.kusama.usd", r#"{"kusama":{"usd":NaN}}"#).is_err());
+	}
+
+	#[test]
+	fn http_json_price_oracle_works() {
+		let url = start_stub_server(r#"{"kusama":{"btc":0.0091}}"#);
+		let oracle = HttpJsonPriceOracle::new(url, "$.kusama.btc".into());
+		assert_eq!(async_std::task::block_on(oracle.price()).map_err(drop), Ok(0.0091));
+	}
+
+	#[test]
+	fn file_price_oracle_works() {
+		let path = std::env::temp_dir()
+			.join(format!("relay-utils-file-price-oracle-{}.json", std::process::id()));
+		std::fs::write(&path, r#"{"polkadot":{"btc":0.00061}}"#).unwrap();
+		let oracle = FilePriceOracle::new(path.clone(), "$.polkadot.btc".into());
+		assert_eq!(async_std::task::block_on(oracle.price()).map_err(drop), Ok(0.00061));
+
+		std::fs::write(&path, r#"{"polkadot":{"btc":0.00062}}"#).unwrap();
+		assert_eq!(async_std::task::block_on(oracle.price()).map_err(drop), Ok(0.00062));
+
+		std::fs::remove_file(&path).unwrap();
+		assert!(async_std::task::block_on(oracle.price()).is_err());
+	}
+
+	#[test]
+	fn cached_price_oracle_works() {
+		let oracle = CachedPriceOracle::new(
+			Arc::new(CountingPriceOracle::default()),
+			Duration::from_secs(3600),
+		);
+		assert_eq!(async_std::task::block_on(oracle.price()).map_err(drop), Ok(1.0));
+		assert_eq!(async_std::task::block_on(oracle.price()).map_err(drop), Ok(1.0));
+
+		let oracle = CachedPriceOracle::new(
+			Arc::new(CountingPriceOracle::default()),
+			Duration::from_secs(0),
+		);
+		assert_eq!(async_std::task::block_on(oracle.price()).map_err(drop), Ok(1.0));
+		assert_eq!(async_std::task::block_on(oracle.price()).map_err(drop), Ok(2.0));
+	}
+}